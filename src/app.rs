@@ -1,40 +1,303 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
 use ratatui::widgets::ListState;
 
+use crate::config::{Config, LogFilterPreset};
+
 use crate::service::{
-    execute_unit_action, fetch_log_entries, fetch_log_entries_after_cursor,
-    fetch_unit_file_content, fetch_unit_properties, fetch_units, CommandRunner, LogEntry,
-    SystemdUnit, TimeRange, UnitAction, UnitProperties, UnitType, FILE_STATE_OPTIONS,
-    TIME_RANGES, UNIT_TYPES,
+    count_journal_matches, escalated_command, execute_unit_action_bulk,
+    execute_unit_action_with_output, fetch_active_timestamps, fetch_dependencies, fetch_log_entries,
+    fetch_log_entries_after_cursor, fetch_log_entries_before_cursor, fetch_monotonic_now,
+    fetch_raw_properties, fetch_reverse_dependencies, fetch_security_analysis,
+    fetch_unit_file_with_dropins, fetch_unit_properties, fetch_units, find_word_matches,
+    format_elapsed, format_log_timestamp, is_missing_binary_error, journal_pager_args,
+    log_export_path, needs_privilege, priority_label, raw_properties_path, regex_escape,
+    save_text_file, unit_type_from_name, CommandRunner, Escalation, LogEntry, LogExportFormat,
+    LogFilters, LogGrep, LogSource, SystemdUnit, TimeRange, TimestampFormat, UnitAction, UnitFile,
+    UnitProperties, UnitType, DEFAULT_LOG_LINE_LIMIT, FILE_STATE_OPTIONS, LOG_EXPORT_FORMATS,
+    LOG_LINE_LIMITS, TIMESTAMP_FORMATS, TIME_RANGES, UNIT_TYPES,
 };
 
+/// Lines moved per mouse wheel notch in scrollable views.
+pub const MOUSE_SCROLL_LINES: usize = 3;
+
+/// Picker shortcuts named in the unit list footer.
+pub const FOOTER_ACTION_HINTS: usize = 3;
+
+/// Dependent units named in the stop/disable warning before "…".
+pub const DEPENDENTS_WARNING_NAMES: usize = 3;
+
+/// Rows of per-unit results shown at once in the bulk action dialog.
+pub const BATCH_RESULTS_SHOWN: usize = 10;
+
+/// Intervals cycled through by the auto-refresh key, after "off".
+pub const AUTO_REFRESH_INTERVALS: [Duration; 4] = [
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+    Duration::from_secs(30),
+];
+
+/// How often live tail polls the journal unless the config says otherwise.
+pub const DEFAULT_LIVE_TAIL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Intervals cycled through by the live tail interval key.
+pub const LIVE_TAIL_INTERVALS: [Duration; 4] = [
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+];
+
+/// Cadence of the `[LIVE]` indicator blink, independent of the live tail
+/// interval so a slow poll doesn't make the UI look stuck.
+pub const LIVE_INDICATOR_BLINK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// "N units depend on X: a, b, c, …" when anything depends on `unit`.
+/// A failed lookup gives no warning rather than blocking the action.
+pub fn dependents_warning(unit: &str, user_mode: bool, runner: &dyn CommandRunner) -> Option<String> {
+    let dependents = fetch_reverse_dependencies(unit, user_mode, runner).ok()?;
+    if dependents.is_empty() {
+        return None;
+    }
+    let mut names = dependents
+        .iter()
+        .take(DEPENDENTS_WARNING_NAMES)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if dependents.len() > DEPENDENTS_WARNING_NAMES {
+        names.push_str(", \u{2026}");
+    }
+    let noun = if dependents.len() == 1 { "unit depends" } else { "units depend" };
+    Some(format!("{} {} on {}: {}", dependents.len(), noun, unit, names))
+}
+
+/// Advances a blinking indicator by the whole `cadence` periods in
+/// `elapsed`: returns the new on/off state and the time those periods
+/// account for, to add to the last-toggle instant.
+pub fn advance_blink(on: bool, elapsed: Duration, cadence: Duration) -> (bool, Duration) {
+    let periods = (elapsed.as_nanos() / cadence.as_nanos().max(1)) as u32;
+    (on ^ (periods % 2 == 1), cadence * periods)
+}
+
+/// Whether the unit list is due for an auto-refresh, given the time since the
+/// last one. Always false when auto-refresh is off.
+pub fn auto_refresh_due(elapsed: Duration, interval: Option<Duration>) -> bool {
+    interval.is_some_and(|interval| elapsed >= interval)
+}
+
+/// Units whose sub-state differs between a `unit -> sub` snapshot and the
+/// current list, as `unit -> (old, new)`. Units missing from either side are
+/// not reported.
+pub fn diff_states(
+    prev: &HashMap<String, String>,
+    current: &HashMap<String, String>,
+) -> HashMap<String, (String, String)> {
+    current
+        .iter()
+        .filter_map(|(unit, sub)| match prev.get(unit) {
+            Some(old) if old != sub => Some((unit.clone(), (old.clone(), sub.clone()))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Units whose sub-state is "failed" in `current` but wasn't in `prev`,
+/// sorted. Units new to the list count when they show up failed; a unit
+/// that was already failed does not.
+pub fn newly_failed(prev: &HashMap<String, String>, current: &HashMap<String, String>) -> Vec<String> {
+    let mut failed: Vec<String> = current
+        .iter()
+        .filter(|&(unit, sub)| sub == "failed" && prev.get(unit).is_none_or(|old| old != "failed"))
+        .map(|(unit, _)| unit.clone())
+        .collect();
+    failed.sort();
+    failed
+}
+
+/// Indices of the lines containing `query`, case-insensitively.
+pub fn find_matching_lines(lines: &[String], query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = query.to_lowercase();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.to_lowercase().contains(&query))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Which separators go before `current`: (boot changed, unit restarted).
+/// A restart is only reported within the same boot, against the last
+/// invocation id seen.
+pub fn log_boundary_before_entry(
+    prev: &LogEntry,
+    current: &LogEntry,
+    last_invocation_id: Option<&str>,
+) -> (bool, bool) {
+    let boot_changed = matches!(
+        (&prev.boot_id, &current.boot_id),
+        (Some(a), Some(b)) if a != b
+    );
+    let invocation_changed = !boot_changed
+        && matches!(
+            (last_invocation_id, current.invocation_id.as_deref()),
+            (Some(a), Some(b)) if a != b
+        );
+    (boot_changed, invocation_changed)
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break,
+/// doubling any quotes inside.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// The panes of the split view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    List,
+    Logs,
+}
+
+/// The list pickers, for code that treats them alike (mouse wheel and
+/// clicks).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Picker {
+    Status,
+    Type,
+    Priority,
+    Time,
+    FileState,
+    TimestampFormat,
+    Preset,
+    Export,
+    Action,
+}
+
+/// A background log fetch result, tagged with the unit it was requested for
+/// (`None` for the journal-wide view).
+pub struct LogLoad {
+    pub unit: Option<String>,
+    /// The main PID the fetch was narrowed to, if "follow main PID" was on
+    /// and the unit had one.
+    pub main_pid: Option<u32>,
+    pub result: Result<Vec<LogEntry>, String>,
+}
+
+/// UPTIME column data: active units' monotonic enter timestamps and the
+/// host's monotonic clock when they were read.
+pub struct ActiveTimestamps {
+    pub timestamps: HashMap<String, i64>,
+    pub now: Option<i64>,
+}
+
+/// A refetched unit file, tagged with the unit it is for.
+pub struct UnitFileLoad {
+    pub unit: String,
+    pub result: Result<UnitFile, String>,
+}
+
+/// A timer's properties, tagged with the timer they were fetched for.
+pub struct TimerProperties {
+    pub timer: String,
+    pub props: UnitProperties,
+}
+
+/// A `systemctl list-dependencies` result, tagged with the unit it is for.
+pub struct DependencyList {
+    pub unit: String,
+    pub result: Result<Vec<String>, String>,
+}
+
+/// A `systemd-analyze security` report, tagged with the unit it is for.
+pub struct SecurityReport {
+    pub unit: String,
+    pub result: Result<Vec<String>, String>,
+}
+
+/// Result of counting search matches across the whole journal, tagged with
+/// the query it was run for.
+pub struct JournalCount {
+    pub query: String,
+    pub whole_word: bool,
+    pub result: Result<usize, String>,
+}
+
 pub struct App {
     pub services: Vec<SystemdUnit>,
     pub list_state: ListState,
+    /// Time and position of the last left click on the unit list, for
+    /// double-click detection.
+    pub last_list_click: Option<(Instant, u16, u16)>,
     pub should_quit: bool,
+    pub pending_quit_confirm: bool,
     pub error: Option<String>,
     pub search_query: String,
     pub search_mode: bool,
+    pub jump_mode: bool,
+    pub jump_query: String,
     pub filtered_indices: Vec<usize>,
+    pub selected_units: HashSet<String>,
+    pub show_state_changes: bool,
+    pub state_changes: HashMap<String, (String, String)>,
+    pub sub_state_snapshot: Option<HashMap<String, String>>,
+    /// Flashed in the header when auto-refresh sees units newly fail;
+    /// cleared with the status message.
+    pub failure_alert: Option<String>,
+    /// Set with `failure_alert` when `bell_on_failure` is on; the main loop
+    /// rings the terminal bell and clears it.
+    pub bell_pending: bool,
+    /// Newly failed units for the main loop to send as a desktop
+    /// notification, when `desktop_notifications` is on.
+    pub notify_pending: Vec<String>,
+    pub favorites: HashSet<String>,
+    pub config_path: Option<PathBuf>,
     pub logs: Vec<LogEntry>,
     pub cached_entry_heights: Vec<usize>,
     pub cached_entry_heights_width: usize,
     pub cached_entry_heights_query: String,
     pub cached_entry_heights_dirty: bool,
+    pub cached_log_repeat_counts: Vec<usize>,
     pub logs_scroll: usize,
+    pub log_wrap: bool,
+    pub collapse_log_repeats: bool,
+    pub logs_h_scroll: usize,
+    pub log_history_exhausted: bool,
     pub last_selected_service: Option<String>,
+    pub log_scroll_memory: HashMap<String, usize>,
+    pub pending_log_scroll: Option<usize>,
     pub status_filter: Option<String>,
     pub show_logs: bool,
+    /// Unit list and logs side by side.
+    pub split_view: bool,
+    /// Which split view pane gets the keyboard.
+    pub focused_pane: Pane,
     pub show_help: bool,
     pub help_scroll: u16,
+    /// Typing a filter for the help lines after `/`.
+    pub help_search_mode: bool,
+    /// Keeps only the help entries containing it (case-insensitive).
+    pub help_search_query: String,
     pub help_content_lines: u16,
     pub help_viewport_lines: u16,
     pub show_status_picker: bool,
     pub status_picker_state: ListState,
     pub log_search_query: String,
     pub log_search_mode: bool,
+    pub log_search_whole_word: bool,
+    pub log_journal_match_count: Option<Result<usize, String>>,
+    pub journal_count_receiver: Option<mpsc::Receiver<JournalCount>>,
     pub log_search_matches: Vec<usize>,
     pub log_search_match_index: Option<usize>,
     pub user_mode: bool,
@@ -43,22 +306,85 @@ pub struct App {
     pub unit_type: UnitType,
     pub show_type_picker: bool,
     pub type_picker_state: ListState,
-    pub log_priority_filter: Option<u8>,
+    pub log_priority_filter: Option<(u8, u8)>,
     pub log_time_range: TimeRange,
+    pub log_grep: Option<LogGrep>,
+    pub filter_main_pid: bool,
+    pub log_main_pid: Option<u32>,
+    pub log_line_limit: usize,
+    pub log_timestamp_format: TimestampFormat,
+    pub use_utc: bool,
+    pub log_timestamp_micros: bool,
+    pub log_show_hostname: bool,
+    pub log_boot_indices: bool,
+    /// Log entries less severe than this priority render greyed out.
+    pub dim_below_priority: Option<u8>,
     pub log_filters_dirty: bool,
     pub show_priority_picker: bool,
     pub priority_picker_state: ListState,
+    pub priority_range_anchor: Option<u8>,
     pub show_time_picker: bool,
     pub time_picker_state: ListState,
+    pub show_custom_time_input: bool,
+    pub custom_since_input: String,
+    pub custom_until_input: String,
+    pub custom_time_editing_until: bool,
+    pub show_grep_input: bool,
+    pub grep_input: String,
+    pub grep_case_sensitive_input: bool,
+    // Log filter presets
+    pub log_presets: Vec<LogFilterPreset>,
+    pub show_preset_picker: bool,
+    pub preset_picker_state: ListState,
+    pub show_preset_name_input: bool,
+    pub preset_name_input: String,
+    pub show_export_picker: bool,
+    pub export_picker_state: ListState,
+    pub show_timestamp_format_picker: bool,
+    pub timestamp_format_picker_state: ListState,
     // Details modal
     pub show_details: bool,
     pub detail_scroll: usize,
     pub detail_properties: Option<UnitProperties>,
     pub detail_unit_name: Option<String>,
     pub detail_content_height: usize,
+    pub detail_content_text: Vec<String>,
+    pub detail_search_query: String,
+    pub detail_search_mode: bool,
+    pub detail_search_matches: Vec<usize>,
+    pub detail_search_match_index: Option<usize>,
     pub properties_cache: HashMap<String, UnitProperties>,
+    pub detail_receiver: Option<mpsc::Receiver<UnitProperties>>,
+    /// Properties of a timer whose target `S` is waiting for; only fetched
+    /// when the timer isn't in properties_cache yet.
+    pub timer_target_receiver: Option<mpsc::Receiver<TimerProperties>>,
+    /// Where the `w` export was saved, or why it failed.
+    pub raw_properties_receiver: Option<mpsc::Receiver<Result<PathBuf, String>>>,
+    pub show_active_since: bool,
+    pub active_timestamps: HashMap<String, i64>,
+    pub active_clock: Option<(i64, Instant)>,
+    pub active_timestamps_receiver: Option<mpsc::Receiver<ActiveTimestamps>>,
     // File state filter
     pub file_state_filter: Option<String>,
+    /// Restricts the list to these units (a unit and its dependencies).
+    pub dependency_filter: Option<HashSet<String>>,
+    /// The unit whose dependencies `dependency_filter` holds.
+    pub dependency_filter_unit: Option<String>,
+    /// `systemctl list-dependencies` is slow over SSH, so it runs off the
+    /// UI thread.
+    pub dependency_filter_receiver: Option<mpsc::Receiver<DependencyList>>,
+    pub filter_inverted: bool,
+    pub row_severity_colors: bool,
+    pub wrap_navigation: bool,
+    pub hide_noise: bool,
+    /// List every loaded unit (`list-units --all`); off lists only the
+    /// active and failed ones.
+    pub show_all_units: bool,
+    pub no_confirm: bool,
+    pub confirm_on_quit: bool,
+    pub bell_on_failure: bool,
+    pub desktop_notifications: bool,
+    pub color_enabled: bool,
     pub show_file_state_picker: bool,
     pub file_state_picker_state: ListState,
     // Unit actions
@@ -68,29 +394,86 @@ pub struct App {
     pub show_confirm: bool,
     pub confirm_action: Option<UnitAction>,
     pub confirm_unit_name: Option<String>,
+    pub confirm_targets: Vec<String>,
+    /// Shown in the confirm prompt, e.g. what depends on a unit being stopped.
+    pub confirm_warning: Option<String>,
+    /// The dependents lookup behind `confirm_warning`, run off the UI
+    /// thread since it is a round trip over `--ssh`.
+    pub confirm_warning_receiver: Option<mpsc::Receiver<Option<String>>>,
+    /// `--no-confirm` action waiting on that lookup: it runs by itself
+    /// unless the lookup finds dependents.
+    pub confirm_auto_run: bool,
+    /// Pass `--no-block` to job actions: they report "requested" as soon
+    /// as the job is queued instead of waiting for it to finish.
+    pub no_block: bool,
     pub action_in_progress: bool,
     pub action_result: Option<Result<String, String>>,
-    pub action_receiver: Option<mpsc::Receiver<Result<String, String>>>,
+    /// The action's result and everything its command printed.
+    pub action_receiver: Option<mpsc::Receiver<(Result<String, String>, String)>>,
+    /// Full output of the most recent action, shown by the output popup.
+    pub last_action_output: Option<String>,
+    /// The last single-unit action that succeeded and can be reversed,
+    /// offered by `z`.
+    pub last_action: Option<(UnitAction, String)>,
+    /// Per-unit outcomes of the running (or just finished) bulk action, in
+    /// completion order.
+    pub batch_results: Vec<(String, Result<(), String>)>,
+    pub batch_receiver: Option<mpsc::Receiver<(String, Result<(), String>)>>,
+    /// First visible row of the batch results; `None` follows the newest.
+    pub batch_scroll: Option<usize>,
     pub refresh_receiver: Option<mpsc::Receiver<Vec<SystemdUnit>>>,
     // Live tail runs on a background thread so a slow runner (SSH) never
     // blocks the UI. A result is only merged if its generation still matches
     // log_stream_generation, which is bumped whenever the log buffer is
     // replaced or cleared.
     pub log_refresh_receiver: Option<mpsc::Receiver<Vec<LogEntry>>>,
+    pub log_load_receiver: Option<mpsc::Receiver<LogLoad>>,
+    pub older_logs_receiver: Option<mpsc::Receiver<Result<Vec<LogEntry>, String>>>,
     pub log_refresh_generation: u64,
     pub log_stream_generation: u64,
     pub status_message: Option<String>,
     pub system_logs_mode: bool,
+    pub log_source: LogSource,
     pub navigated_from_system_logs: bool,
     pub log_paused: bool,
+    /// While live (not paused), new entries scroll the view to the bottom.
+    /// Scrolling up or `s` locks the view in place; fetching continues.
+    pub log_follow: bool,
     pub log_selected_entry: Option<usize>,
+    pub show_log_fields: bool,
+    pub log_fields_scroll: usize,
+    // Reverse dependencies popup: units that depend on `reverse_deps_unit`
+    pub show_reverse_deps: bool,
+    pub reverse_deps: Vec<String>,
+    pub reverse_deps_unit: String,
+    pub reverse_deps_scroll: usize,
+    /// Like the dependency filter, the reverse lookup runs off the UI thread.
+    pub reverse_deps_receiver: Option<mpsc::Receiver<DependencyList>>,
+    // Last action output popup
+    pub show_action_output: bool,
+    pub action_output_scroll: usize,
+    // Security analysis popup (`systemd-analyze security`)
+    pub show_security: bool,
+    pub security_lines: Vec<String>,
+    pub security_unit: String,
+    pub security_scroll: usize,
+    /// `systemd-analyze security` takes a second or more, so it runs off
+    /// the UI thread.
+    pub security_receiver: Option<mpsc::Receiver<SecurityReport>>,
     pub logs_at_bottom: bool,
     pub last_refreshed: Option<chrono::DateTime<chrono::Local>>,
+    pub auto_refresh_interval: Option<Duration>,
+    /// Poll interval for live tail.
+    pub live_tail_interval: Duration,
+    pub pending_count: Option<usize>,
     // Unit file viewer
     pub show_unit_file: bool,
     pub unit_file_content: Vec<String>,
     pub unit_file_scroll: usize,
     pub unit_file_unit_name: Option<String>,
+    pub unit_file_path: Option<String>,
+    /// A reload after `systemctl edit`, fetched off the UI thread.
+    pub unit_file_receiver: Option<mpsc::Receiver<UnitFileLoad>>,
     pub unit_file_search_query: String,
     pub unit_file_search_mode: bool,
     pub unit_file_search_matches: Vec<usize>,
@@ -98,54 +481,138 @@ pub struct App {
 }
 
 impl App {
-    pub fn new(runner: Arc<dyn CommandRunner>, host_label: Option<String>) -> Self {
+    pub fn new(
+        runner: Arc<dyn CommandRunner>,
+        host_label: Option<String>,
+        user_mode: bool,
+        unit_type: UnitType,
+    ) -> Self {
         let mut app = Self {
             services: Vec::new(),
             list_state: ListState::default(),
+            last_list_click: None,
             should_quit: false,
+            pending_quit_confirm: false,
             error: None,
             search_query: String::new(),
             search_mode: false,
+            jump_mode: false,
+            jump_query: String::new(),
             filtered_indices: Vec::new(),
+            selected_units: HashSet::new(),
+            show_state_changes: false,
+            state_changes: HashMap::new(),
+            sub_state_snapshot: None,
+            failure_alert: None,
+            bell_pending: false,
+            notify_pending: Vec::new(),
+            favorites: HashSet::new(),
+            config_path: None,
             logs: Vec::new(),
             cached_entry_heights: Vec::new(),
             cached_entry_heights_width: 0,
             cached_entry_heights_query: String::new(),
             cached_entry_heights_dirty: true,
+            cached_log_repeat_counts: Vec::new(),
             logs_scroll: 0,
+            log_wrap: true,
+            collapse_log_repeats: false,
+            logs_h_scroll: 0,
+            log_history_exhausted: false,
             last_selected_service: None,
+            log_scroll_memory: HashMap::new(),
+            pending_log_scroll: None,
             status_filter: None,
             show_logs: false,
+            split_view: false,
+            focused_pane: Pane::List,
             show_help: false,
             help_scroll: 0,
+            help_search_mode: false,
+            help_search_query: String::new(),
             help_content_lines: 0,
             help_viewport_lines: 0,
             show_status_picker: false,
             status_picker_state: ListState::default(),
             log_search_query: String::new(),
             log_search_mode: false,
+            log_search_whole_word: false,
+            log_journal_match_count: None,
+            journal_count_receiver: None,
             log_search_matches: Vec::new(),
             log_search_match_index: None,
-            user_mode: false,
+            user_mode,
             runner,
             host_label,
-            unit_type: UnitType::Service,
+            unit_type,
             show_type_picker: false,
             type_picker_state: ListState::default(),
             log_priority_filter: None,
             log_time_range: TimeRange::All,
+            log_grep: None,
+            filter_main_pid: false,
+            log_main_pid: None,
+            log_line_limit: DEFAULT_LOG_LINE_LIMIT,
+            log_timestamp_format: TimestampFormat::Short,
+            use_utc: false,
+            log_timestamp_micros: false,
+            log_show_hostname: false,
+            log_boot_indices: false,
+            dim_below_priority: None,
             log_filters_dirty: false,
             show_priority_picker: false,
             priority_picker_state: ListState::default(),
+            priority_range_anchor: None,
             show_time_picker: false,
             time_picker_state: ListState::default(),
+            show_custom_time_input: false,
+            custom_since_input: String::new(),
+            custom_until_input: String::new(),
+            custom_time_editing_until: false,
+            show_grep_input: false,
+            grep_input: String::new(),
+            grep_case_sensitive_input: false,
+            log_presets: Vec::new(),
+            show_preset_picker: false,
+            preset_picker_state: ListState::default(),
+            show_preset_name_input: false,
+            preset_name_input: String::new(),
+            show_export_picker: false,
+            export_picker_state: ListState::default(),
+            show_timestamp_format_picker: false,
+            timestamp_format_picker_state: ListState::default(),
             show_details: false,
             detail_scroll: 0,
             detail_properties: None,
             detail_unit_name: None,
             detail_content_height: 0,
+            detail_content_text: Vec::new(),
+            detail_search_query: String::new(),
+            detail_search_mode: false,
+            detail_search_matches: Vec::new(),
+            detail_search_match_index: None,
             properties_cache: HashMap::new(),
+            detail_receiver: None,
+            timer_target_receiver: None,
+            raw_properties_receiver: None,
+            show_active_since: false,
+            active_timestamps: HashMap::new(),
+            active_clock: None,
+            active_timestamps_receiver: None,
             file_state_filter: None,
+            dependency_filter: None,
+            dependency_filter_unit: None,
+            dependency_filter_receiver: None,
+            filter_inverted: false,
+            row_severity_colors: true,
+            wrap_navigation: false,
+            hide_noise: false,
+            show_all_units: true,
+            no_confirm: false,
+            confirm_on_quit: false,
+            bell_on_failure: false,
+            desktop_notifications: false,
+            color_enabled: true,
             show_file_state_picker: false,
             file_state_picker_state: ListState::default(),
             show_action_picker: false,
@@ -154,24 +621,57 @@ impl App {
             show_confirm: false,
             confirm_action: None,
             confirm_unit_name: None,
+            confirm_targets: Vec::new(),
+            confirm_warning: None,
+            confirm_warning_receiver: None,
+            confirm_auto_run: false,
+            no_block: false,
             action_in_progress: false,
             action_result: None,
             action_receiver: None,
+            last_action_output: None,
+            last_action: None,
+            batch_results: Vec::new(),
+            batch_receiver: None,
+            batch_scroll: None,
             refresh_receiver: None,
             log_refresh_receiver: None,
+            log_load_receiver: None,
+            older_logs_receiver: None,
             log_refresh_generation: 0,
             log_stream_generation: 0,
             status_message: None,
             system_logs_mode: false,
+            log_source: LogSource::Unit,
             navigated_from_system_logs: false,
             log_paused: false,
+            log_follow: true,
             log_selected_entry: None,
+            show_log_fields: false,
+            log_fields_scroll: 0,
+            show_reverse_deps: false,
+            reverse_deps: Vec::new(),
+            reverse_deps_unit: String::new(),
+            reverse_deps_scroll: 0,
+            reverse_deps_receiver: None,
+            show_action_output: false,
+            action_output_scroll: 0,
+            show_security: false,
+            security_lines: Vec::new(),
+            security_unit: String::new(),
+            security_scroll: 0,
+            security_receiver: None,
             logs_at_bottom: true,
             last_refreshed: None,
+            auto_refresh_interval: None,
+            live_tail_interval: DEFAULT_LIVE_TAIL_INTERVAL,
+            pending_count: None,
             show_unit_file: false,
             unit_file_content: Vec::new(),
             unit_file_scroll: 0,
             unit_file_unit_name: None,
+            unit_file_path: None,
+            unit_file_receiver: None,
             unit_file_search_query: String::new(),
             unit_file_search_mode: false,
             unit_file_search_matches: Vec::new(),
@@ -191,18 +691,20 @@ impl App {
 
     pub fn load_services(&mut self) {
         self.properties_cache.clear();
-        match fetch_units(self.unit_type, self.user_mode, self.runner()) {
+        match fetch_units(self.unit_type, self.user_mode, self.show_all_units, self.runner()) {
             Ok(services) => {
                 self.services = services;
                 self.error = None;
                 self.last_refreshed = Some(chrono::Local::now());
+                self.update_state_changes();
+                self.start_active_timestamps_fetch();
                 self.update_filter();
                 if !self.filtered_indices.is_empty() && self.list_state.selected().is_none() {
                     self.list_state.select(Some(0));
                 }
             }
             Err(e) => {
-                if self.user_mode {
+                if self.user_mode || is_missing_binary_error(&e) {
                     self.error = Some(e);
                 } else {
                     self.error = Some(format!("{} (press 'u' to switch to user mode)", e));
@@ -211,8 +713,93 @@ impl App {
         }
     }
 
+    /// Explicit refresh (`r`): remembers every unit's sub-state first, so
+    /// the changes mode can show what moved since this point. Auto and
+    /// post-action refreshes keep comparing against the same snapshot.
+    pub fn refresh_units(&mut self) {
+        self.sub_state_snapshot = Some(self.sub_states());
+        self.load_services();
+    }
+
+    fn sub_states(&self) -> HashMap<String, String> {
+        self.services
+            .iter()
+            .map(|u| (u.unit.clone(), u.sub.clone()))
+            .collect()
+    }
+
+    fn update_state_changes(&mut self) {
+        self.state_changes = match &self.sub_state_snapshot {
+            Some(prev) => diff_states(prev, &self.sub_states()),
+            None => HashMap::new(),
+        };
+    }
+
+    /// Forgets the snapshot; it describes a different unit list.
+    fn clear_state_changes(&mut self) {
+        self.sub_state_snapshot = None;
+        self.state_changes.clear();
+    }
+
+    pub fn toggle_show_state_changes(&mut self) {
+        self.show_state_changes = !self.show_state_changes;
+        self.status_message = Some(if !self.show_state_changes {
+            "Change highlighting off".to_string()
+        } else if self.sub_state_snapshot.is_none() {
+            "Change highlighting on (press r to take a snapshot)".to_string()
+        } else {
+            format!("Change highlighting on ({} changed since last refresh)", self.state_changes.len())
+        });
+    }
+
+    /// Shows or hides the UPTIME column (time since each active unit
+    /// entered the active state).
+    pub fn toggle_active_since_column(&mut self) {
+        self.show_active_since = !self.show_active_since;
+        self.start_active_timestamps_fetch();
+    }
+
+    /// Refetches the UPTIME column's timestamps on a background thread,
+    /// along with the host's monotonic clock to measure them against. Does
+    /// nothing while the column is hidden.
+    fn start_active_timestamps_fetch(&mut self) {
+        if !self.show_active_since {
+            return;
+        }
+        let unit_type = self.unit_type;
+        let user_mode = self.user_mode;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.active_timestamps_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let timestamps = fetch_active_timestamps(unit_type, user_mode, runner.as_ref());
+            let now = fetch_monotonic_now(runner.as_ref());
+            let _ = tx.send(ActiveTimestamps { timestamps, now });
+        });
+    }
+
+    pub fn active_timestamps_loading(&self) -> bool {
+        self.active_timestamps_receiver.is_some()
+    }
+
+    /// How long the unit has been active, for the UPTIME column. The host
+    /// clock read at fetch time is advanced locally, so the value keeps
+    /// ticking between refreshes.
+    pub fn active_elapsed(&self, unit: &str) -> Option<String> {
+        let enter = *self.active_timestamps.get(unit)?;
+        let (now, fetched_at) = self.active_clock?;
+        Some(format_elapsed(enter, now + fetched_at.elapsed().as_micros() as i64))
+    }
+
+    /// Whether static and indirect units are currently left out: the setting
+    /// is on and neither a search nor a file state filter asks for them.
+    pub fn hiding_noise(&self) -> bool {
+        self.hide_noise && self.search_query.is_empty() && self.file_state_filter.is_none()
+    }
+
     pub fn update_filter(&mut self) {
         let query = self.search_query.to_lowercase();
+        let hides_noise = self.hiding_noise();
         self.filtered_indices = self
             .services
             .iter()
@@ -223,18 +810,32 @@ impl App {
                     || service.unit.to_lowercase().contains(&query)
                     || service.description.to_lowercase().contains(&query);
 
-                // Status filter
+                // Status filter (negated when inverted; search never is)
                 let matches_status = self.status_filter.is_none()
-                    || self.status_filter.as_ref() == Some(&service.sub);
+                    || (self.status_filter.as_ref() == Some(&service.sub)) != self.filter_inverted;
 
                 // File state filter
                 let matches_file_state = self.file_state_filter.is_none()
-                    || service.file_state.as_ref() == self.file_state_filter.as_ref();
+                    || (service.file_state.as_ref() == self.file_state_filter.as_ref())
+                        != self.filter_inverted;
+
+                // Static/indirect units, unless a search or file state
+                // filter is asking for them
+                let matches_noise = !hides_noise
+                    || !matches!(service.file_state.as_deref(), Some("static" | "indirect"));
 
-                matches_search && matches_status && matches_file_state
+                let matches_dependencies = self
+                    .dependency_filter
+                    .as_ref()
+                    .is_none_or(|units| units.contains(&service.unit));
+
+                matches_search && matches_status && matches_file_state && matches_noise && matches_dependencies
             })
             .map(|(i, _)| i)
             .collect();
+        // Favorites first; the sort is stable so each group keeps list order.
+        self.filtered_indices
+            .sort_by_key(|&i| !self.favorites.contains(&self.services[i].unit));
 
         // Reset selection if current selection is out of bounds
         if let Some(selected) = self.list_state.selected() {
@@ -250,11 +851,295 @@ impl App {
         }
     }
 
+    /// Pins or unpins the highlighted unit, keeping it highlighted as it
+    /// moves, and writes the favorites to the config file.
+    pub fn toggle_favorite(&mut self) {
+        let Some(name) = self.selected_unit().map(|u| u.unit.clone()) else {
+            return;
+        };
+        if !self.favorites.remove(&name) {
+            self.favorites.insert(name.clone());
+        }
+        self.update_filter();
+        if let Some(pos) = self
+            .filtered_indices
+            .iter()
+            .position(|&i| self.services[i].unit == name)
+        {
+            self.list_state.select(Some(pos));
+        }
+        self.save_favorites();
+    }
+
+    fn save_favorites(&mut self) {
+        let mut favorites: Vec<String> = self.favorites.iter().cloned().collect();
+        favorites.sort();
+        self.save_config("favorites", |config| config.favorites = favorites);
+    }
+
+    /// Re-reads the config file, applies `update` and writes it back, so
+    /// settings edited by hand while the app runs aren't clobbered.
+    fn save_config(&mut self, what: &str, update: impl FnOnce(&mut Config)) {
+        let Some(path) = self.config_path.as_ref() else {
+            return;
+        };
+        let result = Config::load(path).and_then(|mut config| {
+            update(&mut config);
+            config.save(path)
+        });
+        if let Err(e) = result {
+            self.status_message = Some(format!("Could not save {}: {}", what, e));
+        }
+    }
+
+    /// Shows or hides static and indirect units and remembers the choice in
+    /// the config file.
+    pub fn toggle_hide_noise(&mut self) {
+        self.hide_noise = !self.hide_noise;
+        self.update_filter();
+        self.status_message = Some(if self.hide_noise {
+            "Hiding static and indirect units".to_string()
+        } else {
+            "Showing static and indirect units".to_string()
+        });
+        let hide_noise = self.hide_noise;
+        self.save_config("settings", |config| config.hide_noise = hide_noise);
+    }
+
+    /// Switches between every loaded unit and only active (and failed)
+    /// ones, reloading the list.
+    pub fn toggle_show_all_units(&mut self) {
+        self.show_all_units = !self.show_all_units;
+        // A pending refresh was fetched with the old listing.
+        self.refresh_receiver = None;
+        self.load_services();
+        if self.error.is_none() {
+            self.status_message = Some(if self.show_all_units {
+                "Showing all loaded units".to_string()
+            } else {
+                "Showing active units only".to_string()
+            });
+        }
+    }
+
+    pub fn toggle_filter_inverted(&mut self) {
+        self.filter_inverted = !self.filter_inverted;
+        self.update_filter();
+    }
+
     pub fn clear_search(&mut self) {
         self.search_query.clear();
         self.update_filter();
     }
 
+    /// Narrows the list to the highlighted unit and everything it depends
+    /// on, once the lookup finishes; pressed again, shows the whole list.
+    pub fn toggle_dependency_filter(&mut self) {
+        if self.dependency_filter.is_some() {
+            self.clear_dependency_filter();
+            return;
+        }
+        if self.dependency_filter_loading() {
+            return;
+        }
+        let Some(name) = self.selected_unit().map(|u| u.unit.clone()) else {
+            return;
+        };
+        let user_mode = self.user_mode;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.dependency_filter_receiver = Some(rx);
+        self.status_message = Some(format!("Loading dependencies of {}\u{2026}", name));
+        std::thread::spawn(move || {
+            let result = fetch_dependencies(&name, user_mode, runner.as_ref());
+            let _ = tx.send(DependencyList { unit: name, result });
+        });
+    }
+
+    pub fn dependency_filter_loading(&self) -> bool {
+        self.dependency_filter_receiver.is_some()
+    }
+
+    fn finish_dependency_filter(&mut self, list: DependencyList) {
+        self.dependency_filter_receiver = None;
+        match list.result {
+            Ok(units) => {
+                self.status_message = None;
+                let name = list.unit;
+                self.dependency_filter = Some(units.into_iter().chain([name.clone()]).collect());
+                self.dependency_filter_unit = Some(name.clone());
+                self.update_filter();
+                self.select_unit_by_name(&name);
+            }
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+
+    /// Lists what depends on the highlighted unit, i.e. what stopping it
+    /// could take down. The popup opens once the lookup finishes.
+    pub fn open_reverse_dependencies(&mut self) {
+        let Some(name) = self.selected_unit().map(|u| u.unit.clone()) else {
+            return;
+        };
+        let user_mode = self.user_mode;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.reverse_deps_receiver = Some(rx);
+        self.status_message = Some(format!("Loading what depends on {}\u{2026}", name));
+        std::thread::spawn(move || {
+            let result = fetch_reverse_dependencies(&name, user_mode, runner.as_ref());
+            let _ = tx.send(DependencyList { unit: name, result });
+        });
+    }
+
+    pub fn reverse_deps_loading(&self) -> bool {
+        self.reverse_deps_receiver.is_some()
+    }
+
+    fn finish_reverse_dependencies(&mut self, list: DependencyList) {
+        self.reverse_deps_receiver = None;
+        match list.result {
+            Ok(units) => {
+                self.status_message = None;
+                self.reverse_deps = units;
+                self.reverse_deps_unit = list.unit;
+                self.reverse_deps_scroll = 0;
+                self.show_reverse_deps = true;
+            }
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+
+    /// Rates the highlighted service's sandboxing with `systemd-analyze
+    /// security`; other unit types only get a header message.
+    pub fn open_security_analysis(&mut self) {
+        let Some(name) = self.selected_unit().map(|u| u.unit.clone()) else {
+            return;
+        };
+        let user_mode = self.user_mode;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.security_receiver = Some(rx);
+        self.status_message = Some(format!("Analyzing security of {}\u{2026}", name));
+        std::thread::spawn(move || {
+            let result = fetch_security_analysis(&name, user_mode, runner.as_ref());
+            let _ = tx.send(SecurityReport { unit: name, result });
+        });
+    }
+
+    pub fn security_loading(&self) -> bool {
+        self.security_receiver.is_some()
+    }
+
+    fn finish_security_analysis(&mut self, report: SecurityReport) {
+        self.security_receiver = None;
+        match report.result {
+            Ok(lines) => {
+                self.status_message = None;
+                self.security_lines = lines;
+                self.security_unit = report.unit;
+                self.security_scroll = 0;
+                self.show_security = true;
+            }
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+
+    pub fn close_security_analysis(&mut self) {
+        self.show_security = false;
+    }
+
+    pub fn security_scroll_up(&mut self, amount: usize) {
+        self.security_scroll = self.security_scroll.saturating_sub(amount);
+    }
+
+    /// Clamped by the renderer.
+    pub fn security_scroll_down(&mut self, amount: usize) {
+        self.security_scroll = self.security_scroll.saturating_add(amount);
+    }
+
+    /// Shows everything the last action's command printed.
+    pub fn open_action_output(&mut self) {
+        if self.last_action_output.is_none() {
+            self.status_message = Some("No action has run yet".to_string());
+            return;
+        }
+        self.show_action_output = true;
+        self.action_output_scroll = 0;
+    }
+
+    pub fn close_action_output(&mut self) {
+        self.show_action_output = false;
+    }
+
+    pub fn action_output_scroll_up(&mut self, amount: usize) {
+        self.action_output_scroll = self.action_output_scroll.saturating_sub(amount);
+    }
+
+    /// Clamped by the renderer, which knows the wrapped content height.
+    pub fn action_output_scroll_down(&mut self, amount: usize) {
+        self.action_output_scroll = self.action_output_scroll.saturating_add(amount);
+    }
+
+    pub fn close_reverse_dependencies(&mut self) {
+        self.show_reverse_deps = false;
+    }
+
+    pub fn reverse_deps_scroll_up(&mut self, amount: usize) {
+        self.reverse_deps_scroll = self.reverse_deps_scroll.saturating_sub(amount);
+    }
+
+    /// Clamped by the renderer, like the journal fields popup.
+    pub fn reverse_deps_scroll_down(&mut self, amount: usize) {
+        self.reverse_deps_scroll = self.reverse_deps_scroll.saturating_add(amount);
+    }
+
+    pub fn clear_dependency_filter(&mut self) {
+        self.dependency_filter = None;
+        self.dependency_filter_unit = None;
+        self.dependency_filter_receiver = None;
+        self.update_filter();
+    }
+
+    /// Opens the go-to-unit prompt. Unlike search it never filters the list;
+    /// it only moves the selection as the prefix is typed.
+    pub fn open_jump(&mut self) {
+        self.jump_mode = true;
+        self.jump_query.clear();
+    }
+
+    pub fn close_jump(&mut self) {
+        self.jump_mode = false;
+        self.jump_query.clear();
+    }
+
+    /// Selects the first visible unit whose name starts with `prefix`
+    /// (ignoring ASCII case). Returns false and leaves the selection alone
+    /// when nothing matches.
+    pub fn jump_to(&mut self, prefix: &str) -> bool {
+        let prefix = prefix.to_ascii_lowercase();
+        let found = self
+            .filtered_indices
+            .iter()
+            .position(|&i| self.services[i].unit.to_ascii_lowercase().starts_with(&prefix));
+        if let Some(pos) = found {
+            self.list_state.select(Some(pos));
+        }
+        found.is_some()
+    }
+
+    pub fn jump_query_push(&mut self, c: char) {
+        self.jump_query.push(c);
+        let query = self.jump_query.clone();
+        self.jump_to(&query);
+    }
+
+    pub fn jump_query_pop(&mut self) {
+        self.jump_query.pop();
+        let query = self.jump_query.clone();
+        self.jump_to(&query);
+    }
+
     pub fn open_status_picker(&mut self) {
         self.show_status_picker = true;
         let options = self.unit_type.status_options();
@@ -330,39 +1215,152 @@ impl App {
 
     pub fn type_picker_confirm(&mut self) {
         if let Some(i) = self.type_picker_state.selected() {
-            let new_type = UNIT_TYPES[i];
-            if new_type != self.unit_type {
-                self.unit_type = new_type;
-                self.system_logs_mode = false;
-                self.status_filter = None;
-                self.file_state_filter = None;
-                self.search_query.clear();
-                self.last_selected_service = None;
-                // A pending post-action refresh belongs to the old unit type.
-                self.refresh_receiver = None;
-                self.invalidate_log_stream();
-                self.logs.clear();
-                self.clear_log_search();
-                self.log_priority_filter = None;
-                self.log_time_range = TimeRange::All;
-                self.properties_cache.clear();
-                self.load_services();
-            }
+            self.set_unit_type(UNIT_TYPES[i]);
         }
         self.show_type_picker = false;
     }
 
+    /// Shows `new_type`'s units, resetting the filters and log state that
+    /// belonged to the old list.
+    pub fn set_unit_type(&mut self, new_type: UnitType) {
+        if new_type == self.unit_type {
+            return;
+        }
+        self.unit_type = new_type;
+        self.system_logs_mode = false;
+        self.status_filter = None;
+        self.file_state_filter = None;
+        self.dependency_filter = None;
+        self.dependency_filter_unit = None;
+        self.dependency_filter_receiver = None;
+        self.search_query.clear();
+        self.last_selected_service = None;
+        // A pending post-action refresh belongs to the old unit type.
+        self.refresh_receiver = None;
+        self.invalidate_log_stream();
+        self.logs.clear();
+        self.clear_log_search();
+        self.log_priority_filter = None;
+        self.log_time_range = TimeRange::All;
+        self.log_grep = None;
+        self.selected_units.clear();
+        self.properties_cache.clear();
+        self.log_scroll_memory.clear();
+        self.clear_state_changes();
+        self.load_services();
+    }
+
+    /// Switches to the next unit type in picker order, wrapping around.
+    pub fn next_unit_type(&mut self) {
+        let i = UNIT_TYPES.iter().position(|&t| t == self.unit_type).unwrap_or(0);
+        self.set_unit_type(UNIT_TYPES[(i + 1) % UNIT_TYPES.len()]);
+    }
+
+    /// Switches to the previous unit type in picker order, wrapping around.
+    pub fn prev_unit_type(&mut self) {
+        let i = UNIT_TYPES.iter().position(|&t| t == self.unit_type).unwrap_or(0);
+        self.set_unit_type(UNIT_TYPES[(i + UNIT_TYPES.len() - 1) % UNIT_TYPES.len()]);
+    }
+
+    /// The unit the highlighted timer triggers, or a status message saying
+    /// why there is none. A timer whose properties aren't cached yet has
+    /// them fetched in the background; open_timer_target_logs runs again
+    /// once they arrive.
+    fn selected_timer_target(&mut self) -> Option<String> {
+        let name = self.selected_unit()?.unit.clone();
+        if !name.ends_with(".timer") {
+            self.status_message = Some(format!("{} is not a timer", name));
+            return None;
+        }
+        let Some(props) = self.properties_cache.get(&name) else {
+            let user_mode = self.user_mode;
+            let runner = Arc::clone(&self.runner);
+            let (tx, rx) = mpsc::channel();
+            self.timer_target_receiver = Some(rx);
+            std::thread::spawn(move || {
+                let props = fetch_unit_properties(&name, user_mode, runner.as_ref());
+                let _ = tx.send(TimerProperties { timer: name, props });
+            });
+            return None;
+        };
+        let target = props.triggered_service(&name);
+        if target.is_none() {
+            self.status_message = Some(format!("{} triggers no unit", name));
+        }
+        target
+    }
+
+    /// Opens the logs of the unit the highlighted timer triggers, switching
+    /// the list to that unit's type. A unit the filters would hide is
+    /// brought into view by searching for its name.
+    pub fn open_timer_target_logs(&mut self) {
+        let Some(target) = self.selected_timer_target() else {
+            return;
+        };
+        // The all-units list already has the target, whatever its type.
+        if self.unit_type != UnitType::All {
+            let Some(unit_type) = unit_type_from_name(&target) else {
+                self.status_message = Some(format!("Can't list {}: unsupported unit type", target));
+                return;
+            };
+            self.set_unit_type(unit_type);
+        }
+        if !self.select_unit_by_name(&target) {
+            self.search_query = target.clone();
+            self.update_filter();
+            if !self.select_unit_by_name(&target) {
+                self.status_message = Some(format!("{} not found", target));
+                return;
+            }
+        }
+        self.system_logs_mode = false;
+        self.navigated_from_system_logs = false;
+        self.show_logs = true;
+        self.log_paused = false;
+        self.log_follow = true;
+        self.log_selected_entry = None;
+    }
+
+    pub fn timer_target_loading(&self) -> bool {
+        self.timer_target_receiver.is_some()
+    }
+
+    /// Caches the fetched properties and finishes the jump, unless the
+    /// user has moved off the timer meanwhile.
+    fn finish_timer_target(&mut self, fetched: TimerProperties) {
+        self.timer_target_receiver = None;
+        let still_selected = self.selected_unit().is_some_and(|u| u.unit == fetched.timer);
+        self.properties_cache.insert(fetched.timer, fetched.props);
+        if still_selected {
+            self.open_timer_target_logs();
+        }
+    }
+
+    /// Highlights `name` if it is in the filtered list.
+    fn select_unit_by_name(&mut self, name: &str) -> bool {
+        match self.filtered_indices.iter().position(|&i| self.services[i].unit == name) {
+            Some(pos) => {
+                self.list_state.select(Some(pos));
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn open_priority_picker(&mut self) {
         self.show_priority_picker = true;
-        let index = match self.log_priority_filter {
-            None => 0,
-            Some(p) => (p as usize) + 1,
+        let (index, anchor) = match self.log_priority_filter {
+            None => (0, None),
+            Some((0, high)) => ((high as usize) + 1, None),
+            Some((low, high)) => ((high as usize) + 1, Some(low)),
         };
+        self.priority_range_anchor = anchor;
         self.priority_picker_state.select(Some(index));
     }
 
     pub fn close_priority_picker(&mut self) {
         self.show_priority_picker = false;
+        self.priority_range_anchor = None;
     }
 
     pub fn priority_picker_next(&mut self) {
@@ -379,24 +1377,49 @@ impl App {
         self.priority_picker_state.select(Some(prev));
     }
 
-    pub fn priority_picker_confirm(&mut self) {
-        if let Some(i) = self.priority_picker_state.selected() {
-            if i == 0 {
+    /// Marks the highlighted level as one end of a range; the next Enter
+    /// uses the highlighted level as the other end. Toggling the same level
+    /// again drops the mark.
+    pub fn priority_picker_toggle_anchor(&mut self) {
+        let Some(i) = self.priority_picker_state.selected() else {
+            return;
+        };
+        if i == 0 {
+            return;
+        }
+        let level = (i - 1) as u8;
+        if self.priority_range_anchor == Some(level) {
+            self.priority_range_anchor = None;
+        } else {
+            self.priority_range_anchor = Some(level);
+        }
+    }
+
+    pub fn priority_picker_confirm(&mut self) {
+        if let Some(i) = self.priority_picker_state.selected() {
+            if i == 0 {
                 self.log_priority_filter = None;
             } else {
-                self.log_priority_filter = Some((i - 1) as u8);
+                let level = (i - 1) as u8;
+                self.log_priority_filter = Some(match self.priority_range_anchor {
+                    Some(anchor) => (anchor.min(level), anchor.max(level)),
+                    None => (0, level),
+                });
             }
             self.mark_logs_dirty();
         }
-        self.show_priority_picker = false;
+        self.close_priority_picker();
     }
 
     pub fn open_time_picker(&mut self) {
         self.show_time_picker = true;
-        let index = TIME_RANGES
-            .iter()
-            .position(|&t| t == self.log_time_range)
-            .unwrap_or(0);
+        let index = match self.log_time_range {
+            TimeRange::Custom { .. } => TIME_RANGES.len(),
+            ref current => TIME_RANGES
+                .iter()
+                .position(|t| t == current)
+                .unwrap_or(0),
+        };
         self.time_picker_state.select(Some(index));
     }
 
@@ -405,14 +1428,14 @@ impl App {
     }
 
     pub fn time_picker_next(&mut self) {
-        let len = TIME_RANGES.len();
+        let len = TIME_RANGES.len() + 1; // presets + "Custom..."
         let i = self.time_picker_state.selected().unwrap_or(0);
         let next = (i + 1) % len;
         self.time_picker_state.select(Some(next));
     }
 
     pub fn time_picker_previous(&mut self) {
-        let len = TIME_RANGES.len();
+        let len = TIME_RANGES.len() + 1;
         let i = self.time_picker_state.selected().unwrap_or(0);
         let prev = if i == 0 { len - 1 } else { i - 1 };
         self.time_picker_state.select(Some(prev));
@@ -420,1752 +1443,4863 @@ impl App {
 
     pub fn time_picker_confirm(&mut self) {
         if let Some(i) = self.time_picker_state.selected() {
-            self.log_time_range = TIME_RANGES[i];
+            if i >= TIME_RANGES.len() {
+                self.show_time_picker = false;
+                self.open_custom_time_input();
+                return;
+            }
+            self.log_time_range = TIME_RANGES[i].clone();
             self.mark_logs_dirty();
         }
         self.show_time_picker = false;
     }
 
-    pub fn next(&mut self) {
-        if self.filtered_indices.is_empty() {
-            return;
+    /// Opens the since/until prompt. The active custom range (or whatever
+    /// was typed last time) is kept so it can be tweaked rather than retyped.
+    pub fn open_custom_time_input(&mut self) {
+        if let TimeRange::Custom { since, until } = &self.log_time_range {
+            self.custom_since_input = since.clone();
+            self.custom_until_input = until.clone().unwrap_or_default();
         }
-        let max_index = self.filtered_indices.len() - 1;
-        let i = match self.list_state.selected() {
-            Some(i) => i.min(max_index).saturating_add(1).min(max_index),
-            None => 0,
-        };
-        self.list_state.select(Some(i));
+        self.custom_time_editing_until = false;
+        self.show_custom_time_input = true;
     }
 
-    pub fn previous(&mut self) {
-        if self.filtered_indices.is_empty() {
-            return;
-        }
-        let max_index = self.filtered_indices.len() - 1;
-        let i = match self.list_state.selected() {
-            Some(i) => i.min(max_index).saturating_sub(1),
-            None => 0,
-        };
-        self.list_state.select(Some(i));
+    pub fn close_custom_time_input(&mut self) {
+        self.show_custom_time_input = false;
     }
 
-    pub fn go_to_top(&mut self) {
-        if !self.filtered_indices.is_empty() {
-            self.list_state.select(Some(0));
-        }
+    pub fn custom_time_input_toggle_field(&mut self) {
+        self.custom_time_editing_until = !self.custom_time_editing_until;
     }
 
-    pub fn go_to_bottom(&mut self) {
-        if !self.filtered_indices.is_empty() {
-            self.list_state.select(Some(self.filtered_indices.len() - 1));
+    pub fn custom_time_input_push(&mut self, c: char) {
+        if self.custom_time_editing_until {
+            self.custom_until_input.push(c);
+        } else {
+            self.custom_since_input.push(c);
         }
     }
 
-    pub fn selected_unit(&self) -> Option<&SystemdUnit> {
-        self.list_state
-            .selected()
-            .and_then(|i| self.filtered_indices.get(i))
-            .map(|&i| &self.services[i])
+    pub fn custom_time_input_pop(&mut self) {
+        if self.custom_time_editing_until {
+            self.custom_until_input.pop();
+        } else {
+            self.custom_since_input.pop();
+        }
     }
 
-    pub fn load_logs_for_selected(&mut self) {
-        if self.system_logs_mode {
-            if !self.log_filters_dirty && !self.logs.is_empty() {
-                return;
-            }
-            self.invalidate_log_stream();
-            self.invalidate_log_entry_heights_cache();
-            self.log_filters_dirty = false;
-            self.logs_scroll = 0;
-            self.clear_log_search();
-            match fetch_log_entries(
-                None,
-                1000,
-                self.user_mode,
-                self.log_priority_filter,
-                self.log_time_range,
-                self.runner(),
-            ) {
-                Ok(logs) => {
-                    self.logs = logs;
-                    if !self.logs.is_empty() {
-                        self.logs_scroll = usize::MAX;
-                    }
-                }
-                Err(e) => {
-                    self.logs = vec![LogEntry {
-                        timestamp: None,
-                        priority: None,
-                        pid: None,
-                        identifier: None,
-                        message: format!("Error fetching logs: {}", e),
-                        message_styles: Vec::new(),
-                        boot_id: None,
-                        invocation_id: None,
-                        cursor: None,
-                        unit: None,
-                    }];
-                }
-            }
+    /// Applies the typed range. `since` is required; an empty `until` means
+    /// "up to now".
+    pub fn custom_time_input_confirm(&mut self) {
+        let since = self.custom_since_input.trim();
+        if since.is_empty() {
             return;
         }
+        let until = self.custom_until_input.trim();
+        self.log_time_range = TimeRange::Custom {
+            since: since.to_string(),
+            until: (!until.is_empty()).then(|| until.to_string()),
+        };
+        self.mark_logs_dirty();
+        self.show_custom_time_input = false;
+    }
 
-        let current_service = self.selected_unit().map(|s| s.unit.clone());
+    /// Steps the fetch limit to the next larger preset and reloads logs.
+    pub fn increase_log_line_limit(&mut self) {
+        if let Some(&next) = LOG_LINE_LIMITS.iter().find(|&&n| n > self.log_line_limit) {
+            self.log_line_limit = next;
+            self.mark_logs_dirty();
+        }
+    }
 
-        if current_service != self.last_selected_service || self.log_filters_dirty {
-            self.invalidate_log_stream();
-            self.invalidate_log_entry_heights_cache();
-            self.last_selected_service = current_service.clone();
-            self.log_filters_dirty = false;
-            self.logs_scroll = 0;
-            self.clear_log_search();
+    /// Steps the fetch limit to the next smaller preset and reloads logs.
+    pub fn decrease_log_line_limit(&mut self) {
+        if let Some(&prev) = LOG_LINE_LIMITS.iter().rev().find(|&&n| n < self.log_line_limit) {
+            self.log_line_limit = prev;
+            self.mark_logs_dirty();
+        }
+    }
 
-            if let Some(unit) = current_service {
-                match fetch_log_entries(
-                    Some(&unit),
-                    1000,
-                    self.user_mode,
-                    self.log_priority_filter,
-                    self.log_time_range,
-                    self.runner(),
-                ) {
-                    Ok(logs) => {
-                        self.logs = logs;
-                        if !self.logs.is_empty() {
-                            self.logs_scroll = usize::MAX;
-                        }
-                    }
-                    Err(e) => {
-                        self.logs = vec![LogEntry {
-                            timestamp: None,
-                            priority: None,
-                            pid: None,
-                            identifier: None,
-                            message: format!("Error fetching logs: {}", e),
-                            message_styles: Vec::new(),
-                            boot_id: None,
-                            invocation_id: None,
-                            cursor: None,
-                            unit: None,
-                        }];
-                    }
-                }
-            } else {
-                self.logs.clear();
+    /// Opens the journalctl `--grep` prompt, pre-filled with the active pattern.
+    pub fn open_grep_input(&mut self) {
+        match &self.log_grep {
+            Some(grep) => {
+                self.grep_input = grep.pattern.clone();
+                self.grep_case_sensitive_input = grep.case_sensitive;
+            }
+            None => {
+                self.grep_input.clear();
+                self.grep_case_sensitive_input = false;
             }
         }
+        self.show_grep_input = true;
     }
 
-    pub fn mark_logs_dirty(&mut self) {
-        self.log_filters_dirty = true;
+    pub fn close_grep_input(&mut self) {
+        self.show_grep_input = false;
     }
 
-    pub fn invalidate_log_entry_heights_cache(&mut self) {
-        self.cached_entry_heights_dirty = true;
+    pub fn grep_input_push(&mut self, c: char) {
+        self.grep_input.push(c);
     }
 
-    pub fn scroll_logs_up(&mut self, amount: usize) {
-        self.logs_scroll = self.logs_scroll.saturating_sub(amount);
+    pub fn grep_input_pop(&mut self) {
+        self.grep_input.pop();
     }
 
-    pub fn scroll_logs_down(&mut self, amount: usize) {
-        if !self.logs.is_empty() {
-            let max_scroll = self.logs.len().saturating_sub(1);
-            self.logs_scroll = self.logs_scroll.saturating_add(amount).min(max_scroll);
-        }
+    pub fn grep_input_toggle_case(&mut self) {
+        self.grep_case_sensitive_input = !self.grep_case_sensitive_input;
     }
 
-    pub fn toggle_logs(&mut self) {
-        self.show_logs = !self.show_logs;
-        self.log_paused = false;
-        self.log_selected_entry = None;
-        self.system_logs_mode = false;
-        self.navigated_from_system_logs = false;
-        if !self.show_logs {
-            self.last_selected_service = None;
+    /// Applies the typed pattern; an empty pattern clears the filter.
+    pub fn grep_input_confirm(&mut self) {
+        let grep = (!self.grep_input.is_empty()).then(|| LogGrep {
+            pattern: self.grep_input.clone(),
+            case_sensitive: self.grep_case_sensitive_input,
+        });
+        if grep != self.log_grep {
+            self.log_grep = grep;
+            self.mark_logs_dirty();
         }
+        self.show_grep_input = false;
     }
 
-    pub fn toggle_system_logs(&mut self) {
-        if self.system_logs_mode && self.show_logs {
-            self.system_logs_mode = false;
-            self.navigated_from_system_logs = false;
-            self.show_logs = false;
-            self.log_paused = false;
-            self.log_selected_entry = None;
-            self.last_selected_service = None;
-        } else {
-            self.system_logs_mode = true;
-            self.navigated_from_system_logs = false;
-            self.show_logs = true;
-            self.log_paused = false;
-            self.log_selected_entry = None;
-            self.invalidate_log_stream();
-            self.logs.clear();
-            self.invalidate_log_entry_heights_cache();
-            self.clear_log_search();
-            self.log_filters_dirty = true;
-        }
+    pub fn open_export_picker(&mut self) {
+        self.show_export_picker = true;
+        self.export_picker_state.select(Some(0));
     }
 
-    pub fn toggle_log_paused(&mut self, visible_lines: usize) {
-        self.log_paused = !self.log_paused;
-        if self.log_paused {
-            if !self.logs.is_empty() {
-                // Resolve a stale logs_scroll (including the usize::MAX "go to bottom"
-                // sentinel) so the walk below starts from a valid index.
-                self.logs_scroll = self.logs_scroll.min(self.logs.len().saturating_sub(1));
-                // Walk entries forward from logs_scroll, accumulating each entry's
-                // visual height (from cached_entry_heights, defaulting to 1). Stop
-                // when the next entry would exceed visible_lines, then select the
-                // last entry that fit — i.e. the bottom-most visible entry. The
-                // `used > 0` guard guarantees at least one entry is always selected,
-                // even if a single entry is taller than the viewport.
-                let mut used = 0;
-                let mut last = self.logs_scroll;
-                for i in self.logs_scroll..self.logs.len() {
-                    let h = self.cached_entry_heights.get(i).copied().unwrap_or(1);
-                    if used + h > visible_lines && used > 0 {
-                        break;
-                    }
-                    last = i;
-                    used += h;
-                }
-                self.log_selected_entry = Some(last);
-            }
-        } else {
-            self.log_selected_entry = None;
-            self.logs_go_to_bottom();
-        }
+    pub fn close_export_picker(&mut self) {
+        self.show_export_picker = false;
     }
 
-    pub fn log_select_next(&mut self) {
-        if let Some(sel) = self.log_selected_entry {
-            let max = self.logs.len().saturating_sub(1);
-            self.log_selected_entry = Some((sel + 1).min(max));
-        }
+    pub fn export_picker_next(&mut self) {
+        let len = LOG_EXPORT_FORMATS.len();
+        let i = self.export_picker_state.selected().unwrap_or(0);
+        self.export_picker_state.select(Some((i + 1) % len));
     }
 
-    pub fn log_select_previous(&mut self) {
-        if let Some(sel) = self.log_selected_entry {
-            self.log_selected_entry = Some(sel.saturating_sub(1));
-        }
+    pub fn export_picker_previous(&mut self) {
+        let len = LOG_EXPORT_FORMATS.len();
+        let i = self.export_picker_state.selected().unwrap_or(0);
+        let prev = if i == 0 { len - 1 } else { i - 1 };
+        self.export_picker_state.select(Some(prev));
     }
 
-    pub fn navigate_to_log_unit(&mut self) {
-        let unit_name = match self
-            .log_selected_entry
-            .and_then(|idx| self.logs.get(idx))
-            .and_then(|e| e.unit.as_ref())
-        {
-            Some(name) => name.clone(),
-            None => return,
-        };
-        // Find service in filtered list; bail out if it's excluded by current filters
-        let pos = match self
-            .filtered_indices
-            .iter()
-            .position(|&i| self.services[i].unit == unit_name)
-        {
-            Some(pos) => pos,
-            None => return,
-        };
-        self.list_state.select(Some(pos));
-        // Switch to per-unit log view
-        self.navigated_from_system_logs = true;
-        self.system_logs_mode = false;
-        self.log_selected_entry = None;
-        self.last_selected_service = None;
-        self.log_filters_dirty = true;
-        self.show_logs = true;
-        self.log_paused = false;
+    pub fn export_picker_confirm(&mut self) {
+        if let Some(i) = self.export_picker_state.selected() {
+            self.export_logs(LOG_EXPORT_FORMATS[i]);
+        }
+        self.show_export_picker = false;
     }
 
-    /// Starts a live-tail refresh on a background thread. No-op while a
-    /// previous refresh is still in flight; results are merged by
-    /// check_log_refresh_progress on the UI thread.
-    pub fn refresh_logs(&mut self) {
-        if self.log_refresh_receiver.is_some() {
+    /// Writes the loaded log entries to a new, user-only file in the temp
+    /// directory (see `save_text_file`) and reports the path in the status
+    /// bar.
+    pub fn export_logs(&mut self, format: LogExportFormat) {
+        if self.logs.is_empty() {
+            self.status_message = Some("No log entries to export".to_string());
             return;
         }
-        let unit_name = if self.system_logs_mode {
-            None
+        let source = if self.showing_kernel_logs() {
+            "kernel".to_string()
+        } else if self.system_logs_mode {
+            "journal".to_string()
         } else {
-            match self.last_selected_service.as_ref() {
-                Some(u) => Some(u.clone()),
-                None => return,
-            }
+            self.last_selected_service.clone().unwrap_or_else(|| "logs".to_string())
         };
-        let cursor = match self.logs.last().and_then(|e| e.cursor.as_ref()) {
-            Some(c) => c.clone(),
-            None => return,
+        let content = match format {
+            LogExportFormat::Plain => self.export_logs_plain(),
+            LogExportFormat::Csv => self.export_logs_csv(),
+            LogExportFormat::Json => self.export_logs_json(),
         };
-
-        let user_mode = self.user_mode;
-        let priority = self.log_priority_filter;
-        let time_range = self.log_time_range;
-        let runner = Arc::clone(&self.runner);
-        let (tx, rx) = mpsc::channel();
-        self.log_refresh_receiver = Some(rx);
-        self.log_refresh_generation = self.log_stream_generation;
-        std::thread::spawn(move || {
-            let entries = fetch_log_entries_after_cursor(
-                unit_name.as_deref(),
-                &cursor,
-                user_mode,
-                priority,
-                time_range,
-                runner.as_ref(),
-            )
-            .unwrap_or_default();
-            let _ = tx.send(entries);
+        let path = log_export_path(&std::env::temp_dir(), &source, format, chrono::Local::now());
+        self.status_message = Some(match save_text_file(&path, &content) {
+            Ok(()) => format!("Exported {} log entries to {}", self.logs.len(), path.display()),
+            Err(e) => e,
         });
     }
 
-    pub fn log_refresh_in_flight(&self) -> bool {
-        self.log_refresh_receiver.is_some()
+    /// Exported timestamps are always full ISO-8601 with microseconds, in
+    /// the zone the logs view is showing.
+    fn export_timestamp(&self, entry: &LogEntry) -> String {
+        entry
+            .timestamp
+            .map(|ts| format_log_timestamp(ts, TimestampFormat::Iso, self.use_utc, true))
+            .unwrap_or_default()
     }
 
-    pub fn check_log_refresh_progress(&mut self) {
-        let Some(rx) = &self.log_refresh_receiver else {
-            return;
-        };
-        let entries = match rx.try_recv() {
-            Ok(entries) => entries,
-            Err(mpsc::TryRecvError::Empty) => return,
-            Err(mpsc::TryRecvError::Disconnected) => {
-                self.log_refresh_receiver = None;
-                return;
+    /// One `<time> <host> <identifier>[<pid>]: <message>` line per entry,
+    /// like `journalctl -o short-iso`.
+    pub fn export_logs_plain(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.logs {
+            let mut prefix: Vec<String> = Vec::new();
+            if entry.timestamp.is_some() {
+                prefix.push(self.export_timestamp(entry));
             }
-        };
-        self.log_refresh_receiver = None;
-
-        // Drop results that belong to a log buffer that has since been
-        // replaced (unit switch, filter change, scope toggle), or that would
-        // disturb a paused view; the unchanged cursor refetches them later.
-        if self.log_refresh_generation != self.log_stream_generation
-            || !self.show_logs
-            || self.log_paused
-            || entries.is_empty()
-        {
-            return;
+            prefix.extend(entry.hostname.clone());
+            let source = match (&entry.identifier, &entry.pid) {
+                (Some(id), Some(pid)) => Some(format!("{}[{}]:", id, pid)),
+                (Some(id), None) => Some(format!("{}:", id)),
+                _ => None,
+            };
+            prefix.extend(source);
+            prefix.push(entry.message.clone());
+            out.push_str(&prefix.join(" "));
+            out.push('\n');
         }
-
-        self.logs.extend(entries);
-        self.invalidate_log_entry_heights_cache();
-        self.logs_scroll = usize::MAX;
+        out
+    }
+
+    /// RFC 4180 CSV with a header row; fields containing commas, quotes or
+    /// newlines are quoted.
+    pub fn export_logs_csv(&self) -> String {
+        let mut out = String::from("timestamp,hostname,priority,identifier,pid,message\n");
+        for entry in &self.logs {
+            let fields = [
+                self.export_timestamp(entry),
+                entry.hostname.clone().unwrap_or_default(),
+                entry.priority.map(|p| priority_label(p).to_string()).unwrap_or_default(),
+                entry.identifier.clone().unwrap_or_default(),
+                entry.pid.clone().unwrap_or_default(),
+                entry.message.clone(),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
     }
 
-    /// Marks the current log buffer as replaced, so in-flight live-tail
-    /// results for the previous buffer are discarded instead of merged.
-    fn invalidate_log_stream(&mut self) {
-        self.log_stream_generation = self.log_stream_generation.wrapping_add(1);
-        self.log_refresh_receiver = None;
+    /// A JSON array of objects with the same fields as the CSV export;
+    /// missing values are `null`.
+    pub fn export_logs_json(&self) -> String {
+        let entries: Vec<serde_json::Value> = self
+            .logs
+            .iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "timestamp": entry.timestamp.map(|_| self.export_timestamp(entry)),
+                    "hostname": entry.hostname,
+                    "priority": entry.priority.map(priority_label),
+                    "identifier": entry.identifier,
+                    "pid": entry.pid,
+                    "message": entry.message,
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&entries).unwrap_or_default()
     }
 
-    pub fn toggle_help(&mut self) {
-        self.show_help = !self.show_help;
-        self.help_scroll = 0;
+    /// Opens the presets picker: "Save current filters..." followed by the
+    /// saved presets, with the one matching the active filters highlighted.
+    pub fn open_preset_picker(&mut self) {
+        let index = self
+            .log_presets
+            .iter()
+            .position(|p| p.priority == self.log_priority_filter && p.time_range == self.log_time_range)
+            .map_or(0, |i| i + 1);
+        self.preset_picker_state.select(Some(index));
+        self.show_preset_picker = true;
     }
 
-    fn help_max_scroll(&self) -> u16 {
-        self.help_content_lines
-            .saturating_sub(self.help_viewport_lines)
+    pub fn close_preset_picker(&mut self) {
+        self.show_preset_picker = false;
     }
 
-    pub fn help_scroll_down(&mut self, amount: u16) {
-        let max = self.help_max_scroll();
-        self.help_scroll = self.help_scroll.saturating_add(amount).min(max);
+    pub fn preset_picker_next(&mut self) {
+        let len = self.log_presets.len() + 1;
+        let i = self.preset_picker_state.selected().unwrap_or(0);
+        self.preset_picker_state.select(Some((i + 1) % len));
     }
 
-    pub fn help_scroll_up(&mut self, amount: u16) {
-        self.help_scroll = self.help_scroll.saturating_sub(amount);
-    }
-
-    pub fn help_scroll_to_top(&mut self) {
-        self.help_scroll = 0;
-    }
-
-    pub fn help_scroll_to_bottom(&mut self) {
-        self.help_scroll = self.help_max_scroll();
+    pub fn preset_picker_previous(&mut self) {
+        let len = self.log_presets.len() + 1;
+        let i = self.preset_picker_state.selected().unwrap_or(0);
+        let prev = if i == 0 { len - 1 } else { i - 1 };
+        self.preset_picker_state.select(Some(prev));
     }
 
-    pub fn page_up(&mut self, page_size: usize) {
-        if self.filtered_indices.is_empty() {
-            return;
+    pub fn preset_picker_confirm(&mut self) {
+        match self.preset_picker_state.selected() {
+            Some(0) => {
+                self.show_preset_picker = false;
+                self.preset_name_input.clear();
+                self.show_preset_name_input = true;
+            }
+            Some(i) => self.apply_log_preset(i - 1),
+            None => {}
         }
-        let current = self.list_state.selected().unwrap_or(0);
-        let new_index = current.saturating_sub(page_size);
-        self.list_state.select(Some(new_index));
     }
 
-    pub fn page_down(&mut self, page_size: usize) {
-        if self.filtered_indices.is_empty() {
+    /// Deletes the highlighted preset (the "Save" row is left alone).
+    pub fn preset_picker_delete(&mut self) {
+        let Some(i) = self.preset_picker_state.selected().filter(|&i| i > 0) else {
             return;
-        }
-        let current = self.list_state.selected().unwrap_or(0);
-        let max_index = self.filtered_indices.len().saturating_sub(1);
-        let new_index = (current + page_size).min(max_index);
-        self.list_state.select(Some(new_index));
+        };
+        self.log_presets.remove(i - 1);
+        self.preset_picker_state.select(Some(i.min(self.log_presets.len())));
+        self.save_log_presets();
     }
 
-    pub fn update_log_search(&mut self) {
-        self.invalidate_log_entry_heights_cache();
-        self.log_search_matches.clear();
-        self.log_search_match_index = None;
-
-        if self.log_search_query.is_empty() {
+    /// Switches the logs to a saved preset's priority and time range.
+    pub fn apply_log_preset(&mut self, index: usize) {
+        let Some(preset) = self.log_presets.get(index) else {
             return;
-        }
+        };
+        self.log_priority_filter = preset.priority;
+        self.log_time_range = preset.time_range.clone();
+        self.mark_logs_dirty();
+        self.show_preset_picker = false;
+    }
 
-        let query = self.log_search_query.to_lowercase();
-        for (i, entry) in self.logs.iter().enumerate() {
-            if entry.message.to_lowercase().contains(&query) {
-                self.log_search_matches.push(i);
-            }
-        }
+    pub fn close_preset_name_input(&mut self) {
+        self.show_preset_name_input = false;
+    }
 
-        // Auto-scroll to first match
-        if !self.log_search_matches.is_empty() {
-            self.log_search_match_index = Some(0);
-            self.logs_scroll = self.log_search_matches[0];
-        }
+    pub fn preset_name_input_push(&mut self, c: char) {
+        self.preset_name_input.push(c);
     }
 
-    pub fn clear_log_search(&mut self) {
-        self.log_search_query.clear();
-        self.log_search_mode = false;
-        self.log_search_matches.clear();
-        self.log_search_match_index = None;
-        self.log_selected_entry = None;
-        self.invalidate_log_entry_heights_cache();
+    pub fn preset_name_input_pop(&mut self) {
+        self.preset_name_input.pop();
     }
 
-    pub fn next_log_match(&mut self, visible_lines: usize) {
-        if self.log_search_matches.is_empty() {
+    /// Saves the current priority and time range under the typed name,
+    /// replacing a preset that already has it. An empty name does nothing.
+    pub fn preset_name_input_confirm(&mut self) {
+        let name = self.preset_name_input.trim();
+        if name.is_empty() {
             return;
         }
-        let next = match self.log_search_match_index {
-            Some(i) => (i + 1) % self.log_search_matches.len(),
-            None => 0,
+        let preset = LogFilterPreset {
+            name: name.to_string(),
+            priority: self.log_priority_filter,
+            time_range: self.log_time_range.clone(),
         };
-        self.log_search_match_index = Some(next);
-        let line_idx = self.log_search_matches[next];
-        // Scroll so match is visible
-        if line_idx < self.logs_scroll || line_idx >= self.logs_scroll + visible_lines {
-            self.logs_scroll = line_idx;
+        match self.log_presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.log_presets.push(preset),
         }
+        self.show_preset_name_input = false;
+        self.save_log_presets();
     }
 
-    pub fn prev_log_match(&mut self, visible_lines: usize) {
-        if self.log_search_matches.is_empty() {
-            return;
-        }
-        let prev = match self.log_search_match_index {
-            Some(0) => self.log_search_matches.len() - 1,
-            Some(i) => i - 1,
-            None => self.log_search_matches.len() - 1,
-        };
-        self.log_search_match_index = Some(prev);
-        let line_idx = self.log_search_matches[prev];
-        if line_idx < self.logs_scroll || line_idx >= self.logs_scroll + visible_lines {
-            self.logs_scroll = line_idx;
-        }
+    fn save_log_presets(&mut self) {
+        let presets = self.log_presets.clone();
+        self.save_config("presets", |config| config.log_presets = presets);
     }
 
-    pub fn logs_go_to_top(&mut self) {
-        self.logs_scroll = 0;
+    pub fn open_timestamp_format_picker(&mut self) {
+        self.show_timestamp_format_picker = true;
+        let index = TIMESTAMP_FORMATS
+            .iter()
+            .position(|&f| f == self.log_timestamp_format)
+            .unwrap_or(0);
+        self.timestamp_format_picker_state.select(Some(index));
     }
 
-    pub fn logs_go_to_bottom(&mut self) {
-        if !self.logs.is_empty() {
-            // Sentinel value resolved by UI once panel dimensions are known.
-            self.logs_scroll = usize::MAX;
-        }
+    pub fn close_timestamp_format_picker(&mut self) {
+        self.show_timestamp_format_picker = false;
     }
 
-    pub fn toggle_user_mode(&mut self) {
-        self.user_mode = !self.user_mode;
-        self.system_logs_mode = false;
-        self.last_selected_service = None;
-        // A pending post-action refresh belongs to the old scope.
-        self.refresh_receiver = None;
-        self.invalidate_log_stream();
-        self.logs.clear();
-        self.invalidate_log_entry_heights_cache();
-        self.clear_log_search();
-        self.log_priority_filter = None;
-        self.log_time_range = TimeRange::All;
-        self.properties_cache.clear();
-        self.file_state_filter = None;
-        self.load_services();
+    pub fn timestamp_format_picker_next(&mut self) {
+        let len = TIMESTAMP_FORMATS.len();
+        let i = self.timestamp_format_picker_state.selected().unwrap_or(0);
+        let next = (i + 1) % len;
+        self.timestamp_format_picker_state.select(Some(next));
     }
 
-    // Details modal methods
+    pub fn timestamp_format_picker_previous(&mut self) {
+        let len = TIMESTAMP_FORMATS.len();
+        let i = self.timestamp_format_picker_state.selected().unwrap_or(0);
+        let prev = if i == 0 { len - 1 } else { i - 1 };
+        self.timestamp_format_picker_state.select(Some(prev));
+    }
 
-    pub fn open_details(&mut self) {
-        if let Some(unit) = self.selected_unit() {
-            let name = unit.unit.clone();
-            let props = if let Some(cached) = self.properties_cache.get(&name) {
-                cached.clone()
-            } else {
-                let props = fetch_unit_properties(&name, self.user_mode, self.runner());
-                self.properties_cache.insert(name.clone(), props.clone());
-                props
-            };
-            self.detail_unit_name = Some(name);
-            self.detail_properties = Some(props);
-            self.detail_scroll = 0;
-            self.show_details = true;
+    pub fn timestamp_format_picker_confirm(&mut self) {
+        if let Some(i) = self.timestamp_format_picker_state.selected() {
+            self.log_timestamp_format = TIMESTAMP_FORMATS[i];
+            // Timestamp width changes, so wrapped entry heights do too.
+            self.invalidate_log_entry_heights_cache();
         }
+        self.show_timestamp_format_picker = false;
     }
 
-    pub fn close_details(&mut self) {
-        self.show_details = false;
-        self.detail_properties = None;
-        self.detail_unit_name = None;
-        self.detail_scroll = 0;
+    pub fn next(&mut self) {
+        self.next_by(1);
     }
 
-    pub fn detail_scroll_up(&mut self, amount: usize) {
-        self.detail_scroll = self.detail_scroll.saturating_sub(amount);
+    pub fn previous(&mut self) {
+        self.previous_by(1);
     }
 
-    pub fn detail_scroll_down(&mut self, amount: usize, content_height: usize, visible_height: usize) {
-        if content_height > visible_height {
-            let max_scroll = content_height.saturating_sub(visible_height);
-            self.detail_scroll = (self.detail_scroll.saturating_add(amount)).min(max_scroll);
+    /// Moves the selection down `count` rows, clamped at the last row (or
+    /// wrapping past it with `wrap_navigation`).
+    pub fn next_by(&mut self, count: usize) {
+        if self.filtered_indices.is_empty() {
+            return;
         }
+        let len = self.filtered_indices.len();
+        let max_index = len - 1;
+        let i = match self.list_state.selected() {
+            Some(i) if self.wrap_navigation => (i.min(max_index) + count % len) % len,
+            Some(i) => i.min(max_index).saturating_add(count).min(max_index),
+            None => 0,
+        };
+        self.list_state.select(Some(i));
     }
 
-    // File state picker methods
-
-    pub fn open_file_state_picker(&mut self) {
-        self.show_file_state_picker = true;
-        let index = match &self.file_state_filter {
+    /// Moves the selection up `count` rows, clamped at the first row (or
+    /// wrapping past it with `wrap_navigation`).
+    pub fn previous_by(&mut self, count: usize) {
+        if self.filtered_indices.is_empty() {
+            return;
+        }
+        let len = self.filtered_indices.len();
+        let max_index = len - 1;
+        let i = match self.list_state.selected() {
+            Some(i) if self.wrap_navigation => (i.min(max_index) + len - count % len) % len,
+            Some(i) => i.min(max_index).saturating_sub(count),
             None => 0,
-            Some(s) => FILE_STATE_OPTIONS
-                .iter()
-                .position(|&opt| opt == s)
-                .unwrap_or(0),
         };
-        self.file_state_picker_state.select(Some(index));
+        self.list_state.select(Some(i));
     }
 
-    pub fn close_file_state_picker(&mut self) {
-        self.show_file_state_picker = false;
+    /// Selects the 1-based `line` of the list (vim's `10G`), clamped to the
+    /// list bounds.
+    pub fn go_to_line(&mut self, line: usize) {
+        if self.filtered_indices.is_empty() {
+            return;
+        }
+        let i = line.saturating_sub(1).min(self.filtered_indices.len() - 1);
+        self.list_state.select(Some(i));
     }
 
-    pub fn file_state_picker_next(&mut self) {
-        let len = FILE_STATE_OPTIONS.len();
-        let i = self.file_state_picker_state.selected().unwrap_or(0);
-        let next = (i + 1) % len;
-        self.file_state_picker_state.select(Some(next));
+    /// Appends a digit to the pending count prefix (`5` then `j`). The
+    /// count is shown in the status bar until a motion consumes it.
+    pub fn push_count_digit(&mut self, digit: u32) {
+        let count = self
+            .pending_count
+            .unwrap_or(0)
+            .saturating_mul(10)
+            .saturating_add(digit as usize);
+        self.pending_count = Some(count);
+        self.status_message = Some(count.to_string());
     }
 
-    pub fn file_state_picker_previous(&mut self) {
-        let len = FILE_STATE_OPTIONS.len();
-        let i = self.file_state_picker_state.selected().unwrap_or(0);
-        let prev = if i == 0 { len - 1 } else { i - 1 };
-        self.file_state_picker_state.select(Some(prev));
+    pub fn go_to_top(&mut self) {
+        if !self.filtered_indices.is_empty() {
+            self.list_state.select(Some(0));
+        }
     }
 
-    pub fn file_state_picker_confirm(&mut self) {
-        if let Some(i) = self.file_state_picker_state.selected() {
-            if i == 0 {
-                self.file_state_filter = None;
-            } else {
-                self.file_state_filter = Some(FILE_STATE_OPTIONS[i].to_string());
-            }
-            self.update_filter();
+    pub fn go_to_bottom(&mut self) {
+        if !self.filtered_indices.is_empty() {
+            self.list_state.select(Some(self.filtered_indices.len() - 1));
         }
-        self.show_file_state_picker = false;
     }
 
-    // Unit action picker methods
+    pub fn selected_unit(&self) -> Option<&SystemdUnit> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.filtered_indices.get(i))
+            .map(|&i| &self.services[i])
+    }
 
-    pub fn open_action_picker(&mut self) {
-        if let Some(unit) = self.selected_unit() {
-            let sub = unit.sub.clone();
-            let file_state = unit.file_state.clone();
-            self.available_actions =
-                UnitAction::available_actions(&sub, file_state.as_deref());
-            if !self.available_actions.is_empty() {
-                self.action_picker_state.select(Some(0));
-                self.show_action_picker = true;
+    /// Starts loading logs for the highlighted unit (or the journal-wide
+    /// view) when the selection or filters changed. The fetch runs on a
+    /// background thread; the previous entries stay on screen, dimmed, until
+    /// `check_log_load_progress` swaps the new ones in.
+    pub fn load_logs_for_selected(&mut self) {
+        let unit = if self.system_logs_mode {
+            if !self.log_filters_dirty && (self.logs_loading() || !self.logs.is_empty()) {
+                return;
             }
-        }
+            None
+        } else {
+            let current_service = self.selected_unit().map(|s| s.unit.clone());
+            if current_service == self.last_selected_service && !self.log_filters_dirty {
+                return;
+            }
+            if current_service != self.last_selected_service
+                && let Some(previous) = &self.last_selected_service
+                && !self.logs.is_empty()
+            {
+                self.log_scroll_memory.insert(previous.clone(), self.logs_scroll);
+            }
+            if current_service != self.last_selected_service {
+                self.pending_log_scroll = None;
+            }
+            self.last_selected_service = current_service.clone();
+            match current_service {
+                Some(unit) => Some(unit),
+                None => {
+                    self.invalidate_log_stream();
+                    self.log_filters_dirty = false;
+                    self.finish_log_load(Ok(Vec::new()));
+                    return;
+                }
+            }
+        };
+
+        self.invalidate_log_stream();
+        self.log_filters_dirty = false;
+        let lines = self.log_line_limit;
+        let user_mode = self.user_mode;
+        let mut filters = self.log_filters();
+        let filter_main_pid = self.filter_main_pid;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.log_load_receiver = Some(rx);
+        std::thread::spawn(move || {
+            // The PID can change on every restart, so resolve it fresh for
+            // each load; a unit that isn't running falls back to all lines.
+            filters.main_pid = match &unit {
+                Some(name) if filter_main_pid => {
+                    let pid = fetch_unit_properties(name, user_mode, runner.as_ref()).main_pid;
+                    (pid > 0).then_some(pid)
+                }
+                _ => None,
+            };
+            let main_pid = filters.main_pid;
+            let result =
+                fetch_log_entries(unit.as_deref(), lines, user_mode, &filters, runner.as_ref());
+            let _ = tx.send(LogLoad { unit, main_pid, result });
+        });
     }
 
-    pub fn close_action_picker(&mut self) {
-        self.show_action_picker = false;
+    /// Approximate size of the loaded logs: the sum of message lengths in
+    /// bytes, ignoring timestamps and other fields.
+    pub fn logs_byte_size(&self) -> u64 {
+        self.logs.iter().map(|e| e.message.len() as u64).sum()
     }
 
-    pub fn action_picker_next(&mut self) {
-        let len = self.available_actions.len();
-        if len == 0 {
+    pub fn logs_loading(&self) -> bool {
+        self.log_load_receiver.is_some()
+    }
+
+    pub fn check_log_load_progress(&mut self) {
+        let Some(rx) = &self.log_load_receiver else {
             return;
+        };
+        let load = match rx.try_recv() {
+            Ok(load) => load,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.log_load_receiver = None;
+                return;
+            }
+        };
+        self.log_load_receiver = None;
+        if self.log_load_is_current(&load) {
+            self.log_main_pid = load.main_pid;
+            self.finish_log_load(load.result);
         }
-        let i = self.action_picker_state.selected().unwrap_or(0);
-        let next = (i + 1) % len;
-        self.action_picker_state.select(Some(next));
     }
 
-    pub fn action_picker_previous(&mut self) {
-        let len = self.available_actions.len();
-        if len == 0 {
-            return;
+    /// A load is current only if it was requested for what the log panel is
+    /// showing now; anything else belongs to a unit the user moved away from.
+    fn log_load_is_current(&self, load: &LogLoad) -> bool {
+        if self.system_logs_mode {
+            load.unit.is_none()
+        } else {
+            load.unit.is_some() && load.unit == self.last_selected_service
         }
-        let i = self.action_picker_state.selected().unwrap_or(0);
-        let prev = if i == 0 { len - 1 } else { i - 1 };
-        self.action_picker_state.select(Some(prev));
     }
 
-    pub fn action_picker_confirm(&mut self) {
-        if let Some(i) = self.action_picker_state.selected()
-            && let Some(&action) = self.available_actions.get(i)
-        {
-            let unit_name = self
-                .selected_unit()
-                .map(|u| u.unit.clone())
-                .unwrap_or_default();
-            self.confirm_action = Some(action);
-            self.confirm_unit_name = Some(unit_name);
-            self.show_action_picker = false;
-            self.show_confirm = true;
+    fn finish_log_load(&mut self, result: Result<Vec<LogEntry>, String>) {
+        self.invalidate_log_entry_heights_cache();
+        self.logs_scroll = 0;
+        self.logs_h_scroll = 0;
+        self.log_history_exhausted = false;
+        self.clear_log_search();
+        self.logs = match result {
+            Ok(logs) => logs,
+            Err(e) => vec![LogEntry {
+                timestamp: None,
+                priority: None,
+                pid: None,
+                identifier: None,
+                message: format!("Error fetching logs: {}", e),
+                message_styles: Vec::new(),
+                boot_id: None,
+                invocation_id: None,
+                cursor: None,
+                unit: None,
+                hostname: None,
+                fields: HashMap::new(),
+            }],
+        };
+        if !self.logs.is_empty() {
+            self.logs_scroll = usize::MAX;
+            // Live tail always follows the newest entry; only a paused view
+            // goes back to where the user left this unit's logs.
+            if self.log_paused
+                && !self.system_logs_mode
+                && let Some(saved) = self
+                    .last_selected_service
+                    .as_ref()
+                    .and_then(|unit| self.log_scroll_memory.get(unit))
+            {
+                self.logs_scroll = *saved;
+            }
+            if let Some(scroll) = self.pending_log_scroll.take() {
+                self.logs_scroll = scroll.min(self.logs.len() - 1);
+            }
         }
+        self.log_follow = self.logs_scroll == usize::MAX || self.logs.is_empty();
+        self.pending_log_scroll = None;
     }
 
-    pub fn confirm_yes(&mut self) {
-        if let (Some(action), Some(unit_name)) = (self.confirm_action, &self.confirm_unit_name)
-        {
-            let unit_name = unit_name.clone();
-            let user_mode = self.user_mode;
-            let unit_type = self.unit_type;
-            let runner = Arc::clone(&self.runner);
-            let (action_tx, action_rx) = mpsc::channel();
-            let (refresh_tx, refresh_rx) = mpsc::channel();
-            self.action_in_progress = true;
-            self.action_receiver = Some(action_rx);
-            self.refresh_receiver = Some(refresh_rx);
-            std::thread::spawn(move || {
-                let result = execute_unit_action(action, &unit_name, user_mode, runner.as_ref());
-                let _ = action_tx.send(result);
-                if let Ok(units) = fetch_units(unit_type, user_mode, runner.as_ref()) {
-                    let _ = refresh_tx.send(units);
-                }
-                // Unit state can still be settling right after the job
-                // completes (deactivating, auto-restart, oneshot exit);
-                // refetch once more so the list converges on the final state.
-                std::thread::sleep(std::time::Duration::from_millis(1500));
-                if let Ok(units) = fetch_units(unit_type, user_mode, runner.as_ref()) {
-                    let _ = refresh_tx.send(units);
-                }
-            });
+    pub fn log_filters(&self) -> LogFilters {
+        // Kernel messages only replace the journal-wide view; a unit opened
+        // from it always shows that unit's own entries.
+        let source = if self.system_logs_mode {
+            self.log_source
+        } else {
+            LogSource::Unit
+        };
+        LogFilters {
+            source,
+            priority: self.log_priority_filter,
+            time_range: self.log_time_range.clone(),
+            grep: self.log_grep.clone(),
+            main_pid: if self.filter_main_pid && !self.system_logs_mode {
+                self.log_main_pid
+            } else {
+                None
+            },
         }
     }
 
-    pub fn refresh_in_flight(&self) -> bool {
-        self.refresh_receiver.is_some()
+    /// journalctl arguments for viewing the current logs in the external
+    /// pager: the open log view with its filters, or, from the unit list, the
+    /// highlighted unit with the priority and time range filters.
+    pub fn journal_pager_args(&self) -> Option<Vec<String>> {
+        if self.show_logs {
+            let unit = if self.system_logs_mode {
+                None
+            } else {
+                Some(self.last_selected_service.as_deref()?)
+            };
+            return Some(journal_pager_args(unit, self.user_mode, &self.log_filters()));
+        }
+        let unit = self.selected_unit()?;
+        let filters = LogFilters {
+            source: LogSource::Unit,
+            main_pid: None,
+            ..self.log_filters()
+        };
+        Some(journal_pager_args(Some(&unit.unit), self.user_mode, &filters))
     }
 
-    pub fn check_action_progress(&mut self) {
-        if let Some(ref rx) = self.action_receiver
-            && let Ok(result) = rx.try_recv()
-        {
-            self.action_in_progress = false;
-            self.action_result = Some(result);
-            self.action_receiver = None;
-            if self.show_logs {
-                self.mark_logs_dirty();
-            }
+    /// Toggles narrowing unit logs to the unit's main process (`_PID=`),
+    /// hiding lines from its children. Reloads the logs.
+    pub fn toggle_filter_main_pid(&mut self) {
+        self.filter_main_pid = !self.filter_main_pid;
+        self.log_main_pid = None;
+        self.mark_logs_dirty();
+    }
+
+    /// Fetches the chunk of entries preceding the oldest loaded one on a
+    /// background thread; check_older_logs_progress prepends it. Called when
+    /// scrolling up past the top of the buffer.
+    pub fn load_older_logs(&mut self) {
+        if self.log_history_exhausted || self.logs_loading() || self.older_logs_loading() {
+            return;
         }
-        // The action thread sends several list refreshes; apply everything
-        // queued and keep the receiver until the thread is done with it.
-        if let Some(rx) = self.refresh_receiver.take() {
-            let mut disconnected = false;
-            loop {
-                match rx.try_recv() {
-                    Ok(units) => {
-                        self.properties_cache.clear();
-                        self.services = units;
-                        self.last_refreshed = Some(chrono::Local::now());
-                        self.update_filter();
-                    }
-                    Err(mpsc::TryRecvError::Empty) => break,
-                    Err(mpsc::TryRecvError::Disconnected) => {
-                        disconnected = true;
-                        break;
-                    }
-                }
+        let unit_name = if self.system_logs_mode {
+            None
+        } else {
+            match self.last_selected_service.as_ref() {
+                Some(u) => Some(u.clone()),
+                None => return,
             }
-            if !disconnected {
-                self.refresh_receiver = Some(rx);
+        };
+        let cursor = match self.logs.first().and_then(|e| e.cursor.as_ref()) {
+            Some(c) => c.clone(),
+            None => return,
+        };
+        let lines = self.log_line_limit;
+        let user_mode = self.user_mode;
+        let filters = self.log_filters();
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.older_logs_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let result = fetch_log_entries_before_cursor(
+                unit_name.as_deref(),
+                &cursor,
+                lines,
+                user_mode,
+                &filters,
+                runner.as_ref(),
+            );
+            let _ = tx.send(result);
+        });
+    }
+
+    pub fn older_logs_loading(&self) -> bool {
+        self.older_logs_receiver.is_some()
+    }
+
+    /// Only an empty chunk means the journal has nothing older; a failed
+    /// fetch (e.g. a dropped SSH connection) is reported and can be retried
+    /// by scrolling up again.
+    pub fn check_older_logs_progress(&mut self) {
+        let Some(rx) = &self.older_logs_receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.older_logs_receiver = None;
+                return;
             }
+        };
+        self.older_logs_receiver = None;
+        match result {
+            Ok(older) if older.is_empty() => self.log_history_exhausted = true,
+            Ok(older) => self.prepend_logs(older),
+            Err(e) => self.status_message = Some(format!("Could not load older logs: {}", e)),
         }
     }
 
-    pub fn confirm_no(&mut self) {
-        self.show_confirm = false;
-        self.confirm_action = None;
-        self.confirm_unit_name = None;
-        self.action_in_progress = false;
-        self.action_result = None;
-        self.action_receiver = None;
+    /// Inserts older entries in front of the buffer, shifting every stored
+    /// entry index so the view, selection and search hits stay on the same
+    /// entries.
+    fn prepend_logs(&mut self, older: Vec<LogEntry>) {
+        let added = older.len();
+        self.logs.splice(0..0, older);
+        self.logs_scroll = self.logs_scroll.saturating_add(added);
+        if let Some(sel) = self.log_selected_entry.as_mut() {
+            *sel += added;
+        }
+        for m in self.log_search_matches.iter_mut() {
+            *m += added;
+        }
+        self.invalidate_log_entry_heights_cache();
     }
 
-    // Note: the refresh receiver is deliberately kept alive here — dismissing
-    // the result popup must not discard a unit-list refresh that is still in
-    // flight (easily hit over SSH, where refetching takes a network round
-    // trip).
-    pub fn dismiss_action_result(&mut self) {
-        self.show_confirm = false;
-        self.confirm_action = None;
-        self.confirm_unit_name = None;
-        self.action_in_progress = false;
-        self.action_result = None;
-        self.action_receiver = None;
+    pub fn mark_logs_dirty(&mut self) {
+        self.log_filters_dirty = true;
     }
 
-    pub fn clear_status_message(&mut self) {
-        self.status_message = None;
+    /// Re-fetches the open logs from scratch on the next render, e.g. after
+    /// something outside the app rotated or vacuumed the journal. A view
+    /// scrolled away from the newest entries keeps its position.
+    pub fn force_reload_logs(&mut self) {
+        self.pending_log_scroll = (!self.logs_at_bottom).then_some(self.logs_scroll);
+        self.mark_logs_dirty();
     }
 
-    // Unit file viewer methods
+    pub fn invalidate_log_entry_heights_cache(&mut self) {
+        self.cached_entry_heights_dirty = true;
+    }
 
-    pub fn open_unit_file(&mut self) {
-        if let Some(unit) = self.selected_unit() {
-            let name = unit.unit.clone();
-            match fetch_unit_file_content(&name, self.user_mode, self.runner()) {
-                Ok(lines) => {
-                    self.unit_file_content = lines;
-                }
-                Err(e) => {
-                    self.unit_file_content = vec![format!("Error: {}", e)];
-                }
+    pub fn scroll_logs_up(&mut self, amount: usize) {
+        self.log_follow = false;
+        if self.cached_log_repeat_counts.is_empty() {
+            self.logs_scroll = self.logs_scroll.saturating_sub(amount);
+        } else {
+            self.logs_scroll = self.log_rows_back(self.logs_scroll, amount);
+        }
+    }
+
+    pub fn scroll_logs_down(&mut self, amount: usize) {
+        if !self.logs.is_empty() {
+            let max_scroll = self.logs.len().saturating_sub(1);
+            if self.cached_log_repeat_counts.is_empty() {
+                self.logs_scroll = self.logs_scroll.saturating_add(amount).min(max_scroll);
+            } else {
+                self.logs_scroll = self.log_rows_forward(self.logs_scroll, amount);
             }
-            self.unit_file_unit_name = Some(name);
-            self.unit_file_scroll = 0;
-            self.unit_file_search_query.clear();
-            self.unit_file_search_matches.clear();
-            self.unit_file_search_match_index = None;
-            self.unit_file_search_mode = false;
-            self.show_unit_file = true;
         }
     }
 
-    pub fn close_unit_file(&mut self) {
-        self.show_unit_file = false;
-        self.unit_file_content.clear();
-        self.unit_file_scroll = 0;
-        self.unit_file_unit_name = None;
-        self.unit_file_search_query.clear();
-        self.unit_file_search_matches.clear();
-        self.unit_file_search_match_index = None;
-        self.unit_file_search_mode = false;
+    /// Toggles folding runs of identical consecutive messages into one line
+    /// with a repeat count. Only the display changes; `logs` and search
+    /// still see every entry.
+    pub fn toggle_collapse_log_repeats(&mut self) {
+        self.collapse_log_repeats = !self.collapse_log_repeats;
+        self.invalidate_log_entry_heights_cache();
     }
 
-    pub fn scroll_unit_file_up(&mut self, amount: usize) {
-        self.unit_file_scroll = self.unit_file_scroll.saturating_sub(amount);
+    /// How many entries the displayed row starting at `index` stands for:
+    /// 1 normally, N for the first entry of a collapsed run, 0 for entries
+    /// folded into the row above.
+    pub fn log_repeat_count(&self, index: usize) -> usize {
+        self.cached_log_repeat_counts.get(index).copied().unwrap_or(1)
     }
 
-    pub fn scroll_unit_file_down(&mut self, amount: usize) {
-        if !self.unit_file_content.is_empty() {
-            self.unit_file_scroll = self.unit_file_scroll.saturating_add(amount);
+    /// The first entry of the displayed row that `index` belongs to.
+    pub fn log_row_start(&self, mut index: usize) -> usize {
+        while index > 0 && self.log_repeat_count(index) == 0 {
+            index -= 1;
         }
+        index
+    }
+
+    /// Moves `rows` displayed rows down from `index`, stepping over
+    /// collapsed runs and stopping at the last row.
+    fn log_rows_forward(&self, index: usize, rows: usize) -> usize {
+        let max = self.logs.len().saturating_sub(1);
+        let mut pos = self.log_row_start(index.min(max));
+        for _ in 0..rows {
+            let next = pos + self.log_repeat_count(pos).max(1);
+            if next > max {
+                break;
+            }
+            pos = next;
+        }
+        pos
     }
 
-    pub fn unit_file_go_to_top(&mut self) {
-        self.unit_file_scroll = 0;
+    /// Moves `rows` displayed rows up from `index`, landing on row starts.
+    fn log_rows_back(&self, index: usize, rows: usize) -> usize {
+        let mut pos = self.log_row_start(index.min(self.logs.len().saturating_sub(1)));
+        for _ in 0..rows {
+            if pos == 0 {
+                break;
+            }
+            pos = self.log_row_start(pos - 1);
+        }
+        pos
     }
 
-    pub fn unit_file_go_to_bottom(&mut self) {
-        if !self.unit_file_content.is_empty() {
-            self.unit_file_scroll = usize::MAX;
+    /// Toggles line wrapping in the logs view. Horizontal scrolling only
+    /// applies to unwrapped lines, so the offset starts over either way.
+    pub fn toggle_log_wrap(&mut self) {
+        self.log_wrap = !self.log_wrap;
+        self.logs_h_scroll = 0;
+        self.invalidate_log_entry_heights_cache();
+    }
+
+    pub fn toggle_utc(&mut self) {
+        self.use_utc = !self.use_utc;
+        // Offsets like "+05:30" vs "+00:00" can change ISO timestamp widths.
+        self.invalidate_log_entry_heights_cache();
+    }
+
+    /// Shows or hides the microseconds in log timestamps.
+    pub fn toggle_log_timestamp_micros(&mut self) {
+        self.log_timestamp_micros = !self.log_timestamp_micros;
+        // Seven more characters per timestamp changes wrapped heights.
+        self.invalidate_log_entry_heights_cache();
+    }
+
+    /// The entry the fields popup describes: the selected one while paused,
+    /// otherwise the newest.
+    pub fn log_fields_entry(&self) -> Option<&LogEntry> {
+        match self.log_selected_entry {
+            Some(idx) => self.logs.get(idx),
+            None => self.logs.last(),
         }
     }
 
-    pub fn update_unit_file_search(&mut self) {
-        self.unit_file_search_matches.clear();
-        self.unit_file_search_match_index = None;
+    /// Every journal field of the current entry as sorted `(name, value)`
+    /// pairs.
+    pub fn log_fields(&self) -> Vec<(&str, &str)> {
+        let mut fields: Vec<(&str, &str)> = self
+            .log_fields_entry()
+            .map(|entry| entry.fields.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect())
+            .unwrap_or_default();
+        fields.sort();
+        fields
+    }
 
-        if self.unit_file_search_query.is_empty() {
+    pub fn open_log_fields(&mut self) {
+        if self.log_fields_entry().is_none_or(|entry| entry.fields.is_empty()) {
+            self.status_message = Some("No journal fields for this entry".to_string());
             return;
         }
+        self.show_log_fields = true;
+        self.log_fields_scroll = 0;
+    }
 
-        let query = self.unit_file_search_query.to_lowercase();
-        for (i, line) in self.unit_file_content.iter().enumerate() {
-            if line.to_lowercase().contains(&query) {
-                self.unit_file_search_matches.push(i);
-            }
-        }
+    pub fn close_log_fields(&mut self) {
+        self.show_log_fields = false;
+    }
 
-        if !self.unit_file_search_matches.is_empty() {
-            self.unit_file_search_match_index = Some(0);
-            self.unit_file_scroll = self.unit_file_search_matches[0];
-        }
+    pub fn log_fields_scroll_up(&mut self, amount: usize) {
+        self.log_fields_scroll = self.log_fields_scroll.saturating_sub(amount);
     }
 
-    pub fn clear_unit_file_search(&mut self) {
-        self.unit_file_search_query.clear();
-        self.unit_file_search_mode = false;
-        self.unit_file_search_matches.clear();
-        self.unit_file_search_match_index = None;
+    /// Clamped by the renderer, which knows the wrapped content height.
+    pub fn log_fields_scroll_down(&mut self, amount: usize) {
+        self.log_fields_scroll = self.log_fields_scroll.saturating_add(amount);
     }
 
-    pub fn next_unit_file_match(&mut self, visible_lines: usize) {
-        if self.unit_file_search_matches.is_empty() {
-            return;
-        }
-        let next = match self.unit_file_search_match_index {
-            Some(i) => (i + 1) % self.unit_file_search_matches.len(),
-            None => 0,
+    /// Labels boot separators `Boot #N` instead of a truncated boot id.
+    pub fn toggle_log_boot_indices(&mut self) {
+        self.log_boot_indices = !self.log_boot_indices;
+    }
+
+    /// Cycles the dim threshold: off, then warning, then err.
+    pub fn cycle_dim_below_priority(&mut self) {
+        self.dim_below_priority = match self.dim_below_priority {
+            None => Some(4),
+            Some(4) => Some(3),
+            _ => None,
         };
-        self.unit_file_search_match_index = Some(next);
-        let line_idx = self.unit_file_search_matches[next];
-        if line_idx < self.unit_file_scroll
-            || line_idx >= self.unit_file_scroll + visible_lines
-        {
-            self.unit_file_scroll = line_idx;
+        self.status_message = Some(match self.dim_below_priority {
+            Some(p) => format!("Dimming entries below {}", priority_label(p)),
+            None => "Dimming off".into(),
+        });
+    }
+
+    /// Shows or hides each entry's `_HOSTNAME` after the timestamp.
+    pub fn toggle_log_hostname(&mut self) {
+        self.log_show_hostname = !self.log_show_hostname;
+        self.invalidate_log_entry_heights_cache();
+    }
+
+    pub fn scroll_logs_left(&mut self, amount: usize) {
+        self.logs_h_scroll = self.logs_h_scroll.saturating_sub(amount);
+    }
+
+    /// Clamped against the longest visible line by the UI once the panel
+    /// width is known.
+    pub fn scroll_logs_right(&mut self, amount: usize) {
+        if !self.log_wrap {
+            self.logs_h_scroll = self.logs_h_scroll.saturating_add(amount);
         }
     }
 
-    pub fn prev_unit_file_match(&mut self, visible_lines: usize) {
-        if self.unit_file_search_matches.is_empty() {
+    pub fn toggle_logs(&mut self) {
+        // From the split view, the logs go full screen.
+        if self.split_view {
+            self.split_view = false;
+            self.focused_pane = Pane::List;
             return;
         }
-        let prev = match self.unit_file_search_match_index {
-            Some(0) => self.unit_file_search_matches.len() - 1,
-            Some(i) => i - 1,
-            None => self.unit_file_search_matches.len() - 1,
-        };
-        self.unit_file_search_match_index = Some(prev);
-        let line_idx = self.unit_file_search_matches[prev];
-        if line_idx < self.unit_file_scroll
-            || line_idx >= self.unit_file_scroll + visible_lines
-        {
-            self.unit_file_scroll = line_idx;
+        self.show_logs = !self.show_logs;
+        self.log_paused = false;
+        self.log_follow = true;
+        self.log_selected_entry = None;
+        self.system_logs_mode = false;
+        self.navigated_from_system_logs = false;
+        if !self.show_logs {
+            self.last_selected_service = None;
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::service::{LogEntry, SystemdUnit, UnitAction, UnitProperties, UnitType, TimeRange};
+    /// Shows the unit list and the selected unit's logs side by side, or
+    /// goes back to the list alone. Opening it from the full-screen logs
+    /// keeps whatever logs are showing.
+    pub fn toggle_split_view(&mut self) {
+        if self.split_view {
+            self.split_view = false;
+            self.toggle_logs();
+        } else {
+            if !self.show_logs {
+                self.toggle_logs();
+            }
+            self.split_view = true;
+        }
+        self.focused_pane = Pane::List;
+    }
 
-    fn make_unit(name: &str, sub: &str, desc: &str, file_state: Option<&str>) -> SystemdUnit {
-        SystemdUnit {
-            unit: name.into(),
-            load: "loaded".into(),
-            active: "active".into(),
-            sub: sub.into(),
-            description: desc.into(),
-            detail: None,
-            file_state: file_state.map(|s| s.into()),
+    /// Moves the keyboard to the other split view pane.
+    pub fn focus_next_pane(&mut self) {
+        if !self.split_view {
+            return;
         }
+        self.focused_pane = match self.focused_pane {
+            Pane::List => Pane::Logs,
+            Pane::Logs => Pane::List,
+        };
     }
 
-    fn make_log(message: &str) -> LogEntry {
-        LogEntry {
-            timestamp: None,
-            priority: None,
-            pid: None,
-            identifier: None,
-            message: message.into(),
-            message_styles: Vec::new(),
-            boot_id: None,
-            invocation_id: None,
-            cursor: None,
-            unit: None,
+    /// Whether keys go to the logs: the full-screen logs view, or the split
+    /// view with the logs pane focused.
+    pub fn logs_focused(&self) -> bool {
+        self.show_logs && (!self.split_view || self.focused_pane == Pane::Logs)
+    }
+
+    pub fn toggle_system_logs(&mut self) {
+        self.toggle_journal_logs(LogSource::Unit);
+    }
+
+    pub fn toggle_kernel_logs(&mut self) {
+        self.toggle_journal_logs(LogSource::Kernel);
+    }
+
+    pub fn showing_kernel_logs(&self) -> bool {
+        self.system_logs_mode && self.log_source == LogSource::Kernel
+    }
+
+    /// Opens the journal-wide logs view for `source`, or closes it when that
+    /// source is already showing. Switching between sources reloads in place.
+    fn toggle_journal_logs(&mut self, source: LogSource) {
+        if self.system_logs_mode && self.show_logs && self.log_source == source {
+            self.system_logs_mode = false;
+            self.navigated_from_system_logs = false;
+            self.show_logs = false;
+            self.split_view = false;
+            self.log_paused = false;
+            self.log_follow = true;
+            self.log_selected_entry = None;
+            self.last_selected_service = None;
+        } else {
+            self.system_logs_mode = true;
+            self.log_source = source;
+            self.navigated_from_system_logs = false;
+            self.show_logs = true;
+            self.log_paused = false;
+            self.log_follow = true;
+            self.log_selected_entry = None;
+            self.invalidate_log_stream();
+            self.logs.clear();
+            self.invalidate_log_entry_heights_cache();
+            self.clear_log_search();
+            self.log_filters_dirty = true;
         }
     }
 
-    fn test_app_with_services(services: Vec<SystemdUnit>) -> App {
-        let len = services.len();
-        let mut app = App {
-            services,
-            list_state: ListState::default(),
-            should_quit: false,
-            error: None,
-            search_query: String::new(),
-            search_mode: false,
-            filtered_indices: (0..len).collect(),
-            logs: Vec::new(),
-            cached_entry_heights: Vec::new(),
-            cached_entry_heights_width: 0,
-            cached_entry_heights_query: String::new(),
-            cached_entry_heights_dirty: true,
-            logs_scroll: 0,
-            last_selected_service: None,
-            status_filter: None,
-            show_logs: false,
-            show_help: false,
-            help_scroll: 0,
-            help_content_lines: 0,
-            help_viewport_lines: 0,
-            show_status_picker: false,
-            status_picker_state: ListState::default(),
-            log_search_query: String::new(),
-            log_search_mode: false,
-            log_search_matches: Vec::new(),
-            log_search_match_index: None,
-            user_mode: false,
-            runner: Arc::new(crate::service::LocalRunner),
-            host_label: None,
-            unit_type: UnitType::Service,
-            show_type_picker: false,
-            type_picker_state: ListState::default(),
-            log_priority_filter: None,
-            log_time_range: TimeRange::All,
-            log_filters_dirty: false,
-            show_priority_picker: false,
-            priority_picker_state: ListState::default(),
-            show_time_picker: false,
-            time_picker_state: ListState::default(),
-            show_details: false,
-            detail_scroll: 0,
-            detail_properties: None,
-            detail_unit_name: None,
-            detail_content_height: 0,
-            properties_cache: HashMap::new(),
-            file_state_filter: None,
-            show_file_state_picker: false,
-            file_state_picker_state: ListState::default(),
-            show_action_picker: false,
-            action_picker_state: ListState::default(),
-            available_actions: Vec::new(),
-            show_confirm: false,
-            confirm_action: None,
-            confirm_unit_name: None,
-            action_in_progress: false,
-            action_result: None,
-            action_receiver: None,
-            refresh_receiver: None,
-            log_refresh_receiver: None,
-            log_refresh_generation: 0,
-            log_stream_generation: 0,
-            status_message: None,
-            system_logs_mode: false,
-            navigated_from_system_logs: false,
-            log_paused: false,
-            log_selected_entry: None,
-            logs_at_bottom: true,
-            last_refreshed: None,
-            show_unit_file: false,
-            unit_file_content: Vec::new(),
-            unit_file_scroll: 0,
-            unit_file_unit_name: None,
-            unit_file_search_query: String::new(),
-            unit_file_search_mode: false,
-            unit_file_search_matches: Vec::new(),
-            unit_file_search_match_index: None,
-        };
-        if !app.filtered_indices.is_empty() {
-            app.list_state.select(Some(0));
+    pub fn toggle_log_paused(&mut self, visible_lines: usize) {
+        self.log_paused = !self.log_paused;
+        if self.log_paused {
+            if !self.logs.is_empty() {
+                // Resolve a stale logs_scroll (including the usize::MAX "go to bottom"
+                // sentinel) so the walk below starts from a valid index.
+                self.logs_scroll = self.logs_scroll.min(self.logs.len().saturating_sub(1));
+                // Walk entries forward from logs_scroll, accumulating each entry's
+                // visual height (from cached_entry_heights, defaulting to 1). Stop
+                // when the next entry would exceed visible_lines, then select the
+                // last entry that fit — i.e. the bottom-most visible entry. The
+                // `used > 0` guard guarantees at least one entry is always selected,
+                // even if a single entry is taller than the viewport.
+                let mut used = 0;
+                let mut last = self.logs_scroll;
+                for i in self.logs_scroll..self.logs.len() {
+                    let h = self.cached_entry_heights.get(i).copied().unwrap_or(1);
+                    if h == 0 {
+                        // Folded into a collapsed run above.
+                        continue;
+                    }
+                    if used + h > visible_lines && used > 0 {
+                        break;
+                    }
+                    last = i;
+                    used += h;
+                }
+                self.log_selected_entry = Some(last);
+            }
+        } else {
+            self.log_selected_entry = None;
+            self.logs_go_to_bottom();
         }
-        app
     }
 
-    fn test_app_empty() -> App {
-        test_app_with_services(Vec::new())
+    pub fn log_select_next(&mut self) {
+        if let Some(sel) = self.log_selected_entry {
+            let max = self.logs.len().saturating_sub(1);
+            self.log_selected_entry = Some(if self.cached_log_repeat_counts.is_empty() {
+                (sel + 1).min(max)
+            } else {
+                self.log_rows_forward(sel, 1)
+            });
+        }
     }
 
-    // Post-action unit-list refresh
+    pub fn log_select_previous(&mut self) {
+        if let Some(sel) = self.log_selected_entry {
+            self.log_selected_entry = Some(if self.cached_log_repeat_counts.is_empty() {
+                sel.saturating_sub(1)
+            } else {
+                self.log_rows_back(sel, 1)
+            });
+        }
+    }
 
-    #[test]
-    fn test_dismiss_action_result_keeps_pending_refresh() {
-        let mut app = test_app_empty();
+    pub fn navigate_to_log_unit(&mut self) {
+        let unit_name = match self
+            .log_selected_entry
+            .and_then(|idx| self.logs.get(idx))
+            .and_then(|e| e.unit.as_ref())
+        {
+            Some(name) => name.clone(),
+            None => return,
+        };
+        // Find service in filtered list; bail out if it's excluded by current filters
+        let pos = match self
+            .filtered_indices
+            .iter()
+            .position(|&i| self.services[i].unit == unit_name)
+        {
+            Some(pos) => pos,
+            None => return,
+        };
+        self.list_state.select(Some(pos));
+        // Switch to per-unit log view
+        self.navigated_from_system_logs = true;
+        self.system_logs_mode = false;
+        self.log_selected_entry = None;
+        self.last_selected_service = None;
+        self.log_filters_dirty = true;
+        self.show_logs = true;
+        self.log_paused = false;
+        self.log_follow = true;
+    }
+
+    /// Starts a live-tail refresh on a background thread. No-op while a
+    /// previous refresh is still in flight; results are merged by
+    /// check_log_refresh_progress on the UI thread.
+    pub fn refresh_logs(&mut self) {
+        // The buffer is about to be replaced; its cursor no longer applies.
+        if self.log_refresh_receiver.is_some() || self.logs_loading() {
+            return;
+        }
+        let unit_name = if self.system_logs_mode {
+            None
+        } else {
+            match self.last_selected_service.as_ref() {
+                Some(u) => Some(u.clone()),
+                None => return,
+            }
+        };
+        let cursor = match self.logs.last().and_then(|e| e.cursor.as_ref()) {
+            Some(c) => c.clone(),
+            None => return,
+        };
+
+        let user_mode = self.user_mode;
+        let filters = self.log_filters();
+        let runner = Arc::clone(&self.runner);
         let (tx, rx) = mpsc::channel();
-        app.refresh_receiver = Some(rx);
-        app.action_result = Some(Ok("Start succeeded".into()));
-        app.dismiss_action_result();
-        assert!(
-            app.refresh_in_flight(),
-            "dismissing the popup must not discard the refresh"
+        self.log_refresh_receiver = Some(rx);
+        self.log_refresh_generation = self.log_stream_generation;
+        std::thread::spawn(move || {
+            let entries = fetch_log_entries_after_cursor(
+                unit_name.as_deref(),
+                &cursor,
+                user_mode,
+                &filters,
+                runner.as_ref(),
+            )
+            .unwrap_or_default();
+            let _ = tx.send(entries);
+        });
+    }
+
+    pub fn log_refresh_in_flight(&self) -> bool {
+        self.log_refresh_receiver.is_some()
+    }
+
+    pub fn check_log_refresh_progress(&mut self) {
+        let Some(rx) = &self.log_refresh_receiver else {
+            return;
+        };
+        let entries = match rx.try_recv() {
+            Ok(entries) => entries,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.log_refresh_receiver = None;
+                return;
+            }
+        };
+        self.log_refresh_receiver = None;
+
+        // Drop results that belong to a log buffer that has since been
+        // replaced (unit switch, filter change, scope toggle), or that would
+        // disturb a paused view; the unchanged cursor refetches them later.
+        if self.log_refresh_generation != self.log_stream_generation
+            || !self.show_logs
+            || self.log_paused
+            || entries.is_empty()
+        {
+            return;
+        }
+
+        self.logs.extend(entries);
+        self.invalidate_log_entry_heights_cache();
+        if self.log_follow {
+            self.logs_scroll = usize::MAX;
+        }
+    }
+
+    /// Marks the current log buffer as replaced, so in-flight live-tail
+    /// results for the previous buffer are discarded instead of merged.
+    fn invalidate_log_stream(&mut self) {
+        self.log_stream_generation = self.log_stream_generation.wrapping_add(1);
+        self.log_refresh_receiver = None;
+        self.log_load_receiver = None;
+        self.older_logs_receiver = None;
+        self.reset_journal_match_count();
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+        self.help_scroll = 0;
+        self.help_search_mode = false;
+        self.help_search_query.clear();
+    }
+
+    pub fn help_search_push(&mut self, c: char) {
+        self.help_search_query.push(c);
+        self.help_scroll = 0;
+    }
+
+    pub fn help_search_pop(&mut self) {
+        self.help_search_query.pop();
+        self.help_scroll = 0;
+    }
+
+    pub fn clear_help_search(&mut self) {
+        self.help_search_mode = false;
+        self.help_search_query.clear();
+        self.help_scroll = 0;
+    }
+
+    fn help_max_scroll(&self) -> u16 {
+        self.help_content_lines
+            .saturating_sub(self.help_viewport_lines)
+    }
+
+    pub fn help_scroll_down(&mut self, amount: u16) {
+        let max = self.help_max_scroll();
+        self.help_scroll = self.help_scroll.saturating_add(amount).min(max);
+    }
+
+    pub fn help_scroll_up(&mut self, amount: u16) {
+        self.help_scroll = self.help_scroll.saturating_sub(amount);
+    }
+
+    pub fn help_scroll_to_top(&mut self) {
+        self.help_scroll = 0;
+    }
+
+    pub fn help_scroll_to_bottom(&mut self) {
+        self.help_scroll = self.help_max_scroll();
+    }
+
+    pub fn page_up(&mut self, page_size: usize) {
+        if self.filtered_indices.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let new_index = current.saturating_sub(page_size);
+        self.list_state.select(Some(new_index));
+    }
+
+    pub fn page_down(&mut self, page_size: usize) {
+        if self.filtered_indices.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        let max_index = self.filtered_indices.len().saturating_sub(1);
+        let new_index = (current + page_size).min(max_index);
+        self.list_state.select(Some(new_index));
+    }
+
+    pub fn update_log_search(&mut self) {
+        self.reset_journal_match_count();
+        self.invalidate_log_entry_heights_cache();
+        self.log_search_matches.clear();
+        self.log_search_match_index = None;
+
+        if self.log_search_query.is_empty() {
+            return;
+        }
+
+        let query = self.log_search_query.to_lowercase();
+        for (i, entry) in self.logs.iter().enumerate() {
+            let found = if self.log_search_whole_word {
+                !find_word_matches(&entry.message, &query).is_empty()
+            } else {
+                entry.message.to_lowercase().contains(&query)
+            };
+            if found {
+                self.log_search_matches.push(i);
+            }
+        }
+
+        // Auto-scroll to first match
+        if !self.log_search_matches.is_empty() {
+            self.log_search_match_index = Some(0);
+            self.logs_scroll = self.log_search_matches[0];
+            self.log_follow = false;
+        }
+    }
+
+    /// Toggles whole-word log search ("err" no longer matches "error") and
+    /// re-runs the current query.
+    pub fn toggle_log_search_whole_word(&mut self) {
+        self.log_search_whole_word = !self.log_search_whole_word;
+        self.update_log_search();
+    }
+
+    /// Counts matches for the log search query in the whole journal (same
+    /// unit and filters, no line limit) on a background thread, since the
+    /// in-memory count only covers the fetched lines. Not available while a
+    /// `--grep` filter is active, as journalctl takes a single pattern.
+    pub fn start_journal_match_count(&mut self) {
+        if self.log_search_query.is_empty() {
+            return;
+        }
+        if self.log_grep.is_some() {
+            self.log_journal_match_count =
+                Some(Err("not available with a grep filter".to_string()));
+            return;
+        }
+        let unit = if self.system_logs_mode {
+            None
+        } else {
+            match &self.last_selected_service {
+                Some(unit) => Some(unit.clone()),
+                None => return,
+            }
+        };
+        let query = self.log_search_query.clone();
+        let whole_word = self.log_search_whole_word;
+        let mut filters = self.log_filters();
+        let escaped = regex_escape(&query);
+        filters.grep = Some(LogGrep {
+            pattern: if whole_word { format!("\\b{}\\b", escaped) } else { escaped },
+            case_sensitive: false,
+        });
+        let user_mode = self.user_mode;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.log_journal_match_count = None;
+        self.journal_count_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let result = count_journal_matches(unit.as_deref(), user_mode, &filters, runner.as_ref());
+            let _ = tx.send(JournalCount { query, whole_word, result });
+        });
+    }
+
+    pub fn journal_count_in_flight(&self) -> bool {
+        self.journal_count_receiver.is_some()
+    }
+
+    pub fn check_journal_count_progress(&mut self) {
+        let Some(rx) = &self.journal_count_receiver else {
+            return;
+        };
+        let count = match rx.try_recv() {
+            Ok(count) => count,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.journal_count_receiver = None;
+                return;
+            }
+        };
+        self.journal_count_receiver = None;
+        if count.query == self.log_search_query && count.whole_word == self.log_search_whole_word {
+            self.log_journal_match_count = Some(count.result);
+        }
+    }
+
+    /// Forgets the journal-wide count; it no longer describes the current
+    /// query or buffer.
+    fn reset_journal_match_count(&mut self) {
+        self.log_journal_match_count = None;
+        self.journal_count_receiver = None;
+    }
+
+    pub fn clear_log_search(&mut self) {
+        self.reset_journal_match_count();
+        self.log_search_query.clear();
+        self.log_search_mode = false;
+        self.log_search_matches.clear();
+        self.log_search_match_index = None;
+        self.log_selected_entry = None;
+        self.invalidate_log_entry_heights_cache();
+    }
+
+    pub fn next_log_match(&mut self, visible_lines: usize) {
+        if self.log_search_matches.is_empty() {
+            return;
+        }
+        let next = match self.log_search_match_index {
+            Some(i) => (i + 1) % self.log_search_matches.len(),
+            None => 0,
+        };
+        self.log_search_match_index = Some(next);
+        let line_idx = self.log_search_matches[next];
+        // Scroll so match is visible
+        if line_idx < self.logs_scroll || line_idx >= self.logs_scroll + visible_lines {
+            self.logs_scroll = line_idx;
+            self.log_follow = false;
+        }
+    }
+
+    pub fn prev_log_match(&mut self, visible_lines: usize) {
+        if self.log_search_matches.is_empty() {
+            return;
+        }
+        let prev = match self.log_search_match_index {
+            Some(0) => self.log_search_matches.len() - 1,
+            Some(i) => i - 1,
+            None => self.log_search_matches.len() - 1,
+        };
+        self.log_search_match_index = Some(prev);
+        let line_idx = self.log_search_matches[prev];
+        if line_idx < self.logs_scroll || line_idx >= self.logs_scroll + visible_lines {
+            self.logs_scroll = line_idx;
+            self.log_follow = false;
+        }
+    }
+
+    /// The entry the boot and error jumps start from: the selected one while
+    /// paused, else the top of the view.
+    fn log_jump_origin(&self) -> usize {
+        self.log_selected_entry
+            .unwrap_or(self.logs_scroll)
+            .min(self.logs.len().saturating_sub(1))
+    }
+
+    fn is_boot_start(&self, idx: usize) -> bool {
+        idx > 0 && log_boundary_before_entry(&self.logs[idx - 1], &self.logs[idx], None).0
+    }
+
+    fn jump_to_log_entry(&mut self, idx: usize) {
+        self.logs_scroll = idx;
+        self.log_follow = false;
+        if self.log_selected_entry.is_some() {
+            self.log_selected_entry = Some(idx);
+        }
+    }
+
+    /// Scrolls to the first entry of the next boot, if any.
+    pub fn next_boot_boundary(&mut self) {
+        let origin = self.log_jump_origin();
+        if let Some(idx) = (origin + 1..self.logs.len()).find(|&i| self.is_boot_start(i)) {
+            self.jump_to_log_entry(idx);
+        }
+    }
+
+    /// Scrolls to the first entry of the boot before the current one's
+    /// start; the oldest boot starts at the first loaded entry.
+    pub fn prev_boot_boundary(&mut self) {
+        if self.logs.is_empty() {
+            return;
+        }
+        let origin = self.log_jump_origin();
+        let idx = (1..origin).rev().find(|&i| self.is_boot_start(i)).unwrap_or(0);
+        self.jump_to_log_entry(idx);
+    }
+
+    fn is_error_entry(entry: &LogEntry) -> bool {
+        matches!(entry.priority, Some(p) if p <= 3)
+    }
+
+    /// Scrolls to the next entry at err priority or worse, wrapping around.
+    pub fn next_error(&mut self) {
+        let len = self.logs.len();
+        if len == 0 {
+            return;
+        }
+        let origin = self.log_jump_origin();
+        match (1..=len)
+            .map(|step| (origin + step) % len)
+            .find(|&i| Self::is_error_entry(&self.logs[i]))
+        {
+            Some(idx) => self.jump_to_log_entry(idx),
+            None => self.status_message = Some("No errors in loaded logs".into()),
+        }
+    }
+
+    /// Scrolls to the previous entry at err priority or worse, wrapping around.
+    pub fn prev_error(&mut self) {
+        let len = self.logs.len();
+        if len == 0 {
+            return;
+        }
+        let origin = self.log_jump_origin();
+        match (1..=len)
+            .map(|step| (origin + len - step) % len)
+            .find(|&i| Self::is_error_entry(&self.logs[i]))
+        {
+            Some(idx) => self.jump_to_log_entry(idx),
+            None => self.status_message = Some("No errors in loaded logs".into()),
+        }
+    }
+
+    pub fn logs_go_to_top(&mut self) {
+        self.logs_scroll = 0;
+        self.log_follow = false;
+    }
+
+    pub fn logs_go_to_bottom(&mut self) {
+        if !self.logs.is_empty() {
+            // Sentinel value resolved by UI once panel dimensions are known.
+            self.logs_scroll = usize::MAX;
+        }
+        self.log_follow = true;
+    }
+
+    /// `f`: resumes a paused view, or a live view locked away from the
+    /// newest entries, at the bottom; pauses a view that is already
+    /// following.
+    pub fn toggle_log_follow(&mut self, visible_lines: usize) {
+        if !self.log_paused && !self.log_follow {
+            self.logs_go_to_bottom();
+        } else {
+            self.toggle_log_paused(visible_lines);
+        }
+    }
+
+    /// `s`: pins a live view where it is, so new entries are still fetched
+    /// but no longer scroll it; again follows from the bottom.
+    pub fn toggle_log_scroll_lock(&mut self) {
+        if self.log_paused {
+            return;
+        }
+        if self.log_follow {
+            self.log_follow = false;
+        } else {
+            self.logs_go_to_bottom();
+        }
+    }
+
+    pub fn toggle_user_mode(&mut self) {
+        self.user_mode = !self.user_mode;
+        self.system_logs_mode = false;
+        self.last_selected_service = None;
+        // A pending post-action refresh belongs to the old scope.
+        self.refresh_receiver = None;
+        self.invalidate_log_stream();
+        self.logs.clear();
+        self.invalidate_log_entry_heights_cache();
+        self.clear_log_search();
+        self.log_priority_filter = None;
+        self.log_time_range = TimeRange::All;
+        self.log_grep = None;
+        self.selected_units.clear();
+        self.properties_cache.clear();
+        self.log_scroll_memory.clear();
+        self.clear_state_changes();
+        self.file_state_filter = None;
+        self.dependency_filter = None;
+        self.dependency_filter_unit = None;
+        self.dependency_filter_receiver = None;
+        // Undo would run in the new scope, on a unit it never touched.
+        self.last_action = None;
+        self.load_services();
+    }
+
+    // Details modal methods
+
+    /// Opens the details modal. Cached properties show immediately; otherwise
+    /// the modal shows a loading placeholder while `systemctl show` runs on a
+    /// background thread, and `check_action_progress` fills it in.
+    pub fn open_details(&mut self) {
+        let Some(name) = self.selected_unit().map(|u| u.unit.clone()) else {
+            return;
+        };
+        // Already loading this unit: don't spawn a second fetch.
+        if self.detail_receiver.is_some() && self.detail_unit_name.as_deref() == Some(name.as_str()) {
+            self.show_details = true;
+            return;
+        }
+        self.detail_receiver = None;
+        self.detail_properties = self.properties_cache.get(&name).cloned();
+        if self.detail_properties.is_none() {
+            let user_mode = self.user_mode;
+            let runner = Arc::clone(&self.runner);
+            let unit_name = name.clone();
+            let (tx, rx) = mpsc::channel();
+            self.detail_receiver = Some(rx);
+            std::thread::spawn(move || {
+                let _ = tx.send(fetch_unit_properties(&unit_name, user_mode, runner.as_ref()));
+            });
+        }
+        self.detail_unit_name = Some(name);
+        self.detail_scroll = 0;
+        self.clear_detail_search();
+        self.detail_content_text.clear();
+        self.show_details = true;
+    }
+
+    pub fn details_loading(&self) -> bool {
+        self.detail_receiver.is_some()
+    }
+
+    pub fn close_details(&mut self) {
+        self.show_details = false;
+        self.detail_properties = None;
+        self.detail_unit_name = None;
+        self.detail_receiver = None;
+        self.detail_scroll = 0;
+        self.clear_detail_search();
+        self.detail_content_text.clear();
+    }
+
+    pub fn detail_scroll_up(&mut self, amount: usize) {
+        self.detail_scroll = self.detail_scroll.saturating_sub(amount);
+    }
+
+    pub fn detail_scroll_down(&mut self, amount: usize, content_height: usize, visible_height: usize) {
+        if content_height > visible_height {
+            let max_scroll = content_height.saturating_sub(visible_height);
+            self.detail_scroll = (self.detail_scroll.saturating_add(amount)).min(max_scroll);
+        }
+    }
+
+    /// The open list picker, checked in the same order as key handling so
+    /// the mouse acts on the one that receives keys.
+    pub fn open_picker(&self) -> Option<Picker> {
+        if self.show_status_picker {
+            Some(Picker::Status)
+        } else if self.show_type_picker {
+            Some(Picker::Type)
+        } else if self.show_priority_picker {
+            Some(Picker::Priority)
+        } else if self.show_time_picker {
+            Some(Picker::Time)
+        } else if self.show_file_state_picker {
+            Some(Picker::FileState)
+        } else if self.show_timestamp_format_picker {
+            Some(Picker::TimestampFormat)
+        } else if self.show_preset_picker {
+            Some(Picker::Preset)
+        } else if self.show_export_picker {
+            Some(Picker::Export)
+        } else if self.show_action_picker {
+            Some(Picker::Action)
+        } else {
+            None
+        }
+    }
+
+    /// Number of rows in a picker's list.
+    pub fn picker_len(&self, picker: Picker) -> usize {
+        match picker {
+            Picker::Status => self.unit_type.status_options().len(),
+            Picker::Type => UNIT_TYPES.len(),
+            // "All" plus the eight syslog levels
+            Picker::Priority => 9,
+            // Presets plus "Custom..."
+            Picker::Time => TIME_RANGES.len() + 1,
+            Picker::FileState => FILE_STATE_OPTIONS.len(),
+            Picker::TimestampFormat => TIMESTAMP_FORMATS.len(),
+            // "Save current filters..." plus the presets
+            Picker::Preset => self.log_presets.len() + 1,
+            Picker::Export => LOG_EXPORT_FORMATS.len(),
+            Picker::Action => self.available_actions.len(),
+        }
+    }
+
+    pub fn picker_state(&self, picker: Picker) -> &ListState {
+        match picker {
+            Picker::Status => &self.status_picker_state,
+            Picker::Type => &self.type_picker_state,
+            Picker::Priority => &self.priority_picker_state,
+            Picker::Time => &self.time_picker_state,
+            Picker::FileState => &self.file_state_picker_state,
+            Picker::TimestampFormat => &self.timestamp_format_picker_state,
+            Picker::Preset => &self.preset_picker_state,
+            Picker::Export => &self.export_picker_state,
+            Picker::Action => &self.action_picker_state,
+        }
+    }
+
+    fn picker_next(&mut self, picker: Picker) {
+        match picker {
+            Picker::Status => self.status_picker_next(),
+            Picker::Type => self.type_picker_next(),
+            Picker::Priority => self.priority_picker_next(),
+            Picker::Time => self.time_picker_next(),
+            Picker::FileState => self.file_state_picker_next(),
+            Picker::TimestampFormat => self.timestamp_format_picker_next(),
+            Picker::Preset => self.preset_picker_next(),
+            Picker::Export => self.export_picker_next(),
+            Picker::Action => self.action_picker_next(),
+        }
+    }
+
+    fn picker_previous(&mut self, picker: Picker) {
+        match picker {
+            Picker::Status => self.status_picker_previous(),
+            Picker::Type => self.type_picker_previous(),
+            Picker::Priority => self.priority_picker_previous(),
+            Picker::Time => self.time_picker_previous(),
+            Picker::FileState => self.file_state_picker_previous(),
+            Picker::TimestampFormat => self.timestamp_format_picker_previous(),
+            Picker::Preset => self.preset_picker_previous(),
+            Picker::Export => self.export_picker_previous(),
+            Picker::Action => self.action_picker_previous(),
+        }
+    }
+
+    /// Selects a picker row and confirms it, as if highlighted and Enter
+    /// pressed. Out-of-range rows are ignored.
+    pub fn pick_picker_row(&mut self, picker: Picker, index: usize) {
+        if index >= self.picker_len(picker) {
+            return;
+        }
+        let state = match picker {
+            Picker::Status => &mut self.status_picker_state,
+            Picker::Type => &mut self.type_picker_state,
+            Picker::Priority => &mut self.priority_picker_state,
+            Picker::Time => &mut self.time_picker_state,
+            Picker::FileState => &mut self.file_state_picker_state,
+            Picker::TimestampFormat => &mut self.timestamp_format_picker_state,
+            Picker::Preset => &mut self.preset_picker_state,
+            Picker::Export => &mut self.export_picker_state,
+            Picker::Action => &mut self.action_picker_state,
+        };
+        state.select(Some(index));
+        match picker {
+            Picker::Status => self.status_picker_confirm(),
+            Picker::Type => self.type_picker_confirm(),
+            Picker::Priority => self.priority_picker_confirm(),
+            Picker::Time => self.time_picker_confirm(),
+            Picker::FileState => self.file_state_picker_confirm(),
+            Picker::TimestampFormat => self.timestamp_format_picker_confirm(),
+            Picker::Preset => self.preset_picker_confirm(),
+            Picker::Export => self.export_picker_confirm(),
+            Picker::Action => self.action_picker_confirm(),
+        }
+    }
+
+    /// Applies one mouse wheel notch to the open modal: scrolls the help,
+    /// details or unit file view, or moves a picker's selection. Returns
+    /// false when nothing open scrolls (text prompts and confirmations).
+    pub fn scroll_modal(&mut self, up: bool, details_visible: usize) -> bool {
+        if self.show_help {
+            if up {
+                self.help_scroll_up(MOUSE_SCROLL_LINES as u16);
+            } else {
+                self.help_scroll_down(MOUSE_SCROLL_LINES as u16);
+            }
+        } else if self.show_custom_time_input || self.show_grep_input || self.show_preset_name_input {
+            return false;
+        } else if let Some(picker) = self.open_picker() {
+            if up {
+                self.picker_previous(picker);
+            } else {
+                self.picker_next(picker);
+            }
+        } else if self.show_confirm && !self.batch_results.is_empty() {
+            if up {
+                self.batch_scroll_up(MOUSE_SCROLL_LINES);
+            } else {
+                self.batch_scroll_down(MOUSE_SCROLL_LINES);
+            }
+        } else if self.pending_quit_confirm || self.show_confirm {
+            return false;
+        } else if self.show_details {
+            if up {
+                self.detail_scroll_up(MOUSE_SCROLL_LINES);
+            } else {
+                let content_height = self.detail_content_height;
+                self.detail_scroll_down(MOUSE_SCROLL_LINES, content_height, details_visible);
+            }
+        } else if self.show_log_fields {
+            if up {
+                self.log_fields_scroll_up(MOUSE_SCROLL_LINES);
+            } else {
+                self.log_fields_scroll_down(MOUSE_SCROLL_LINES);
+            }
+        } else if self.show_reverse_deps {
+            if up {
+                self.reverse_deps_scroll_up(MOUSE_SCROLL_LINES);
+            } else {
+                self.reverse_deps_scroll_down(MOUSE_SCROLL_LINES);
+            }
+        } else if self.show_security {
+            if up {
+                self.security_scroll_up(MOUSE_SCROLL_LINES);
+            } else {
+                self.security_scroll_down(MOUSE_SCROLL_LINES);
+            }
+        } else if self.show_action_output {
+            if up {
+                self.action_output_scroll_up(MOUSE_SCROLL_LINES);
+            } else {
+                self.action_output_scroll_down(MOUSE_SCROLL_LINES);
+            }
+        } else if self.show_unit_file {
+            if up {
+                self.scroll_unit_file_up(MOUSE_SCROLL_LINES);
+            } else {
+                self.scroll_unit_file_down(MOUSE_SCROLL_LINES);
+            }
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Saves the details unit's full `systemctl show` output to a file in
+    /// the temp directory on a background thread; the path is reported in
+    /// the status bar when done. The cached properties are only a parsed
+    /// subset, so the raw output is always fetched.
+    pub fn export_raw_properties(&mut self) {
+        if self.raw_properties_loading() {
+            return;
+        }
+        let Some(name) = self.detail_unit_name.clone() else {
+            return;
+        };
+        let user_mode = self.user_mode;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.raw_properties_receiver = Some(rx);
+        self.status_message = Some(format!("Saving systemctl show output for {}\u{2026}", name));
+        std::thread::spawn(move || {
+            let result = fetch_raw_properties(&name, user_mode, runner.as_ref()).and_then(|content| {
+                let path = raw_properties_path(&std::env::temp_dir(), &name, chrono::Local::now());
+                save_text_file(&path, &content).map(|_| path)
+            });
+            let _ = tx.send(result);
+        });
+    }
+
+    pub fn raw_properties_loading(&self) -> bool {
+        self.raw_properties_receiver.is_some()
+    }
+
+    fn finish_raw_properties_export(&mut self, result: Result<PathBuf, String>) {
+        self.raw_properties_receiver = None;
+        self.status_message = Some(match result {
+            Ok(path) => format!("Saved systemctl show output to {}", path.display()),
+            Err(e) => e,
+        });
+    }
+
+    /// Records the details modal's rendered text (set on every draw, since
+    /// the lines are built by the renderer). When it changed, e.g. because
+    /// the properties finished loading, search matches are recomputed.
+    pub fn set_detail_content(&mut self, text: Vec<String>) {
+        if text == self.detail_content_text {
+            return;
+        }
+        self.detail_content_text = text;
+        self.detail_search_matches =
+            find_matching_lines(&self.detail_content_text, &self.detail_search_query);
+        self.detail_search_match_index = if self.detail_search_matches.is_empty() {
+            None
+        } else {
+            Some(self.detail_search_match_index.unwrap_or(0).min(self.detail_search_matches.len() - 1))
+        };
+    }
+
+    pub fn update_detail_search(&mut self) {
+        self.detail_search_matches =
+            find_matching_lines(&self.detail_content_text, &self.detail_search_query);
+        self.detail_search_match_index = None;
+
+        if !self.detail_search_matches.is_empty() {
+            self.detail_search_match_index = Some(0);
+            self.detail_scroll = self.detail_search_matches[0];
+        }
+    }
+
+    pub fn clear_detail_search(&mut self) {
+        self.detail_search_query.clear();
+        self.detail_search_mode = false;
+        self.detail_search_matches.clear();
+        self.detail_search_match_index = None;
+    }
+
+    pub fn next_detail_match(&mut self, visible_lines: usize) {
+        if self.detail_search_matches.is_empty() {
+            return;
+        }
+        let next = match self.detail_search_match_index {
+            Some(i) => (i + 1) % self.detail_search_matches.len(),
+            None => 0,
+        };
+        self.detail_search_match_index = Some(next);
+        self.scroll_detail_to(self.detail_search_matches[next], visible_lines);
+    }
+
+    pub fn prev_detail_match(&mut self, visible_lines: usize) {
+        if self.detail_search_matches.is_empty() {
+            return;
+        }
+        let prev = match self.detail_search_match_index {
+            Some(0) | None => self.detail_search_matches.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.detail_search_match_index = Some(prev);
+        self.scroll_detail_to(self.detail_search_matches[prev], visible_lines);
+    }
+
+    fn scroll_detail_to(&mut self, line_idx: usize, visible_lines: usize) {
+        if line_idx < self.detail_scroll || line_idx >= self.detail_scroll + visible_lines {
+            self.detail_scroll = line_idx;
+        }
+    }
+
+    // File state picker methods
+
+    pub fn open_file_state_picker(&mut self) {
+        self.show_file_state_picker = true;
+        let index = match &self.file_state_filter {
+            None => 0,
+            Some(s) => FILE_STATE_OPTIONS
+                .iter()
+                .position(|&opt| opt == s)
+                .unwrap_or(0),
+        };
+        self.file_state_picker_state.select(Some(index));
+    }
+
+    pub fn close_file_state_picker(&mut self) {
+        self.show_file_state_picker = false;
+    }
+
+    pub fn file_state_picker_next(&mut self) {
+        let len = FILE_STATE_OPTIONS.len();
+        let i = self.file_state_picker_state.selected().unwrap_or(0);
+        let next = (i + 1) % len;
+        self.file_state_picker_state.select(Some(next));
+    }
+
+    pub fn file_state_picker_previous(&mut self) {
+        let len = FILE_STATE_OPTIONS.len();
+        let i = self.file_state_picker_state.selected().unwrap_or(0);
+        let prev = if i == 0 { len - 1 } else { i - 1 };
+        self.file_state_picker_state.select(Some(prev));
+    }
+
+    pub fn file_state_picker_confirm(&mut self) {
+        if let Some(i) = self.file_state_picker_state.selected() {
+            if i == 0 {
+                self.file_state_filter = None;
+            } else {
+                self.file_state_filter = Some(FILE_STATE_OPTIONS[i].to_string());
+            }
+            self.update_filter();
+        }
+        self.show_file_state_picker = false;
+    }
+
+    // Multi-select methods
+
+    pub fn toggle_unit_selection(&mut self) {
+        let Some(name) = self.selected_unit().map(|u| u.unit.clone()) else {
+            return;
+        };
+        if !self.selected_units.remove(&name) {
+            self.selected_units.insert(name);
+        }
+    }
+
+    pub fn clear_unit_selection(&mut self) {
+        self.selected_units.clear();
+    }
+
+    /// Units a confirmed action should run on, sorted by name (selected
+    /// units hidden by the current filter included). Empty means
+    /// "just the highlighted unit" — no multi-selection, or an action such
+    /// as daemon-reload that isn't per-unit.
+    pub fn bulk_action_targets(&self, action: UnitAction) -> Vec<String> {
+        if matches!(action, UnitAction::DaemonReload | UnitAction::VacuumJournal) {
+            return Vec::new();
+        }
+        let mut targets: Vec<String> = self.selected_units.iter().cloned().collect();
+        targets.sort();
+        targets
+    }
+
+    // Unit action picker methods
+
+    /// The actions the picker would offer: those that apply to every
+    /// selected unit, else to the highlighted one. `None` with no unit.
+    fn picker_actions(&self) -> Option<Vec<UnitAction>> {
+        if !self.selected_units.is_empty() {
+            let mut common: Option<Vec<UnitAction>> = None;
+            for unit in self.services.iter().filter(|u| self.selected_units.contains(&u.unit)) {
+                let actions = UnitAction::available_actions(&unit.sub, unit.file_state.as_deref(), &unit.load);
+                common = Some(match common {
+                    None => actions,
+                    Some(prev) => prev.into_iter().filter(|a| actions.contains(a)).collect(),
+                });
+            }
+            Some(common.unwrap_or_default())
+        } else {
+            let unit = self.selected_unit()?;
+            Some(UnitAction::available_actions(&unit.sub, unit.file_state.as_deref(), &unit.load))
+        }
+    }
+
+    /// The footer's action segment, naming the picker shortcuts that apply
+    /// to the highlighted unit (or selection), e.g. "x: s Start · e Enable"
+    /// for a stopped, disabled unit. Daemon reload, always there, is left
+    /// out, and at most `FOOTER_ACTION_HINTS` are named.
+    pub fn footer_action_hints(&self) -> String {
+        let hints: Vec<String> = self
+            .picker_actions()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|&a| a != UnitAction::DaemonReload)
+            .take(FOOTER_ACTION_HINTS)
+            .map(|a| format!("{} {}", a.shortcut(), a.label()))
+            .collect();
+        if hints.is_empty() {
+            "x: Actions".to_string()
+        } else {
+            format!("x: {}", hints.join(" \u{b7} "))
+        }
+    }
+
+    pub fn open_action_picker(&mut self) {
+        let Some(actions) = self.picker_actions() else {
+            return;
+        };
+        self.available_actions = actions;
+        if self.selected_units.is_empty()
+            && let Some(unit) = self.selected_unit()
+            && let Some(reason) = UnitAction::start_blocked_reason(&unit.load)
+        {
+            self.status_message = Some(format!("{}: {}", unit.unit, reason));
+        }
+        if !self.available_actions.is_empty() {
+            self.action_picker_state.select(Some(0));
+            self.show_action_picker = true;
+        }
+    }
+
+    pub fn close_action_picker(&mut self) {
+        self.show_action_picker = false;
+    }
+
+    pub fn action_picker_next(&mut self) {
+        let len = self.available_actions.len();
+        if len == 0 {
+            return;
+        }
+        let i = self.action_picker_state.selected().unwrap_or(0);
+        let next = (i + 1) % len;
+        self.action_picker_state.select(Some(next));
+    }
+
+    pub fn action_picker_previous(&mut self) {
+        let len = self.available_actions.len();
+        if len == 0 {
+            return;
+        }
+        let i = self.action_picker_state.selected().unwrap_or(0);
+        let prev = if i == 0 { len - 1 } else { i - 1 };
+        self.action_picker_state.select(Some(prev));
+    }
+
+    pub fn action_picker_confirm(&mut self) {
+        if let Some(i) = self.action_picker_state.selected()
+            && let Some(&action) = self.available_actions.get(i)
+        {
+            let unit_name = self
+                .selected_unit()
+                .map(|u| u.unit.clone())
+                .unwrap_or_default();
+            self.confirm_action = Some(action);
+            self.confirm_unit_name = Some(unit_name);
+            self.confirm_targets = self.bulk_action_targets(action);
+            self.show_action_picker = false;
+            self.show_confirm = true;
+            // Skipping the prompt still goes through the dialog, which shows
+            // progress and then the result. A dependents warning always
+            // prompts, since nothing else stands between the user and
+            // stopping e.g. network.target.
+            self.load_confirm_warning();
+            if self.no_confirm && !action.requires_confirm() {
+                if self.confirm_warning_loading() {
+                    self.confirm_auto_run = true;
+                } else {
+                    self.confirm_yes();
+                }
+            }
+        }
+    }
+
+    /// Names of the listed units whose sub-state is "failed", in list order.
+    pub fn failed_unit_names(&self) -> Vec<String> {
+        self.services
+            .iter()
+            .filter(|u| u.sub == "failed")
+            .map(|u| u.unit.clone())
+            .collect()
+    }
+
+    /// Asks to restart every failed unit of the current type, running them
+    /// through the same path as a multi-selection.
+    pub fn open_restart_failed_confirm(&mut self) {
+        let failed = self.failed_unit_names();
+        if failed.is_empty() {
+            let kind = match self.unit_type {
+                UnitType::All => "units".to_string(),
+                other => other.label().to_lowercase(),
+            };
+            self.status_message = Some(format!("No failed {}", kind));
+            return;
+        }
+        self.confirm_action = Some(UnitAction::Restart);
+        self.confirm_unit_name = Some(String::new());
+        self.confirm_targets = failed;
+        self.show_confirm = true;
+    }
+
+    /// Offers the inverse of `last_action` through the confirm dialog.
+    pub fn open_undo_confirm(&mut self) {
+        let Some((action, unit_name)) = self.last_action.clone() else {
+            self.status_message = Some("Nothing to undo".to_string());
+            return;
+        };
+        let Some(inverse) = action.inverse() else {
+            return;
+        };
+        self.confirm_action = Some(inverse);
+        self.confirm_unit_name = Some(unit_name);
+        self.confirm_targets.clear();
+        self.show_confirm = true;
+        self.load_confirm_warning();
+    }
+
+    /// Starts the dependents lookup for a single-unit stop or disable when
+    /// its prompt opens; `check_action_progress` fills in the warning.
+    fn load_confirm_warning(&mut self) {
+        self.confirm_warning = None;
+        self.confirm_warning_receiver = None;
+        self.confirm_auto_run = false;
+        if !matches!(self.confirm_action, Some(UnitAction::Stop | UnitAction::Disable)) {
+            return;
+        }
+        let unit = match self.confirm_targets.as_slice() {
+            [] => self.confirm_unit_name.clone().unwrap_or_default(),
+            [only] => only.clone(),
+            _ => return,
+        };
+        if unit.is_empty() {
+            return;
+        }
+        let user_mode = self.user_mode;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.confirm_warning_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(dependents_warning(&unit, user_mode, runner.as_ref()));
+        });
+    }
+
+    pub fn confirm_warning_loading(&self) -> bool {
+        self.confirm_warning_receiver.is_some()
+    }
+
+    /// Applies a finished dependents lookup, running a waiting
+    /// `--no-confirm` action if nothing depends on its unit.
+    fn finish_confirm_warning(&mut self, warning: Option<String>) {
+        self.confirm_warning_receiver = None;
+        self.confirm_warning = warning;
+        if std::mem::take(&mut self.confirm_auto_run) && self.confirm_warning.is_none() {
+            self.confirm_yes();
+        }
+    }
+
+    /// Asks for confirmation before rotating and vacuuming the journal.
+    pub fn open_vacuum_confirm(&mut self) {
+        self.confirm_action = Some(UnitAction::VacuumJournal);
+        self.confirm_unit_name = Some(String::new());
+        self.confirm_targets.clear();
+        self.show_confirm = true;
+    }
+
+    /// `b` in the confirm prompt: switches `--no-block` for this and later
+    /// actions.
+    pub fn toggle_no_block(&mut self) {
+        self.no_block = !self.no_block;
+    }
+
+    /// Handles `y`/`Y` in the confirm dialog. Destructive actions only
+    /// accept the uppercase key.
+    pub fn confirm_key(&mut self, key: char) {
+        let strong = self
+            .confirm_action
+            .is_some_and(|a| a.requires_strong_confirmation());
+        if key == 'Y' || (key == 'y' && !strong) {
+            self.confirm_yes();
+        }
+    }
+
+    pub fn confirm_yes(&mut self) {
+        // Confirmed already; a late warning has nothing left to guard.
+        self.confirm_warning_receiver = None;
+        self.confirm_auto_run = false;
+        if let (Some(action), Some(unit_name)) = (self.confirm_action, &self.confirm_unit_name)
+        {
+            let unit_name = unit_name.clone();
+            let targets = self.confirm_targets.clone();
+            let user_mode = self.user_mode;
+            let no_block = self.no_block;
+            let show_all = self.show_all_units;
+            let unit_type = self.unit_type;
+            let runner = Arc::clone(&self.runner);
+            let (action_tx, action_rx) = mpsc::channel();
+            let (refresh_tx, refresh_rx) = mpsc::channel();
+            let (batch_tx, batch_rx) = mpsc::channel();
+            self.action_in_progress = true;
+            self.action_receiver = Some(action_rx);
+            self.refresh_receiver = Some(refresh_rx);
+            self.batch_results.clear();
+            self.batch_scroll = None;
+            self.batch_receiver = (!targets.is_empty()).then_some(batch_rx);
+            std::thread::spawn(move || {
+                let outcome = if targets.is_empty() {
+                    execute_unit_action_with_output(action, &unit_name, user_mode, no_block, runner.as_ref())
+                } else {
+                    // Each unit's full message, which carries its output.
+                    let mut output = Vec::new();
+                    let result = execute_unit_action_bulk(action, &targets, user_mode, no_block, runner.as_ref(), |name, result| {
+                        let _ = batch_tx.send((name.to_string(), result.clone().map(|_| ())));
+                        let (Ok(message) | Err(message)) = result;
+                        output.push(format!("{}: {}", name, message));
+                    });
+                    (result, output.join("\n"))
+                };
+                let _ = action_tx.send(outcome);
+                if let Ok(units) = fetch_units(unit_type, user_mode, show_all, runner.as_ref()) {
+                    let _ = refresh_tx.send(units);
+                }
+                // Unit state can still be settling right after the job
+                // completes (deactivating, auto-restart, oneshot exit);
+                // refetch once more so the list converges on the final state.
+                std::thread::sleep(std::time::Duration::from_millis(1500));
+                if let Ok(units) = fetch_units(unit_type, user_mode, show_all, runner.as_ref()) {
+                    let _ = refresh_tx.send(units);
+                }
+            });
+        }
+    }
+
+    /// Cycles auto-refresh through off and each of `AUTO_REFRESH_INTERVALS`.
+    pub fn cycle_auto_refresh(&mut self) {
+        self.auto_refresh_interval = match self.auto_refresh_interval {
+            None => Some(AUTO_REFRESH_INTERVALS[0]),
+            Some(current) => AUTO_REFRESH_INTERVALS
+                .iter()
+                .position(|&i| i == current)
+                .and_then(|pos| AUTO_REFRESH_INTERVALS.get(pos + 1))
+                .copied(),
+        };
+        self.status_message = Some(match self.auto_refresh_interval {
+            Some(interval) => format!("Auto-refresh every {}s", interval.as_secs()),
+            None => "Auto-refresh off".to_string(),
+        });
+    }
+
+    /// Steps live tail to the next of `LIVE_TAIL_INTERVALS`, wrapping to
+    /// the fastest; an interval from the config that isn't in the list goes
+    /// to the next longer one.
+    pub fn cycle_live_tail_interval(&mut self) {
+        self.live_tail_interval = LIVE_TAIL_INTERVALS
+            .iter()
+            .copied()
+            .find(|&i| i > self.live_tail_interval)
+            .unwrap_or(LIVE_TAIL_INTERVALS[0]);
+        let ms = self.live_tail_interval.as_millis();
+        let label = if ms.is_multiple_of(1000) { format!("{}s", ms / 1000) } else { format!("{}ms", ms) };
+        self.status_message = Some(format!("Live tail every {}", label));
+    }
+
+    /// Refetches the unit list on a background thread; the result is applied
+    /// by `check_action_progress`. Does nothing while another refresh (e.g.
+    /// the post-action one) is still pending.
+    pub fn start_background_refresh(&mut self) {
+        if self.refresh_receiver.is_some() {
+            return;
+        }
+        let unit_type = self.unit_type;
+        let user_mode = self.user_mode;
+        let show_all = self.show_all_units;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.refresh_receiver = Some(rx);
+        std::thread::spawn(move || {
+            if let Ok(units) = fetch_units(unit_type, user_mode, show_all, runner.as_ref()) {
+                let _ = tx.send(units);
+            }
+        });
+    }
+
+    /// Replaces the unit list with a background refresh result, keeping the
+    /// highlighted unit selected by name even if its position changed.
+    fn apply_refreshed_units(&mut self, units: Vec<SystemdUnit>) {
+        let selected_name = self.selected_unit().map(|u| u.unit.clone());
+        let prev_states = self.sub_states();
+        self.properties_cache.clear();
+        self.services = units;
+        if self.auto_refresh_interval.is_some() && !prev_states.is_empty() {
+            self.alert_newly_failed(&newly_failed(&prev_states, &self.sub_states()));
+        }
+        self.last_refreshed = Some(chrono::Local::now());
+        self.update_state_changes();
+        self.start_active_timestamps_fetch();
+        self.update_filter();
+        if let Some(name) = selected_name
+            && let Some(pos) = self
+                .filtered_indices
+                .iter()
+                .position(|&i| self.services[i].unit == name)
+        {
+            self.list_state.select(Some(pos));
+        }
+    }
+
+    fn alert_newly_failed(&mut self, failed: &[String]) {
+        let message = match failed {
+            [] => return,
+            [only] => format!("\u{26a0} {} failed", only),
+            many => format!("\u{26a0} {} units failed: {}", many.len(), many.join(", ")),
+        };
+        self.failure_alert = Some(message);
+        self.bell_pending = self.bell_on_failure;
+        if self.desktop_notifications {
+            self.notify_pending = failed.to_vec();
+        }
+    }
+
+    pub fn refresh_in_flight(&self) -> bool {
+        self.refresh_receiver.is_some()
+    }
+
+    pub fn check_action_progress(&mut self) {
+        if let Some(ref rx) = self.action_receiver
+            && let Ok((result, output)) = rx.try_recv()
+        {
+            self.action_in_progress = false;
+            self.last_action_output = Some(output);
+            if result.is_ok()
+                && self.confirm_targets.is_empty()
+                && let Some(action) = self.confirm_action.filter(|a| a.inverse().is_some())
+                && let Some(unit_name) = self.confirm_unit_name.clone().filter(|n| !n.is_empty())
+            {
+                self.last_action = Some((action, unit_name));
+            }
+            self.action_result = Some(result);
+            self.action_receiver = None;
+            if self.show_logs {
+                self.mark_logs_dirty();
+            }
+        }
+        // Per-unit results are all sent before the aggregate one, so
+        // draining after it never leaves a straggler behind.
+        if let Some(ref rx) = self.batch_receiver {
+            loop {
+                match rx.try_recv() {
+                    Ok(outcome) => self.batch_results.push(outcome),
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.batch_receiver = None;
+                        break;
+                    }
+                }
+            }
+        }
+        if let Some(ref rx) = self.detail_receiver {
+            match rx.try_recv() {
+                Ok(props) => {
+                    if let Some(name) = self.detail_unit_name.clone() {
+                        self.properties_cache.insert(name, props.clone());
+                    }
+                    self.detail_properties = Some(props);
+                    self.detail_receiver = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.detail_receiver = None,
+            }
+        }
+        if let Some(ref rx) = self.unit_file_receiver {
+            match rx.try_recv() {
+                Ok(load) => self.finish_unit_file_reload(load),
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.unit_file_receiver = None,
+            }
+        }
+        if let Some(ref rx) = self.raw_properties_receiver {
+            match rx.try_recv() {
+                Ok(result) => self.finish_raw_properties_export(result),
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.raw_properties_receiver = None,
+            }
+        }
+        if let Some(ref rx) = self.timer_target_receiver {
+            match rx.try_recv() {
+                Ok(fetched) => self.finish_timer_target(fetched),
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.timer_target_receiver = None,
+            }
+        }
+        if let Some(ref rx) = self.dependency_filter_receiver {
+            match rx.try_recv() {
+                Ok(list) => self.finish_dependency_filter(list),
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.dependency_filter_receiver = None,
+            }
+        }
+        if let Some(ref rx) = self.reverse_deps_receiver {
+            match rx.try_recv() {
+                Ok(list) => self.finish_reverse_dependencies(list),
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.reverse_deps_receiver = None,
+            }
+        }
+        if let Some(ref rx) = self.security_receiver {
+            match rx.try_recv() {
+                Ok(report) => self.finish_security_analysis(report),
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.security_receiver = None,
+            }
+        }
+        if let Some(ref rx) = self.confirm_warning_receiver {
+            match rx.try_recv() {
+                Ok(warning) => self.finish_confirm_warning(warning),
+                Err(mpsc::TryRecvError::Empty) => {}
+                // A lookup that died is a failed lookup: no warning.
+                Err(mpsc::TryRecvError::Disconnected) => self.finish_confirm_warning(None),
+            }
+        }
+        if let Some(ref rx) = self.active_timestamps_receiver {
+            match rx.try_recv() {
+                Ok(fetched) => {
+                    self.active_timestamps = fetched.timestamps;
+                    self.active_clock = fetched.now.map(|now| (now, Instant::now()));
+                    self.active_timestamps_receiver = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.active_timestamps_receiver = None,
+            }
+        }
+        // The action thread sends several list refreshes; apply everything
+        // queued and keep the receiver until the thread is done with it.
+        if let Some(rx) = self.refresh_receiver.take() {
+            let mut disconnected = false;
+            loop {
+                match rx.try_recv() {
+                    Ok(units) => self.apply_refreshed_units(units),
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        disconnected = true;
+                        break;
+                    }
+                }
+            }
+            if !disconnected {
+                self.refresh_receiver = Some(rx);
+            }
+        }
+    }
+
+    /// Quits, unless an action is still running (quitting would abandon its
+    /// thread mid-way) or `confirm_on_quit` is set: then ask first.
+    pub fn request_quit(&mut self) {
+        if self.action_in_progress || self.confirm_on_quit {
+            self.pending_quit_confirm = true;
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    /// Answers the quit prompt.
+    pub fn answer_quit_confirm(&mut self, quit: bool) {
+        self.pending_quit_confirm = false;
+        if quit {
+            self.should_quit = true;
+        }
+    }
+
+    pub fn confirm_no(&mut self) {
+        self.show_confirm = false;
+        self.confirm_action = None;
+        self.confirm_unit_name = None;
+        self.confirm_targets.clear();
+        self.confirm_warning = None;
+        self.confirm_warning_receiver = None;
+        self.confirm_auto_run = false;
+        self.action_in_progress = false;
+        self.action_result = None;
+        self.action_receiver = None;
+    }
+
+    /// The command line for retrying the action in the result dialog
+    /// through `escalation`, if it failed for lack of privilege. Only
+    /// single-unit, system-scope systemctl actions qualify.
+    pub fn privileged_retry_command(&self, escalation: Escalation) -> Option<Vec<String>> {
+        let Some(Err(error)) = &self.action_result else {
+            return None;
+        };
+        if !needs_privilege(error) || self.user_mode || !self.confirm_targets.is_empty() {
+            return None;
+        }
+        let action = self.confirm_action.filter(|&a| a != UnitAction::VacuumJournal)?;
+        let unit_name = self.confirm_unit_name.as_deref()?;
+        Some(escalated_command(escalation, action, unit_name, self.no_block))
+    }
+
+    pub fn can_retry_privileged(&self) -> bool {
+        self.privileged_retry_command(Escalation::Sudo).is_some()
+    }
+
+    /// Replaces the failed result with the escalated retry's outcome and
+    /// reloads the unit list.
+    pub fn finish_privileged_retry(&mut self, escalation: Escalation, outcome: Result<(), String>) {
+        let (Some(action), Some(unit_name)) = (self.confirm_action, self.confirm_unit_name.clone()) else {
+            return;
+        };
+        let result = match outcome {
+            Ok(()) => {
+                if action.inverse().is_some() && !unit_name.is_empty() {
+                    self.last_action = Some((action, unit_name.clone()));
+                }
+                let outcome = if self.no_block && action.queues_job() { "requested" } else { "succeeded" };
+                Ok(format!("{} {} for {} (via {})", action.label(), outcome, unit_name, escalation.program()))
+            }
+            Err(e) => Err(format!("{} failed via {}: {}", action.label(), escalation.program(), e)),
+        };
+        // What it printed went to the terminal.
+        self.last_action_output = Some(match &result {
+            Ok(message) | Err(message) => message.clone(),
+        });
+        self.action_result = Some(result);
+        self.load_services();
+        if self.show_logs {
+            self.mark_logs_dirty();
+        }
+    }
+
+    // Note: the refresh receiver is deliberately kept alive here — dismissing
+    // the result popup must not discard a unit-list refresh that is still in
+    // flight (easily hit over SSH, where refetching takes a network round
+    // trip).
+    pub fn dismiss_action_result(&mut self) {
+        self.show_confirm = false;
+        self.confirm_action = None;
+        self.confirm_unit_name = None;
+        self.confirm_targets.clear();
+        self.confirm_warning = None;
+        self.confirm_warning_receiver = None;
+        self.confirm_auto_run = false;
+        self.action_in_progress = false;
+        self.action_result = None;
+        self.action_receiver = None;
+        self.batch_results.clear();
+        self.batch_receiver = None;
+        self.batch_scroll = None;
+    }
+
+    /// First batch result row that fits when following the newest.
+    fn batch_scroll_max(&self) -> usize {
+        self.batch_results.len().saturating_sub(BATCH_RESULTS_SHOWN)
+    }
+
+    /// The first batch result row to draw.
+    pub fn batch_scroll_offset(&self) -> usize {
+        self.batch_scroll.unwrap_or(usize::MAX).min(self.batch_scroll_max())
+    }
+
+    pub fn batch_scroll_up(&mut self, amount: usize) {
+        self.batch_scroll = Some(self.batch_scroll_offset().saturating_sub(amount));
+    }
+
+    /// Scrolling back to the end resumes following new results.
+    pub fn batch_scroll_down(&mut self, amount: usize) {
+        let offset = self.batch_scroll_offset() + amount;
+        self.batch_scroll = (offset < self.batch_scroll_max()).then_some(offset);
+    }
+
+    pub fn clear_status_message(&mut self) {
+        self.status_message = None;
+        self.failure_alert = None;
+    }
+
+    // Unit file viewer methods
+
+    pub fn open_unit_file(&mut self) {
+        if let Some(unit) = self.selected_unit() {
+            let name = unit.unit.clone();
+            match fetch_unit_file_with_dropins(&name, self.user_mode, self.runner()) {
+                Ok(file) => {
+                    self.unit_file_content = file.lines;
+                    self.unit_file_path = file.fragment_path;
+                }
+                Err(e) => {
+                    self.unit_file_content = vec![format!("Error: {}", e)];
+                    self.unit_file_path = None;
+                }
+            }
+            self.unit_file_unit_name = Some(name);
+            self.unit_file_scroll = 0;
+            self.unit_file_search_query.clear();
+            self.unit_file_search_matches.clear();
+            self.unit_file_search_match_index = None;
+            self.unit_file_search_mode = false;
+            self.show_unit_file = true;
+        }
+    }
+
+    /// Refetches the open unit file (after an edit) on a background thread
+    /// and drops its cached properties. The old text stays up until the new
+    /// one arrives.
+    pub fn reload_unit_file(&mut self) {
+        let Some(name) = self.unit_file_unit_name.clone() else {
+            return;
+        };
+        self.properties_cache.remove(&name);
+        let user_mode = self.user_mode;
+        let runner = Arc::clone(&self.runner);
+        let (tx, rx) = mpsc::channel();
+        self.unit_file_receiver = Some(rx);
+        std::thread::spawn(move || {
+            let result = fetch_unit_file_with_dropins(&name, user_mode, runner.as_ref());
+            let _ = tx.send(UnitFileLoad { unit: name, result });
+        });
+    }
+
+    pub fn unit_file_loading(&self) -> bool {
+        self.unit_file_receiver.is_some()
+    }
+
+    /// Swaps in the reloaded file, keeping the scroll position and search.
+    fn finish_unit_file_reload(&mut self, load: UnitFileLoad) {
+        self.unit_file_receiver = None;
+        if self.unit_file_unit_name.as_deref() != Some(load.unit.as_str()) {
+            return;
+        }
+        match load.result {
+            Ok(file) => {
+                self.unit_file_content = file.lines;
+                self.unit_file_path = file.fragment_path;
+            }
+            Err(e) => {
+                self.unit_file_content = vec![format!("Error: {}", e)];
+                self.unit_file_path = None;
+            }
+        }
+        let scroll = self.unit_file_scroll;
+        self.update_unit_file_search();
+        self.unit_file_scroll = scroll;
+    }
+
+    pub fn close_unit_file(&mut self) {
+        self.show_unit_file = false;
+        self.unit_file_content.clear();
+        self.unit_file_scroll = 0;
+        self.unit_file_unit_name = None;
+        self.unit_file_path = None;
+        self.unit_file_receiver = None;
+        self.unit_file_search_query.clear();
+        self.unit_file_search_matches.clear();
+        self.unit_file_search_match_index = None;
+        self.unit_file_search_mode = false;
+    }
+
+    pub fn scroll_unit_file_up(&mut self, amount: usize) {
+        self.unit_file_scroll = self.unit_file_scroll.saturating_sub(amount);
+    }
+
+    pub fn scroll_unit_file_down(&mut self, amount: usize) {
+        if !self.unit_file_content.is_empty() {
+            self.unit_file_scroll = self.unit_file_scroll.saturating_add(amount);
+        }
+    }
+
+    pub fn unit_file_go_to_top(&mut self) {
+        self.unit_file_scroll = 0;
+    }
+
+    pub fn unit_file_go_to_bottom(&mut self) {
+        if !self.unit_file_content.is_empty() {
+            self.unit_file_scroll = usize::MAX;
+        }
+    }
+
+    pub fn update_unit_file_search(&mut self) {
+        self.unit_file_search_matches =
+            find_matching_lines(&self.unit_file_content, &self.unit_file_search_query);
+        self.unit_file_search_match_index = None;
+
+        if !self.unit_file_search_matches.is_empty() {
+            self.unit_file_search_match_index = Some(0);
+            self.unit_file_scroll = self.unit_file_search_matches[0];
+        }
+    }
+
+    pub fn clear_unit_file_search(&mut self) {
+        self.unit_file_search_query.clear();
+        self.unit_file_search_mode = false;
+        self.unit_file_search_matches.clear();
+        self.unit_file_search_match_index = None;
+    }
+
+    pub fn next_unit_file_match(&mut self, visible_lines: usize) {
+        if self.unit_file_search_matches.is_empty() {
+            return;
+        }
+        let next = match self.unit_file_search_match_index {
+            Some(i) => (i + 1) % self.unit_file_search_matches.len(),
+            None => 0,
+        };
+        self.unit_file_search_match_index = Some(next);
+        let line_idx = self.unit_file_search_matches[next];
+        if line_idx < self.unit_file_scroll
+            || line_idx >= self.unit_file_scroll + visible_lines
+        {
+            self.unit_file_scroll = line_idx;
+        }
+    }
+
+    pub fn prev_unit_file_match(&mut self, visible_lines: usize) {
+        if self.unit_file_search_matches.is_empty() {
+            return;
+        }
+        let prev = match self.unit_file_search_match_index {
+            Some(0) => self.unit_file_search_matches.len() - 1,
+            Some(i) => i - 1,
+            None => self.unit_file_search_matches.len() - 1,
+        };
+        self.unit_file_search_match_index = Some(prev);
+        let line_idx = self.unit_file_search_matches[prev];
+        if line_idx < self.unit_file_scroll
+            || line_idx >= self.unit_file_scroll + visible_lines
+        {
+            self.unit_file_scroll = line_idx;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::test_runners::{CannedRunner, UnavailableRunner};
+    use crate::service::{LogEntry, LogGrep, LogSource, SystemdUnit, UnitAction, UnitProperties, UnitType, TimeRange};
+
+    fn make_unit(name: &str, sub: &str, desc: &str, file_state: Option<&str>) -> SystemdUnit {
+        SystemdUnit {
+            unit: name.into(),
+            load: "loaded".into(),
+            active: "active".into(),
+            sub: sub.into(),
+            description: desc.into(),
+            detail: None,
+            file_state: file_state.map(|s| s.into()),
+            next_elapse_us: None,
+            last_trigger_us: None,
+            socket_stats: None,
+        }
+    }
+
+    fn make_log(message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: None,
+            priority: None,
+            pid: None,
+            identifier: None,
+            message: message.into(),
+            message_styles: Vec::new(),
+            boot_id: None,
+            invocation_id: None,
+            cursor: None,
+            unit: None,
+            hostname: None,
+            fields: HashMap::new(),
+        }
+    }
+
+    fn test_app_with_services(services: Vec<SystemdUnit>) -> App {
+        let len = services.len();
+        let mut app = App {
+            services,
+            list_state: ListState::default(),
+            last_list_click: None,
+            should_quit: false,
+            pending_quit_confirm: false,
+            error: None,
+            search_query: String::new(),
+            search_mode: false,
+            jump_mode: false,
+            jump_query: String::new(),
+            filtered_indices: (0..len).collect(),
+            selected_units: HashSet::new(),
+            show_state_changes: false,
+            state_changes: HashMap::new(),
+            sub_state_snapshot: None,
+            failure_alert: None,
+            bell_pending: false,
+            notify_pending: Vec::new(),
+            favorites: HashSet::new(),
+            config_path: None,
+            logs: Vec::new(),
+            cached_entry_heights: Vec::new(),
+            cached_entry_heights_width: 0,
+            cached_entry_heights_query: String::new(),
+            cached_entry_heights_dirty: true,
+            cached_log_repeat_counts: Vec::new(),
+            logs_scroll: 0,
+            log_wrap: true,
+            collapse_log_repeats: false,
+            logs_h_scroll: 0,
+            log_history_exhausted: false,
+            last_selected_service: None,
+            log_scroll_memory: HashMap::new(),
+            pending_log_scroll: None,
+            status_filter: None,
+            show_logs: false,
+            split_view: false,
+            focused_pane: Pane::List,
+            show_help: false,
+            help_scroll: 0,
+            help_search_mode: false,
+            help_search_query: String::new(),
+            help_content_lines: 0,
+            help_viewport_lines: 0,
+            show_status_picker: false,
+            status_picker_state: ListState::default(),
+            log_search_query: String::new(),
+            log_search_mode: false,
+            log_search_whole_word: false,
+            log_journal_match_count: None,
+            journal_count_receiver: None,
+            log_search_matches: Vec::new(),
+            log_search_match_index: None,
+            user_mode: false,
+            runner: Arc::new(crate::service::LocalRunner),
+            host_label: None,
+            unit_type: UnitType::Service,
+            show_type_picker: false,
+            type_picker_state: ListState::default(),
+            log_priority_filter: None,
+            log_time_range: TimeRange::All,
+            log_grep: None,
+            filter_main_pid: false,
+            log_main_pid: None,
+            log_line_limit: DEFAULT_LOG_LINE_LIMIT,
+            log_timestamp_format: TimestampFormat::Short,
+            use_utc: false,
+            log_timestamp_micros: false,
+            log_show_hostname: false,
+            log_boot_indices: false,
+            dim_below_priority: None,
+            log_filters_dirty: false,
+            show_priority_picker: false,
+            priority_picker_state: ListState::default(),
+            priority_range_anchor: None,
+            show_time_picker: false,
+            time_picker_state: ListState::default(),
+            show_custom_time_input: false,
+            custom_since_input: String::new(),
+            custom_until_input: String::new(),
+            custom_time_editing_until: false,
+            show_grep_input: false,
+            grep_input: String::new(),
+            grep_case_sensitive_input: false,
+            log_presets: Vec::new(),
+            show_preset_picker: false,
+            preset_picker_state: ListState::default(),
+            show_preset_name_input: false,
+            preset_name_input: String::new(),
+            show_export_picker: false,
+            export_picker_state: ListState::default(),
+            show_timestamp_format_picker: false,
+            timestamp_format_picker_state: ListState::default(),
+            show_details: false,
+            detail_scroll: 0,
+            detail_properties: None,
+            detail_unit_name: None,
+            detail_content_height: 0,
+            detail_content_text: Vec::new(),
+            detail_search_query: String::new(),
+            detail_search_mode: false,
+            detail_search_matches: Vec::new(),
+            detail_search_match_index: None,
+            properties_cache: HashMap::new(),
+            detail_receiver: None,
+            timer_target_receiver: None,
+            raw_properties_receiver: None,
+            show_active_since: false,
+            active_timestamps: HashMap::new(),
+            active_clock: None,
+            active_timestamps_receiver: None,
+            file_state_filter: None,
+            dependency_filter: None,
+            dependency_filter_unit: None,
+            dependency_filter_receiver: None,
+            filter_inverted: false,
+            row_severity_colors: true,
+            wrap_navigation: false,
+            hide_noise: false,
+            show_all_units: true,
+            no_confirm: false,
+            confirm_on_quit: false,
+            bell_on_failure: false,
+            desktop_notifications: false,
+            color_enabled: true,
+            show_file_state_picker: false,
+            file_state_picker_state: ListState::default(),
+            show_action_picker: false,
+            action_picker_state: ListState::default(),
+            available_actions: Vec::new(),
+            show_confirm: false,
+            confirm_action: None,
+            confirm_unit_name: None,
+            confirm_targets: Vec::new(),
+            confirm_warning: None,
+            confirm_warning_receiver: None,
+            confirm_auto_run: false,
+            no_block: false,
+            action_in_progress: false,
+            action_result: None,
+            action_receiver: None,
+            last_action_output: None,
+            last_action: None,
+            batch_results: Vec::new(),
+            batch_receiver: None,
+            batch_scroll: None,
+            refresh_receiver: None,
+            log_refresh_receiver: None,
+            log_load_receiver: None,
+            older_logs_receiver: None,
+            log_refresh_generation: 0,
+            log_stream_generation: 0,
+            status_message: None,
+            system_logs_mode: false,
+            log_source: LogSource::Unit,
+            navigated_from_system_logs: false,
+            log_paused: false,
+            log_follow: true,
+            log_selected_entry: None,
+            show_log_fields: false,
+            log_fields_scroll: 0,
+            show_reverse_deps: false,
+            reverse_deps: Vec::new(),
+            reverse_deps_unit: String::new(),
+            reverse_deps_scroll: 0,
+            reverse_deps_receiver: None,
+            show_action_output: false,
+            action_output_scroll: 0,
+            show_security: false,
+            security_lines: Vec::new(),
+            security_unit: String::new(),
+            security_scroll: 0,
+            security_receiver: None,
+            logs_at_bottom: true,
+            last_refreshed: None,
+            auto_refresh_interval: None,
+            live_tail_interval: DEFAULT_LIVE_TAIL_INTERVAL,
+            pending_count: None,
+            show_unit_file: false,
+            unit_file_content: Vec::new(),
+            unit_file_scroll: 0,
+            unit_file_unit_name: None,
+            unit_file_path: None,
+            unit_file_receiver: None,
+            unit_file_search_query: String::new(),
+            unit_file_search_mode: false,
+            unit_file_search_matches: Vec::new(),
+            unit_file_search_match_index: None,
+        };
+        if !app.filtered_indices.is_empty() {
+            app.list_state.select(Some(0));
+        }
+        app
+    }
+
+    fn test_app_empty() -> App {
+        test_app_with_services(Vec::new())
+    }
+
+    // Post-action unit-list refresh
+
+    #[test]
+    fn test_dismiss_action_result_keeps_pending_refresh() {
+        let mut app = test_app_empty();
+        let (tx, rx) = mpsc::channel();
+        app.refresh_receiver = Some(rx);
+        app.action_result = Some(Ok("Start succeeded".into()));
+        app.dismiss_action_result();
+        assert!(
+            app.refresh_in_flight(),
+            "dismissing the popup must not discard the refresh"
+        );
+
+        // The refresh that arrives after dismissal still lands.
+        tx.send(vec![make_unit("late.service", "running", "Late", None)])
+            .unwrap();
+        app.check_action_progress();
+        assert_eq!(app.services.len(), 1);
+        assert_eq!(app.services[0].unit, "late.service");
+    }
+
+    #[test]
+    fn test_check_action_progress_applies_all_queued_refreshes() {
+        let mut app = test_app_empty();
+        let (tx, rx) = mpsc::channel();
+        app.refresh_receiver = Some(rx);
+        tx.send(vec![make_unit("first.service", "activating", "First", None)])
+            .unwrap();
+        tx.send(vec![make_unit("second.service", "running", "Second", None)])
+            .unwrap();
+        app.check_action_progress();
+        assert_eq!(app.services[0].unit, "second.service", "latest refresh wins");
+        // Channel still open: keep waiting for the delayed refetch.
+        assert!(app.refresh_in_flight());
+        drop(tx);
+        app.check_action_progress();
+        assert!(
+            !app.refresh_in_flight(),
+            "receiver dropped once the thread is done"
+        );
+    }
+
+    #[test]
+    fn test_toggle_user_mode_drops_stale_refresh() {
+        let mut app = test_app_empty();
+        let (_tx, rx) = mpsc::channel::<Vec<SystemdUnit>>();
+        app.refresh_receiver = Some(rx);
+        app.toggle_user_mode();
+        assert!(!app.refresh_in_flight());
+    }
+
+    #[test]
+    fn test_advance_blink() {
+        let cadence = Duration::from_millis(500);
+        assert_eq!(advance_blink(true, Duration::from_millis(499), cadence), (true, Duration::ZERO));
+        assert_eq!(advance_blink(true, Duration::from_millis(500), cadence), (false, cadence));
+        assert_eq!(advance_blink(true, Duration::from_millis(1200), cadence), (true, Duration::from_secs(1)));
+        assert_eq!(
+            advance_blink(false, Duration::from_millis(1600), cadence),
+            (true, Duration::from_millis(1500)),
+            "a stalled loop catches up in one call"
+        );
+    }
+
+    #[test]
+    fn test_auto_refresh_due_off() {
+        assert!(!auto_refresh_due(Duration::from_secs(3600), None));
+    }
+
+    #[test]
+    fn test_auto_refresh_due_elapsed() {
+        let interval = Some(Duration::from_secs(5));
+        assert!(!auto_refresh_due(Duration::from_millis(4999), interval));
+        assert!(auto_refresh_due(Duration::from_secs(5), interval));
+        assert!(auto_refresh_due(Duration::from_secs(12), interval));
+    }
+
+    #[test]
+    fn test_cycle_auto_refresh_wraps_to_off() {
+        let mut app = test_app_empty();
+        let mut seen = Vec::new();
+        for _ in 0..=AUTO_REFRESH_INTERVALS.len() {
+            app.cycle_auto_refresh();
+            seen.push(app.auto_refresh_interval);
+        }
+        assert_eq!(seen[0], Some(AUTO_REFRESH_INTERVALS[0]));
+        assert_eq!(seen[AUTO_REFRESH_INTERVALS.len() - 1], AUTO_REFRESH_INTERVALS.last().copied());
+        assert_eq!(seen[AUTO_REFRESH_INTERVALS.len()], None);
+    }
+
+    #[test]
+    fn test_cycle_live_tail_interval() {
+        let mut app = test_app_empty();
+        assert_eq!(app.live_tail_interval, Duration::from_millis(500));
+        app.cycle_live_tail_interval();
+        assert_eq!(app.live_tail_interval, Duration::from_secs(1));
+        assert_eq!(app.status_message.as_deref(), Some("Live tail every 1s"));
+        app.cycle_live_tail_interval();
+        app.cycle_live_tail_interval();
+        assert_eq!(app.live_tail_interval, Duration::from_millis(250), "wraps");
+        assert_eq!(app.status_message.as_deref(), Some("Live tail every 250ms"));
+
+        app.live_tail_interval = Duration::from_millis(700);
+        app.cycle_live_tail_interval();
+        assert_eq!(app.live_tail_interval, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_background_refresh_preserves_selection_by_name() {
+        let mut app = test_app_with_services(vec![
+            make_unit("a.service", "running", "A", None),
+            make_unit("b.service", "running", "B", None),
+        ]);
+        app.list_state.select(Some(1));
+        let (tx, rx) = mpsc::channel();
+        app.refresh_receiver = Some(rx);
+        tx.send(vec![
+            make_unit("0.service", "running", "New", None),
+            make_unit("a.service", "running", "A", None),
+            make_unit("b.service", "running", "B", None),
+        ])
+        .unwrap();
+        app.check_action_progress();
+        assert_eq!(app.selected_unit().unwrap().unit, "b.service");
+    }
+
+    #[test]
+    fn test_start_background_refresh_skips_when_pending() {
+        let mut app = test_app_empty();
+        let (tx, rx) = mpsc::channel();
+        app.refresh_receiver = Some(rx);
+        app.start_background_refresh();
+        tx.send(vec![make_unit("kept.service", "running", "Kept", None)])
+            .unwrap();
+        app.check_action_progress();
+        assert_eq!(app.services[0].unit, "kept.service", "pending receiver kept");
+    }
+
+    // Async live-tail refresh
+
+    fn app_with_pending_log_refresh(entries: Vec<LogEntry>) -> App {
+        let mut app = test_app_empty();
+        app.show_logs = true;
+        app.logs = vec![make_log("existing")];
+        let (tx, rx) = mpsc::channel();
+        tx.send(entries).unwrap();
+        app.log_refresh_receiver = Some(rx);
+        app
+    }
+
+    #[test]
+    fn test_log_refresh_merge_appends_entries() {
+        let mut app = app_with_pending_log_refresh(vec![make_log("new entry")]);
+        app.check_log_refresh_progress();
+        assert_eq!(app.logs.len(), 2);
+        assert_eq!(app.logs[1].message, "new entry");
+        assert_eq!(app.logs_scroll, usize::MAX);
+        assert!(!app.log_refresh_in_flight());
+    }
+
+    #[test]
+    fn test_log_refresh_stale_generation_dropped() {
+        let mut app = test_app_empty();
+        app.show_logs = true;
+        app.logs = vec![make_log("existing")];
+        app.invalidate_log_stream();
+        // A result tagged with a pre-invalidation generation must be ignored.
+        let (tx, rx) = mpsc::channel();
+        tx.send(vec![make_log("stale entry")]).unwrap();
+        app.log_refresh_receiver = Some(rx);
+        app.log_refresh_generation = app.log_stream_generation.wrapping_sub(1);
+        app.check_log_refresh_progress();
+        assert_eq!(app.logs.len(), 1);
+        assert!(!app.log_refresh_in_flight());
+    }
+
+    #[test]
+    fn test_log_refresh_locked_view_keeps_scroll() {
+        let mut app = app_with_pending_log_refresh(vec![make_log("new entry")]);
+        app.logs_scroll = 0;
+        app.log_follow = false;
+        app.check_log_refresh_progress();
+        assert_eq!(app.logs.len(), 2, "still fetched while locked");
+        assert_eq!(app.logs_scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_up_stops_following_but_keeps_live_tail() {
+        let mut app = test_app_empty();
+        app.show_logs = true;
+        app.logs = (0..20).map(|i| make_log(&i.to_string())).collect();
+        app.logs_scroll = 10;
+        assert!(app.log_follow);
+        app.scroll_logs_up(1);
+        assert!(!app.log_follow);
+        assert!(!app.log_paused, "live tail keeps fetching");
+    }
+
+    #[test]
+    fn test_log_scroll_lock_toggle() {
+        let mut app = test_app_empty();
+        app.show_logs = true;
+        app.logs = vec![make_log("a"), make_log("b")];
+        app.toggle_log_scroll_lock();
+        assert!(!app.log_follow);
+        assert!(!app.log_paused);
+        app.toggle_log_scroll_lock();
+        assert!(app.log_follow);
+        assert_eq!(app.logs_scroll, usize::MAX);
+
+        // No lock while paused: nothing is fetched to scroll past.
+        app.log_paused = true;
+        app.toggle_log_scroll_lock();
+        assert!(app.log_follow);
+    }
+
+    #[test]
+    fn test_log_follow_key_states() {
+        let mut app = test_app_empty();
+        app.show_logs = true;
+        app.logs = vec![make_log("a"), make_log("b")];
+
+        // Live and locked: follow again without pausing.
+        app.logs_scroll = 0;
+        app.log_follow = false;
+        app.toggle_log_follow(10);
+        assert!(!app.log_paused);
+        assert!(app.log_follow);
+        assert_eq!(app.logs_scroll, usize::MAX);
+
+        // Live and following: pause.
+        app.toggle_log_follow(10);
+        assert!(app.log_paused);
+
+        // Paused: resume following.
+        app.toggle_log_follow(10);
+        assert!(!app.log_paused);
+        assert!(app.log_follow);
+    }
+
+    #[test]
+    fn test_log_refresh_dropped_when_paused() {
+        let mut app = app_with_pending_log_refresh(vec![make_log("while paused")]);
+        app.log_paused = true;
+        app.check_log_refresh_progress();
+        assert_eq!(app.logs.len(), 1);
+    }
+
+    #[test]
+    fn test_log_refresh_dropped_when_logs_closed() {
+        let mut app = app_with_pending_log_refresh(vec![make_log("after close")]);
+        app.show_logs = false;
+        app.check_log_refresh_progress();
+        assert_eq!(app.logs.len(), 1);
+    }
+
+    #[test]
+    fn test_refresh_logs_noop_while_in_flight() {
+        let mut app = app_with_pending_log_refresh(vec![make_log("pending")]);
+        app.last_selected_service = Some("test.service".into());
+        app.log_stream_generation = 7;
+        app.log_refresh_generation = 3;
+        app.refresh_logs();
+        // A second refresh must not be started: the pending receiver and its
+        // generation tag stay untouched.
+        assert_eq!(app.log_refresh_generation, 3);
+        app.check_log_refresh_progress();
+        assert_eq!(app.logs.len(), 1, "stale-tagged result must be dropped");
+    }
+
+    #[test]
+    fn test_invalidate_log_stream_bumps_generation_and_drops_receiver() {
+        let mut app = app_with_pending_log_refresh(Vec::new());
+        let before = app.log_stream_generation;
+        app.invalidate_log_stream();
+        assert_ne!(app.log_stream_generation, before);
+        assert!(!app.log_refresh_in_flight());
+    }
+
+    fn test_app_with_subs(subs: &[&str]) -> App {
+        let services: Vec<SystemdUnit> = subs
+            .iter()
+            .enumerate()
+            .map(|(i, sub)| make_unit(&format!("unit{}.service", i), sub, &format!("Unit {}", i), None))
+            .collect();
+        test_app_with_services(services)
+    }
+
+    // Phase 1 — Navigation: next
+
+    #[test]
+    fn test_next_moves_down() {
+        let mut app = test_app_with_subs(&["running", "exited", "dead"]);
+        assert_eq!(app.list_state.selected(), Some(0));
+        app.next();
+        assert_eq!(app.list_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_next_clamps_at_end() {
+        let mut app = test_app_with_subs(&["running", "exited"]);
+        app.list_state.select(Some(1));
+        app.next();
+        assert_eq!(app.list_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_next_no_op_on_empty() {
+        let mut app = test_app_empty();
+        app.next();
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_next_selects_0_from_none() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.list_state.select(None);
+        app.next();
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    // Phase 1 — Navigation: previous
+
+    #[test]
+    fn test_previous_moves_up() {
+        let mut app = test_app_with_subs(&["running", "exited", "dead"]);
+        app.list_state.select(Some(2));
+        app.previous();
+        assert_eq!(app.list_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_previous_clamps_at_top() {
+        let mut app = test_app_with_subs(&["running", "exited"]);
+        app.list_state.select(Some(0));
+        app.previous();
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_previous_no_op_on_empty() {
+        let mut app = test_app_empty();
+        app.previous();
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_previous_selects_0_from_none() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.list_state.select(None);
+        app.previous();
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    // Phase 1 — Navigation: repeat counts
+
+    #[test]
+    fn test_next_by_moves_count_rows() {
+        let mut app = test_app_with_subs(&["a", "b", "c", "d", "e", "f"]);
+        app.next_by(3);
+        assert_eq!(app.list_state.selected(), Some(3));
+        app.next_by(10);
+        assert_eq!(app.list_state.selected(), Some(5));
+    }
+
+    #[test]
+    fn test_previous_by_moves_count_rows() {
+        let mut app = test_app_with_subs(&["a", "b", "c", "d", "e", "f"]);
+        app.list_state.select(Some(5));
+        app.previous_by(2);
+        assert_eq!(app.list_state.selected(), Some(3));
+        app.previous_by(10);
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_next_by_and_previous_by_no_op_on_empty() {
+        let mut app = test_app_empty();
+        app.next_by(5);
+        app.previous_by(5);
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_wrap_navigation_next_at_end_selects_first() {
+        let mut app = test_app_with_subs(&["a", "b", "c"]);
+        app.wrap_navigation = true;
+        app.list_state.select(Some(2));
+        app.next();
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_wrap_navigation_previous_at_start_selects_last() {
+        let mut app = test_app_with_subs(&["a", "b", "c"]);
+        app.wrap_navigation = true;
+        app.list_state.select(Some(0));
+        app.previous();
+        assert_eq!(app.list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_wrap_navigation_with_count() {
+        let mut app = test_app_with_subs(&["a", "b", "c"]);
+        app.wrap_navigation = true;
+        app.list_state.select(Some(1));
+        app.next_by(4);
+        assert_eq!(app.list_state.selected(), Some(2));
+        app.previous_by(5);
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_go_to_line_is_one_based_and_clamped() {
+        let mut app = test_app_with_subs(&["a", "b", "c", "d"]);
+        app.go_to_line(2);
+        assert_eq!(app.list_state.selected(), Some(1));
+        app.go_to_line(0);
+        assert_eq!(app.list_state.selected(), Some(0));
+        app.go_to_line(99);
+        assert_eq!(app.list_state.selected(), Some(3));
+    }
+
+    #[test]
+    fn test_push_count_digit_accumulates() {
+        let mut app = test_app_with_subs(&["a"]);
+        app.push_count_digit(1);
+        app.push_count_digit(0);
+        assert_eq!(app.pending_count, Some(10));
+        assert_eq!(app.status_message.as_deref(), Some("10"));
+    }
+
+    // Phase 1 — Navigation: go_to_top / go_to_bottom
+
+    #[test]
+    fn test_go_to_top() {
+        let mut app = test_app_with_subs(&["a", "b", "c"]);
+        app.list_state.select(Some(2));
+        app.go_to_top();
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_go_to_top_empty() {
+        let mut app = test_app_empty();
+        app.go_to_top();
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_go_to_bottom() {
+        let mut app = test_app_with_subs(&["a", "b", "c"]);
+        app.go_to_bottom();
+        assert_eq!(app.list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_go_to_bottom_empty() {
+        let mut app = test_app_empty();
+        app.go_to_bottom();
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    // Phase 1 — Navigation: page_up / page_down
+
+    #[test]
+    fn test_page_down() {
+        let mut app = test_app_with_subs(&["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]);
+        app.page_down(3);
+        assert_eq!(app.list_state.selected(), Some(3));
+    }
+
+    #[test]
+    fn test_page_down_clamps() {
+        let mut app = test_app_with_subs(&["a", "b", "c"]);
+        app.page_down(10);
+        assert_eq!(app.list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_page_up() {
+        let mut app = test_app_with_subs(&["a", "b", "c", "d", "e"]);
+        app.list_state.select(Some(4));
+        app.page_up(3);
+        assert_eq!(app.list_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_page_up_clamps() {
+        let mut app = test_app_with_subs(&["a", "b", "c"]);
+        app.list_state.select(Some(1));
+        app.page_up(10);
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_page_up_empty() {
+        let mut app = test_app_empty();
+        app.page_up(5);
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_page_down_empty() {
+        let mut app = test_app_empty();
+        app.page_down(5);
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    // Phase 1 — Selection & filtering
+
+    #[test]
+    fn test_selected_unit_returns_correct_unit() {
+        let app = test_app_with_subs(&["running", "exited", "dead"]);
+        let unit = app.selected_unit().unwrap();
+        assert_eq!(unit.sub, "running");
+    }
+
+    #[test]
+    fn test_selected_unit_index_1() {
+        let mut app = test_app_with_subs(&["running", "exited", "dead"]);
+        app.list_state.select(Some(1));
+        let unit = app.selected_unit().unwrap();
+        assert_eq!(unit.sub, "exited");
+    }
+
+    #[test]
+    fn test_selected_unit_with_filter() {
+        let mut app = test_app_with_services(vec![
+            make_unit("a.service", "running", "A", None),
+            make_unit("b.service", "dead", "B", None),
+            make_unit("c.service", "running", "C", None),
+        ]);
+        app.status_filter = Some("running".into());
+        app.update_filter();
+        // filtered_indices should be [0, 2]
+        assert_eq!(app.filtered_indices, vec![0, 2]);
+        // Select the second filtered item
+        app.list_state.select(Some(1));
+        let unit = app.selected_unit().unwrap();
+        assert_eq!(unit.unit, "c.service");
+    }
+
+    #[test]
+    fn test_selected_unit_none_empty() {
+        let app = test_app_empty();
+        assert!(app.selected_unit().is_none());
+    }
+
+    #[test]
+    fn test_selected_unit_none_no_selection() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.list_state.select(None);
+        assert!(app.selected_unit().is_none());
+    }
+
+    #[test]
+    fn test_update_filter_no_filters() {
+        let mut app = test_app_with_subs(&["running", "exited", "dead"]);
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0, 1, 2]);
+    }
+
+    fn app_with_noise() -> App {
+        test_app_with_services(vec![
+            make_unit("a.service", "running", "A", Some("enabled")),
+            make_unit("b.service", "dead", "B", Some("static")),
+            make_unit("c.service", "dead", "C", Some("indirect")),
+            make_unit("d.service", "dead", "D", None),
+        ])
+    }
+
+    #[test]
+    fn test_hide_noise_drops_static_and_indirect() {
+        let mut app = app_with_noise();
+        app.hide_noise = true;
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0, 3]);
+        assert!(app.hiding_noise());
+    }
+
+    #[test]
+    fn test_hide_noise_off_during_search() {
+        let mut app = app_with_noise();
+        app.hide_noise = true;
+        app.search_query = ".service".into();
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0, 1, 2, 3]);
+        assert!(!app.hiding_noise());
+    }
+
+    #[test]
+    fn test_hide_noise_off_with_file_state_filter() {
+        let mut app = app_with_noise();
+        app.hide_noise = true;
+        app.file_state_filter = Some("static".into());
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_toggle_hide_noise() {
+        let mut app = app_with_noise();
+        app.toggle_hide_noise();
+        assert!(app.hide_noise);
+        assert_eq!(app.filtered_indices, vec![0, 3]);
+        app.toggle_hide_noise();
+        assert!(!app.hide_noise);
+        assert_eq!(app.filtered_indices, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_update_filter_search_by_unit_name() {
+        let mut app = test_app_with_services(vec![
+            make_unit("ssh.service", "running", "SSH", None),
+            make_unit("sshd.service", "running", "SSH Daemon", None),
+            make_unit("nginx.service", "running", "Nginx", None),
+        ]);
+        app.search_query = "ssh".into();
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_update_filter_search_by_description() {
+        let mut app = test_app_with_services(vec![
+            make_unit("pg.service", "running", "PostgreSQL database", None),
+            make_unit("nginx.service", "running", "Nginx web", None),
+        ]);
+        app.search_query = "database".into();
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0]);
+    }
+
+    #[test]
+    fn test_update_filter_search_case_insensitive() {
+        let mut app = test_app_with_services(vec![
+            make_unit("SSH.service", "running", "desc", None),
+        ]);
+        app.search_query = "ssh".into();
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0]);
+    }
+
+    #[test]
+    fn test_update_filter_status_filter() {
+        let mut app = test_app_with_subs(&["running", "dead", "running"]);
+        app.status_filter = Some("running".into());
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_update_filter_combined_search_and_status() {
+        let mut app = test_app_with_services(vec![
+            make_unit("ssh.service", "running", "SSH", None),
+            make_unit("sshd.service", "dead", "SSH Daemon", None),
+            make_unit("nginx.service", "running", "Nginx", None),
+        ]);
+        app.search_query = "ssh".into();
+        app.status_filter = Some("running".into());
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0]);
+    }
+
+    #[test]
+    fn test_update_filter_no_matches_clears_selection() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.search_query = "nonexistent".into();
+        app.update_filter();
+        assert!(app.filtered_indices.is_empty());
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_update_filter_resets_out_of_bounds_selection() {
+        let mut app = test_app_with_subs(&["running", "dead", "exited"]);
+        app.list_state.select(Some(2));
+        app.status_filter = Some("running".into());
+        app.update_filter();
+        // Only 1 match, selection 2 is out of bounds → reset to 0
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_clear_search() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.search_query = "nonexistent".into();
+        app.update_filter();
+        assert!(app.filtered_indices.is_empty());
+        app.clear_search();
+        assert!(app.search_query.is_empty());
+        assert_eq!(app.filtered_indices, vec![0, 1]);
+    }
+
+    // Go-to-unit prompt
+
+    fn jump_test_app() -> App {
+        test_app_with_services(vec![
+            make_unit("cron.service", "running", "", None),
+            make_unit("nginx.service", "running", "", None),
+            make_unit("NetworkManager.service", "running", "", None),
+            make_unit("sshd.service", "running", "", None),
+        ])
+    }
+
+    #[test]
+    fn test_jump_to_prefix_selects_first_match() {
+        let mut app = jump_test_app();
+        assert!(app.jump_to("ss"));
+        assert_eq!(app.list_state.selected(), Some(3));
+        // Case-insensitive, first match in list order wins.
+        assert!(app.jump_to("n"));
+        assert_eq!(app.list_state.selected(), Some(1));
+        assert!(app.jump_to("netw"));
+        assert_eq!(app.list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_jump_to_no_match_leaves_selection() {
+        let mut app = jump_test_app();
+        app.list_state.select(Some(2));
+        assert!(!app.jump_to("zzz"));
+        assert_eq!(app.list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_jump_keeps_full_list() {
+        let mut app = jump_test_app();
+        app.open_jump();
+        for c in "sshd".chars() {
+            app.jump_query_push(c);
+        }
+        assert_eq!(app.list_state.selected(), Some(3));
+        assert_eq!(app.filtered_indices, vec![0, 1, 2, 3]);
+        app.jump_query_pop();
+        assert_eq!(app.jump_query, "ssh");
+        app.close_jump();
+        assert!(!app.jump_mode);
+        assert!(app.jump_query.is_empty());
+        assert_eq!(app.list_state.selected(), Some(3));
+    }
+
+    #[test]
+    fn test_jump_to_respects_filter() {
+        let mut app = jump_test_app();
+        app.search_query = "service".into();
+        app.status_filter = Some("running".into());
+        app.services[0].sub = "dead".into();
+        app.update_filter();
+        assert!(!app.jump_to("cron"));
+        assert!(app.jump_to("nginx"));
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    // Phase 4 — File state filtering
+
+    #[test]
+    fn test_update_filter_file_state() {
+        let mut app = test_app_with_services(vec![
+            make_unit("a.service", "running", "A", Some("enabled")),
+            make_unit("b.service", "running", "B", Some("disabled")),
+            make_unit("c.service", "running", "C", Some("enabled")),
+        ]);
+        app.file_state_filter = Some("enabled".into());
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_update_filter_combined_search_status_file_state() {
+        let mut app = test_app_with_services(vec![
+            make_unit("ssh.service", "running", "SSH", Some("enabled")),
+            make_unit("sshd.service", "running", "SSH Daemon", Some("disabled")),
+            make_unit("nginx.service", "dead", "Nginx", Some("enabled")),
+            make_unit("pg.service", "running", "PostgreSQL", Some("enabled")),
+        ]);
+        app.search_query = "ssh".into();
+        app.status_filter = Some("running".into());
+        app.file_state_filter = Some("enabled".into());
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![0]);
+    }
+
+    // Phase 1 — Status picker
+
+    #[test]
+    fn test_status_picker_next() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_status_picker();
+        assert_eq!(app.status_picker_state.selected(), Some(0));
+        app.status_picker_next();
+        assert_eq!(app.status_picker_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_status_picker_previous() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_status_picker();
+        app.status_picker_state.select(Some(2));
+        app.status_picker_previous();
+        assert_eq!(app.status_picker_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_status_picker_next_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_status_picker();
+        let last = app.unit_type.status_options().len() - 1;
+        app.status_picker_state.select(Some(last));
+        app.status_picker_next();
+        assert_eq!(app.status_picker_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_status_picker_previous_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_status_picker();
+        app.status_picker_state.select(Some(0));
+        app.status_picker_previous();
+        let last = app.unit_type.status_options().len() - 1;
+        assert_eq!(app.status_picker_state.selected(), Some(last));
+    }
+
+    #[test]
+    fn test_status_picker_confirm_all() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.status_filter = Some("running".into());
+        app.open_status_picker();
+        app.status_picker_state.select(Some(0)); // "All"
+        app.status_picker_confirm();
+        assert_eq!(app.status_filter, None);
+        assert!(!app.show_status_picker);
+    }
+
+    #[test]
+    fn test_pick_picker_row_selects_and_confirms() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.open_status_picker();
+        assert_eq!(app.open_picker(), Some(Picker::Status));
+        app.pick_picker_row(Picker::Status, 1); // "running"
+        assert_eq!(app.status_filter, Some("running".into()));
+        assert!(!app.show_status_picker);
+        assert_eq!(app.open_picker(), None);
+    }
+
+    #[test]
+    fn test_pick_picker_row_out_of_range_ignored() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.open_file_state_picker();
+        let len = app.picker_len(Picker::FileState);
+        app.pick_picker_row(Picker::FileState, len);
+        assert!(app.show_file_state_picker);
+        assert_eq!(app.file_state_filter, None);
+    }
+
+    #[test]
+    fn test_status_picker_confirm_running() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.open_status_picker();
+        app.status_picker_state.select(Some(1)); // "running"
+        app.status_picker_confirm();
+        assert_eq!(app.status_filter, Some("running".into()));
+        assert_eq!(app.filtered_indices, vec![0]);
+        assert!(!app.show_status_picker);
+    }
+
+    #[test]
+    fn test_open_status_picker_preselects_all() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.status_filter = None;
+        app.open_status_picker();
+        assert_eq!(app.status_picker_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_open_status_picker_preselects_current() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.status_filter = Some("running".into());
+        app.open_status_picker();
+        // "running" is index 1 for Service type
+        assert_eq!(app.status_picker_state.selected(), Some(1));
+    }
+
+    // Phase 2 — Type picker
+
+    #[test]
+    fn test_type_picker_next() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_type_picker();
+        assert_eq!(app.type_picker_state.selected(), Some(0));
+        app.type_picker_next();
+        assert_eq!(app.type_picker_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_type_picker_previous() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_type_picker();
+        app.type_picker_state.select(Some(2));
+        app.type_picker_previous();
+        assert_eq!(app.type_picker_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_type_picker_next_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_type_picker();
+        app.type_picker_state.select(Some(UNIT_TYPES.len() - 1));
+        app.type_picker_next();
+        assert_eq!(app.type_picker_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_type_picker_previous_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_type_picker();
+        app.type_picker_state.select(Some(0));
+        app.type_picker_previous();
+        assert_eq!(
+            app.type_picker_state.selected(),
+            Some(UNIT_TYPES.len() - 1)
         );
+    }
 
-        // The refresh that arrives after dismissal still lands.
-        tx.send(vec![make_unit("late.service", "running", "Late", None)])
-            .unwrap();
-        app.check_action_progress();
-        assert_eq!(app.services.len(), 1);
-        assert_eq!(app.services[0].unit, "late.service");
+    #[test]
+    fn test_dependents_warning_none_without_dependents() {
+        assert_eq!(dependents_warning("leaf.service", false, &CannedRunner::ok("leaf.service\n")), None);
+        assert_eq!(dependents_warning("leaf.service", false, &UnavailableRunner), None, "lookup errors don't warn");
     }
 
     #[test]
-    fn test_check_action_progress_applies_all_queued_refreshes() {
-        let mut app = test_app_empty();
-        let (tx, rx) = mpsc::channel();
-        app.refresh_receiver = Some(rx);
-        tx.send(vec![make_unit("first.service", "activating", "First", None)])
-            .unwrap();
-        tx.send(vec![make_unit("second.service", "running", "Second", None)])
-            .unwrap();
-        app.check_action_progress();
-        assert_eq!(app.services[0].unit, "second.service", "latest refresh wins");
-        // Channel still open: keep waiting for the delayed refetch.
-        assert!(app.refresh_in_flight());
-        drop(tx);
-        app.check_action_progress();
-        assert!(
-            !app.refresh_in_flight(),
-            "receiver dropped once the thread is done"
+    fn test_dependents_warning_counts_and_names_first_few() {
+        let runner = CannedRunner::ok(
+            "network.target\n  sshd.service\n  nginx.service\n  multi-user.target\n    graphical.target\n",
+        );
+        assert_eq!(
+            dependents_warning("network.target", false, &runner).as_deref(),
+            Some("4 units depend on network.target: sshd.service, nginx.service, multi-user.target, \u{2026}")
+        );
+        assert_eq!(
+            dependents_warning("a.socket", false, &CannedRunner::ok("a.socket\n  a.service\n")).as_deref(),
+            Some("1 unit depends on a.socket: a.service")
         );
     }
 
     #[test]
-    fn test_toggle_user_mode_drops_stale_refresh() {
-        let mut app = test_app_empty();
-        let (_tx, rx) = mpsc::channel::<Vec<SystemdUnit>>();
-        app.refresh_receiver = Some(rx);
-        app.toggle_user_mode();
-        assert!(!app.refresh_in_flight());
+    fn test_stop_confirm_loads_dependents_warning() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.runner = Arc::new(CannedRunner::ok("x\n  dependent.service\n"));
+        app.open_action_picker();
+        let stop = app.available_actions.iter().position(|&a| a == UnitAction::Stop).unwrap();
+        app.action_picker_state.select(Some(stop));
+        app.action_picker_confirm();
+        assert!(app.confirm_warning_loading(), "looked up off the UI thread");
+        settle(&mut app, App::confirm_warning_loading);
+        assert!(app.confirm_warning.as_deref().is_some_and(|w| w.contains("dependent.service")));
+        app.confirm_no();
+        assert!(app.confirm_warning.is_none());
     }
 
-    // Async live-tail refresh
-
-    fn app_with_pending_log_refresh(entries: Vec<LogEntry>) -> App {
-        let mut app = test_app_empty();
-        app.show_logs = true;
-        app.logs = vec![make_log("existing")];
-        let (tx, rx) = mpsc::channel();
-        tx.send(entries).unwrap();
-        app.log_refresh_receiver = Some(rx);
-        app
+    /// Polls background results until `busy` clears.
+    fn settle(app: &mut App, busy: fn(&App) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while busy(app) && Instant::now() < deadline {
+            app.check_action_progress();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(!busy(app), "background fetch never finished");
     }
 
-    #[test]
-    fn test_log_refresh_merge_appends_entries() {
-        let mut app = app_with_pending_log_refresh(vec![make_log("new entry")]);
-        app.check_log_refresh_progress();
-        assert_eq!(app.logs.len(), 2);
-        assert_eq!(app.logs[1].message, "new entry");
-        assert_eq!(app.logs_scroll, usize::MAX);
-        assert!(!app.log_refresh_in_flight());
+    /// Behaves like a host without systemd: every binary is missing.
+    struct MissingBinaries;
+
+    impl CommandRunner for MissingBinaries {
+        fn run(&self, program: &str, _args: &[&str]) -> Result<crate::service::CommandOutput, String> {
+            let e = std::io::Error::from(std::io::ErrorKind::NotFound);
+            Err(crate::service::spawn_error(program, &e))
+        }
     }
 
     #[test]
-    fn test_log_refresh_stale_generation_dropped() {
-        let mut app = test_app_empty();
-        app.show_logs = true;
-        app.logs = vec![make_log("existing")];
-        app.invalidate_log_stream();
-        // A result tagged with a pre-invalidation generation must be ignored.
-        let (tx, rx) = mpsc::channel();
-        tx.send(vec![make_log("stale entry")]).unwrap();
-        app.log_refresh_receiver = Some(rx);
-        app.log_refresh_generation = app.log_stream_generation.wrapping_sub(1);
-        app.check_log_refresh_progress();
-        assert_eq!(app.logs.len(), 1);
-        assert!(!app.log_refresh_in_flight());
+    fn test_missing_systemctl_is_the_main_error() {
+        let app = App::new(Arc::new(MissingBinaries), None, false, UnitType::Service);
+        let error = app.error.expect("load error");
+        assert!(error.starts_with("systemctl "), "{error}");
+        // Detected as a missing binary, so no user-mode hint is appended.
+        assert!(is_missing_binary_error(&error), "{error}");
     }
 
     #[test]
-    fn test_log_refresh_dropped_when_paused() {
-        let mut app = app_with_pending_log_refresh(vec![make_log("while paused")]);
-        app.log_paused = true;
-        app.check_log_refresh_progress();
-        assert_eq!(app.logs.len(), 1);
+    fn test_reverse_dependencies_error_keeps_popup_closed() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.runner = Arc::new(UnavailableRunner);
+        app.open_reverse_dependencies();
+        settle(&mut app, App::reverse_deps_loading);
+        assert!(!app.show_reverse_deps);
+        assert!(app.status_message.as_deref().is_some_and(|m| m.contains("not available")));
     }
 
     #[test]
-    fn test_log_refresh_dropped_when_logs_closed() {
-        let mut app = app_with_pending_log_refresh(vec![make_log("after close")]);
-        app.show_logs = false;
-        app.check_log_refresh_progress();
-        assert_eq!(app.logs.len(), 1);
+    fn test_reverse_dependencies_open_when_lookup_finishes() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.runner = Arc::new(CannedRunner::ok("unit0.service\nmulti-user.target\n"));
+        app.open_reverse_dependencies();
+        assert!(app.reverse_deps_loading(), "runs off the UI thread");
+        assert!(!app.show_reverse_deps);
+        settle(&mut app, App::reverse_deps_loading);
+        assert!(app.show_reverse_deps);
+        assert_eq!(app.reverse_deps, ["multi-user.target"]);
+        assert_eq!(app.reverse_deps_unit, "unit0.service");
     }
 
     #[test]
-    fn test_refresh_logs_noop_while_in_flight() {
-        let mut app = app_with_pending_log_refresh(vec![make_log("pending")]);
-        app.last_selected_service = Some("test.service".into());
-        app.log_stream_generation = 7;
-        app.log_refresh_generation = 3;
-        app.refresh_logs();
-        // A second refresh must not be started: the pending receiver and its
-        // generation tag stay untouched.
-        assert_eq!(app.log_refresh_generation, 3);
-        app.check_log_refresh_progress();
-        assert_eq!(app.logs.len(), 1, "stale-tagged result must be dropped");
+    fn test_next_unit_type_wraps_through_all_types() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.runner = Arc::new(UnavailableRunner);
+        let mut seen = vec![app.unit_type];
+        for _ in 1..UNIT_TYPES.len() {
+            app.next_unit_type();
+            seen.push(app.unit_type);
+        }
+        assert_eq!(seen, UNIT_TYPES);
+        app.next_unit_type();
+        assert_eq!(app.unit_type, UNIT_TYPES[0]);
+        app.prev_unit_type();
+        assert_eq!(app.unit_type, UNIT_TYPES[UNIT_TYPES.len() - 1]);
     }
 
     #[test]
-    fn test_invalidate_log_stream_bumps_generation_and_drops_receiver() {
-        let mut app = app_with_pending_log_refresh(Vec::new());
-        let before = app.log_stream_generation;
-        app.invalidate_log_stream();
-        assert_ne!(app.log_stream_generation, before);
-        assert!(!app.log_refresh_in_flight());
+    fn test_next_unit_type_clears_filters_like_picker() {
+        let mut app = test_app_with_subs(&["running", "failed"]);
+        app.runner = Arc::new(UnavailableRunner);
+        app.status_filter = Some("failed".into());
+        app.file_state_filter = Some("enabled".into());
+        app.search_query = "ssh".into();
+        app.log_priority_filter = Some((0, 3));
+        app.selected_units.insert("test.service".into());
+        app.next_unit_type();
+        assert_eq!(app.unit_type, UNIT_TYPES[1]);
+        assert_eq!(app.status_filter, None);
+        assert_eq!(app.file_state_filter, None);
+        assert!(app.search_query.is_empty());
+        assert_eq!(app.log_priority_filter, None);
+        assert!(app.selected_units.is_empty());
     }
 
-    fn test_app_with_subs(subs: &[&str]) -> App {
-        let services: Vec<SystemdUnit> = subs
-            .iter()
-            .enumerate()
-            .map(|(i, sub)| make_unit(&format!("unit{}.service", i), sub, &format!("Unit {}", i), None))
-            .collect();
-        test_app_with_services(services)
+    #[test]
+    fn test_type_picker_confirm_same_type_no_change() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        let original_services_len = app.services.len();
+        app.open_type_picker();
+        // Service is index 0, which is already the current type
+        app.type_picker_state.select(Some(0));
+        app.type_picker_confirm();
+        // Services should not be reloaded (same type)
+        assert_eq!(app.services.len(), original_services_len);
+        assert!(!app.show_type_picker);
     }
 
-    // Phase 1 — Navigation: next
+    // Phase 4 — File state picker
 
     #[test]
-    fn test_next_moves_down() {
-        let mut app = test_app_with_subs(&["running", "exited", "dead"]);
-        assert_eq!(app.list_state.selected(), Some(0));
-        app.next();
-        assert_eq!(app.list_state.selected(), Some(1));
+    fn test_file_state_picker_next() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_file_state_picker();
+        assert_eq!(app.file_state_picker_state.selected(), Some(0));
+        app.file_state_picker_next();
+        assert_eq!(app.file_state_picker_state.selected(), Some(1));
     }
 
     #[test]
-    fn test_next_clamps_at_end() {
-        let mut app = test_app_with_subs(&["running", "exited"]);
-        app.list_state.select(Some(1));
-        app.next();
-        assert_eq!(app.list_state.selected(), Some(1));
+    fn test_file_state_picker_previous() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_file_state_picker();
+        app.file_state_picker_state.select(Some(3));
+        app.file_state_picker_previous();
+        assert_eq!(app.file_state_picker_state.selected(), Some(2));
     }
 
     #[test]
-    fn test_next_no_op_on_empty() {
-        let mut app = test_app_empty();
-        app.next();
-        assert_eq!(app.list_state.selected(), None);
+    fn test_file_state_picker_next_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_file_state_picker();
+        app.file_state_picker_state
+            .select(Some(FILE_STATE_OPTIONS.len() - 1));
+        app.file_state_picker_next();
+        assert_eq!(app.file_state_picker_state.selected(), Some(0));
     }
 
     #[test]
-    fn test_next_selects_0_from_none() {
+    fn test_file_state_picker_previous_wraps() {
         let mut app = test_app_with_subs(&["running"]);
-        app.list_state.select(None);
-        app.next();
-        assert_eq!(app.list_state.selected(), Some(0));
+        app.open_file_state_picker();
+        app.file_state_picker_state.select(Some(0));
+        app.file_state_picker_previous();
+        assert_eq!(
+            app.file_state_picker_state.selected(),
+            Some(FILE_STATE_OPTIONS.len() - 1)
+        );
     }
 
-    // Phase 1 — Navigation: previous
-
     #[test]
-    fn test_previous_moves_up() {
-        let mut app = test_app_with_subs(&["running", "exited", "dead"]);
-        app.list_state.select(Some(2));
-        app.previous();
-        assert_eq!(app.list_state.selected(), Some(1));
+    fn test_file_state_picker_confirm_all() {
+        let mut app = test_app_with_services(vec![
+            make_unit("a.service", "running", "A", Some("enabled")),
+            make_unit("b.service", "running", "B", Some("disabled")),
+        ]);
+        app.file_state_filter = Some("enabled".into());
+        app.open_file_state_picker();
+        app.file_state_picker_state.select(Some(0)); // "All"
+        app.file_state_picker_confirm();
+        assert_eq!(app.file_state_filter, None);
+        assert!(!app.show_file_state_picker);
     }
 
     #[test]
-    fn test_previous_clamps_at_top() {
-        let mut app = test_app_with_subs(&["running", "exited"]);
-        app.list_state.select(Some(0));
-        app.previous();
-        assert_eq!(app.list_state.selected(), Some(0));
+    fn test_file_state_picker_confirm_enabled() {
+        let mut app = test_app_with_services(vec![
+            make_unit("a.service", "running", "A", Some("enabled")),
+            make_unit("b.service", "running", "B", Some("disabled")),
+        ]);
+        app.open_file_state_picker();
+        app.file_state_picker_state.select(Some(1)); // "enabled"
+        app.file_state_picker_confirm();
+        assert_eq!(app.file_state_filter, Some("enabled".into()));
+        assert_eq!(app.filtered_indices, vec![0]);
+        assert!(!app.show_file_state_picker);
     }
 
     #[test]
-    fn test_previous_no_op_on_empty() {
-        let mut app = test_app_empty();
-        app.previous();
-        assert_eq!(app.list_state.selected(), None);
+    fn test_open_file_state_picker_preselects_all() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.file_state_filter = None;
+        app.open_file_state_picker();
+        assert_eq!(app.file_state_picker_state.selected(), Some(0));
     }
 
     #[test]
-    fn test_previous_selects_0_from_none() {
+    fn test_open_file_state_picker_preselects_current() {
         let mut app = test_app_with_subs(&["running"]);
-        app.list_state.select(None);
-        app.previous();
-        assert_eq!(app.list_state.selected(), Some(0));
+        app.file_state_filter = Some("disabled".into());
+        app.open_file_state_picker();
+        // "disabled" is index 2 in FILE_STATE_OPTIONS
+        assert_eq!(app.file_state_picker_state.selected(), Some(2));
     }
 
-    // Phase 1 — Navigation: go_to_top / go_to_bottom
+    // Phase 3 — Priority picker
 
     #[test]
-    fn test_go_to_top() {
-        let mut app = test_app_with_subs(&["a", "b", "c"]);
-        app.list_state.select(Some(2));
-        app.go_to_top();
-        assert_eq!(app.list_state.selected(), Some(0));
+    fn test_priority_picker_next() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_priority_picker();
+        assert_eq!(app.priority_picker_state.selected(), Some(0));
+        app.priority_picker_next();
+        assert_eq!(app.priority_picker_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_priority_picker_previous() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_priority_picker();
+        app.priority_picker_state.select(Some(3));
+        app.priority_picker_previous();
+        assert_eq!(app.priority_picker_state.selected(), Some(2));
     }
 
     #[test]
-    fn test_go_to_top_empty() {
-        let mut app = test_app_empty();
-        app.go_to_top();
-        assert_eq!(app.list_state.selected(), None);
+    fn test_priority_picker_next_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_priority_picker();
+        app.priority_picker_state.select(Some(8)); // last item (0-8 = 9 items)
+        app.priority_picker_next();
+        assert_eq!(app.priority_picker_state.selected(), Some(0));
     }
 
     #[test]
-    fn test_go_to_bottom() {
-        let mut app = test_app_with_subs(&["a", "b", "c"]);
-        app.go_to_bottom();
-        assert_eq!(app.list_state.selected(), Some(2));
+    fn test_priority_picker_previous_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_priority_picker();
+        app.priority_picker_state.select(Some(0));
+        app.priority_picker_previous();
+        assert_eq!(app.priority_picker_state.selected(), Some(8));
     }
 
     #[test]
-    fn test_go_to_bottom_empty() {
-        let mut app = test_app_empty();
-        app.go_to_bottom();
-        assert_eq!(app.list_state.selected(), None);
+    fn test_priority_picker_confirm_all() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.log_priority_filter = Some((0, 3));
+        app.open_priority_picker();
+        app.priority_picker_state.select(Some(0)); // "All"
+        app.priority_picker_confirm();
+        assert_eq!(app.log_priority_filter, None);
+        assert!(!app.show_priority_picker);
     }
 
-    // Phase 1 — Navigation: page_up / page_down
-
     #[test]
-    fn test_page_down() {
-        let mut app = test_app_with_subs(&["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]);
-        app.page_down(3);
-        assert_eq!(app.list_state.selected(), Some(3));
+    fn test_priority_picker_confirm_err() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_priority_picker();
+        app.priority_picker_state.select(Some(4)); // err (index-1 = 3)
+        app.priority_picker_confirm();
+        assert_eq!(app.log_priority_filter, Some((0, 3)));
+        assert!(app.log_filters_dirty);
+        assert!(!app.show_priority_picker);
     }
 
     #[test]
-    fn test_page_down_clamps() {
-        let mut app = test_app_with_subs(&["a", "b", "c"]);
-        app.page_down(10);
-        assert_eq!(app.list_state.selected(), Some(2));
+    fn test_open_priority_picker_preselects_current() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.log_priority_filter = Some((0, 5));
+        app.open_priority_picker();
+        assert_eq!(app.priority_picker_state.selected(), Some(6)); // 5 + 1
+        assert_eq!(app.priority_range_anchor, None);
     }
 
     #[test]
-    fn test_page_up() {
-        let mut app = test_app_with_subs(&["a", "b", "c", "d", "e"]);
-        app.list_state.select(Some(4));
-        app.page_up(3);
-        assert_eq!(app.list_state.selected(), Some(1));
+    fn test_open_priority_picker_restores_range_anchor() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.log_priority_filter = Some((3, 4));
+        app.open_priority_picker();
+        assert_eq!(app.priority_picker_state.selected(), Some(5));
+        assert_eq!(app.priority_range_anchor, Some(3));
     }
 
     #[test]
-    fn test_page_up_clamps() {
-        let mut app = test_app_with_subs(&["a", "b", "c"]);
-        app.list_state.select(Some(1));
-        app.page_up(10);
-        assert_eq!(app.list_state.selected(), Some(0));
+    fn test_priority_picker_confirm_range() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_priority_picker();
+        app.priority_picker_state.select(Some(5)); // warning
+        app.priority_picker_toggle_anchor();
+        assert_eq!(app.priority_range_anchor, Some(4));
+        app.priority_picker_previous(); // err
+        app.priority_picker_confirm();
+        assert_eq!(app.log_priority_filter, Some((3, 4)));
+        assert_eq!(app.priority_range_anchor, None);
+        assert!(!app.show_priority_picker);
     }
 
     #[test]
-    fn test_page_up_empty() {
-        let mut app = test_app_empty();
-        app.page_up(5);
-        assert_eq!(app.list_state.selected(), None);
+    fn test_priority_picker_toggle_anchor_twice_clears() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_priority_picker();
+        app.priority_picker_state.select(Some(4));
+        app.priority_picker_toggle_anchor();
+        app.priority_picker_toggle_anchor();
+        assert_eq!(app.priority_range_anchor, None);
     }
 
     #[test]
-    fn test_page_down_empty() {
-        let mut app = test_app_empty();
-        app.page_down(5);
-        assert_eq!(app.list_state.selected(), None);
+    fn test_priority_picker_toggle_anchor_ignores_all() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_priority_picker();
+        app.priority_picker_toggle_anchor();
+        assert_eq!(app.priority_range_anchor, None);
     }
 
-    // Phase 1 — Selection & filtering
+    // Phase 3 — Time picker
 
     #[test]
-    fn test_selected_unit_returns_correct_unit() {
-        let app = test_app_with_subs(&["running", "exited", "dead"]);
-        let unit = app.selected_unit().unwrap();
-        assert_eq!(unit.sub, "running");
+    fn test_time_picker_next() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_time_picker();
+        assert_eq!(app.time_picker_state.selected(), Some(0));
+        app.time_picker_next();
+        assert_eq!(app.time_picker_state.selected(), Some(1));
     }
 
     #[test]
-    fn test_selected_unit_index_1() {
-        let mut app = test_app_with_subs(&["running", "exited", "dead"]);
-        app.list_state.select(Some(1));
-        let unit = app.selected_unit().unwrap();
-        assert_eq!(unit.sub, "exited");
+    fn test_time_picker_previous() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_time_picker();
+        app.time_picker_state.select(Some(3));
+        app.time_picker_previous();
+        assert_eq!(app.time_picker_state.selected(), Some(2));
     }
 
     #[test]
-    fn test_selected_unit_with_filter() {
-        let mut app = test_app_with_services(vec![
-            make_unit("a.service", "running", "A", None),
-            make_unit("b.service", "dead", "B", None),
-            make_unit("c.service", "running", "C", None),
-        ]);
-        app.status_filter = Some("running".into());
-        app.update_filter();
-        // filtered_indices should be [0, 2]
-        assert_eq!(app.filtered_indices, vec![0, 2]);
-        // Select the second filtered item
-        app.list_state.select(Some(1));
-        let unit = app.selected_unit().unwrap();
-        assert_eq!(unit.unit, "c.service");
+    fn test_time_picker_next_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_time_picker();
+        app.time_picker_state.select(Some(TIME_RANGES.len())); // "Custom..."
+        app.time_picker_next();
+        assert_eq!(app.time_picker_state.selected(), Some(0));
     }
 
     #[test]
-    fn test_selected_unit_none_empty() {
-        let app = test_app_empty();
-        assert!(app.selected_unit().is_none());
+    fn test_time_picker_previous_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_time_picker();
+        app.time_picker_state.select(Some(0));
+        app.time_picker_previous();
+        assert_eq!(app.time_picker_state.selected(), Some(TIME_RANGES.len()));
     }
 
     #[test]
-    fn test_selected_unit_none_no_selection() {
+    fn test_time_picker_confirm() {
         let mut app = test_app_with_subs(&["running"]);
-        app.list_state.select(None);
-        assert!(app.selected_unit().is_none());
+        app.open_time_picker();
+        app.time_picker_state.select(Some(2)); // OneHour
+        app.time_picker_confirm();
+        assert_eq!(app.log_time_range, TimeRange::OneHour);
+        assert!(app.log_filters_dirty);
+        assert!(!app.show_time_picker);
     }
 
     #[test]
-    fn test_update_filter_no_filters() {
-        let mut app = test_app_with_subs(&["running", "exited", "dead"]);
-        app.update_filter();
-        assert_eq!(app.filtered_indices, vec![0, 1, 2]);
+    fn test_open_time_picker_preselects_current() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.log_time_range = TimeRange::SevenDays;
+        app.open_time_picker();
+        assert_eq!(app.time_picker_state.selected(), Some(4)); // SevenDays is index 4
     }
 
     #[test]
-    fn test_update_filter_search_by_unit_name() {
-        let mut app = test_app_with_services(vec![
-            make_unit("ssh.service", "running", "SSH", None),
-            make_unit("sshd.service", "running", "SSH Daemon", None),
-            make_unit("nginx.service", "running", "Nginx", None),
-        ]);
-        app.search_query = "ssh".into();
-        app.update_filter();
-        assert_eq!(app.filtered_indices, vec![0, 1]);
+    fn test_time_picker_confirm_custom_opens_input() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_time_picker();
+        app.time_picker_state.select(Some(TIME_RANGES.len()));
+        app.time_picker_confirm();
+        assert!(!app.show_time_picker);
+        assert!(app.show_custom_time_input);
+        assert!(!app.log_filters_dirty);
     }
 
     #[test]
-    fn test_update_filter_search_by_description() {
-        let mut app = test_app_with_services(vec![
-            make_unit("pg.service", "running", "PostgreSQL database", None),
-            make_unit("nginx.service", "running", "Nginx web", None),
-        ]);
-        app.search_query = "database".into();
-        app.update_filter();
-        assert_eq!(app.filtered_indices, vec![0]);
+    fn test_custom_time_input_confirm() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_custom_time_input();
+        for c in "2024-01-01 08:00".chars() {
+            app.custom_time_input_push(c);
+        }
+        app.custom_time_input_toggle_field();
+        for c in "2024-01-01 09:00".chars() {
+            app.custom_time_input_push(c);
+        }
+        app.custom_time_input_confirm();
+        assert_eq!(
+            app.log_time_range,
+            TimeRange::Custom {
+                since: "2024-01-01 08:00".into(),
+                until: Some("2024-01-01 09:00".into()),
+            }
+        );
+        assert!(app.log_filters_dirty);
+        assert!(!app.show_custom_time_input);
     }
 
     #[test]
-    fn test_update_filter_search_case_insensitive() {
-        let mut app = test_app_with_services(vec![
-            make_unit("SSH.service", "running", "desc", None),
-        ]);
-        app.search_query = "ssh".into();
-        app.update_filter();
-        assert_eq!(app.filtered_indices, vec![0]);
+    fn test_custom_time_input_empty_until_is_none() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_custom_time_input();
+        app.custom_time_input_push('x');
+        app.custom_time_input_pop();
+        for c in "yesterday".chars() {
+            app.custom_time_input_push(c);
+        }
+        app.custom_time_input_confirm();
+        assert_eq!(
+            app.log_time_range,
+            TimeRange::Custom { since: "yesterday".into(), until: None }
+        );
     }
 
     #[test]
-    fn test_update_filter_status_filter() {
-        let mut app = test_app_with_subs(&["running", "dead", "running"]);
-        app.status_filter = Some("running".into());
-        app.update_filter();
-        assert_eq!(app.filtered_indices, vec![0, 2]);
+    fn test_custom_time_input_requires_since() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_custom_time_input();
+        app.custom_time_input_confirm();
+        assert!(app.show_custom_time_input);
+        assert_eq!(app.log_time_range, TimeRange::All);
     }
 
     #[test]
-    fn test_update_filter_combined_search_and_status() {
-        let mut app = test_app_with_services(vec![
-            make_unit("ssh.service", "running", "SSH", None),
-            make_unit("sshd.service", "dead", "SSH Daemon", None),
-            make_unit("nginx.service", "running", "Nginx", None),
-        ]);
-        app.search_query = "ssh".into();
-        app.status_filter = Some("running".into());
+    fn test_favorites_sort_first() {
+        let mut app = test_app_with_subs(&["running", "dead", "failed", "running"]);
+        app.favorites.insert("unit2.service".into());
+        app.favorites.insert("unit3.service".into());
         app.update_filter();
-        assert_eq!(app.filtered_indices, vec![0]);
+        assert_eq!(app.filtered_indices, vec![2, 3, 0, 1]);
     }
 
     #[test]
-    fn test_update_filter_no_matches_clears_selection() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.search_query = "nonexistent".into();
-        app.update_filter();
-        assert!(app.filtered_indices.is_empty());
-        assert_eq!(app.list_state.selected(), None);
+    fn test_toggle_favorite_moves_unit_and_keeps_highlight() {
+        let mut app = test_app_with_subs(&["running", "dead", "failed"]);
+        app.list_state.select(Some(2));
+        app.toggle_favorite();
+        assert!(app.favorites.contains("unit2.service"));
+        assert_eq!(app.filtered_indices, vec![2, 0, 1]);
+        assert_eq!(app.list_state.selected(), Some(0));
+        assert_eq!(app.selected_unit().unwrap().unit, "unit2.service");
     }
 
     #[test]
-    fn test_update_filter_resets_out_of_bounds_selection() {
-        let mut app = test_app_with_subs(&["running", "dead", "exited"]);
-        app.list_state.select(Some(2));
-        app.status_filter = Some("running".into());
-        app.update_filter();
-        // Only 1 match, selection 2 is out of bounds → reset to 0
-        assert_eq!(app.list_state.selected(), Some(0));
+    fn test_toggle_favorite_twice_restores_order() {
+        let mut app = test_app_with_subs(&["running", "dead", "failed"]);
+        app.list_state.select(Some(1));
+        app.toggle_favorite();
+        app.toggle_favorite();
+        assert!(app.favorites.is_empty());
+        assert_eq!(app.filtered_indices, vec![0, 1, 2]);
+        assert_eq!(app.selected_unit().unwrap().unit, "unit1.service");
     }
 
     #[test]
-    fn test_clear_search() {
+    fn test_favorites_respect_filters() {
         let mut app = test_app_with_subs(&["running", "dead"]);
-        app.search_query = "nonexistent".into();
+        app.favorites.insert("unit1.service".into());
+        app.status_filter = Some("running".into());
         app.update_filter();
-        assert!(app.filtered_indices.is_empty());
-        app.clear_search();
-        assert!(app.search_query.is_empty());
-        assert_eq!(app.filtered_indices, vec![0, 1]);
+        assert_eq!(app.filtered_indices, vec![0]);
     }
 
-    // Phase 4 — File state filtering
+    #[test]
+    fn test_toggle_favorite_persists_to_config() {
+        let dir = std::env::temp_dir().join(format!("systemdmgr-fav-{}", std::process::id()));
+        let path = dir.join("config.json");
+        let mut app = test_app_with_subs(&["running"]);
+        app.config_path = Some(path.clone());
+        app.toggle_favorite();
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.favorites, vec!["unit0.service".to_string()]);
+        let _ = std::fs::remove_dir_all(dir);
+    }
 
     #[test]
-    fn test_update_filter_file_state() {
-        let mut app = test_app_with_services(vec![
-            make_unit("a.service", "running", "A", Some("enabled")),
-            make_unit("b.service", "running", "B", Some("disabled")),
-            make_unit("c.service", "running", "C", Some("enabled")),
-        ]);
-        app.file_state_filter = Some("enabled".into());
-        app.update_filter();
+    fn test_inverted_status_filter_shows_non_matching() {
+        let mut app = test_app_with_subs(&["running", "dead", "running", "failed"]);
+        app.status_filter = Some("running".into());
+        app.toggle_filter_inverted();
+        assert_eq!(app.filtered_indices, vec![1, 3]);
+        app.toggle_filter_inverted();
         assert_eq!(app.filtered_indices, vec![0, 2]);
     }
 
     #[test]
-    fn test_update_filter_combined_search_status_file_state() {
-        let mut app = test_app_with_services(vec![
-            make_unit("ssh.service", "running", "SSH", Some("enabled")),
-            make_unit("sshd.service", "running", "SSH Daemon", Some("disabled")),
-            make_unit("nginx.service", "dead", "Nginx", Some("enabled")),
-            make_unit("pg.service", "running", "PostgreSQL", Some("enabled")),
-        ]);
-        app.search_query = "ssh".into();
-        app.status_filter = Some("running".into());
-        app.file_state_filter = Some("enabled".into());
-        app.update_filter();
-        assert_eq!(app.filtered_indices, vec![0]);
+    fn test_inverted_without_filters_keeps_everything() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.toggle_filter_inverted();
+        assert_eq!(app.filtered_indices, vec![0, 1]);
     }
 
-    // Phase 1 — Status picker
+    #[test]
+    fn test_inverted_filter_keeps_search_positive() {
+        let mut app = test_app_with_subs(&["running", "dead", "failed"]);
+        app.status_filter = Some("running".into());
+        app.search_query = "unit2".into();
+        app.toggle_filter_inverted();
+        assert_eq!(app.filtered_indices, vec![2]);
+    }
 
     #[test]
-    fn test_status_picker_next() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.open_status_picker();
-        assert_eq!(app.status_picker_state.selected(), Some(0));
-        app.status_picker_next();
-        assert_eq!(app.status_picker_state.selected(), Some(1));
+    fn test_inverted_file_state_filter() {
+        let services = vec![
+            make_unit("a.service", "running", "A", Some("enabled")),
+            make_unit("b.service", "running", "B", Some("disabled")),
+            make_unit("c.service", "running", "C", None),
+        ];
+        let mut app = test_app_with_services(services);
+        app.file_state_filter = Some("enabled".into());
+        app.toggle_filter_inverted();
+        assert_eq!(app.filtered_indices, vec![1, 2]);
     }
 
     #[test]
-    fn test_status_picker_previous() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.open_status_picker();
-        app.status_picker_state.select(Some(2));
-        app.status_picker_previous();
-        assert_eq!(app.status_picker_state.selected(), Some(1));
+    fn test_toggle_unit_selection() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        let name = app.selected_unit().unwrap().unit.clone();
+        app.toggle_unit_selection();
+        assert!(app.selected_units.contains(&name));
+        app.toggle_unit_selection();
+        assert!(app.selected_units.is_empty());
     }
 
     #[test]
-    fn test_status_picker_next_wraps() {
+    fn test_bulk_action_targets_sorted() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_status_picker();
-        let last = app.unit_type.status_options().len() - 1;
-        app.status_picker_state.select(Some(last));
-        app.status_picker_next();
-        assert_eq!(app.status_picker_state.selected(), Some(0));
+        app.selected_units.insert("b.service".into());
+        app.selected_units.insert("a.service".into());
+        assert_eq!(
+            app.bulk_action_targets(UnitAction::Restart),
+            vec!["a.service".to_string(), "b.service".to_string()]
+        );
     }
 
     #[test]
-    fn test_status_picker_previous_wraps() {
+    fn test_bulk_action_targets_empty_for_daemon_reload() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_status_picker();
-        app.status_picker_state.select(Some(0));
-        app.status_picker_previous();
-        let last = app.unit_type.status_options().len() - 1;
-        assert_eq!(app.status_picker_state.selected(), Some(last));
+        app.selected_units.insert("a.service".into());
+        assert!(app.bulk_action_targets(UnitAction::DaemonReload).is_empty());
     }
 
     #[test]
-    fn test_status_picker_confirm_all() {
-        let mut app = test_app_with_subs(&["running", "dead"]);
-        app.status_filter = Some("running".into());
-        app.open_status_picker();
-        app.status_picker_state.select(Some(0)); // "All"
-        app.status_picker_confirm();
-        assert_eq!(app.status_filter, None);
-        assert!(!app.show_status_picker);
+    fn test_action_picker_confirm_records_bulk_targets() {
+        let mut app = test_app_with_subs(&["running", "running", "dead"]);
+        let names: Vec<String> = app.services.iter().map(|u| u.unit.clone()).collect();
+        app.selected_units.insert(names[0].clone());
+        app.selected_units.insert(names[1].clone());
+        app.open_action_picker();
+        assert!(app.show_action_picker);
+        assert!(app.available_actions.contains(&UnitAction::Restart));
+        let idx = app.available_actions.iter().position(|&a| a == UnitAction::Restart).unwrap();
+        app.action_picker_state.select(Some(idx));
+        app.action_picker_confirm();
+        assert_eq!(app.confirm_targets.len(), 2);
+        app.confirm_no();
+        assert!(app.confirm_targets.is_empty());
     }
 
     #[test]
-    fn test_status_picker_confirm_running() {
+    fn test_open_action_picker_intersects_selected_actions() {
         let mut app = test_app_with_subs(&["running", "dead"]);
-        app.open_status_picker();
-        app.status_picker_state.select(Some(1)); // "running"
-        app.status_picker_confirm();
-        assert_eq!(app.status_filter, Some("running".into()));
-        assert_eq!(app.filtered_indices, vec![0]);
-        assert!(!app.show_status_picker);
+        for u in &app.services {
+            app.selected_units.insert(u.unit.clone());
+        }
+        app.open_action_picker();
+        // running offers Stop/Restart/Reload, dead offers Start: only the
+        // always-available daemon reload is common.
+        assert_eq!(app.available_actions, vec![UnitAction::DaemonReload]);
     }
 
     #[test]
-    fn test_open_status_picker_preselects_all() {
+    fn test_toggle_user_mode_clears_selection() {
         let mut app = test_app_with_subs(&["running"]);
-        app.status_filter = None;
-        app.open_status_picker();
-        assert_eq!(app.status_picker_state.selected(), Some(0));
+        app.selected_units.insert("x.service".into());
+        app.toggle_user_mode();
+        assert!(app.selected_units.is_empty());
     }
 
     #[test]
-    fn test_open_status_picker_preselects_current() {
+    fn test_toggle_kernel_logs_opens_and_closes() {
         let mut app = test_app_with_subs(&["running"]);
-        app.status_filter = Some("running".into());
-        app.open_status_picker();
-        // "running" is index 1 for Service type
-        assert_eq!(app.status_picker_state.selected(), Some(1));
+        app.toggle_kernel_logs();
+        assert!(app.show_logs);
+        assert!(app.showing_kernel_logs());
+        assert_eq!(app.log_filters().source, LogSource::Kernel);
+        app.toggle_kernel_logs();
+        assert!(!app.show_logs);
+        assert!(!app.system_logs_mode);
     }
 
-    // Phase 2 — Type picker
-
     #[test]
-    fn test_type_picker_next() {
+    fn test_switch_between_system_and_kernel_logs() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_type_picker();
-        assert_eq!(app.type_picker_state.selected(), Some(0));
-        app.type_picker_next();
-        assert_eq!(app.type_picker_state.selected(), Some(1));
+        app.toggle_kernel_logs();
+        app.log_filters_dirty = false;
+        app.toggle_system_logs();
+        assert!(app.show_logs);
+        assert!(app.system_logs_mode);
+        assert!(!app.showing_kernel_logs());
+        assert!(app.log_filters_dirty);
     }
 
     #[test]
-    fn test_type_picker_previous() {
+    fn test_log_filters_unit_view_ignores_kernel_source() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_type_picker();
-        app.type_picker_state.select(Some(2));
-        app.type_picker_previous();
-        assert_eq!(app.type_picker_state.selected(), Some(1));
+        app.log_source = LogSource::Kernel;
+        app.system_logs_mode = false;
+        assert_eq!(app.log_filters().source, LogSource::Unit);
     }
 
     #[test]
-    fn test_type_picker_next_wraps() {
+    fn test_prepend_logs_keeps_view_stable() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_type_picker();
-        app.type_picker_state.select(Some(UNIT_TYPES.len() - 1));
-        app.type_picker_next();
-        assert_eq!(app.type_picker_state.selected(), Some(0));
+        app.logs = vec![make_log("c"), make_log("d")];
+        app.logs_scroll = 0;
+        app.cached_entry_heights_dirty = false;
+        app.prepend_logs(vec![make_log("a"), make_log("b")]);
+        assert_eq!(app.logs.len(), 4);
+        assert_eq!(app.logs[0].message, "a");
+        assert_eq!(app.logs_scroll, 2); // still showing "c" at the top
+        assert!(app.cached_entry_heights_dirty);
+        app.scroll_logs_up(1);
+        assert_eq!(app.logs_scroll, 1);
     }
 
     #[test]
-    fn test_type_picker_previous_wraps() {
+    fn test_prepend_logs_shifts_selection_and_matches() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_type_picker();
-        app.type_picker_state.select(Some(0));
-        app.type_picker_previous();
-        assert_eq!(
-            app.type_picker_state.selected(),
-            Some(UNIT_TYPES.len() - 1)
-        );
+        app.logs = vec![make_log("x"), make_log("y")];
+        app.log_selected_entry = Some(1);
+        app.log_search_matches = vec![0, 1];
+        app.prepend_logs(vec![make_log("w")]);
+        assert_eq!(app.log_selected_entry, Some(2));
+        assert_eq!(app.log_search_matches, vec![1, 2]);
     }
 
     #[test]
-    fn test_type_picker_confirm_same_type_no_change() {
-        let mut app = test_app_with_subs(&["running", "dead"]);
-        let original_services_len = app.services.len();
-        app.open_type_picker();
-        // Service is index 0, which is already the current type
-        app.type_picker_state.select(Some(0));
-        app.type_picker_confirm();
-        // Services should not be reloaded (same type)
-        assert_eq!(app.services.len(), original_services_len);
-        assert!(!app.show_type_picker);
+    fn test_load_older_logs_noop_when_exhausted_or_no_cursor() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.system_logs_mode = true;
+        app.logs = vec![make_log("no cursor")];
+        app.load_older_logs();
+        assert_eq!(app.logs.len(), 1);
+        app.log_history_exhausted = true;
+        app.load_older_logs();
+        assert_eq!(app.logs.len(), 1);
     }
 
-    // Phase 4 — File state picker
+    #[test]
+    fn test_older_logs_failure_is_reported_not_exhausted() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = vec![make_log("x")];
+        let (tx, rx) = mpsc::channel();
+        app.older_logs_receiver = Some(rx);
+        tx.send(Err("ssh: connection reset".to_string())).unwrap();
+        app.check_older_logs_progress();
+        assert!(!app.older_logs_loading());
+        assert!(!app.log_history_exhausted);
+        assert_eq!(app.status_message.as_deref(), Some("Could not load older logs: ssh: connection reset"));
+
+        let (tx, rx) = mpsc::channel();
+        app.older_logs_receiver = Some(rx);
+        tx.send(Ok(vec![make_log("w")])).unwrap();
+        app.check_older_logs_progress();
+        assert_eq!(app.logs.len(), 2);
+        assert!(!app.log_history_exhausted);
+
+        let (tx, rx) = mpsc::channel();
+        app.older_logs_receiver = Some(rx);
+        tx.send(Ok(Vec::new())).unwrap();
+        app.check_older_logs_progress();
+        assert!(app.log_history_exhausted);
+    }
 
     #[test]
-    fn test_file_state_picker_next() {
+    fn test_increase_log_line_limit_steps_and_marks_dirty() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_file_state_picker();
-        assert_eq!(app.file_state_picker_state.selected(), Some(0));
-        app.file_state_picker_next();
-        assert_eq!(app.file_state_picker_state.selected(), Some(1));
+        assert_eq!(app.log_line_limit, 1000);
+        app.increase_log_line_limit();
+        assert_eq!(app.log_line_limit, 5000);
+        assert!(app.log_filters_dirty);
+        app.increase_log_line_limit();
+        assert_eq!(app.log_line_limit, 10000);
     }
 
     #[test]
-    fn test_file_state_picker_previous() {
+    fn test_increase_log_line_limit_stops_at_max() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_file_state_picker();
-        app.file_state_picker_state.select(Some(3));
-        app.file_state_picker_previous();
-        assert_eq!(app.file_state_picker_state.selected(), Some(2));
+        app.log_line_limit = 10000;
+        app.increase_log_line_limit();
+        assert_eq!(app.log_line_limit, 10000);
+        assert!(!app.log_filters_dirty);
     }
 
     #[test]
-    fn test_file_state_picker_next_wraps() {
+    fn test_decrease_log_line_limit_stops_at_min() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_file_state_picker();
-        app.file_state_picker_state
-            .select(Some(FILE_STATE_OPTIONS.len() - 1));
-        app.file_state_picker_next();
-        assert_eq!(app.file_state_picker_state.selected(), Some(0));
+        app.decrease_log_line_limit();
+        assert_eq!(app.log_line_limit, 500);
+        app.log_filters_dirty = false;
+        app.decrease_log_line_limit();
+        assert_eq!(app.log_line_limit, 500);
+        assert!(!app.log_filters_dirty);
     }
 
     #[test]
-    fn test_file_state_picker_previous_wraps() {
+    fn test_grep_input_confirm_sets_filter() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_file_state_picker();
-        app.file_state_picker_state.select(Some(0));
-        app.file_state_picker_previous();
+        app.open_grep_input();
+        for c in "timeout".chars() {
+            app.grep_input_push(c);
+        }
+        app.grep_input_toggle_case();
+        app.grep_input_confirm();
         assert_eq!(
-            app.file_state_picker_state.selected(),
-            Some(FILE_STATE_OPTIONS.len() - 1)
+            app.log_grep,
+            Some(LogGrep { pattern: "timeout".into(), case_sensitive: true })
         );
+        assert!(app.log_filters_dirty);
+        assert!(!app.show_grep_input);
+    }
+
+    fn app_with_presets() -> App {
+        let mut app = test_app_with_subs(&["running"]);
+        app.log_presets = vec![
+            LogFilterPreset { name: "errors".into(), priority: Some((0, 3)), time_range: TimeRange::OneHour },
+            LogFilterPreset { name: "today".into(), priority: None, time_range: TimeRange::Today },
+        ];
+        app
     }
 
     #[test]
-    fn test_file_state_picker_confirm_all() {
-        let mut app = test_app_with_services(vec![
-            make_unit("a.service", "running", "A", Some("enabled")),
-            make_unit("b.service", "running", "B", Some("disabled")),
-        ]);
-        app.file_state_filter = Some("enabled".into());
-        app.open_file_state_picker();
-        app.file_state_picker_state.select(Some(0)); // "All"
-        app.file_state_picker_confirm();
-        assert_eq!(app.file_state_filter, None);
-        assert!(!app.show_file_state_picker);
+    fn test_apply_log_preset_sets_filters_and_marks_dirty() {
+        let mut app = app_with_presets();
+        app.show_preset_picker = true;
+        app.apply_log_preset(0);
+        assert_eq!(app.log_priority_filter, Some((0, 3)));
+        assert_eq!(app.log_time_range, TimeRange::OneHour);
+        assert!(app.log_filters_dirty);
+        assert!(!app.show_preset_picker);
     }
 
     #[test]
-    fn test_file_state_picker_confirm_enabled() {
-        let mut app = test_app_with_services(vec![
-            make_unit("a.service", "running", "A", Some("enabled")),
-            make_unit("b.service", "running", "B", Some("disabled")),
-        ]);
-        app.open_file_state_picker();
-        app.file_state_picker_state.select(Some(1)); // "enabled"
-        app.file_state_picker_confirm();
-        assert_eq!(app.file_state_filter, Some("enabled".into()));
-        assert_eq!(app.filtered_indices, vec![0]);
-        assert!(!app.show_file_state_picker);
+    fn test_apply_log_preset_clears_priority() {
+        let mut app = app_with_presets();
+        app.log_priority_filter = Some((0, 4));
+        app.apply_log_preset(1);
+        assert_eq!(app.log_priority_filter, None);
+        assert_eq!(app.log_time_range, TimeRange::Today);
     }
 
     #[test]
-    fn test_open_file_state_picker_preselects_all() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.file_state_filter = None;
-        app.open_file_state_picker();
-        assert_eq!(app.file_state_picker_state.selected(), Some(0));
+    fn test_open_preset_picker_highlights_matching_preset() {
+        let mut app = app_with_presets();
+        app.log_time_range = TimeRange::Today;
+        app.open_preset_picker();
+        assert_eq!(app.preset_picker_state.selected(), Some(2));
+        app.log_time_range = TimeRange::SevenDays;
+        app.open_preset_picker();
+        assert_eq!(app.preset_picker_state.selected(), Some(0));
     }
 
     #[test]
-    fn test_open_file_state_picker_preselects_current() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.file_state_filter = Some("disabled".into());
-        app.open_file_state_picker();
-        // "disabled" is index 2 in FILE_STATE_OPTIONS
-        assert_eq!(app.file_state_picker_state.selected(), Some(2));
+    fn test_preset_picker_save_row_opens_name_prompt() {
+        let mut app = app_with_presets();
+        app.open_preset_picker();
+        app.preset_picker_state.select(Some(0));
+        app.preset_picker_confirm();
+        assert!(!app.show_preset_picker);
+        assert!(app.show_preset_name_input);
     }
 
-    // Phase 3 — Priority picker
+    #[test]
+    fn test_preset_name_input_saves_current_filters() {
+        let mut app = app_with_presets();
+        app.log_priority_filter = Some((2, 4));
+        app.log_time_range = TimeRange::SevenDays;
+        app.show_preset_name_input = true;
+        for c in " week ".chars() {
+            app.preset_name_input_push(c);
+        }
+        app.preset_name_input_confirm();
+        assert!(!app.show_preset_name_input);
+        let saved = app.log_presets.last().unwrap();
+        assert_eq!(saved.name, "week");
+        assert_eq!(saved.priority, Some((2, 4)));
+        assert_eq!(saved.time_range, TimeRange::SevenDays);
+    }
 
     #[test]
-    fn test_priority_picker_next() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.open_priority_picker();
-        assert_eq!(app.priority_picker_state.selected(), Some(0));
-        app.priority_picker_next();
-        assert_eq!(app.priority_picker_state.selected(), Some(1));
+    fn test_preset_name_input_replaces_same_name() {
+        let mut app = app_with_presets();
+        app.log_time_range = TimeRange::FifteenMinutes;
+        app.preset_name_input = "errors".into();
+        app.preset_name_input_confirm();
+        assert_eq!(app.log_presets.len(), 2);
+        assert_eq!(app.log_presets[0].time_range, TimeRange::FifteenMinutes);
     }
 
     #[test]
-    fn test_priority_picker_previous() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.open_priority_picker();
-        app.priority_picker_state.select(Some(3));
-        app.priority_picker_previous();
-        assert_eq!(app.priority_picker_state.selected(), Some(2));
+    fn test_preset_name_input_empty_ignored() {
+        let mut app = app_with_presets();
+        app.show_preset_name_input = true;
+        app.preset_name_input = "  ".into();
+        app.preset_name_input_confirm();
+        assert!(app.show_preset_name_input);
+        assert_eq!(app.log_presets.len(), 2);
     }
 
     #[test]
-    fn test_priority_picker_next_wraps() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.open_priority_picker();
-        app.priority_picker_state.select(Some(8)); // last item (0-8 = 9 items)
-        app.priority_picker_next();
-        assert_eq!(app.priority_picker_state.selected(), Some(0));
+    fn test_preset_picker_delete() {
+        let mut app = app_with_presets();
+        app.open_preset_picker();
+        app.preset_picker_state.select(Some(2));
+        app.preset_picker_delete();
+        assert_eq!(app.log_presets.len(), 1);
+        assert_eq!(app.preset_picker_state.selected(), Some(1));
+        // The save row can't be deleted.
+        app.preset_picker_state.select(Some(0));
+        app.preset_picker_delete();
+        assert_eq!(app.log_presets.len(), 1);
     }
 
     #[test]
-    fn test_priority_picker_previous_wraps() {
+    fn test_grep_input_empty_clears_filter() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_priority_picker();
-        app.priority_picker_state.select(Some(0));
-        app.priority_picker_previous();
-        assert_eq!(app.priority_picker_state.selected(), Some(8));
+        app.log_grep = Some(LogGrep { pattern: "x".into(), case_sensitive: false });
+        app.open_grep_input();
+        assert_eq!(app.grep_input, "x");
+        app.grep_input_pop();
+        app.grep_input_confirm();
+        assert_eq!(app.log_grep, None);
+        assert!(app.log_filters_dirty);
     }
 
     #[test]
-    fn test_priority_picker_confirm_all() {
+    fn test_grep_input_unchanged_does_not_reload() {
         let mut app = test_app_with_subs(&["running"]);
-        app.log_priority_filter = Some(3);
-        app.open_priority_picker();
-        app.priority_picker_state.select(Some(0)); // "All"
-        app.priority_picker_confirm();
-        assert_eq!(app.log_priority_filter, None);
-        assert!(!app.show_priority_picker);
+        app.log_grep = Some(LogGrep { pattern: "x".into(), case_sensitive: false });
+        app.open_grep_input();
+        app.grep_input_confirm();
+        assert!(!app.log_filters_dirty);
     }
 
     #[test]
-    fn test_priority_picker_confirm_err() {
+    fn test_reopen_custom_time_input_keeps_values() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_priority_picker();
-        app.priority_picker_state.select(Some(4)); // err (index-1 = 3)
-        app.priority_picker_confirm();
-        assert_eq!(app.log_priority_filter, Some(3));
-        assert!(app.log_filters_dirty);
-        assert!(!app.show_priority_picker);
+        app.log_time_range = TimeRange::Custom {
+            since: "-2h".into(),
+            until: Some("-1h".into()),
+        };
+        app.open_time_picker();
+        assert_eq!(app.time_picker_state.selected(), Some(TIME_RANGES.len()));
+        app.open_custom_time_input();
+        assert_eq!(app.custom_since_input, "-2h");
+        assert_eq!(app.custom_until_input, "-1h");
+        assert!(!app.custom_time_editing_until);
     }
 
+    // Timestamp format picker
+
     #[test]
-    fn test_open_priority_picker_preselects_current() {
+    fn test_timestamp_format_picker_next_wraps() {
         let mut app = test_app_with_subs(&["running"]);
-        app.log_priority_filter = Some(5);
-        app.open_priority_picker();
-        assert_eq!(app.priority_picker_state.selected(), Some(6)); // 5 + 1
+        app.open_timestamp_format_picker();
+        assert_eq!(app.timestamp_format_picker_state.selected(), Some(0));
+        app.timestamp_format_picker_next();
+        app.timestamp_format_picker_next();
+        app.timestamp_format_picker_next();
+        assert_eq!(app.timestamp_format_picker_state.selected(), Some(0));
     }
 
-    // Phase 3 — Time picker
-
     #[test]
-    fn test_time_picker_next() {
+    fn test_timestamp_format_picker_confirm() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_time_picker();
-        assert_eq!(app.time_picker_state.selected(), Some(0));
-        app.time_picker_next();
-        assert_eq!(app.time_picker_state.selected(), Some(1));
+        app.cached_entry_heights_dirty = false;
+        app.open_timestamp_format_picker();
+        app.timestamp_format_picker_previous();
+        app.timestamp_format_picker_confirm();
+        assert_eq!(app.log_timestamp_format, TimestampFormat::Relative);
+        assert!(!app.show_timestamp_format_picker);
+        assert!(app.cached_entry_heights_dirty);
     }
 
     #[test]
-    fn test_time_picker_previous() {
+    fn test_open_timestamp_format_picker_preselects_current() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_time_picker();
-        app.time_picker_state.select(Some(3));
-        app.time_picker_previous();
-        assert_eq!(app.time_picker_state.selected(), Some(2));
+        app.log_timestamp_format = TimestampFormat::Iso;
+        app.open_timestamp_format_picker();
+        assert_eq!(app.timestamp_format_picker_state.selected(), Some(1));
     }
 
     #[test]
-    fn test_time_picker_next_wraps() {
+    fn test_toggle_log_timestamp_micros_invalidates_heights() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_time_picker();
-        app.time_picker_state.select(Some(TIME_RANGES.len() - 1));
-        app.time_picker_next();
-        assert_eq!(app.time_picker_state.selected(), Some(0));
+        app.cached_entry_heights_dirty = false;
+        app.toggle_log_timestamp_micros();
+        assert!(app.log_timestamp_micros);
+        assert!(app.cached_entry_heights_dirty);
+        app.toggle_log_timestamp_micros();
+        assert!(!app.log_timestamp_micros);
+    }
+
+    fn entry_with_fields(message: &str, fields: &[(&str, &str)]) -> LogEntry {
+        LogEntry {
+            fields: fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            ..make_log(message)
+        }
     }
 
     #[test]
-    fn test_time_picker_previous_wraps() {
+    fn test_log_fields_follow_selected_entry_sorted() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_time_picker();
-        app.time_picker_state.select(Some(0));
-        app.time_picker_previous();
-        assert_eq!(
-            app.time_picker_state.selected(),
-            Some(TIME_RANGES.len() - 1)
-        );
+        app.logs = vec![
+            entry_with_fields("a", &[("MESSAGE", "a"), ("CODE_LINE", "7")]),
+            entry_with_fields("b", &[("MESSAGE", "b")]),
+        ];
+        assert_eq!(app.log_fields(), vec![("MESSAGE", "b")], "newest when nothing is selected");
+        app.log_selected_entry = Some(0);
+        assert_eq!(app.log_fields(), vec![("CODE_LINE", "7"), ("MESSAGE", "a")]);
+        app.open_log_fields();
+        assert!(app.show_log_fields);
+        assert_eq!(app.log_fields_scroll, 0);
     }
 
     #[test]
-    fn test_time_picker_confirm() {
+    fn test_open_log_fields_without_fields_reports() {
         let mut app = test_app_with_subs(&["running"]);
-        app.open_time_picker();
-        app.time_picker_state.select(Some(2)); // OneHour
-        app.time_picker_confirm();
-        assert_eq!(app.log_time_range, TimeRange::OneHour);
-        assert!(app.log_filters_dirty);
-        assert!(!app.show_time_picker);
+        app.logs = vec![make_log("placeholder")];
+        app.open_log_fields();
+        assert!(!app.show_log_fields);
+        assert!(app.status_message.is_some());
     }
 
     #[test]
-    fn test_open_time_picker_preselects_current() {
+    fn test_toggle_log_hostname_invalidates_heights() {
         let mut app = test_app_with_subs(&["running"]);
-        app.log_time_range = TimeRange::SevenDays;
-        app.open_time_picker();
-        assert_eq!(app.time_picker_state.selected(), Some(4)); // SevenDays is index 4
+        assert!(!app.log_show_hostname, "off by default");
+        app.cached_entry_heights_dirty = false;
+        app.toggle_log_hostname();
+        assert!(app.log_show_hostname);
+        assert!(app.cached_entry_heights_dirty);
     }
 
     // Phase 1 — Toggles
@@ -2218,6 +6352,24 @@ mod tests {
         assert_eq!(app.help_scroll, 0, "toggling help resets scroll");
     }
 
+    #[test]
+    fn test_help_search_resets_scroll() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.help_content_lines = 30;
+        app.help_viewport_lines = 10;
+        app.toggle_help();
+        app.help_scroll_down(15);
+        app.help_search_push('l');
+        assert_eq!(app.help_scroll, 0);
+        app.help_scroll_down(3);
+        app.help_search_pop();
+        assert_eq!(app.help_scroll, 0);
+        app.help_search_push('x');
+        app.toggle_help();
+        app.toggle_help();
+        assert!(app.help_search_query.is_empty(), "reopening starts unfiltered");
+    }
+
     #[test]
     fn test_help_scroll_no_op_when_content_fits() {
         let mut app = test_app_with_subs(&["running"]);
@@ -2279,7 +6431,7 @@ mod tests {
         app.last_selected_service = Some("test".into());
         app.logs = vec![make_log("log1")];
         app.log_search_query = "search".into();
-        app.log_priority_filter = Some(3);
+        app.log_priority_filter = Some((0, 3));
         app.log_time_range = TimeRange::OneHour;
         app.properties_cache
             .insert("test".into(), UnitProperties::default());
@@ -2349,34 +6501,507 @@ mod tests {
     }
 
     #[test]
-    fn test_scroll_logs_down_empty() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.scroll_logs_down(1);
-        assert_eq!(app.logs_scroll, 0);
+    fn test_scroll_logs_down_empty() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.scroll_logs_down(1);
+        assert_eq!(app.logs_scroll, 0);
+    }
+
+    fn boot_logs(boots: &[&str]) -> Vec<LogEntry> {
+        boots
+            .iter()
+            .map(|boot| {
+                let mut entry = make_log("line");
+                entry.boot_id = Some(boot.to_string());
+                entry
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_next_boot_boundary_lands_on_first_entry_of_each_boot() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = boot_logs(&["aaa", "aaa", "bbb", "bbb", "ccc"]);
+        app.logs_scroll = 0;
+        app.next_boot_boundary();
+        assert_eq!(app.logs_scroll, 2);
+        app.next_boot_boundary();
+        assert_eq!(app.logs_scroll, 4);
+        // No later boot: stays put.
+        app.next_boot_boundary();
+        assert_eq!(app.logs_scroll, 4);
+    }
+
+    #[test]
+    fn test_prev_boot_boundary_lands_on_first_entry_of_each_boot() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = boot_logs(&["aaa", "aaa", "bbb", "bbb"]);
+        app.logs_scroll = 3;
+        app.prev_boot_boundary();
+        assert_eq!(app.logs_scroll, 2);
+        app.prev_boot_boundary();
+        assert_eq!(app.logs_scroll, 0);
+        app.prev_boot_boundary();
+        assert_eq!(app.logs_scroll, 0);
+    }
+
+    #[test]
+    fn test_boot_boundary_from_bottom_sentinel() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = boot_logs(&["aaa", "bbb", "bbb"]);
+        app.logs_scroll = usize::MAX;
+        app.prev_boot_boundary();
+        assert_eq!(app.logs_scroll, 1);
+    }
+
+    #[test]
+    fn test_boot_boundary_moves_selected_entry() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = boot_logs(&["aaa", "aaa", "bbb"]);
+        app.logs_scroll = 0;
+        app.log_selected_entry = Some(1);
+        app.next_boot_boundary();
+        assert_eq!(app.logs_scroll, 2);
+        assert_eq!(app.log_selected_entry, Some(2));
+    }
+
+    fn priority_logs(priorities: &[u8]) -> Vec<LogEntry> {
+        priorities
+            .iter()
+            .map(|&p| {
+                let mut entry = make_log("line");
+                entry.priority = Some(p);
+                entry
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_next_error_skips_info_and_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        // info, err, info, notice, crit, debug
+        app.logs = priority_logs(&[6, 3, 6, 5, 2, 7]);
+        app.logs_scroll = 0;
+        app.next_error();
+        assert_eq!(app.logs_scroll, 1);
+        app.next_error();
+        assert_eq!(app.logs_scroll, 4);
+        app.next_error();
+        assert_eq!(app.logs_scroll, 1);
+    }
+
+    #[test]
+    fn test_prev_error_skips_info_and_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = priority_logs(&[6, 3, 6, 5, 2, 7]);
+        app.logs_scroll = 3;
+        app.prev_error();
+        assert_eq!(app.logs_scroll, 1);
+        app.prev_error();
+        assert_eq!(app.logs_scroll, 4);
+    }
+
+    #[test]
+    fn test_next_error_without_errors_reports() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = priority_logs(&[6, 4, 5]);
+        app.logs_scroll = 1;
+        app.next_error();
+        assert_eq!(app.logs_scroll, 1);
+        assert_eq!(app.status_message.as_deref(), Some("No errors in loaded logs"));
+    }
+
+    #[test]
+    fn test_cycle_dim_below_priority() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.cycle_dim_below_priority();
+        assert_eq!(app.dim_below_priority, Some(4));
+        assert_eq!(app.status_message.as_deref(), Some("Dimming entries below warning"));
+        app.cycle_dim_below_priority();
+        assert_eq!(app.dim_below_priority, Some(3));
+        app.cycle_dim_below_priority();
+        assert_eq!(app.dim_below_priority, None);
+    }
+
+    #[test]
+    fn test_toggle_split_view_opens_logs_beside_list() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.toggle_split_view();
+        assert!(app.split_view);
+        assert!(app.show_logs);
+        assert!(!app.logs_focused());
+        app.toggle_split_view();
+        assert!(!app.split_view);
+        assert!(!app.show_logs);
+    }
+
+    #[test]
+    fn test_toggle_logs_from_split_view_goes_full_screen() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.toggle_split_view();
+        app.toggle_logs();
+        assert!(!app.split_view);
+        assert!(app.show_logs);
+        assert!(app.logs_focused());
+        // And back: the logs already showing stay open beside the list
+        app.toggle_split_view();
+        assert!(app.split_view);
+        assert!(app.show_logs);
+    }
+
+    #[test]
+    fn test_focus_next_pane_cycles_in_split_view() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.toggle_split_view();
+        assert_eq!(app.focused_pane, Pane::List);
+        app.focus_next_pane();
+        assert_eq!(app.focused_pane, Pane::Logs);
+        assert!(app.logs_focused());
+        app.focus_next_pane();
+        assert_eq!(app.focused_pane, Pane::List);
+        assert!(!app.logs_focused());
+    }
+
+    #[test]
+    fn test_focus_next_pane_needs_split_view() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.focus_next_pane();
+        assert_eq!(app.focused_pane, Pane::List);
+        app.toggle_logs();
+        app.focus_next_pane();
+        assert_eq!(app.focused_pane, Pane::List);
+        // Full-screen logs always have the keyboard.
+        assert!(app.logs_focused());
+    }
+
+    #[test]
+    fn test_leaving_split_view_resets_focus() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.toggle_split_view();
+        app.focus_next_pane();
+        app.toggle_logs();
+        assert_eq!(app.focused_pane, Pane::List);
+        app.toggle_split_view();
+        app.focus_next_pane();
+        app.toggle_split_view();
+        assert_eq!(app.focused_pane, Pane::List);
+    }
+
+    #[test]
+    fn test_list_navigation_leaves_unfocused_logs_alone() {
+        let mut app = test_app_with_subs(&["running", "dead", "failed"]);
+        app.toggle_split_view();
+        app.logs = vec![make_log("a"), make_log("b"), make_log("c")];
+        app.logs_scroll = 1;
+        // With the list focused the key loop sends j/k to the list...
+        assert!(!app.logs_focused());
+        app.next();
+        assert_eq!(app.list_state.selected(), Some(1));
+        // ...and the logs pane keeps its place until the new unit's logs load.
+        assert_eq!(app.logs_scroll, 1);
+    }
+
+    #[test]
+    fn test_dependency_filter_limits_list_to_set() {
+        let mut app = test_app_with_services(vec![
+            make_unit("sshd.service", "running", "", None),
+            make_unit("cron.service", "running", "", None),
+            make_unit("dbus.service", "running", "", None),
+            make_unit("nginx.service", "dead", "", None),
+        ]);
+        app.dependency_filter = Some(["sshd.service".to_string(), "dbus.service".to_string()].into());
+        app.dependency_filter_unit = Some("sshd.service".into());
+        app.update_filter();
+        let names: Vec<&str> = app.filtered_indices.iter().map(|&i| app.services[i].unit.as_str()).collect();
+        assert_eq!(names, ["sshd.service", "dbus.service"]);
+
+        app.clear_dependency_filter();
+        assert_eq!(app.filtered_indices.len(), 4);
+        assert!(app.dependency_filter_unit.is_none());
+    }
+
+    #[test]
+    fn test_dependency_filter_combines_with_search() {
+        let mut app = test_app_with_services(vec![
+            make_unit("sshd.service", "running", "", None),
+            make_unit("dbus.service", "running", "", None),
+        ]);
+        app.dependency_filter = Some(["sshd.service".to_string(), "dbus.service".to_string()].into());
+        app.search_query = "dbus".into();
+        app.update_filter();
+        assert_eq!(app.filtered_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_dependency_filter_applies_when_lookup_finishes() {
+        let mut app = test_app_with_subs(&["running", "running", "running"]);
+        app.runner = Arc::new(CannedRunner::ok("unit0.service\nunit2.service\n"));
+        app.toggle_dependency_filter();
+        assert!(app.dependency_filter_loading(), "runs off the UI thread");
+        assert!(app.dependency_filter.is_none());
+        settle(&mut app, App::dependency_filter_loading);
+        let names: Vec<&str> = app.filtered_indices.iter().map(|&i| app.services[i].unit.as_str()).collect();
+        assert_eq!(names, ["unit0.service", "unit2.service"]);
+        assert_eq!(app.dependency_filter_unit.as_deref(), Some("unit0.service"));
+    }
+
+    #[test]
+    fn test_logs_go_to_top() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = vec![make_log("a"), make_log("b")];
+        app.logs_scroll = 5;
+        app.logs_go_to_top();
+        assert_eq!(app.logs_scroll, 0);
+    }
+
+    #[test]
+    fn test_logs_go_to_bottom() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = vec![
+            make_log("a"),
+            make_log("b"),
+            make_log("c"),
+            make_log("d"),
+            make_log("e"),
+        ];
+        app.logs_scroll = 0;
+        app.logs_go_to_bottom();
+        assert_eq!(app.logs_scroll, usize::MAX);
+    }
+
+    #[test]
+    fn test_toggle_utc() {
+        let mut app = test_app_with_subs(&["running"]);
+        assert!(!app.use_utc);
+        app.cached_entry_heights_dirty = false;
+        app.toggle_utc();
+        assert!(app.use_utc);
+        assert!(app.cached_entry_heights_dirty);
+        app.toggle_utc();
+        assert!(!app.use_utc);
+    }
+
+    // Horizontal log scrolling
+
+    #[test]
+    fn test_scroll_logs_right_noop_when_wrapped() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.scroll_logs_right(8);
+        assert_eq!(app.logs_h_scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_logs_left_clamps_at_zero() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.log_wrap = false;
+        app.scroll_logs_right(8);
+        app.scroll_logs_left(20);
+        assert_eq!(app.logs_h_scroll, 0);
+    }
+
+    #[test]
+    fn test_toggle_log_wrap_resets_h_scroll() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.toggle_log_wrap();
+        assert!(!app.log_wrap);
+        app.scroll_logs_right(16);
+        assert_eq!(app.logs_h_scroll, 16);
+        app.toggle_log_wrap();
+        assert!(app.log_wrap);
+        assert_eq!(app.logs_h_scroll, 0);
+    }
+
+    fn app_loading_logs_for(unit: &str) -> (App, mpsc::Sender<LogLoad>) {
+        let mut app = test_app_with_services(vec![
+            make_unit("a.service", "running", "A", None),
+            make_unit("b.service", "running", "B", None),
+        ]);
+        app.show_logs = true;
+        app.logs = vec![make_log("previous")];
+        app.last_selected_service = Some(unit.to_string());
+        let (tx, rx) = mpsc::channel();
+        app.log_load_receiver = Some(rx);
+        (app, tx)
+    }
+
+    #[test]
+    fn test_log_load_swaps_in_current_unit_result() {
+        let (mut app, tx) = app_loading_logs_for("a.service");
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: None,
+            result: Ok(vec![make_log("fresh")]),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert!(!app.logs_loading());
+        assert_eq!(app.logs.len(), 1);
+        assert_eq!(app.logs[0].message, "fresh");
+        assert_eq!(app.logs_scroll, usize::MAX);
+    }
+
+    #[test]
+    fn test_log_load_rejects_mismatched_unit() {
+        let (mut app, tx) = app_loading_logs_for("b.service");
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: None,
+            result: Ok(vec![make_log("stale")]),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert!(!app.logs_loading());
+        assert_eq!(app.logs[0].message, "previous", "stale result discarded");
+    }
+
+    #[test]
+    fn test_log_load_rejects_unit_result_in_system_logs_mode() {
+        let (mut app, tx) = app_loading_logs_for("a.service");
+        app.system_logs_mode = true;
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: None,
+            result: Ok(vec![make_log("stale")]),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert_eq!(app.logs[0].message, "previous");
+    }
+
+    #[test]
+    fn test_log_load_error_becomes_entry() {
+        let (mut app, tx) = app_loading_logs_for("a.service");
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: None,
+            result: Err("boom".into()),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert_eq!(app.logs[0].message, "Error fetching logs: boom");
+    }
+
+    #[test]
+    fn test_live_tail_waits_for_pending_log_load() {
+        let (mut app, _tx) = app_loading_logs_for("a.service");
+        app.logs[0].cursor = Some("c1".into());
+        app.refresh_logs();
+        assert!(!app.log_refresh_in_flight());
+    }
+
+    #[test]
+    fn test_log_scroll_saved_when_leaving_unit() {
+        let mut app = test_app_empty();
+        app.logs = vec![make_log("a"), make_log("b")];
+        app.logs_scroll = 7;
+        app.last_selected_service = Some("old.service".into());
+        app.load_logs_for_selected();
+        assert_eq!(app.log_scroll_memory.get("old.service"), Some(&7));
+    }
+
+    #[test]
+    fn test_log_scroll_restored_when_paused() {
+        let (mut app, tx) = app_loading_logs_for("a.service");
+        app.log_paused = true;
+        app.log_scroll_memory.insert("a.service".into(), 3);
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: None,
+            result: Ok(vec![make_log("fresh")]),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert_eq!(app.logs_scroll, 3);
+    }
+
+    #[test]
+    fn test_live_tail_overrides_remembered_log_scroll() {
+        let (mut app, tx) = app_loading_logs_for("a.service");
+        app.log_paused = false;
+        app.log_scroll_memory.insert("a.service".into(), 3);
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: None,
+            result: Ok(vec![make_log("fresh")]),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert_eq!(app.logs_scroll, usize::MAX);
+    }
+
+    #[test]
+    fn test_logs_byte_size_sums_message_lengths() {
+        let mut app = test_app_empty();
+        assert_eq!(app.logs_byte_size(), 0);
+        app.logs = vec![make_log("hello"), make_log(""), make_log("caf\u{e9}")];
+        assert_eq!(app.logs_byte_size(), 5 + 5);
+    }
+
+    #[test]
+    fn test_logs_byte_size_includes_live_tail_entries() {
+        let mut app = app_with_pending_log_refresh(vec![make_log("0123456789")]);
+        assert_eq!(app.logs_byte_size(), "existing".len() as u64);
+        app.check_log_refresh_progress();
+        assert_eq!(app.logs_byte_size(), 18);
+    }
+
+    fn app_with_collapsed_logs() -> App {
+        // Rows: a | b (x3) | c
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = ["a", "b", "b", "b", "c"].iter().map(|m| make_log(m)).collect();
+        app.collapse_log_repeats = true;
+        app.cached_log_repeat_counts = vec![1, 3, 0, 0, 1];
+        app
+    }
+
+    #[test]
+    fn test_collapsed_logs_scroll_steps_over_runs() {
+        let mut app = app_with_collapsed_logs();
+        app.logs_scroll = 0;
+        app.scroll_logs_down(1);
+        assert_eq!(app.logs_scroll, 1);
+        app.scroll_logs_down(1);
+        assert_eq!(app.logs_scroll, 4);
+        app.scroll_logs_down(1);
+        assert_eq!(app.logs_scroll, 4, "clamped at the last row");
+        app.scroll_logs_up(1);
+        assert_eq!(app.logs_scroll, 1);
+        app.scroll_logs_up(5);
+        assert_eq!(app.logs_scroll, 0);
+    }
+
+    #[test]
+    fn test_collapsed_logs_selection_steps_over_runs() {
+        let mut app = app_with_collapsed_logs();
+        app.log_selected_entry = Some(1);
+        app.log_select_next();
+        assert_eq!(app.log_selected_entry, Some(4));
+        app.log_select_previous();
+        assert_eq!(app.log_selected_entry, Some(1));
+        assert_eq!(app.log_row_start(3), 1);
     }
 
     #[test]
-    fn test_logs_go_to_top() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.logs = vec![make_log("a"), make_log("b")];
-        app.logs_scroll = 5;
-        app.logs_go_to_top();
-        assert_eq!(app.logs_scroll, 0);
+    fn test_toggle_collapse_log_repeats_keeps_logs() {
+        let mut app = app_with_collapsed_logs();
+        app.cached_entry_heights_dirty = false;
+        app.toggle_collapse_log_repeats();
+        assert!(!app.collapse_log_repeats);
+        assert!(app.cached_entry_heights_dirty);
+        assert_eq!(app.logs.len(), 5);
     }
 
     #[test]
-    fn test_logs_go_to_bottom() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.logs = vec![
-            make_log("a"),
-            make_log("b"),
-            make_log("c"),
-            make_log("d"),
-            make_log("e"),
-        ];
-        app.logs_scroll = 0;
-        app.logs_go_to_bottom();
-        assert_eq!(app.logs_scroll, usize::MAX);
+    fn test_h_scroll_resets_on_service_change() {
+        let mut app = test_app_empty();
+        app.log_wrap = false;
+        app.logs_h_scroll = 24;
+        app.last_selected_service = Some("old.service".into());
+        // No unit is selected, so the "selection changed" path runs without
+        // fetching anything.
+        app.load_logs_for_selected();
+        assert_eq!(app.logs_h_scroll, 0);
+        assert_eq!(app.last_selected_service, None);
     }
 
     // Phase 4 — Detail scrolling
@@ -2421,6 +7046,81 @@ mod tests {
         assert_eq!(app.detail_scroll, 0);
     }
 
+    #[test]
+    fn test_scroll_modal_details() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.show_details = true;
+        app.detail_content_height = 20;
+        assert!(app.scroll_modal(false, 10));
+        assert_eq!(app.detail_scroll, MOUSE_SCROLL_LINES);
+        assert!(app.scroll_modal(true, 10));
+        assert_eq!(app.detail_scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_modal_details_stops_at_end() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.show_details = true;
+        app.detail_content_height = 11;
+        app.scroll_modal(false, 10);
+        assert_eq!(app.detail_scroll, 1);
+    }
+
+    #[test]
+    fn test_scroll_modal_unit_file() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.show_unit_file = true;
+        app.unit_file_content = vec!["line".into(); 50];
+        assert!(app.scroll_modal(false, 10));
+        assert_eq!(app.unit_file_scroll, MOUSE_SCROLL_LINES);
+        assert!(app.scroll_modal(true, 10));
+        assert_eq!(app.unit_file_scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_modal_moves_picker_selection() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.show_status_picker = true;
+        app.status_picker_state.select(Some(0));
+        assert!(app.scroll_modal(false, 10));
+        assert_eq!(app.status_picker_state.selected(), Some(1));
+        assert!(app.scroll_modal(true, 10));
+        assert_eq!(app.status_picker_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_scroll_modal_picker_wins_over_details() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.show_details = true;
+        app.detail_content_height = 20;
+        app.show_file_state_picker = true;
+        app.file_state_picker_state.select(Some(0));
+        app.scroll_modal(false, 10);
+        assert_eq!(app.file_state_picker_state.selected(), Some(1));
+        assert_eq!(app.detail_scroll, 0);
+    }
+
+    #[test]
+    fn test_scroll_modal_help() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.show_help = true;
+        app.help_content_lines = 40;
+        app.help_viewport_lines = 10;
+        assert!(app.scroll_modal(false, 10));
+        assert_eq!(app.help_scroll, MOUSE_SCROLL_LINES as u16);
+    }
+
+    #[test]
+    fn test_scroll_modal_ignored_by_prompts_and_list() {
+        let mut app = test_app_with_subs(&["running"]);
+        assert!(!app.scroll_modal(false, 10), "no modal open");
+        app.show_confirm = true;
+        assert!(!app.scroll_modal(false, 10));
+        app.show_confirm = false;
+        app.show_grep_input = true;
+        assert!(!app.scroll_modal(true, 10));
+    }
+
     // Phase 4 — Details modal
 
     #[test]
@@ -2461,6 +7161,63 @@ mod tests {
         );
     }
 
+    fn app_with_loading_details() -> (App, mpsc::Sender<UnitProperties>) {
+        let mut app = test_app_with_services(vec![
+            make_unit("test.service", "running", "Test", None),
+        ]);
+        app.open_details();
+        let (tx, rx) = mpsc::channel();
+        app.detail_receiver = Some(rx);
+        (app, tx)
+    }
+
+    #[test]
+    fn test_open_details_uncached_starts_loading() {
+        let (app, _tx) = app_with_loading_details();
+        assert!(app.show_details);
+        assert!(app.details_loading());
+        assert!(app.detail_properties.is_none());
+        assert_eq!(app.detail_unit_name.as_deref(), Some("test.service"));
+    }
+
+    #[test]
+    fn test_details_loading_to_loaded() {
+        let (mut app, tx) = app_with_loading_details();
+        app.check_action_progress();
+        assert!(app.details_loading(), "still loading until a result arrives");
+
+        tx.send(UnitProperties {
+            description: "Loaded".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        app.check_action_progress();
+
+        assert!(!app.details_loading());
+        assert_eq!(app.detail_properties.as_ref().unwrap().description, "Loaded");
+        assert_eq!(app.properties_cache["test.service"].description, "Loaded");
+    }
+
+    #[test]
+    fn test_close_details_drops_pending_load() {
+        let (mut app, tx) = app_with_loading_details();
+        app.close_details();
+        assert!(!app.details_loading());
+        let _ = tx.send(UnitProperties::default());
+        app.check_action_progress();
+        assert!(app.detail_properties.is_none());
+        assert!(app.properties_cache.is_empty());
+    }
+
+    #[test]
+    fn test_open_details_while_loading_same_unit_keeps_fetch() {
+        let (mut app, tx) = app_with_loading_details();
+        app.open_details();
+        tx.send(UnitProperties::default()).unwrap();
+        app.check_action_progress();
+        assert!(app.detail_properties.is_some(), "original receiver kept");
+    }
+
     // Phase 3 — Log search
 
     #[test]
@@ -2538,83 +7295,400 @@ mod tests {
     }
 
     #[test]
-    fn test_next_log_match_wraps() {
+    fn test_next_log_match_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = vec![make_log("match1"), make_log("match2")];
+        app.log_search_query = "match".into();
+        app.update_log_search();
+        app.log_search_match_index = Some(1); // last match
+        app.next_log_match(10);
+        assert_eq!(app.log_search_match_index, Some(0));
+    }
+
+    #[test]
+    fn test_prev_log_match_wraps() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = vec![make_log("match1"), make_log("match2")];
+        app.log_search_query = "match".into();
+        app.update_log_search();
+        app.log_search_match_index = Some(0); // first match
+        app.prev_log_match(10);
+        assert_eq!(app.log_search_match_index, Some(1));
+    }
+
+    #[test]
+    fn test_next_log_match_empty() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.next_log_match(10);
+        assert_eq!(app.log_search_match_index, None);
+    }
+
+    #[test]
+    fn test_prev_log_match_empty() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.prev_log_match(10);
+        assert_eq!(app.log_search_match_index, None);
+    }
+
+    #[test]
+    fn test_next_log_match_scrolls_when_out_of_view() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = (0..20)
+            .map(|i| {
+                if i == 0 || i == 15 {
+                    make_log("match")
+                } else {
+                    make_log("no")
+                }
+            })
+            .collect();
+        app.log_search_query = "match".into();
+        app.update_log_search();
+        // matches at 0 and 15
+        assert_eq!(app.log_search_matches, vec![0, 15]);
+        app.logs_scroll = 0;
+        app.next_log_match(5); // visible = 5, match at 15 is out of view
+        assert_eq!(app.log_search_match_index, Some(1));
+        assert_eq!(app.logs_scroll, 15);
+    }
+
+    #[test]
+    fn test_clear_log_search() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.log_search_query = "test".into();
+        app.log_search_matches = vec![0, 1];
+        app.log_search_match_index = Some(0);
+
+        app.clear_log_search();
+
+        assert!(app.log_search_query.is_empty());
+        assert!(app.log_search_matches.is_empty());
+        assert_eq!(app.log_search_match_index, None);
+    }
+
+    #[test]
+    fn test_whole_word_log_search() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.logs = vec![make_log("error opening"), make_log("err: bad"), make_log("ferret")];
+        app.log_search_query = "err".into();
+        app.update_log_search();
+        assert_eq!(app.log_search_matches, vec![0, 1, 2]);
+        app.toggle_log_search_whole_word();
+        assert_eq!(app.log_search_matches, vec![1]);
+        app.toggle_log_search_whole_word();
+        assert_eq!(app.log_search_matches, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_detail_search_navigation() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.set_detail_content(
+            ["General", "  Name: a", "Dependencies", "  After: net", "  Before: net-online"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        );
+        app.detail_search_query = "NET".into();
+        app.update_detail_search();
+        assert_eq!(app.detail_search_matches, vec![3, 4]);
+        assert_eq!(app.detail_search_match_index, Some(0));
+        assert_eq!(app.detail_scroll, 3);
+
+        app.next_detail_match(10);
+        assert_eq!(app.detail_search_match_index, Some(1));
+        app.next_detail_match(10);
+        assert_eq!(app.detail_search_match_index, Some(0));
+        app.prev_detail_match(10);
+        assert_eq!(app.detail_search_match_index, Some(1));
+
+        // New content (e.g. properties arriving) re-runs the search.
+        app.set_detail_content(vec!["  After: net".to_string()]);
+        assert_eq!(app.detail_search_matches, vec![0]);
+        assert_eq!(app.detail_search_match_index, Some(0));
+
+        app.clear_detail_search();
+        assert!(app.detail_search_matches.is_empty());
+        assert_eq!(app.detail_search_match_index, None);
+    }
+
+    #[test]
+    fn test_request_quit_without_action_quits() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.request_quit();
+        assert!(app.should_quit);
+        assert!(!app.pending_quit_confirm);
+    }
+
+    #[test]
+    fn test_request_quit_with_confirm_on_quit_asks_first() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.confirm_on_quit = true;
+        app.request_quit();
+        assert!(!app.should_quit);
+        assert!(app.pending_quit_confirm);
+
+        app.answer_quit_confirm(true);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_request_quit_during_action_asks_first() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.action_in_progress = true;
+        app.request_quit();
+        assert!(!app.should_quit);
+        assert!(app.pending_quit_confirm);
+
+        app.answer_quit_confirm(false);
+        assert!(!app.should_quit);
+        assert!(!app.pending_quit_confirm);
+
+        app.request_quit();
+        app.answer_quit_confirm(true);
+        assert!(app.should_quit);
+        assert!(!app.pending_quit_confirm);
+    }
+
+    #[test]
+    fn test_active_elapsed() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.active_timestamps.insert("unit0.service".into(), 1_000_000);
+        assert_eq!(app.active_elapsed("unit0.service"), None);
+        app.active_clock = Some((3_601_000_000 + 5 * 60_000_000, Instant::now()));
+        assert_eq!(app.active_elapsed("unit0.service"), Some("1h 5m".to_string()));
+        assert_eq!(app.active_elapsed("other.service"), None);
+    }
+
+    fn states(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|&(u, s)| (u.to_string(), s.to_string())).collect()
+    }
+
+    #[test]
+    fn test_newly_failed_transitions() {
+        let prev = states(&[
+            ("a.service", "running"),
+            ("b.service", "failed"),
+            ("c.service", "dead"),
+            ("d.service", "failed"),
+        ]);
+        let current = states(&[
+            ("a.service", "failed"),
+            ("b.service", "failed"),
+            ("c.service", "running"),
+            ("d.service", "dead"),
+            ("e.service", "failed"),
+        ]);
+        assert_eq!(newly_failed(&prev, &current), ["a.service", "e.service"]);
+        assert!(newly_failed(&current, &current).is_empty(), "already failed: no alert");
+    }
+
+    #[test]
+    fn test_auto_refresh_alerts_on_new_failure() {
+        let mut app = test_app_with_services(vec![
+            make_unit("a.service", "running", "", None),
+            make_unit("b.service", "failed", "", None),
+        ]);
+        app.bell_on_failure = true;
+
+        // Without auto-refresh, refreshes never alert.
+        app.apply_refreshed_units(vec![
+            make_unit("a.service", "failed", "", None),
+            make_unit("b.service", "failed", "", None),
+        ]);
+        assert!(app.failure_alert.is_none());
+
+        app.auto_refresh_interval = Some(AUTO_REFRESH_INTERVALS[0]);
+        app.apply_refreshed_units(vec![
+            make_unit("a.service", "running", "", None),
+            make_unit("b.service", "failed", "", None),
+        ]);
+        assert!(app.failure_alert.is_none(), "b was already failed");
+        app.apply_refreshed_units(vec![
+            make_unit("a.service", "failed", "", None),
+            make_unit("b.service", "failed", "", None),
+        ]);
+        assert_eq!(app.failure_alert.as_deref(), Some("\u{26a0} a.service failed"));
+        assert!(app.bell_pending);
+        assert!(app.notify_pending.is_empty(), "desktop notifications are off");
+
+        app.clear_status_message();
+        assert!(app.failure_alert.is_none());
+    }
+
+    #[test]
+    fn test_diff_states() {
+        let prev: HashMap<String, String> = [("a", "running"), ("b", "dead"), ("gone", "running")]
+            .into_iter()
+            .map(|(u, s)| (u.to_string(), s.to_string()))
+            .collect();
+        let current: HashMap<String, String> = [("a", "failed"), ("b", "dead"), ("new", "running")]
+            .into_iter()
+            .map(|(u, s)| (u.to_string(), s.to_string()))
+            .collect();
+        let changes = diff_states(&prev, &current);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes["a"], ("running".to_string(), "failed".to_string()));
+    }
+
+    #[test]
+    fn test_state_changes_follow_snapshot() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        assert!(app.state_changes.is_empty());
+        let first = app.services[0].unit.clone();
+        app.sub_state_snapshot = Some(app.sub_states());
+        let mut units = app.services.clone();
+        units[0].sub = "failed".into();
+        app.apply_refreshed_units(units);
+        assert_eq!(app.state_changes[&first], ("running".to_string(), "failed".to_string()));
+
+        app.toggle_user_mode();
+        assert!(app.sub_state_snapshot.is_none());
+        assert!(app.state_changes.is_empty());
+    }
+
+    #[test]
+    fn test_journal_pager_args_match_in_app_filters() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.log_priority_filter = Some((0, 4));
+        app.log_time_range = TimeRange::OneDay;
+        app.log_main_pid = Some(42);
+        app.filter_main_pid = true;
+        // From the unit list: the highlighted unit, without the PID filter
+        // that belongs to whichever unit's logs were loaded last.
+        let name = app.selected_unit().unwrap().unit.clone();
+        assert_eq!(
+            app.journal_pager_args().unwrap(),
+            vec!["-u", name.as_str(), "-e", "-p", "4", "--since", "1 day ago"]
+        );
+
+        app.show_logs = true;
+        app.last_selected_service = Some("other.service".into());
+        assert_eq!(
+            app.journal_pager_args().unwrap(),
+            vec!["-u", "other.service", "-e", "-p", "4", "--since", "1 day ago", "_PID=42"]
+        );
+
+        app.system_logs_mode = true;
+        assert_eq!(
+            app.journal_pager_args().unwrap(),
+            vec!["-e", "-p", "4", "--since", "1 day ago"]
+        );
+    }
+
+    #[test]
+    fn test_journal_match_count_reset_on_query_change() {
         let mut app = test_app_with_subs(&["running"]);
-        app.logs = vec![make_log("match1"), make_log("match2")];
-        app.log_search_query = "match".into();
+        app.logs = vec![make_log("err: bad")];
+        app.log_search_query = "err".into();
+        app.log_journal_match_count = Some(Ok(480));
+        app.log_search_query = "er".into();
         app.update_log_search();
-        app.log_search_match_index = Some(1); // last match
-        app.next_log_match(10);
-        assert_eq!(app.log_search_match_index, Some(0));
+        assert_eq!(app.log_journal_match_count, None);
     }
 
     #[test]
-    fn test_prev_log_match_wraps() {
+    fn test_journal_match_count_ignores_stale_query() {
         let mut app = test_app_with_subs(&["running"]);
-        app.logs = vec![make_log("match1"), make_log("match2")];
-        app.log_search_query = "match".into();
-        app.update_log_search();
-        app.log_search_match_index = Some(0); // first match
-        app.prev_log_match(10);
-        assert_eq!(app.log_search_match_index, Some(1));
+        app.log_search_query = "err".into();
+        let (tx, rx) = mpsc::channel();
+        app.journal_count_receiver = Some(rx);
+        tx.send(JournalCount { query: "old".into(), whole_word: false, result: Ok(7) }).unwrap();
+        app.check_journal_count_progress();
+        assert!(!app.journal_count_in_flight());
+        assert_eq!(app.log_journal_match_count, None);
+
+        let (tx, rx) = mpsc::channel();
+        app.journal_count_receiver = Some(rx);
+        tx.send(JournalCount { query: "err".into(), whole_word: false, result: Ok(7) }).unwrap();
+        app.check_journal_count_progress();
+        assert_eq!(app.log_journal_match_count, Some(Ok(7)));
     }
 
     #[test]
-    fn test_next_log_match_empty() {
+    fn test_mark_logs_dirty() {
         let mut app = test_app_with_subs(&["running"]);
-        app.next_log_match(10);
-        assert_eq!(app.log_search_match_index, None);
+        assert!(!app.log_filters_dirty);
+        app.mark_logs_dirty();
+        assert!(app.log_filters_dirty);
     }
 
     #[test]
-    fn test_prev_log_match_empty() {
+    fn test_force_reload_logs_marks_dirty() {
         let mut app = test_app_with_subs(&["running"]);
-        app.prev_log_match(10);
-        assert_eq!(app.log_search_match_index, None);
+        app.force_reload_logs();
+        assert!(app.log_filters_dirty);
+        assert_eq!(app.pending_log_scroll, None, "following the bottom");
     }
 
     #[test]
-    fn test_next_log_match_scrolls_when_out_of_view() {
-        let mut app = test_app_with_subs(&["running"]);
-        app.logs = (0..20)
-            .map(|i| {
-                if i == 0 || i == 15 {
-                    make_log("match")
-                } else {
-                    make_log("no")
-                }
-            })
-            .collect();
-        app.log_search_query = "match".into();
-        app.update_log_search();
-        // matches at 0 and 15
-        assert_eq!(app.log_search_matches, vec![0, 15]);
-        app.logs_scroll = 0;
-        app.next_log_match(5); // visible = 5, match at 15 is out of view
-        assert_eq!(app.log_search_match_index, Some(1));
-        assert_eq!(app.logs_scroll, 15);
+    fn test_force_reload_logs_keeps_scroll_when_scrolled_up() {
+        let (mut app, tx) = app_loading_logs_for("a.service");
+        app.logs_scroll = 2;
+        app.logs_at_bottom = false;
+        app.force_reload_logs();
+        assert_eq!(app.pending_log_scroll, Some(2));
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: None,
+            result: Ok(vec![make_log("a"), make_log("b"), make_log("c"), make_log("d")]),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert_eq!(app.logs_scroll, 2);
+        assert_eq!(app.pending_log_scroll, None);
+    }
+
+    #[test]
+    fn test_force_reload_logs_clamps_scroll_to_fewer_entries() {
+        let (mut app, tx) = app_loading_logs_for("a.service");
+        app.logs_scroll = 9;
+        app.logs_at_bottom = false;
+        app.force_reload_logs();
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: None,
+            result: Ok(vec![make_log("a"), make_log("b")]),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert_eq!(app.logs_scroll, 1);
     }
 
     #[test]
-    fn test_clear_log_search() {
+    fn test_toggle_filter_main_pid_marks_dirty() {
         let mut app = test_app_with_subs(&["running"]);
-        app.log_search_query = "test".into();
-        app.log_search_matches = vec![0, 1];
-        app.log_search_match_index = Some(0);
-
-        app.clear_log_search();
-
-        assert!(app.log_search_query.is_empty());
-        assert!(app.log_search_matches.is_empty());
-        assert_eq!(app.log_search_match_index, None);
+        app.log_main_pid = Some(42);
+        app.toggle_filter_main_pid();
+        assert!(app.filter_main_pid);
+        assert!(app.log_filters_dirty);
+        assert_eq!(app.log_main_pid, None, "resolved again on the next load");
     }
 
     #[test]
-    fn test_mark_logs_dirty() {
+    fn test_log_filters_main_pid_only_when_enabled() {
         let mut app = test_app_with_subs(&["running"]);
-        assert!(!app.log_filters_dirty);
-        app.mark_logs_dirty();
-        assert!(app.log_filters_dirty);
+        app.log_main_pid = Some(42);
+        assert_eq!(app.log_filters().main_pid, None);
+        app.filter_main_pid = true;
+        assert_eq!(app.log_filters().main_pid, Some(42));
+        app.system_logs_mode = true;
+        assert_eq!(app.log_filters().main_pid, None);
+    }
+
+    #[test]
+    fn test_log_load_records_main_pid() {
+        let (mut app, tx) = app_loading_logs_for("a.service");
+        app.filter_main_pid = true;
+        tx.send(LogLoad {
+            unit: Some("a.service".into()),
+            main_pid: Some(99),
+            result: Ok(vec![make_log("fresh")]),
+        })
+        .unwrap();
+        app.check_log_load_progress();
+        assert_eq!(app.log_main_pid, Some(99));
+        // Live tail and history paging reuse the same match.
+        assert_eq!(app.log_filters().main_pid, Some(99));
     }
 
     // Unit action picker
@@ -2644,6 +7718,21 @@ mod tests {
         assert!(app.available_actions.contains(&UnitAction::Enable));
     }
 
+    #[test]
+    fn test_open_action_picker_masked_explains_and_offers_unmask() {
+        let mut unit = make_unit("test.service", "dead", "Test", Some("masked"));
+        unit.load = "masked".into();
+        let mut app = test_app_with_services(vec![unit]);
+        app.open_action_picker();
+        assert!(app.show_action_picker);
+        assert!(app.available_actions.contains(&UnitAction::Unmask));
+        assert!(!app.available_actions.contains(&UnitAction::Start));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("test.service: unit is masked; unmask it before starting")
+        );
+    }
+
     #[test]
     fn test_open_action_picker_no_selection() {
         let mut app = test_app_empty();
@@ -2713,6 +7802,374 @@ mod tests {
         assert_eq!(app.confirm_unit_name.as_deref(), Some("test.service"));
     }
 
+    #[test]
+    fn test_no_confirm_runs_trusted_action_immediately() {
+        let mut app = test_app_with_services(vec![
+            make_unit("test.service", "running", "Test", None),
+        ]);
+        app.no_confirm = true;
+        app.open_action_picker();
+        let idx = app.available_actions.iter().position(|&a| a == UnitAction::Restart).unwrap();
+        app.action_picker_state.select(Some(idx));
+        app.action_picker_confirm();
+        assert!(app.show_confirm, "the dialog still shows progress and the result");
+        assert!(app.action_in_progress);
+    }
+
+    #[test]
+    fn test_no_confirm_still_prompts_when_units_depend_on_stop() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.runner = Arc::new(CannedRunner::ok("x\n  dependent.service\n"));
+        app.no_confirm = true;
+        app.open_action_picker();
+        let stop = app.available_actions.iter().position(|&a| a == UnitAction::Stop).unwrap();
+        app.action_picker_state.select(Some(stop));
+        app.action_picker_confirm();
+        settle(&mut app, App::confirm_warning_loading);
+        assert!(app.show_confirm);
+        assert!(!app.action_in_progress, "waits for Y despite --no-confirm");
+        assert!(app.confirm_warning.as_deref().is_some_and(|w| w.contains("dependent.service")));
+    }
+
+    #[test]
+    fn test_no_confirm_runs_stop_once_lookup_finds_no_dependents() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.runner = Arc::new(CannedRunner::ok("unit0.service\n"));
+        app.no_confirm = true;
+        app.open_action_picker();
+        let stop = app.available_actions.iter().position(|&a| a == UnitAction::Stop).unwrap();
+        app.action_picker_state.select(Some(stop));
+        app.action_picker_confirm();
+        assert!(!app.action_in_progress, "waits for the lookup");
+        settle(&mut app, App::confirm_warning_loading);
+        assert!(app.action_in_progress);
+        assert!(app.confirm_warning.is_none());
+    }
+
+    #[test]
+    fn test_no_confirm_still_prompts_for_disable() {
+        let mut app = test_app_with_services(vec![
+            make_unit("test.service", "running", "Test", Some("enabled")),
+        ]);
+        app.no_confirm = true;
+        app.open_action_picker();
+        let idx = app.available_actions.iter().position(|&a| a == UnitAction::Disable).unwrap();
+        app.action_picker_state.select(Some(idx));
+        app.action_picker_confirm();
+        assert!(app.show_confirm);
+        assert!(!app.action_in_progress);
+    }
+
+    fn export_entry(message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: Some(1700000000000123),
+            priority: Some(3),
+            pid: Some("42".into()),
+            identifier: Some("nginx".into()),
+            hostname: Some("web-01".into()),
+            ..make_log(message)
+        }
+    }
+
+    #[test]
+    fn test_export_logs_csv_quotes_commas_and_quotes() {
+        let mut app = test_app_empty();
+        app.use_utc = true;
+        app.logs = vec![export_entry("plain"), export_entry(r#"GET /a,b "quoted""#)];
+        let csv = app.export_logs_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "timestamp,hostname,priority,identifier,pid,message");
+        assert_eq!(lines[1], "2023-11-14T22:13:20.000123+00:00,web-01,err,nginx,42,plain");
+        assert_eq!(
+            lines[2],
+            r#"2023-11-14T22:13:20.000123+00:00,web-01,err,nginx,42,"GET /a,b ""quoted""""#
+        );
+    }
+
+    #[test]
+    fn test_csv_field_quotes_line_breaks_only_when_needed() {
+        assert_eq!(csv_field("simple text"), "simple text");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn test_export_logs_json_fields() {
+        let mut app = test_app_empty();
+        app.use_utc = true;
+        app.logs = vec![export_entry("hi"), make_log("bare")];
+        let parsed: serde_json::Value = serde_json::from_str(&app.export_logs_json()).unwrap();
+        assert_eq!(parsed[0]["timestamp"], "2023-11-14T22:13:20.000123+00:00");
+        assert_eq!(parsed[0]["hostname"], "web-01");
+        assert_eq!(parsed[0]["priority"], "err");
+        assert_eq!(parsed[0]["pid"], "42");
+        assert_eq!(parsed[0]["message"], "hi");
+        assert!(parsed[1]["timestamp"].is_null());
+        assert!(parsed[1]["hostname"].is_null());
+    }
+
+    #[test]
+    fn test_export_logs_plain_lines() {
+        let mut app = test_app_empty();
+        app.use_utc = true;
+        app.logs = vec![export_entry("started"), make_log("bare")];
+        assert_eq!(
+            app.export_logs_plain(),
+            "2023-11-14T22:13:20.000123+00:00 web-01 nginx[42]: started\nbare\n"
+        );
+    }
+
+    #[test]
+    fn test_export_logs_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let mut app = test_app_empty();
+        app.logs = vec![export_entry("password=hunter2")];
+        app.last_selected_service = Some(format!("export-test-{}.service", std::process::id()));
+        app.export_logs(LogExportFormat::Plain);
+        let status = app.status_message.clone().unwrap();
+        let path = status.strip_prefix("Exported 1 log entries to ").expect(&status);
+        let mode = std::fs::metadata(path).unwrap().permissions().mode();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_export_raw_properties_saves_in_background() {
+        let mut app = test_app_empty();
+        app.runner = Arc::new(CannedRunner::ok("Id=raw.service\nMainPID=0\n"));
+        app.detail_unit_name = Some(format!("raw-export-test-{}.service", std::process::id()));
+        app.export_raw_properties();
+        assert!(app.raw_properties_loading(), "runs off the UI thread");
+        settle(&mut app, App::raw_properties_loading);
+        let status = app.status_message.clone().unwrap();
+        let path = status.strip_prefix("Saved systemctl show output to ").expect(&status);
+        let content = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(content, "Id=raw.service\nMainPID=0\n");
+    }
+
+    #[test]
+    fn test_export_logs_without_entries_only_reports() {
+        let mut app = test_app_empty();
+        app.export_logs(LogExportFormat::Csv);
+        assert_eq!(app.status_message.as_deref(), Some("No log entries to export"));
+    }
+
+    #[test]
+    fn test_selected_timer_target_uses_cached_triggers() {
+        let mut app = test_app_with_services(vec![make_unit("nightly.timer", "waiting", "Nightly", None)]);
+        let props = UnitProperties { triggers: vec!["backup-run.service".into()], ..Default::default() };
+        app.properties_cache.insert("nightly.timer".into(), props);
+        assert_eq!(app.selected_timer_target().as_deref(), Some("backup-run.service"));
+    }
+
+    #[test]
+    fn test_timer_target_logs_fetch_uncached_properties_in_background() {
+        let mut app = test_app_with_services(vec![
+            make_unit("nightly.timer", "waiting", "Nightly", None),
+            make_unit("backup-run.service", "dead", "Backup", None),
+        ]);
+        app.unit_type = UnitType::All;
+        app.runner = Arc::new(CannedRunner::ok("Triggers=backup-run.service\n"));
+        app.open_timer_target_logs();
+        assert!(app.timer_target_loading(), "runs off the UI thread");
+        assert!(!app.show_logs);
+        settle(&mut app, App::timer_target_loading);
+        assert!(app.properties_cache.contains_key("nightly.timer"));
+        assert!(app.show_logs);
+        assert_eq!(app.selected_unit().map(|u| u.unit.as_str()), Some("backup-run.service"));
+    }
+
+    #[test]
+    fn test_timer_target_logs_rejects_non_timer() {
+        let mut app = test_app_with_services(vec![make_unit("nginx.service", "running", "Web", None)]);
+        app.open_timer_target_logs();
+        assert!(!app.show_logs);
+        assert_eq!(app.status_message.as_deref(), Some("nginx.service is not a timer"));
+    }
+
+    #[test]
+    fn test_failed_unit_names_in_list_order() {
+        let app = test_app_with_subs(&["failed", "running", "failed", "dead"]);
+        let names: Vec<String> = app.services.iter().map(|u| u.unit.clone()).collect();
+        assert_eq!(app.failed_unit_names(), vec![names[0].clone(), names[2].clone()]);
+    }
+
+    #[test]
+    fn test_restart_failed_confirms_with_every_failed_unit() {
+        let mut app = test_app_with_subs(&["failed", "running", "failed"]);
+        app.open_restart_failed_confirm();
+        assert!(app.show_confirm);
+        assert_eq!(app.confirm_action, Some(UnitAction::Restart));
+        assert_eq!(app.confirm_targets, app.failed_unit_names());
+        assert!(!app.action_in_progress);
+    }
+
+    #[test]
+    fn test_restart_failed_without_failures_only_reports() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.open_restart_failed_confirm();
+        assert!(!app.show_confirm);
+        assert_eq!(app.status_message.as_deref(), Some("No failed services"));
+    }
+
+    #[test]
+    fn test_batch_results_accumulate_as_streamed() {
+        let mut app = test_app_with_subs(&["running"]);
+        let (tx, rx) = mpsc::channel();
+        app.show_confirm = true;
+        app.action_in_progress = true;
+        app.batch_receiver = Some(rx);
+        tx.send(("a.service".to_string(), Ok(()))).unwrap();
+        app.check_action_progress();
+        assert_eq!(app.batch_results, vec![("a.service".to_string(), Ok(()))]);
+        tx.send(("b.service".to_string(), Err("boom".to_string()))).unwrap();
+        tx.send(("c.service".to_string(), Ok(()))).unwrap();
+        app.check_action_progress();
+        assert_eq!(
+            app.batch_results,
+            vec![
+                ("a.service".to_string(), Ok(())),
+                ("b.service".to_string(), Err("boom".to_string())),
+                ("c.service".to_string(), Ok(())),
+            ]
+        );
+        drop(tx);
+        app.check_action_progress();
+        assert!(app.batch_receiver.is_none());
+        assert_eq!(app.batch_results.len(), 3, "results outlive the channel");
+        app.dismiss_action_result();
+        assert!(app.batch_results.is_empty());
+    }
+
+    #[test]
+    fn test_batch_scroll_follows_newest_until_scrolled_up() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.batch_results = (0..BATCH_RESULTS_SHOWN + 5).map(|i| (format!("u{i}.service"), Ok(()))).collect();
+        assert_eq!(app.batch_scroll_offset(), 5);
+        app.batch_scroll_up(2);
+        assert_eq!(app.batch_scroll_offset(), 3);
+        // A new result doesn't move a scrolled-back view.
+        app.batch_results.push(("late.service".to_string(), Ok(())));
+        assert_eq!(app.batch_scroll_offset(), 3);
+        app.batch_scroll_down(10);
+        assert_eq!(app.batch_scroll, None);
+        assert_eq!(app.batch_scroll_offset(), 6);
+    }
+
+    /// Delivers `result` as the outcome of `action` on `unit`, as if the
+    /// confirm dialog had run it.
+    fn finish_action(app: &mut App, action: UnitAction, unit: &str, result: Result<String, String>) {
+        let (tx, rx) = mpsc::channel();
+        app.confirm_action = Some(action);
+        app.confirm_unit_name = Some(unit.to_string());
+        app.show_confirm = true;
+        app.action_receiver = Some(rx);
+        tx.send((result, String::new())).unwrap();
+        app.check_action_progress();
+        app.dismiss_action_result();
+    }
+
+    #[test]
+    fn test_action_output_stored_from_action_thread() {
+        let mut app = test_app_with_subs(&["running"]);
+        let (tx, rx) = mpsc::channel();
+        app.confirm_action = Some(UnitAction::Enable);
+        app.confirm_unit_name = Some("foo.service".into());
+        app.show_confirm = true;
+        app.action_receiver = Some(rx);
+        tx.send((Ok("Enable succeeded for foo.service".into()), "Created symlink a\nCreated symlink b".into()))
+            .unwrap();
+        app.check_action_progress();
+        assert_eq!(app.last_action_output.as_deref(), Some("Created symlink a\nCreated symlink b"));
+
+        app.dismiss_action_result();
+        app.open_action_output();
+        assert!(app.show_action_output);
+        assert_eq!(app.action_output_scroll, 0);
+    }
+
+    #[test]
+    fn test_footer_action_hints_follow_unit_state() {
+        let running = test_app_with_services(vec![make_unit("a.service", "running", "", Some("enabled"))]);
+        assert_eq!(running.footer_action_hints(), "x: t Stop \u{b7} r Restart \u{b7} l Reload");
+        let dead = test_app_with_services(vec![make_unit("a.service", "dead", "", Some("disabled"))]);
+        assert_eq!(dead.footer_action_hints(), "x: s Start \u{b7} e Enable");
+        assert_ne!(running.footer_action_hints(), dead.footer_action_hints());
+        assert_eq!(test_app_empty().footer_action_hints(), "x: Actions");
+    }
+
+    #[test]
+    fn test_security_analysis_of_timer_reports_in_header() {
+        let mut app = test_app_with_services(vec![make_unit("backup.timer", "waiting", "", None)]);
+        app.open_security_analysis();
+        settle(&mut app, App::security_loading);
+        assert!(!app.show_security);
+        assert!(app.status_message.as_deref().is_some_and(|m| m.contains("only applies to services")));
+    }
+
+    #[test]
+    fn test_security_analysis_opens_when_report_arrives() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.runner = Arc::new(CannedRunner::ok(
+            "NAME DESCRIPTION EXPOSURE\n\u{2192} Overall exposure level for unit0.service: 9.6 UNSAFE\n",
+        ));
+        app.open_security_analysis();
+        assert!(app.security_loading(), "runs off the UI thread");
+        assert!(!app.show_security);
+        settle(&mut app, App::security_loading);
+        assert!(app.show_security);
+        assert_eq!(app.security_unit, "unit0.service");
+        assert_eq!(app.security_lines.len(), 2);
+    }
+
+    #[test]
+    fn test_open_action_output_before_any_action() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.open_action_output();
+        assert!(!app.show_action_output);
+        assert_eq!(app.status_message.as_deref(), Some("No action has run yet"));
+    }
+
+    #[test]
+    fn test_undo_offers_inverse_of_last_action() {
+        let mut app = test_app_with_subs(&["running"]);
+        finish_action(&mut app, UnitAction::Disable, "nginx.service", Ok("done".into()));
+        assert_eq!(app.last_action, Some((UnitAction::Disable, "nginx.service".to_string())));
+        app.open_undo_confirm();
+        assert!(app.show_confirm);
+        assert_eq!(app.confirm_action, Some(UnitAction::Enable));
+        assert_eq!(app.confirm_unit_name.as_deref(), Some("nginx.service"));
+        assert!(!app.action_in_progress, "undo still waits for confirmation");
+    }
+
+    #[test]
+    fn test_undo_ignores_failed_and_irreversible_actions() {
+        let mut app = test_app_with_subs(&["running"]);
+        finish_action(&mut app, UnitAction::Stop, "a.service", Err("nope".into()));
+        assert_eq!(app.last_action, None);
+        finish_action(&mut app, UnitAction::Restart, "a.service", Ok("done".into()));
+        assert_eq!(app.last_action, None);
+        app.open_undo_confirm();
+        assert!(!app.show_confirm);
+        assert_eq!(app.status_message.as_deref(), Some("Nothing to undo"));
+    }
+
+    #[test]
+    fn test_vacuum_requires_confirmation() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.selected_units.insert("test.service".into());
+        app.open_vacuum_confirm();
+        assert!(app.show_confirm);
+        assert_eq!(app.confirm_action, Some(UnitAction::VacuumJournal));
+        assert!(app.confirm_targets.is_empty(), "journal-wide, not per unit");
+        assert!(!app.action_in_progress, "nothing runs until confirmed");
+        // A lowercase y is not enough for a destructive action.
+        app.confirm_key('y');
+        assert!(!app.action_in_progress);
+        assert!(app.show_confirm);
+    }
+
     #[test]
     fn test_confirm_no_clears_state() {
         let mut app = test_app_with_subs(&["running"]);
@@ -2726,6 +8183,64 @@ mod tests {
         assert!(app.action_result.is_none());
     }
 
+    /// Leaves the result dialog open on a failed start.
+    fn show_failed_start(app: &mut App, error: &str) {
+        app.confirm_action = Some(UnitAction::Start);
+        app.confirm_unit_name = Some("nginx.service".to_string());
+        app.show_confirm = true;
+        app.action_result = Some(Err(error.to_string()));
+    }
+
+    #[test]
+    fn test_privileged_retry_offered_for_permission_failures() {
+        let mut app = test_app_with_subs(&["dead"]);
+        app.runner = Arc::new(UnavailableRunner);
+        show_failed_start(&mut app, "Start failed: Access denied");
+        assert!(app.can_retry_privileged());
+        assert_eq!(
+            app.privileged_retry_command(Escalation::Pkexec),
+            Some(vec!["pkexec".to_string(), "systemctl".into(), "start".into(), "nginx.service".into()])
+        );
+
+        app.finish_privileged_retry(Escalation::Sudo, Ok(()));
+        assert_eq!(
+            app.action_result,
+            Some(Ok("Start succeeded for nginx.service (via sudo)".to_string()))
+        );
+        assert_eq!(app.last_action, Some((UnitAction::Start, "nginx.service".to_string())));
+        assert!(!app.can_retry_privileged());
+    }
+
+    #[test]
+    fn test_privileged_retry_not_offered_for_other_failures() {
+        let mut app = test_app_with_subs(&["dead"]);
+        show_failed_start(&mut app, "Start failed: Unit nginx.service not found.");
+        assert!(!app.can_retry_privileged());
+
+        show_failed_start(&mut app, "Start failed: Interactive authentication required.");
+        assert!(app.can_retry_privileged());
+        app.no_block = true;
+        assert_eq!(
+            app.privileged_retry_command(Escalation::Sudo),
+            Some(vec!["sudo".to_string(), "systemctl".into(), "--no-block".into(), "start".into(), "nginx.service".into()])
+        );
+        app.user_mode = true;
+        assert!(!app.can_retry_privileged(), "the user manager needs no escalation");
+    }
+
+    #[test]
+    fn test_toggle_show_all_units_reloads() {
+        let mut app = test_app_with_subs(&["running", "dead"]);
+        app.runner = Arc::new(CannedRunner::ok("[]"));
+        app.toggle_show_all_units();
+        assert!(!app.show_all_units);
+        assert_eq!(app.status_message.as_deref(), Some("Showing active units only"));
+        assert!(app.services.is_empty(), "reloaded from the runner");
+        app.toggle_show_all_units();
+        assert!(app.show_all_units);
+        assert_eq!(app.status_message.as_deref(), Some("Showing all loaded units"));
+    }
+
     #[test]
     fn test_dismiss_action_result() {
         let mut app = test_app_with_subs(&["running"]);
@@ -2774,6 +8289,24 @@ mod tests {
         assert!(!app.unit_file_search_mode);
     }
 
+    #[test]
+    fn test_reload_unit_file_swaps_content_in_background() {
+        let mut app = test_app_with_subs(&["running"]);
+        app.runner = Arc::new(UnavailableRunner);
+        app.show_unit_file = true;
+        app.unit_file_content = vec!["[Unit]".into(), "Description=old".into()];
+        app.unit_file_unit_name = Some("unit0.service".into());
+        app.unit_file_scroll = 1;
+        app.properties_cache.insert("unit0.service".into(), UnitProperties::default());
+        app.reload_unit_file();
+        assert!(app.unit_file_loading(), "runs off the UI thread");
+        assert!(!app.properties_cache.contains_key("unit0.service"));
+        assert_eq!(app.unit_file_content[1], "Description=old");
+        settle(&mut app, App::unit_file_loading);
+        assert!(app.unit_file_content[0].starts_with("Error: "), "{:?}", app.unit_file_content);
+        assert_eq!(app.unit_file_scroll, 1);
+    }
+
     #[test]
     fn test_scroll_unit_file_up() {
         let mut app = test_app_with_subs(&["running"]);
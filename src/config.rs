@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::service::{priority_filter_label, TimeRange};
+use crate::theme::Theme;
+
+/// Settings persisted between runs, stored as JSON at
+/// `$XDG_CONFIG_HOME/systemdmgr/config.json` (or `~/.config/...`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// Full unit names (e.g. "nginx.service") pinned to the top of the list.
+    #[serde(default)]
+    pub favorites: Vec<String>,
+    /// Tint failed rows and dim stopped ones in the unit list. Can be turned
+    /// off for colorblind users, leaving only the STATUS column colored.
+    #[serde(default = "default_true")]
+    pub row_severity_colors: bool,
+    /// Down on the last unit goes to the first (and Up on the first to the
+    /// last) instead of stopping.
+    #[serde(default)]
+    pub wrap_navigation: bool,
+    /// Leave static and indirect units out of the list unless searching;
+    /// they can't be started or enabled directly and mostly add clutter.
+    #[serde(default)]
+    pub hide_noise: bool,
+    /// Run start, stop, restart and reload without the confirm dialog.
+    /// Other actions are always confirmed.
+    #[serde(default)]
+    pub no_confirm: bool,
+    /// Ask before quitting from the unit list, for users who hit `q` or
+    /// `Esc` by accident.
+    #[serde(default)]
+    pub confirm_on_quit: bool,
+    /// Run start, stop, restart and reload with `systemctl --no-block`, so
+    /// slow units don't hold the action dialog; success then only means
+    /// the job was queued.
+    #[serde(default)]
+    pub no_block: bool,
+    /// Ring the terminal bell along with the header flash when
+    /// auto-refresh sees a unit newly fail.
+    #[serde(default)]
+    pub bell_on_failure: bool,
+    /// Also send a desktop notification for those units. Needs a build with
+    /// the `desktop-notifications` feature.
+    #[serde(default)]
+    pub desktop_notifications: bool,
+    /// How often live tail polls the journal for new entries, in
+    /// milliseconds. Raise it on slow SSH links.
+    #[serde(default = "default_live_tail_interval_ms")]
+    pub live_tail_interval_ms: u64,
+    /// Named log filter combinations, recalled from the presets picker.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log_presets: Vec<LogFilterPreset>,
+    /// Color overrides; only written back when customized.
+    #[serde(default, skip_serializing_if = "Theme::is_default")]
+    pub theme: Theme,
+}
+
+/// A saved priority + time range combination for the logs view.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogFilterPreset {
+    pub name: String,
+    /// Inclusive syslog priority range; `None` shows every level.
+    #[serde(default)]
+    pub priority: Option<(u8, u8)>,
+    #[serde(default)]
+    pub time_range: TimeRange,
+}
+
+impl LogFilterPreset {
+    /// The filters in words, e.g. "err · Last 1 hour".
+    pub fn summary(&self) -> String {
+        let priority = self
+            .priority
+            .map(priority_filter_label)
+            .unwrap_or_else(|| "All priorities".to_string());
+        format!("{} \u{b7} {}", priority, self.time_range.summary())
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_live_tail_interval_ms() -> u64 {
+    500
+}
+
+/// Live tail intervals below this are raised to it, so a typo like `0`
+/// can't spin the journal in a loop.
+const MIN_LIVE_TAIL_INTERVAL_MS: u64 = 100;
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            favorites: Vec::new(),
+            row_severity_colors: true,
+            wrap_navigation: false,
+            hide_noise: false,
+            no_confirm: false,
+            confirm_on_quit: false,
+            no_block: false,
+            bell_on_failure: false,
+            desktop_notifications: false,
+            live_tail_interval_ms: default_live_tail_interval_ms(),
+            log_presets: Vec::new(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn live_tail_interval(&self) -> Duration {
+        Duration::from_millis(self.live_tail_interval_ms.max(MIN_LIVE_TAIL_INTERVAL_MS))
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("systemdmgr").join("config.json"))
+    }
+
+    /// Reads the config file. A missing file is an empty config; a file that
+    /// exists but can't be parsed is an error so it never gets overwritten.
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("systemdmgr-test-{}-{}", name, std::process::id()))
+            .join("config.json")
+    }
+
+    #[test]
+    fn test_load_missing_file_is_default() {
+        let path = temp_config_path("missing");
+        assert_eq!(Config::load(&path), Ok(Config::default()));
+    }
+
+    #[test]
+    fn test_save_then_load_round_trips() {
+        let path = temp_config_path("roundtrip");
+        let config = Config {
+            favorites: vec!["nginx.service".into(), "backup.timer".into()],
+            row_severity_colors: false,
+            hide_noise: true,
+            ..Config::default()
+        };
+        config.save(&path).unwrap();
+        assert_eq!(Config::load(&path), Ok(config));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_load_tolerates_missing_fields() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert!(config.favorites.is_empty());
+        assert!(config.row_severity_colors);
+        assert!(!config.wrap_navigation);
+        assert!(!config.hide_noise);
+        assert!(!config.no_confirm);
+        assert!(!config.confirm_on_quit);
+        assert!(!config.no_block);
+        assert!(!config.bell_on_failure);
+        assert!(!config.desktop_notifications);
+        assert_eq!(config.live_tail_interval(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_live_tail_interval_from_config() {
+        let config: Config = serde_json::from_str(r#"{"live_tail_interval_ms": 2000}"#).unwrap();
+        assert_eq!(config.live_tail_interval(), Duration::from_secs(2));
+        let config: Config = serde_json::from_str(r#"{"live_tail_interval_ms": 0}"#).unwrap();
+        assert_eq!(config.live_tail_interval(), Duration::from_millis(100), "clamped");
+    }
+
+    #[test]
+    fn test_log_presets_round_trip() {
+        let config = Config {
+            log_presets: vec![
+                LogFilterPreset {
+                    name: "errors today".into(),
+                    priority: Some((0, 3)),
+                    time_range: TimeRange::Today,
+                },
+                LogFilterPreset {
+                    name: "boot window".into(),
+                    priority: None,
+                    time_range: TimeRange::Custom { since: "-2h".into(), until: Some("-1h".into()) },
+                },
+            ],
+            ..Config::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn test_log_preset_summary() {
+        let preset = LogFilterPreset {
+            name: "x".into(),
+            priority: Some((0, 3)),
+            time_range: TimeRange::OneHour,
+        };
+        assert_eq!(preset.summary(), "err \u{b7} Last 1 hour");
+        let preset = LogFilterPreset { name: "y".into(), priority: None, time_range: TimeRange::All };
+        assert_eq!(preset.summary(), "All priorities \u{b7} All");
+    }
+
+    #[test]
+    fn test_default_theme_not_written() {
+        let json = serde_json::to_string(&Config::default()).unwrap();
+        assert!(!json.contains("theme"));
+        assert!(!json.contains("log_presets"));
+    }
+
+    #[test]
+    fn test_load_invalid_json_is_error() {
+        let path = temp_config_path("invalid");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "not json").unwrap();
+        assert!(Config::load(&path).is_err());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -1,8 +1,11 @@
 mod app;
+mod config;
+mod notify;
 mod service;
+mod theme;
 mod ui;
 
-use std::io::{self, stdout};
+use std::io::{self, stdout, Write};
 use std::time::{Duration, Instant};
 
 use crossterm::{
@@ -17,40 +20,204 @@ use ratatui::{prelude::*, Terminal};
 
 use std::sync::Arc;
 
-use app::App;
-use service::{validate_systemctl_version, CommandRunner, LocalRunner, SshRunner};
+use app::{App, Pane, MOUSE_SCROLL_LINES};
+use config::Config;
+use theme::Theme;
+use service::{
+    fetch_units, parse_unit_type, validate_systemctl_version, CommandRunner, Escalation, LocalRunner, SshRunner,
+    SystemdUnit, UnitType, UNIT_TYPES,
+};
 
-const LIVE_TAIL_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+/// Redraw interval for relative times in the unit list (timer countdowns,
+/// the UPTIME column) so they keep moving without input.
+const RELATIVE_TIME_TICK: Duration = Duration::from_secs(1);
+/// Columns moved per Left/Right press in the unwrapped logs view.
+const LOG_H_SCROLL_STEP: usize = 8;
+/// Longest gap between two clicks on the same cell that still counts as a
+/// double-click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
 
-fn main() -> io::Result<()> {
-    let args: Vec<String> = std::env::args().collect();
-    let mut ssh_args: Option<Vec<String>> = None;
-    let mut i = 1;
+const USAGE: &str = "Usage: systemdmgr [version [--json] | failed | export] [--user | --system] [--no-color] [--no-confirm] [--type service|timer|socket|target|path|all] [--ssh [ssh-options] destination]";
+
+/// Non-interactive commands that print to stdout instead of starting the TUI.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    /// List failed units of every type; exits 1 if there are any.
+    Failed,
+    /// Print the unit list of one type (`--type`, default service) as JSON.
+    Export,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct CliArgs {
+    version: bool,
+    /// `version --json` / `version --format json`.
+    json: bool,
+    subcommand: Option<Subcommand>,
+    user_mode: bool,
+    no_color: bool,
+    no_confirm: bool,
+    unit_type: Option<UnitType>,
+    ssh_args: Option<Vec<String>>,
+}
+
+/// Parses the arguments after the program name.
+fn parse_args(args: &[String]) -> Result<CliArgs, String> {
+    let mut cli = CliArgs::default();
+    let mut scope: Option<&str> = None;
+    let mut i = 0;
     while i < args.len() {
         match args[i].as_str() {
-            "version" | "--version" | "-v" => {
-                println!("systemdmgr {}", env!("CARGO_PKG_VERSION"));
-                return Ok(());
+            "version" | "--version" | "-v" => cli.version = true,
+            "failed" => cli.subcommand = Some(Subcommand::Failed),
+            "export" => cli.subcommand = Some(Subcommand::Export),
+            flag @ ("--user" | "--system") => {
+                if scope.is_some_and(|s| s != flag) {
+                    return Err("--user and --system cannot be used together".to_string());
+                }
+                scope = Some(flag);
+                cli.user_mode = flag == "--user";
+            }
+            "--json" => cli.json = true,
+            "--format" => {
+                i += 1;
+                let format = args.get(i).ok_or("--format requires a format")?;
+                cli.json = cli_output_format(format)?;
+            }
+            arg if arg.starts_with("--format=") => {
+                cli.json = cli_output_format(&arg["--format=".len()..])?;
+            }
+            "--no-color" => cli.no_color = true,
+            "--no-confirm" => cli.no_confirm = true,
+            "--type" => {
+                i += 1;
+                let name = args.get(i).ok_or("--type requires a unit type")?;
+                cli.unit_type = Some(cli_unit_type(name)?);
+            }
+            arg if arg.starts_with("--type=") => {
+                cli.unit_type = Some(cli_unit_type(&arg["--type=".len()..])?);
             }
             // Everything after --ssh is forwarded to the ssh client verbatim,
             // using ssh's own `[options] destination` syntax.
             "--ssh" => {
                 let rest = &args[i + 1..];
                 if rest.is_empty() {
-                    eprintln!("--ssh requires ssh arguments (e.g., --ssh user@server or --ssh -p 2222 -i key user@server)");
-                    std::process::exit(1);
+                    return Err("--ssh requires ssh arguments (e.g., --ssh user@server or --ssh -p 2222 -i key user@server)".to_string());
                 }
-                ssh_args = Some(rest.to_vec());
-                i = args.len();
-            }
-            arg => {
-                eprintln!("Unknown argument: {arg}");
-                eprintln!("Usage: systemdmgr [version] [--ssh [ssh-options] destination]");
-                std::process::exit(1);
+                cli.ssh_args = Some(rest.to_vec());
+                break;
             }
+            arg => return Err(format!("Unknown argument: {arg}")),
         }
         i += 1;
     }
+    if cli.json && !cli.version {
+        return Err("--json and --format are only supported with version".to_string());
+    }
+    Ok(cli)
+}
+
+/// `--format text|json`; true for JSON.
+fn cli_output_format(name: &str) -> Result<bool, String> {
+    match name {
+        "json" => Ok(true),
+        "text" => Ok(false),
+        _ => Err(format!("Invalid format '{name}' (expected text or json)")),
+    }
+}
+
+/// What `systemdmgr version` prints: `systemdmgr x.y.z`, or with `--json`
+/// `{"name":"systemdmgr","version":"x.y.z"}` for tooling.
+fn version_output(version: &str, json: bool) -> String {
+    if json {
+        serde_json::json!({ "name": "systemdmgr", "version": version }).to_string()
+    } else {
+        format!("systemdmgr {version}")
+    }
+}
+
+/// Lines printed by `systemdmgr failed`: one per failed unit, with the name
+/// column aligned.
+fn failed_unit_lines(units: &[SystemdUnit]) -> Vec<String> {
+    let failed: Vec<&SystemdUnit> = units.iter().filter(|u| u.sub == "failed").collect();
+    let width = failed.iter().map(|u| u.unit.len()).max().unwrap_or(0);
+    failed
+        .iter()
+        .map(|u| format!("{:width$}  {}", u.unit, u.description).trim_end().to_string())
+        .collect()
+}
+
+/// Exit status for `systemdmgr failed`, so scripts can test for failures.
+fn failed_exit_code(lines: &[String]) -> i32 {
+    if lines.is_empty() { 0 } else { 1 }
+}
+
+/// Runs `systemdmgr failed`. Fetch errors exit 2 to stay distinguishable
+/// from "some units failed".
+fn run_failed(runner: &dyn CommandRunner, user_mode: bool) -> i32 {
+    let mut units = Vec::new();
+    // Every concrete type; `all` would list the same units again.
+    for unit_type in UNIT_TYPES.into_iter().filter(|&t| t != UnitType::All) {
+        match fetch_units(unit_type, user_mode, true, runner) {
+            Ok(fetched) => units.extend(fetched),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                return 2;
+            }
+        }
+    }
+    let lines = failed_unit_lines(&units);
+    for line in &lines {
+        println!("{line}");
+    }
+    failed_exit_code(&lines)
+}
+
+/// Runs `systemdmgr export`: the same units the TUI lists, including the
+/// merged file state and timer/socket detail, as a JSON array.
+fn run_export(runner: &dyn CommandRunner, user_mode: bool, unit_type: UnitType) -> i32 {
+    let json = fetch_units(unit_type, user_mode, true, runner).and_then(|units| {
+        serde_json::to_string_pretty(&units).map_err(|e| format!("Failed to serialize units: {e}"))
+    });
+    match json {
+        Ok(json) => {
+            println!("{json}");
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    }
+}
+
+/// Colors are on unless `--no-color` is given or `NO_COLOR` is set to a
+/// non-empty value (https://no-color.org).
+fn color_enabled(no_color_flag: bool, no_color_env: Option<std::ffi::OsString>) -> bool {
+    !no_color_flag && no_color_env.is_none_or(|v| v.is_empty())
+}
+
+fn cli_unit_type(name: &str) -> Result<UnitType, String> {
+    parse_unit_type(name).ok_or_else(|| {
+        format!("Invalid unit type '{name}' (expected service, timer, socket, target, path, or all)")
+    })
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cli = match parse_args(&args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{USAGE}");
+            std::process::exit(1);
+        }
+    };
+    if cli.version {
+        println!("{}", version_output(env!("CARGO_PKG_VERSION"), cli.json));
+        return Ok(());
+    }
+    let ssh_args = cli.ssh_args;
 
     let (runner, host_label): (Arc<dyn CommandRunner>, Option<String>) = if let Some(ssh_args) = ssh_args {
         let label =
@@ -87,23 +254,84 @@ fn main() -> io::Result<()> {
         }
     }
 
+    if let Some(subcommand) = &cli.subcommand {
+        let code = match subcommand {
+            Subcommand::Failed => run_failed(runner.as_ref(), cli.user_mode),
+            Subcommand::Export => run_export(
+                runner.as_ref(),
+                cli.user_mode,
+                cli.unit_type.unwrap_or(UnitType::Service),
+            ),
+        };
+        // exit() skips destructors; drop first so an SSH master is torn down.
+        drop(runner);
+        std::process::exit(code);
+    }
+
     // Setup terminal with mouse capture
     enable_raw_mode()?;
     execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout());
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(runner, host_label);
+    let mut app = App::new(
+        runner,
+        host_label,
+        cli.user_mode,
+        cli.unit_type.unwrap_or(UnitType::Service),
+    );
+    app.color_enabled = color_enabled(cli.no_color, std::env::var_os("NO_COLOR"));
+    app.no_confirm = cli.no_confirm;
+    let mut theme = Theme::default();
+    if let Some(path) = Config::default_path() {
+        match Config::load(&path) {
+            Ok(config) => {
+                app.live_tail_interval = config.live_tail_interval();
+                app.favorites = config.favorites.into_iter().collect();
+                app.row_severity_colors = config.row_severity_colors;
+                app.wrap_navigation = config.wrap_navigation;
+                app.hide_noise = config.hide_noise;
+                app.no_confirm |= config.no_confirm;
+                app.confirm_on_quit = config.confirm_on_quit;
+                app.no_block = config.no_block;
+                app.bell_on_failure = config.bell_on_failure;
+                app.desktop_notifications = config.desktop_notifications && notify::AVAILABLE;
+                if config.desktop_notifications && !notify::AVAILABLE {
+                    app.status_message = Some(notify::UNAVAILABLE_WARNING.to_string());
+                }
+                app.log_presets = config.log_presets;
+                theme = config.theme;
+                app.config_path = Some(path);
+                app.update_filter();
+            }
+            // Leave config_path unset so a broken file is never overwritten.
+            Err(e) => app.status_message = Some(e),
+        }
+    }
+    theme::init(if app.color_enabled { theme } else { Theme::monochrome() });
     let mut last_live_tail_refresh = Instant::now();
     let mut last_live_indicator_blink = Instant::now();
     let mut live_indicator_on = true;
     let mut was_actively_tailing = false;
+    let mut last_auto_refresh = Instant::now();
+    let started = Instant::now();
 
     loop {
         app.check_action_progress();
+        if std::mem::take(&mut app.bell_pending) {
+            let mut out = stdout();
+            let _ = out.write_all(b"\x07").and_then(|()| out.flush());
+        }
+        let failed = std::mem::take(&mut app.notify_pending);
+        if !failed.is_empty() {
+            notify::notify_failed_units(&failed, app.host_label());
+        }
         app.check_log_refresh_progress();
-        let live_mode = !app.log_paused && app.show_logs;
-        let actively_tailing = live_mode && app.logs_at_bottom;
+        app.check_log_load_progress();
+        app.check_older_logs_progress();
+        app.check_journal_count_progress();
+        // A locked view keeps fetching; only the auto-scroll stops.
+        let actively_tailing = !app.log_paused && app.show_logs;
 
         if actively_tailing && !was_actively_tailing {
             live_indicator_on = true;
@@ -112,37 +340,68 @@ fn main() -> io::Result<()> {
         }
 
         if actively_tailing {
-            while last_live_indicator_blink.elapsed() >= LIVE_TAIL_REFRESH_INTERVAL {
-                live_indicator_on = !live_indicator_on;
-                last_live_indicator_blink += LIVE_TAIL_REFRESH_INTERVAL;
-            }
+            let (on, consumed) = app::advance_blink(
+                live_indicator_on,
+                last_live_indicator_blink.elapsed(),
+                app::LIVE_INDICATOR_BLINK_INTERVAL,
+            );
+            live_indicator_on = on;
+            last_live_indicator_blink += consumed;
 
-            if last_live_tail_refresh.elapsed() >= LIVE_TAIL_REFRESH_INTERVAL {
+            if last_live_tail_refresh.elapsed() >= app.live_tail_interval {
                 app.refresh_logs();
-                while last_live_tail_refresh.elapsed() >= LIVE_TAIL_REFRESH_INTERVAL {
-                    last_live_tail_refresh += LIVE_TAIL_REFRESH_INTERVAL;
+                while last_live_tail_refresh.elapsed() >= app.live_tail_interval {
+                    last_live_tail_refresh += app.live_tail_interval;
                 }
             }
         }
         was_actively_tailing = actively_tailing;
 
-        terminal.draw(|frame| ui::render(frame, &mut app, live_indicator_on))?;
+        if app::auto_refresh_due(last_auto_refresh.elapsed(), app.auto_refresh_interval) {
+            app.start_background_refresh();
+            last_auto_refresh = Instant::now();
+        }
 
-        let mut poll_timeout =
-            if app.action_in_progress || app.refresh_in_flight() || app.log_refresh_in_flight() {
-                Duration::from_millis(100)
-            } else {
-                Duration::from_secs(60)
-            };
+        let spinner = ui::spinner_frame(started.elapsed());
+        terminal.draw(|frame| ui::render(frame, &mut app, live_indicator_on, spinner))?;
+
+        let mut poll_timeout = if app.action_in_progress
+            || app.refresh_in_flight()
+            || app.log_refresh_in_flight()
+            || app.logs_loading()
+            || app.older_logs_loading()
+            || app.details_loading()
+            || app.journal_count_in_flight()
+            || app.active_timestamps_loading()
+            || app.confirm_warning_loading()
+            || app.security_loading()
+            || app.dependency_filter_loading()
+            || app.reverse_deps_loading()
+            || app.timer_target_loading()
+            || app.raw_properties_loading()
+            || app.unit_file_loading()
+        {
+            Duration::from_millis(100)
+        } else {
+            Duration::from_secs(60)
+        };
 
         if actively_tailing {
             let refresh_wait =
-                LIVE_TAIL_REFRESH_INTERVAL.saturating_sub(last_live_tail_refresh.elapsed());
+                app.live_tail_interval.saturating_sub(last_live_tail_refresh.elapsed());
             let blink_wait =
-                LIVE_TAIL_REFRESH_INTERVAL.saturating_sub(last_live_indicator_blink.elapsed());
+                app::LIVE_INDICATOR_BLINK_INTERVAL.saturating_sub(last_live_indicator_blink.elapsed());
             poll_timeout = poll_timeout.min(refresh_wait.min(blink_wait));
         }
 
+        if (!app.show_logs || app.split_view) && (app.unit_type == UnitType::Timer || app.show_active_since) {
+            poll_timeout = poll_timeout.min(RELATIVE_TIME_TICK);
+        }
+
+        if let Some(interval) = app.auto_refresh_interval {
+            poll_timeout = poll_timeout.min(interval.saturating_sub(last_auto_refresh.elapsed()));
+        }
+
         if !event::poll(poll_timeout)? {
             continue;
         }
@@ -154,20 +413,39 @@ fn main() -> io::Result<()> {
                 && !app.show_status_picker && !app.show_type_picker
                 && !app.show_priority_picker && !app.show_time_picker
                 && !app.show_file_state_picker && !app.show_confirm
+                && !app.show_timestamp_format_picker && !app.show_custom_time_input
+                && !app.show_grep_input && !app.pending_quit_confirm
+                && !app.show_preset_picker && !app.show_preset_name_input
+                && !app.show_export_picker && !app.show_log_fields && !app.show_reverse_deps
+                && !app.show_action_output && !app.show_security && !app.detail_search_mode
+                && !app.help_search_mode
             {
                 app.toggle_help();
                 continue;
             }
 
-            // Help overlay: scroll with arrows/PgUp/PgDn/g/G; close with Esc/q
+            // Help overlay: scroll with arrows/j/k/PgUp/PgDn/g/G, filter
+            // with /; close with Esc/q
+            if app.show_help && app.help_search_mode {
+                match key.code {
+                    KeyCode::Esc => app.clear_help_search(),
+                    KeyCode::Enter => app.help_search_mode = false,
+                    KeyCode::Backspace => app.help_search_pop(),
+                    KeyCode::Char(c) => app.help_search_push(c),
+                    _ => {}
+                }
+                continue;
+            }
             if app.show_help {
                 let viewport = app.help_viewport_lines.max(1);
                 match key.code {
+                    KeyCode::Esc if !app.help_search_query.is_empty() => app.clear_help_search(),
                     KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
                         app.show_help = false;
                     }
-                    KeyCode::Down => app.help_scroll_down(1),
-                    KeyCode::Up => app.help_scroll_up(1),
+                    KeyCode::Char('/') => app.help_search_mode = true,
+                    KeyCode::Down | KeyCode::Char('j') => app.help_scroll_down(1),
+                    KeyCode::Up | KeyCode::Char('k') => app.help_scroll_up(1),
                     KeyCode::PageDown | KeyCode::Char(' ') => {
                         app.help_scroll_down(viewport)
                     }
@@ -209,6 +487,7 @@ fn main() -> io::Result<()> {
                     KeyCode::Esc | KeyCode::Char('p') => app.close_priority_picker(),
                     KeyCode::Down => app.priority_picker_next(),
                     KeyCode::Up => app.priority_picker_previous(),
+                    KeyCode::Char(' ') => app.priority_picker_toggle_anchor(),
                     KeyCode::Enter => app.priority_picker_confirm(),
                     _ => {}
                 }
@@ -227,6 +506,34 @@ fn main() -> io::Result<()> {
                 continue;
             }
 
+            // Custom time range input modal
+            if app.show_custom_time_input {
+                match key.code {
+                    KeyCode::Esc => app.close_custom_time_input(),
+                    KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                        app.custom_time_input_toggle_field()
+                    }
+                    KeyCode::Enter => app.custom_time_input_confirm(),
+                    KeyCode::Backspace => app.custom_time_input_pop(),
+                    KeyCode::Char(c) => app.custom_time_input_push(c),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // journalctl --grep input modal
+            if app.show_grep_input {
+                match key.code {
+                    KeyCode::Esc => app.close_grep_input(),
+                    KeyCode::Tab => app.grep_input_toggle_case(),
+                    KeyCode::Enter => app.grep_input_confirm(),
+                    KeyCode::Backspace => app.grep_input_pop(),
+                    KeyCode::Char(c) => app.grep_input_push(c),
+                    _ => {}
+                }
+                continue;
+            }
+
             // File state picker modal
             if app.show_file_state_picker {
                 match key.code {
@@ -239,6 +546,111 @@ fn main() -> io::Result<()> {
                 continue;
             }
 
+            // Timestamp format picker modal
+            if app.show_timestamp_format_picker {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('d') => app.close_timestamp_format_picker(),
+                    KeyCode::Down => app.timestamp_format_picker_next(),
+                    KeyCode::Up => app.timestamp_format_picker_previous(),
+                    KeyCode::Enter => app.timestamp_format_picker_confirm(),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Log filter presets picker
+            if app.show_preset_picker {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('m') => app.close_preset_picker(),
+                    KeyCode::Down => app.preset_picker_next(),
+                    KeyCode::Up => app.preset_picker_previous(),
+                    KeyCode::Enter => app.preset_picker_confirm(),
+                    KeyCode::Char('d') | KeyCode::Delete => app.preset_picker_delete(),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Journal fields popup
+            if app.show_log_fields {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('i' | 'q') => app.close_log_fields(),
+                    KeyCode::Down | KeyCode::Char('j') => app.log_fields_scroll_down(1),
+                    KeyCode::Up | KeyCode::Char('k') => app.log_fields_scroll_up(1),
+                    KeyCode::PageDown => app.log_fields_scroll_down(10),
+                    KeyCode::PageUp => app.log_fields_scroll_up(10),
+                    KeyCode::Char('g') | KeyCode::Home => app.log_fields_scroll = 0,
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Security analysis popup
+            if app.show_security {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('a' | 'q') => app.close_security_analysis(),
+                    KeyCode::Down | KeyCode::Char('j') => app.security_scroll_down(1),
+                    KeyCode::Up | KeyCode::Char('k') => app.security_scroll_up(1),
+                    KeyCode::PageDown => app.security_scroll_down(10),
+                    KeyCode::PageUp => app.security_scroll_up(10),
+                    KeyCode::Char('g') | KeyCode::Home => app.security_scroll = 0,
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Last action output popup
+            if app.show_action_output {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('O' | 'q') => app.close_action_output(),
+                    KeyCode::Down | KeyCode::Char('j') => app.action_output_scroll_down(1),
+                    KeyCode::Up | KeyCode::Char('k') => app.action_output_scroll_up(1),
+                    KeyCode::PageDown => app.action_output_scroll_down(10),
+                    KeyCode::PageUp => app.action_output_scroll_up(10),
+                    KeyCode::Char('g') | KeyCode::Home => app.action_output_scroll = 0,
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Reverse dependencies popup
+            if app.show_reverse_deps {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('w' | 'q') => app.close_reverse_dependencies(),
+                    KeyCode::Down | KeyCode::Char('j') => app.reverse_deps_scroll_down(1),
+                    KeyCode::Up | KeyCode::Char('k') => app.reverse_deps_scroll_up(1),
+                    KeyCode::PageDown => app.reverse_deps_scroll_down(10),
+                    KeyCode::PageUp => app.reverse_deps_scroll_up(10),
+                    KeyCode::Char('g') | KeyCode::Home => app.reverse_deps_scroll = 0,
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Log export format picker
+            if app.show_export_picker {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('E') => app.close_export_picker(),
+                    KeyCode::Down => app.export_picker_next(),
+                    KeyCode::Up => app.export_picker_previous(),
+                    KeyCode::Enter => app.export_picker_confirm(),
+                    _ => {}
+                }
+                continue;
+            }
+
+            // Preset name prompt
+            if app.show_preset_name_input {
+                match key.code {
+                    KeyCode::Esc => app.close_preset_name_input(),
+                    KeyCode::Enter => app.preset_name_input_confirm(),
+                    KeyCode::Backspace => app.preset_name_input_pop(),
+                    KeyCode::Char(c) => app.preset_name_input_push(c),
+                    _ => {}
+                }
+                continue;
+            }
+
             // Action picker modal
             if app.show_action_picker {
                 match key.code {
@@ -257,16 +669,60 @@ fn main() -> io::Result<()> {
                 continue;
             }
 
+            // Quit prompt while an action is still running
+            if app.pending_quit_confirm {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => app.answer_quit_confirm(true),
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        app.answer_quit_confirm(false)
+                    }
+                    _ => {}
+                }
+                if app.should_quit {
+                    break;
+                }
+                continue;
+            }
+
             // Confirmation dialog modal
             if app.show_confirm {
-                if app.action_in_progress {
-                    // Ignore input while action is executing
+                // Bulk results scroll both while running and once done.
+                if !app.batch_results.is_empty()
+                    && matches!(key.code, KeyCode::Up | KeyCode::Down | KeyCode::Char('j' | 'k'))
+                {
+                    if matches!(key.code, KeyCode::Up | KeyCode::Char('k')) {
+                        app.batch_scroll_up(1);
+                    } else {
+                        app.batch_scroll_down(1);
+                    }
+                } else if app.action_in_progress {
+                    // Only quitting is possible while the action executes
+                    if key.code == KeyCode::Char('q') {
+                        app.request_quit();
+                    }
                 } else if app.action_result.is_some() {
-                    // Result showing — any key dismisses
-                    app.dismiss_action_result();
+                    // Result showing — `o` expands the output, `p`/`s`
+                    // retry a permission failure through pkexec/sudo, any
+                    // other key dismisses
+                    let escalation = match key.code {
+                        KeyCode::Char('p') => Some(Escalation::Pkexec),
+                        KeyCode::Char('s') => Some(Escalation::Sudo),
+                        _ => None,
+                    };
+                    if let Some(escalation) = escalation
+                        && let Some(command) = app.privileged_retry_command(escalation)
+                    {
+                        retry_privileged(&mut terminal, &mut app, escalation, &command);
+                    } else {
+                        app.dismiss_action_result();
+                        if key.code == KeyCode::Char('o') {
+                            app.open_action_output();
+                        }
+                    }
                 } else {
                     match key.code {
-                        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_yes(),
+                        KeyCode::Char(c @ ('y' | 'Y')) => app.confirm_key(c),
+                        KeyCode::Char('b') => app.toggle_no_block(),
                         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.confirm_no(),
                         _ => {}
                     }
@@ -276,16 +732,44 @@ fn main() -> io::Result<()> {
 
             // Details modal
             if app.show_details {
-                let visible = ui::get_details_visible_lines(&terminal.get_frame());
+                let visible = ui::get_details_visible_lines(terminal.get_frame().area());
                 let content_height = app.detail_content_height;
+                if app.detail_search_mode {
+                    match key.code {
+                        KeyCode::Esc => app.clear_detail_search(),
+                        KeyCode::Enter => app.detail_search_mode = false,
+                        KeyCode::Backspace => {
+                            app.detail_search_query.pop();
+                            app.update_detail_search();
+                        }
+                        KeyCode::Char(c) => {
+                            app.detail_search_query.push(c);
+                            app.update_detail_search();
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                let half_page = (visible / 2).max(1);
                 match key.code {
+                    KeyCode::Esc if !app.detail_search_query.is_empty() => app.clear_detail_search(),
                     KeyCode::Esc | KeyCode::Char('i') | KeyCode::Enter => app.close_details(),
+                    KeyCode::Char('/') => app.detail_search_mode = true,
+                    KeyCode::Char('w') => app.export_raw_properties(),
+                    KeyCode::Char('n') => app.next_detail_match(visible),
+                    KeyCode::Char('N') => app.prev_detail_match(visible),
                     KeyCode::Down => app.detail_scroll_down(1, content_height, visible),
                     KeyCode::Up => app.detail_scroll_up(1),
                     KeyCode::Char('g') | KeyCode::Home => { app.detail_scroll = 0; }
                     KeyCode::Char('G') | KeyCode::End => app.detail_scroll_down(usize::MAX, content_height, visible),
-                    KeyCode::PageDown => app.detail_scroll_down(10, content_height, visible),
-                    KeyCode::PageUp => app.detail_scroll_up(10),
+                    KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.detail_scroll_down(half_page, content_height, visible)
+                    }
+                    KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.detail_scroll_up(half_page)
+                    }
+                    KeyCode::PageDown => app.detail_scroll_down(visible, content_height, visible),
+                    KeyCode::PageUp => app.detail_scroll_up(visible),
                     _ => {}
                 }
                 continue;
@@ -294,7 +778,7 @@ fn main() -> io::Result<()> {
             // Calculate visible lines for scrolling
             let visible_lines = ui::get_logs_visible_lines(&terminal.get_frame(), app.show_logs);
             let visible_unit_file_lines = ui::get_unit_file_visible_lines(&terminal.get_frame(), app.show_unit_file);
-            let visible_services = ui::get_services_visible_lines(&terminal.get_frame(), app.show_logs);
+            let visible_services = ui::get_services_visible_lines(&terminal.get_frame(), app.show_logs && !app.split_view);
 
             if app.search_mode {
                 // Branch 1: Service search mode (only reachable when show_logs=false)
@@ -324,6 +808,20 @@ fn main() -> io::Result<()> {
                     }
                     _ => {}
                 }
+            } else if app.jump_mode {
+                // Branch 1b: Go-to-unit prompt (moves the selection, never filters)
+                match key.code {
+                    KeyCode::Esc | KeyCode::Enter => {
+                        app.close_jump();
+                    }
+                    KeyCode::Backspace => {
+                        app.jump_query_pop();
+                    }
+                    KeyCode::Char(c) => {
+                        app.jump_query_push(c);
+                    }
+                    _ => {}
+                }
             } else if app.unit_file_search_mode {
                 // Branch 2a: Unit file search typing mode
                 match key.code {
@@ -359,6 +857,26 @@ fn main() -> io::Result<()> {
                     KeyCode::Char('/') => {
                         app.unit_file_search_mode = true;
                     }
+                    KeyCode::Char('e') => {
+                        if let Some(name) = app.unit_file_unit_name.clone() {
+                            let args = service::unit_edit_args(&name, app.user_mode);
+                            let mut cmd = app.runner.interactive_command("systemctl", &args);
+                            match suspend_and_run(&mut terminal, &mut cmd) {
+                                Ok(status) if !status.success() => {
+                                    app.status_message =
+                                        Some(format!("systemctl edit {name} exited with {status}"));
+                                }
+                                Ok(_) => {
+                                    app.status_message = Some(format!("Edited {name}"));
+                                }
+                                Err(e) => {
+                                    app.status_message =
+                                        Some(format!("Failed to run systemctl edit: {e}"));
+                                }
+                            }
+                            app.reload_unit_file();
+                        }
+                    }
                     KeyCode::Char('n') => {
                         app.next_unit_file_match(visible_unit_file_lines);
                     }
@@ -401,6 +919,9 @@ fn main() -> io::Result<()> {
                         app.log_search_query.pop();
                         app.update_log_search();
                     }
+                    KeyCode::Tab => {
+                        app.toggle_log_search_whole_word();
+                    }
                     KeyCode::PageUp => {
                         app.scroll_logs_up(visible_lines);
                     }
@@ -413,16 +934,24 @@ fn main() -> io::Result<()> {
                     }
                     _ => {}
                 }
-            } else if app.show_logs {
+            } else if app.logs_focused() {
                 // Branch 3: Log focus normal mode
                 match key.code {
                     KeyCode::Char('l') => {
                         app.clear_log_search();
                         app.toggle_logs();
                     }
+                    KeyCode::Char('|') => {
+                        app.toggle_split_view();
+                    }
+                    KeyCode::Tab => {
+                        app.focus_next_pane();
+                    }
                     KeyCode::Esc | KeyCode::Char('q') => {
                         if !app.log_search_query.is_empty() {
                             app.clear_log_search();
+                        } else if app.split_view {
+                            app.focused_pane = Pane::List;
                         } else if app.navigated_from_system_logs {
                             // Return to global system logs
                             app.navigated_from_system_logs = false;
@@ -437,6 +966,7 @@ fn main() -> io::Result<()> {
                         } else {
                             app.log_paused = false;
                             app.show_logs = false;
+                            app.split_view = false;
                             app.system_logs_mode = false;
                         }
                     }
@@ -460,9 +990,13 @@ fn main() -> io::Result<()> {
                         if app.log_selected_entry.is_some() {
                             app.log_select_previous();
                         } else {
+                            if app.logs_scroll == 0 {
+                                app.load_older_logs();
+                            }
                             app.scroll_logs_up(1);
                         }
                     }
+                    #[allow(clippy::collapsible_match)]
                     KeyCode::Enter => {
                         if app.log_selected_entry.is_some() && app.system_logs_mode {
                             app.navigate_to_log_unit();
@@ -475,6 +1009,9 @@ fn main() -> io::Result<()> {
                         app.logs_go_to_bottom();
                     }
                     KeyCode::PageUp => {
+                        if app.logs_scroll == 0 {
+                            app.load_older_logs();
+                        }
                         app.scroll_logs_up(visible_lines);
                     }
                     KeyCode::PageDown => {
@@ -492,57 +1029,201 @@ fn main() -> io::Result<()> {
                     KeyCode::Char('t') => {
                         app.open_time_picker();
                     }
+                    KeyCode::Char('m') => {
+                        app.open_preset_picker();
+                    }
+                    KeyCode::Char('d') => {
+                        app.open_timestamp_format_picker();
+                    }
+                    KeyCode::Char('E') => {
+                        app.open_export_picker();
+                    }
+                    KeyCode::Char('h') => {
+                        app.toggle_log_hostname();
+                    }
+                    KeyCode::Char('i') => {
+                        app.open_log_fields();
+                    }
+                    KeyCode::Char('B') => {
+                        app.toggle_log_boot_indices();
+                    }
+                    KeyCode::Char(']') => {
+                        app.next_boot_boundary();
+                    }
+                    KeyCode::Char('[') => {
+                        app.prev_boot_boundary();
+                    }
+                    KeyCode::Char('}') => {
+                        app.next_error();
+                    }
+                    KeyCode::Char('D') => {
+                        app.cycle_dim_below_priority();
+                    }
+                    KeyCode::Char('{') => {
+                        app.prev_error();
+                    }
+                    KeyCode::Char('z') => {
+                        app.toggle_utc();
+                    }
+                    KeyCode::Char('u') => {
+                        app.toggle_log_timestamp_micros();
+                    }
+                    KeyCode::Char('F') => {
+                        app.open_grep_input();
+                    }
+                    KeyCode::Char('P') if !app.system_logs_mode => {
+                        app.toggle_filter_main_pid();
+                    }
+                    KeyCode::Char('+') => {
+                        app.increase_log_line_limit();
+                    }
+                    KeyCode::Char('-') => {
+                        app.decrease_log_line_limit();
+                    }
                     KeyCode::Char('x') => {
                         app.open_action_picker();
                     }
                     KeyCode::Char('L') => {
                         app.toggle_system_logs();
                     }
+                    KeyCode::Char('K') => {
+                        app.toggle_kernel_logs();
+                    }
+                    KeyCode::Char('w') => {
+                        app.toggle_log_wrap();
+                    }
+                    KeyCode::Char('C') => {
+                        app.toggle_collapse_log_repeats();
+                    }
+                    KeyCode::Char('V') => {
+                        app.open_vacuum_confirm();
+                    }
+                    KeyCode::Char('#') => {
+                        app.start_journal_match_count();
+                    }
+                    KeyCode::Char('r') => {
+                        app.force_reload_logs();
+                    }
+                    KeyCode::Char('o') => {
+                        open_journal_pager(&mut terminal, &mut app);
+                    }
+                    KeyCode::Left => {
+                        app.scroll_logs_left(LOG_H_SCROLL_STEP);
+                    }
+                    KeyCode::Right => {
+                        app.scroll_logs_right(LOG_H_SCROLL_STEP);
+                    }
                     KeyCode::Char('f') => {
-                        app.toggle_log_paused(visible_lines);
+                        app.toggle_log_follow(visible_lines);
                         if !app.log_paused {
                             app.refresh_logs();
                         }
                     }
+                    KeyCode::Char('s') => {
+                        app.toggle_log_scroll_lock();
+                    }
+                    KeyCode::Char('A') => {
+                        app.cycle_live_tail_interval();
+                    }
                     _ => {}
                 }
             } else {
                 // Branch 4: Service normal mode
                 app.clear_status_message();
+                // A count prefix survives only into the next key: digits
+                // extend it, anything else consumes or discards it.
+                let count = match key.code {
+                    KeyCode::Char(c @ '1'..='9') => {
+                        app.push_count_digit(c.to_digit(10).unwrap_or(0));
+                        continue;
+                    }
+                    KeyCode::Char('0') if app.pending_count.is_some() => {
+                        app.push_count_digit(0);
+                        continue;
+                    }
+                    _ => app.pending_count.take(),
+                };
                 match key.code {
                     KeyCode::Char('q') => {
-                        app.should_quit = true;
+                        app.request_quit();
+                    }
+                    KeyCode::Tab if app.split_view => {
+                        app.focus_next_pane();
+                    }
+                    KeyCode::Right | KeyCode::Tab => {
+                        app.next_unit_type();
+                    }
+                    KeyCode::Left | KeyCode::BackTab => {
+                        app.prev_unit_type();
                     }
                     KeyCode::Char('l') => {
                         app.toggle_logs();
                     }
+                    KeyCode::Char('|') => {
+                        app.toggle_split_view();
+                    }
                     KeyCode::Char('L') => {
                         app.toggle_system_logs();
                     }
+                    KeyCode::Char('K') => {
+                        app.toggle_kernel_logs();
+                    }
                     KeyCode::Esc => {
                         if !app.search_query.is_empty() {
                             app.clear_search();
+                        } else if app.dependency_filter.is_some() {
+                            app.clear_dependency_filter();
+                        } else if !app.selected_units.is_empty() {
+                            app.clear_unit_selection();
                         } else {
-                            app.should_quit = true;
+                            app.request_quit();
                         }
                     }
+                    KeyCode::Char(' ') => {
+                        app.toggle_unit_selection();
+                    }
+                    KeyCode::Char('d') => {
+                        app.toggle_dependency_filter();
+                    }
+                    KeyCode::Char('w') => {
+                        app.open_reverse_dependencies();
+                    }
+                    KeyCode::Char('O') => {
+                        app.open_action_output();
+                    }
+                    KeyCode::Char('a') => {
+                        app.open_security_analysis();
+                    }
+                    KeyCode::Char('!') => {
+                        app.toggle_filter_inverted();
+                    }
+                    KeyCode::Char('b') => {
+                        app.toggle_favorite();
+                    }
+                    KeyCode::Char('A') => {
+                        app.cycle_auto_refresh();
+                    }
                     KeyCode::Char('/') => {
                         app.search_mode = true;
                     }
-                    KeyCode::Down => {
-                        app.next();
+                    KeyCode::Char('J') => {
+                        app.open_jump();
                     }
-                    KeyCode::Up => {
-                        app.previous();
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.next_by(count.unwrap_or(1));
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        app.previous_by(count.unwrap_or(1));
                     }
                     KeyCode::Char('g') | KeyCode::Home => {
                         app.go_to_top();
                     }
-                    KeyCode::Char('G') | KeyCode::End => {
-                        app.go_to_bottom();
-                    }
+                    KeyCode::Char('G') | KeyCode::End => match count {
+                        Some(line) => app.go_to_line(line),
+                        None => app.go_to_bottom(),
+                    },
                     KeyCode::Char('r') => {
-                        app.load_services();
+                        app.refresh_units();
                         if app.error.is_none() {
                             let ts = app
                                 .last_refreshed
@@ -578,6 +1259,30 @@ fn main() -> io::Result<()> {
                     KeyCode::Char('x') => {
                         app.open_action_picker();
                     }
+                    KeyCode::Char('z') => {
+                        app.open_undo_confirm();
+                    }
+                    KeyCode::Char('S') => {
+                        app.open_timer_target_logs();
+                    }
+                    KeyCode::Char('F') => {
+                        app.open_restart_failed_confirm();
+                    }
+                    KeyCode::Char('o') => {
+                        open_journal_pager(&mut terminal, &mut app);
+                    }
+                    KeyCode::Char('U') => {
+                        app.toggle_active_since_column();
+                    }
+                    KeyCode::Char('D') => {
+                        app.toggle_show_state_changes();
+                    }
+                    KeyCode::Char('H') => {
+                        app.toggle_hide_noise();
+                    }
+                    KeyCode::Char('I') => {
+                        app.toggle_show_all_units();
+                    }
                     KeyCode::Char('R') => {
                         app.confirm_action = Some(service::UnitAction::DaemonReload);
                         app.confirm_unit_name = Some(String::new());
@@ -617,84 +1322,217 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// Hands the terminal to `cmd` (an editor, usually) and takes it back
+/// afterwards: leaves raw mode, the alternate screen and mouse capture for the
+/// child, then restores all three and forces a full redraw.
+fn suspend_and_run(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    cmd: &mut std::process::Command,
+) -> io::Result<std::process::ExitStatus> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    let status = cmd.status();
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    status
+}
+
+/// Retries a failed action through sudo or pkexec attached to the terminal,
+/// so either can ask for a password, then shows the outcome in the dialog.
+fn retry_privileged(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    escalation: Escalation,
+    command: &[String],
+) {
+    let args: Vec<&str> = command[1..].iter().map(String::as_str).collect();
+    let mut cmd = app.runner.interactive_command(escalation.program(), &args);
+    let outcome = match suspend_and_run(terminal, &mut cmd) {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("{} exited with {status}", command.join(" "))),
+        Err(e) => Err(service::spawn_error(escalation.program(), &e)),
+    };
+    app.finish_privileged_retry(escalation, outcome);
+}
+
+/// Shows the current logs in journalctl's own pager (`journalctl -e` with
+/// the in-app filters), suspending the TUI until it exits.
+fn open_journal_pager(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) {
+    let Some(args) = app.journal_pager_args() else {
+        return;
+    };
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let mut cmd = app.runner.interactive_command("journalctl", &args);
+    match suspend_and_run(terminal, &mut cmd) {
+        Ok(status) if !status.success() => {
+            app.status_message = Some(format!("journalctl exited with {status}"));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            app.status_message = Some(service::spawn_error("journalctl", &e));
+        }
+    }
+}
+
 fn handle_mouse_event(app: &mut App, mouse: MouseEvent, frame_size: Rect) {
+    let now = Instant::now();
+    let is_list_double_click = mouse.kind == MouseEventKind::Down(MouseButton::Left)
+        && app.last_list_click.is_some_and(|(at, column, row)| {
+            is_double_click(at, now, (column, row) == (mouse.column, mouse.row))
+        });
+
+    // Clicking the highlighted unit opens its details, so the second click of
+    // a double-click on it lands on the modal; swap the details for the logs.
+    if is_list_double_click && app.show_details && !app.show_logs {
+        app.last_list_click = None;
+        app.close_details();
+        app.toggle_logs();
+        return;
+    }
+
+    // The wheel scrolls whichever modal is open; clicks are still ignored.
+    let wheel_up = match mouse.kind {
+        MouseEventKind::ScrollUp => Some(true),
+        MouseEventKind::ScrollDown => Some(false),
+        _ => None,
+    };
+    if let Some(up) = wheel_up
+        && app.scroll_modal(up, ui::get_details_visible_lines(frame_size))
+    {
+        return;
+    }
+
+    // A click on a picker row picks it; anywhere else leaves the picker open.
+    if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+        && !app.show_help
+        && let Some(picker) = app.open_picker()
+    {
+        let rect = ui::picker_rect(picker, app, frame_size);
+        let offset = app.picker_state(picker).offset();
+        let len = app.picker_len(picker);
+        if let Some(index) = ui::picker_row_at(rect, offset, len, mouse.column, mouse.row) {
+            app.pick_picker_row(picker, index);
+        }
+        return;
+    }
+
     // Don't handle mouse events when help or modal is shown
     if app.show_help || app.show_status_picker || app.show_type_picker
         || app.show_priority_picker || app.show_time_picker
         || app.show_details || app.show_file_state_picker
         || app.show_action_picker || app.show_confirm
-        || app.show_unit_file
+        || app.show_unit_file || app.show_timestamp_format_picker
+        || app.show_custom_time_input || app.show_grep_input
+        || app.show_preset_picker || app.show_preset_name_input
+        || app.show_export_picker || app.show_log_fields || app.show_reverse_deps
+        || app.show_action_output || app.show_security || app.pending_quit_confirm
     {
         return;
     }
 
-    let regions = ui::get_layout_regions(frame_size, app.show_logs);
+    let regions = ui::get_layout_regions(frame_size, app.show_logs, app.split_view);
 
-    if app.show_logs {
-        if let Some(logs_panel) = regions.logs_panel {
-            match mouse.kind {
-                MouseEventKind::ScrollUp => {
-                    if mouse_in_rect(mouse, logs_panel) {
-                        app.scroll_logs_up(3);
-                    }
-                }
-                MouseEventKind::ScrollDown => {
-                    if mouse_in_rect(mouse, logs_panel) {
-                        app.scroll_logs_down(3);
-                    }
+    // In the split view each pane takes the events over it.
+    match regions.logs_panel {
+        Some(logs_panel) if !app.split_view || mouse_in_rect(mouse, logs_panel) => {
+            handle_logs_mouse_event(app, mouse, logs_panel);
+        }
+        _ if !app.show_logs || app.split_view => {
+            handle_list_mouse_event(app, mouse, regions.services_list, now, is_list_double_click);
+        }
+        _ => {}
+    }
+}
+
+#[allow(clippy::collapsible_match)]
+fn handle_logs_mouse_event(app: &mut App, mouse: MouseEvent, logs_panel: Rect) {
+    match mouse.kind {
+        MouseEventKind::ScrollUp => {
+            if mouse_in_rect(mouse, logs_panel) {
+                app.scroll_logs_up(MOUSE_SCROLL_LINES);
+            }
+        }
+        MouseEventKind::ScrollDown => {
+            if mouse_in_rect(mouse, logs_panel) {
+                app.scroll_logs_down(MOUSE_SCROLL_LINES);
+            }
+        }
+        MouseEventKind::Down(MouseButton::Left) => {
+            if mouse_in_rect(mouse, logs_panel) {
+                if app.split_view {
+                    app.focused_pane = Pane::Logs;
                 }
-                MouseEventKind::Down(MouseButton::Left) => {
-                    if mouse_in_rect(mouse, logs_panel) {
-                        // +1 for the border top row
-                        let y_in_panel = mouse.row.saturating_sub(logs_panel.y + 1) as usize;
-                        if let Some(entry_idx) = ui::log_entry_at_y(app, y_in_panel) {
-                            if app.log_selected_entry == Some(entry_idx) && app.system_logs_mode {
-                                // Re-click on selected entry → navigate
-                                app.navigate_to_log_unit();
-                            } else {
-                                // First click → pause and highlight
-                                app.log_paused = true;
-                                app.log_selected_entry = Some(entry_idx);
-                            }
-                        }
+                // +1 for the border top row
+                let y_in_panel = mouse.row.saturating_sub(logs_panel.y + 1) as usize;
+                if let Some(entry_idx) = ui::log_entry_at_row(&app.cached_entry_heights, app.logs_scroll, y_in_panel) {
+                    if app.log_selected_entry == Some(entry_idx) && app.system_logs_mode {
+                        // Re-click on selected entry → navigate
+                        app.navigate_to_log_unit();
+                    } else {
+                        // First click → pause and highlight
+                        app.log_paused = true;
+                        app.log_selected_entry = Some(entry_idx);
                     }
                 }
-                _ => {}
             }
         }
-    } else {
-        // Service mode: existing behavior
-        match mouse.kind {
-            MouseEventKind::Down(MouseButton::Left) => {
-                if mouse_in_rect(mouse, regions.services_list) {
-                    app.clear_status_message();
-                    let y_in_list = mouse.row.saturating_sub(regions.services_list.y + 1);
-                    let clicked_index = app.list_state.offset() + y_in_list as usize;
-                    if clicked_index < app.filtered_indices.len() {
-                        if app.list_state.selected() == Some(clicked_index) {
-                            // Re-click on selected entry → open details
-                            app.open_details();
-                        } else {
-                            app.list_state.select(Some(clicked_index));
+        _ => {}
+    }
+}
+
+#[allow(clippy::collapsible_match)]
+fn handle_list_mouse_event(
+    app: &mut App,
+    mouse: MouseEvent,
+    services_list: Rect,
+    now: Instant,
+    is_list_double_click: bool,
+) {
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if mouse_in_rect(mouse, services_list) {
+                app.clear_status_message();
+                app.focused_pane = Pane::List;
+                let y_in_list = mouse.row.saturating_sub(services_list.y + 1);
+                let clicked_index = app.list_state.offset() + y_in_list as usize;
+                if clicked_index < app.filtered_indices.len() {
+                    app.last_list_click =
+                        (!is_list_double_click).then_some((now, mouse.column, mouse.row));
+                    if is_list_double_click && app.list_state.selected() == Some(clicked_index) {
+                        // The split view already shows the logs.
+                        if !app.split_view {
+                            app.toggle_logs();
                         }
+                    } else if app.list_state.selected() == Some(clicked_index) {
+                        // Re-click on selected entry → open details
+                        app.open_details();
+                    } else {
+                        app.list_state.select(Some(clicked_index));
                     }
                 }
             }
-            MouseEventKind::ScrollUp => {
-                if mouse_in_rect(mouse, regions.services_list) {
-                    app.previous();
-                }
+        }
+        MouseEventKind::ScrollUp => {
+            if mouse_in_rect(mouse, services_list) {
+                app.previous();
             }
-            MouseEventKind::ScrollDown => {
-                if mouse_in_rect(mouse, regions.services_list) {
-                    app.next();
-                }
+        }
+        MouseEventKind::ScrollDown => {
+            if mouse_in_rect(mouse, services_list) {
+                app.next();
             }
-            _ => {}
         }
+        _ => {}
     }
 }
 
+/// Whether a click at `now` completes a double-click with the one at
+/// `last`: quick enough and on the same cell.
+fn is_double_click(last: Instant, now: Instant, same_pos: bool) -> bool {
+    same_pos && now.saturating_duration_since(last) <= DOUBLE_CLICK_INTERVAL
+}
+
 fn mouse_in_rect(mouse: MouseEvent, rect: Rect) -> bool {
     mouse.column >= rect.x
         && mouse.column < rect.x + rect.width
@@ -716,6 +1554,185 @@ mod tests {
         }
     }
 
+    fn cli(args: &[&str]) -> Result<CliArgs, String> {
+        parse_args(&args.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_parse_args_defaults_to_system_mode() {
+        assert_eq!(cli(&[]), Ok(CliArgs::default()));
+        assert_eq!(cli(&["--system"]), Ok(CliArgs::default()));
+    }
+
+    #[test]
+    fn test_parse_args_user_mode() {
+        let parsed = cli(&["--user"]).unwrap();
+        assert!(parsed.user_mode);
+        // Repeating the same flag is harmless.
+        assert!(cli(&["--user", "--user"]).unwrap().user_mode);
+    }
+
+    #[test]
+    fn test_parse_args_rejects_user_and_system() {
+        assert!(cli(&["--user", "--system"]).is_err());
+        assert!(cli(&["--system", "--user"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_ssh_takes_remaining_args() {
+        let parsed = cli(&["--user", "--ssh", "-p", "2222", "host"]).unwrap();
+        assert!(parsed.user_mode);
+        assert_eq!(
+            parsed.ssh_args,
+            Some(vec!["-p".to_string(), "2222".to_string(), "host".to_string()])
+        );
+        assert!(cli(&["--ssh"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_unit_type() {
+        assert_eq!(cli(&["--type", "timer"]).unwrap().unit_type, Some(UnitType::Timer));
+        assert_eq!(cli(&["--type=socket"]).unwrap().unit_type, Some(UnitType::Socket));
+        let parsed = cli(&["--user", "--type", "path"]).unwrap();
+        assert!(parsed.user_mode);
+        assert_eq!(parsed.unit_type, Some(UnitType::Path));
+    }
+
+    #[test]
+    fn test_parse_args_invalid_unit_type() {
+        let err = cli(&["--type", "mount"]).unwrap_err();
+        assert!(err.contains("Invalid unit type 'mount'"));
+        assert!(cli(&["--type"]).is_err());
+    }
+
+    fn unit(name: &str, sub: &str, description: &str) -> SystemdUnit {
+        SystemdUnit {
+            unit: name.into(),
+            load: "loaded".into(),
+            active: String::new(),
+            sub: sub.into(),
+            description: description.into(),
+            detail: None,
+            file_state: None,
+            next_elapse_us: None,
+            last_trigger_us: None,
+            socket_stats: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_args_failed_subcommand() {
+        let parsed = cli(&["failed", "--user"]).unwrap();
+        assert_eq!(parsed.subcommand, Some(Subcommand::Failed));
+        assert!(parsed.user_mode);
+    }
+
+    #[test]
+    fn test_parse_args_export_subcommand() {
+        let parsed = cli(&["export", "--type", "timer"]).unwrap();
+        assert_eq!(parsed.subcommand, Some(Subcommand::Export));
+        assert_eq!(parsed.unit_type, Some(UnitType::Timer));
+    }
+
+    #[test]
+    fn test_failed_unit_lines_filters_and_aligns() {
+        let units = vec![
+            unit("ok.service", "running", "Fine"),
+            unit("a.service", "failed", "Broken A"),
+            unit("longer-name.timer", "failed", "Broken timer"),
+            unit("dead.service", "dead", "Stopped"),
+        ];
+        assert_eq!(
+            failed_unit_lines(&units),
+            vec![
+                "a.service          Broken A".to_string(),
+                "longer-name.timer  Broken timer".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failed_unit_lines_without_description() {
+        let units = vec![unit("x.service", "failed", "")];
+        assert_eq!(failed_unit_lines(&units), vec!["x.service".to_string()]);
+    }
+
+    #[test]
+    fn test_failed_exit_code() {
+        assert_eq!(failed_exit_code(&[]), 0);
+        let lines = failed_unit_lines(&[unit("a.service", "failed", "A")]);
+        assert_eq!(failed_exit_code(&lines), 1);
+        let none = failed_unit_lines(&[unit("a.service", "running", "A")]);
+        assert_eq!(failed_exit_code(&none), 0);
+    }
+
+    #[test]
+    fn test_parse_args_no_color() {
+        assert!(cli(&["--no-color"]).unwrap().no_color);
+        assert!(!cli(&[]).unwrap().no_color);
+    }
+
+    #[test]
+    fn test_parse_args_no_confirm() {
+        assert!(cli(&["--no-confirm", "--user"]).unwrap().no_confirm);
+        assert!(!cli(&[]).unwrap().no_confirm);
+    }
+
+    #[test]
+    fn test_color_enabled_flag_and_env() {
+        assert!(color_enabled(false, None));
+        assert!(!color_enabled(true, None));
+        assert!(!color_enabled(false, Some("1".into())));
+        // An empty NO_COLOR does not disable color.
+        assert!(color_enabled(false, Some("".into())));
+    }
+
+    #[test]
+    fn test_parse_args_version_and_unknown() {
+        assert!(cli(&["version"]).unwrap().version);
+        assert!(cli(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_version_json() {
+        assert!(!cli(&["version"]).unwrap().json);
+        assert!(cli(&["version", "--json"]).unwrap().json);
+        assert!(cli(&["--version", "--format", "json"]).unwrap().json);
+        assert!(!cli(&["version", "--format=text"]).unwrap().json);
+        assert!(cli(&["version", "--format", "yaml"]).is_err());
+        assert!(cli(&["version", "--format"]).is_err());
+        assert!(cli(&["export", "--json"]).is_err());
+    }
+
+    #[test]
+    fn test_version_output() {
+        assert_eq!(version_output("1.2.3", false), "systemdmgr 1.2.3");
+        assert_eq!(version_output("1.2.3", true), r#"{"name":"systemdmgr","version":"1.2.3"}"#);
+        let parsed: serde_json::Value =
+            serde_json::from_str(&version_output(env!("CARGO_PKG_VERSION"), true)).unwrap();
+        assert_eq!(parsed["version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_is_double_click_within_interval() {
+        let first = Instant::now();
+        assert!(is_double_click(first, first + Duration::from_millis(150), true));
+        assert!(is_double_click(first, first + DOUBLE_CLICK_INTERVAL, true));
+    }
+
+    #[test]
+    fn test_is_double_click_too_slow() {
+        let first = Instant::now();
+        let late = first + DOUBLE_CLICK_INTERVAL + Duration::from_millis(1);
+        assert!(!is_double_click(first, late, true));
+    }
+
+    #[test]
+    fn test_is_double_click_needs_same_position() {
+        let first = Instant::now();
+        assert!(!is_double_click(first, first + Duration::from_millis(50), false));
+    }
+
     #[test]
     fn test_mouse_in_rect_inside() {
         let rect = Rect::new(10, 10, 20, 15);
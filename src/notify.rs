@@ -0,0 +1,91 @@
+//! Desktop notifications for units that fail while auto-refresh is on.
+//! Sending needs the `desktop-notifications` feature; without it the
+//! `desktop_notifications` config flag has no effect.
+
+/// Whether this build can send notifications at all.
+pub const AVAILABLE: bool = cfg!(feature = "desktop-notifications");
+
+/// Shown once at startup when the config asks for notifications this build
+/// can't send.
+pub const UNAVAILABLE_WARNING: &str =
+    "desktop_notifications is on, but this build lacks the desktop-notifications feature";
+
+/// Failed units named in the notification body before "…and N more".
+const NOTIFICATION_UNITS_SHOWN: usize = 10;
+
+/// "nginx.service failed", "3 units failed", with " on <host>" for `--ssh`.
+pub fn failure_notification_summary(units: &[String], host: Option<&str>) -> String {
+    let what = match units {
+        [only] => format!("{} failed", only),
+        many => format!("{} units failed", many.len()),
+    };
+    match host {
+        Some(host) => format!("{} on {}", what, host),
+        None => what,
+    }
+}
+
+/// The failed unit names, one per line.
+pub fn failure_notification_body(units: &[String]) -> String {
+    let mut lines: Vec<String> = units.iter().take(NOTIFICATION_UNITS_SHOWN).cloned().collect();
+    if units.len() > NOTIFICATION_UNITS_SHOWN {
+        lines.push(format!("\u{2026}and {} more", units.len() - NOTIFICATION_UNITS_SHOWN));
+    }
+    lines.join("\n")
+}
+
+/// Sends the notification on a background thread, so a slow or missing
+/// notification daemon never stalls the UI. Without the feature no thread
+/// is started.
+pub fn notify_failed_units(units: &[String], host: Option<&str>) {
+    let summary = failure_notification_summary(units, host);
+    let body = failure_notification_body(units);
+    #[cfg(feature = "desktop-notifications")]
+    std::thread::spawn(move || send(&summary, &body));
+    #[cfg(not(feature = "desktop-notifications"))]
+    let _ = (summary, body);
+}
+
+/// Errors (e.g. no notification daemon) are dropped.
+#[cfg(feature = "desktop-notifications")]
+fn send(summary: &str, body: &str) {
+    let _ = notify_rust::Notification::new()
+        .appname("systemdmgr")
+        .summary(summary)
+        .body(body)
+        .icon("dialog-error")
+        .show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(units: &[&str]) -> Vec<String> {
+        units.iter().map(|u| u.to_string()).collect()
+    }
+
+    #[test]
+    fn test_body_lists_failed_units() {
+        let units = names(&["backup.service", "nginx.service"]);
+        assert_eq!(failure_notification_body(&units), "backup.service\nnginx.service");
+    }
+
+    #[test]
+    fn test_body_truncates_long_lists() {
+        let units: Vec<String> = (0..12).map(|i| format!("u{}.service", i)).collect();
+        let body = failure_notification_body(&units);
+        assert_eq!(body.lines().count(), NOTIFICATION_UNITS_SHOWN + 1);
+        assert!(body.starts_with("u0.service\n"));
+        assert!(body.ends_with("u9.service\n\u{2026}and 2 more"));
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(failure_notification_summary(&names(&["nginx.service"]), None), "nginx.service failed");
+        assert_eq!(
+            failure_notification_summary(&names(&["a.service", "b.timer"]), Some("web1")),
+            "2 units failed on web1"
+        );
+    }
+}
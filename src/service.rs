@@ -1,13 +1,14 @@
 use chrono::TimeZone;
 use ratatui::style::{Color, Modifier, Style};
-
-/// Muted foreground color for inactive/dimmed states (visible on DarkGray highlight)
-pub const COLOR_MUTED: Color = Color::Rgb(100, 100, 100);
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::theme::theme;
+
 pub struct CommandOutput {
     pub success: bool,
     pub stdout: Vec<u8>,
@@ -16,19 +17,57 @@ pub struct CommandOutput {
 
 pub trait CommandRunner: Send + Sync {
     fn run(&self, program: &str, args: &[&str]) -> Result<CommandOutput, String>;
+
+    /// Builds a command that runs attached to the user's terminal (e.g. an
+    /// editor session) instead of having its output captured.
+    fn interactive_command(&self, program: &str, args: &[&str]) -> Command {
+        let mut cmd = Command::new(program);
+        cmd.args(args);
+        cmd
+    }
 }
 
 pub const MIN_SYSTEMD_VERSION: u32 = 246;
 
 pub struct LocalRunner;
 
+/// Error for a command that could not be started. A binary missing from
+/// PATH gets one clear message instead of the raw OS error: for systemd's
+/// own tools that means no systemd (e.g. macOS), for anything else (pkexec,
+/// sudo) just that it isn't installed.
+pub fn spawn_error(program: &str, e: &io::Error) -> String {
+    if e.kind() == io::ErrorKind::NotFound {
+        missing_binary_message(program)
+    } else {
+        format!("Failed to execute {}: {}", program, e)
+    }
+}
+
+/// Follows the binary's name in `missing_binary_message`; also what
+/// `is_missing_binary_error` looks for, so the two can't drift apart.
+const MISSING_BINARY_SUFFIX: &str = " not found \u{2014} is this a systemd system?";
+
+fn missing_binary_message(program: &str) -> String {
+    if matches!(program, "systemctl" | "journalctl" | "systemd-analyze") {
+        format!("{}{}", program, MISSING_BINARY_SUFFIX)
+    } else {
+        format!("{} is not installed", program)
+    }
+}
+
+/// Whether `error` came from `spawn_error` for a missing binary, where
+/// hints like switching to user mode can't help.
+pub fn is_missing_binary_error(error: &str) -> bool {
+    error.ends_with(MISSING_BINARY_SUFFIX)
+}
+
 impl CommandRunner for LocalRunner {
     fn run(&self, program: &str, args: &[&str]) -> Result<CommandOutput, String> {
         let output = Command::new(program)
             .stdin(Stdio::null())
             .args(args)
             .output()
-            .map_err(|e| format!("Failed to execute {}: {}", program, e))?;
+            .map_err(|e| spawn_error(program, &e))?;
         Ok(CommandOutput {
             success: output.status.success(),
             stdout: output.stdout,
@@ -228,6 +267,23 @@ fn multiplex_args(control_dir: &std::path::Path, batch_mode: bool) -> Vec<String
     args
 }
 
+/// The ssh arguments that run `program args` on `destination` through the
+/// shared master connection.
+fn remote_command_args(
+    control_dir: &std::path::Path,
+    ssh_options: &[String],
+    destination: &str,
+    program: &str,
+    args: &[&str],
+) -> Vec<String> {
+    let mut ssh_args = multiplex_args(control_dir, true);
+    ssh_args.extend(ssh_options.iter().cloned());
+    ssh_args.push("--".to_string());
+    ssh_args.push(destination.to_string());
+    ssh_args.push(join_remote_command(program, args));
+    ssh_args
+}
+
 impl SshRunner {
     /// `ssh_cli_args` uses ssh's own syntax: `[options] destination`,
     /// e.g. `["deploy@myserver"]` or `["-p", "2222", "-i", "key", "myserver"]`.
@@ -315,11 +371,7 @@ impl CommandRunner for SshRunner {
     fn run(&self, program: &str, args: &[&str]) -> Result<CommandOutput, String> {
         let output = Command::new("ssh")
             .stdin(Stdio::null())
-            .args(multiplex_args(&self.control_dir, true))
-            .args(&self.ssh_options)
-            .arg("--")
-            .arg(&self.destination)
-            .arg(join_remote_command(program, args))
+            .args(remote_command_args(&self.control_dir, &self.ssh_options, &self.destination, program, args))
             .output()
             .map_err(|e| format!("Failed to run ssh: {}", e))?;
 
@@ -329,6 +381,10 @@ impl CommandRunner for SshRunner {
             let stderr = String::from_utf8_lossy(&output.stderr);
             return Err(format!("SSH error: {}", stderr.trim()));
         }
+        // The remote shell's "command not found".
+        if output.status.code() == Some(127) {
+            return Err(missing_binary_message(program));
+        }
 
         Ok(CommandOutput {
             success: output.status.success(),
@@ -336,6 +392,14 @@ impl CommandRunner for SshRunner {
             stderr: output.stderr,
         })
     }
+
+    fn interactive_command(&self, program: &str, args: &[&str]) -> Command {
+        // -t forces a remote pty so the remote $EDITOR gets a real terminal.
+        let mut cmd = Command::new("ssh");
+        cmd.arg("-t")
+            .args(remote_command_args(&self.control_dir, &self.ssh_options, &self.destination, program, args));
+        cmd
+    }
 }
 
 fn run_systemctl(runner: &dyn CommandRunner, extra_args: &[&str]) -> Result<CommandOutput, String> {
@@ -355,6 +419,8 @@ pub enum UnitType {
     Socket,
     Target,
     Path,
+    /// Every loaded unit, whatever its type (no `--type` filter).
+    All,
 }
 
 impl UnitType {
@@ -365,9 +431,12 @@ impl UnitType {
             UnitType::Socket => "Sockets",
             UnitType::Target => "Targets",
             UnitType::Path => "Paths",
+            UnitType::All => "All Units",
         }
     }
 
+    /// The type's name on the command line. `all` is ours; systemctl is
+    /// given no `--type` for it (see `type_filter_arg`).
     pub fn systemctl_type(&self) -> &'static str {
         match self {
             UnitType::Service => "service",
@@ -375,6 +444,20 @@ impl UnitType {
             UnitType::Socket => "socket",
             UnitType::Target => "target",
             UnitType::Path => "path",
+            UnitType::All => "all",
+        }
+    }
+
+    /// `--type=<type>` for `list-units`/`list-unit-files`, none for `All`.
+    fn type_filter_arg(&self) -> Option<String> {
+        (*self != UnitType::All).then(|| format!("--type={}", self.systemctl_type()))
+    }
+
+    /// Glob for `systemctl show` over every loaded unit of the type.
+    fn show_pattern(&self) -> String {
+        match self {
+            UnitType::All => "*".to_string(),
+            _ => format!("*.{}", self.systemctl_type()),
         }
     }
 
@@ -385,18 +468,40 @@ impl UnitType {
             UnitType::Socket => &["All", "listening", "running", "failed"],
             UnitType::Target => &["All", "active", "inactive"],
             UnitType::Path => &["All", "waiting", "running", "failed"],
+            // Sub-states differ by type, so a mixed list has no common set.
+            UnitType::All => &["All"],
         }
     }
 }
 
-pub const UNIT_TYPES: [UnitType; 5] = [
+pub const UNIT_TYPES: [UnitType; 6] = [
     UnitType::Service,
     UnitType::Timer,
     UnitType::Socket,
     UnitType::Target,
     UnitType::Path,
+    UnitType::All,
 ];
 
+/// Maps a systemctl type name (`service`, `timer`, ...) to a `UnitType`.
+pub fn parse_unit_type(name: &str) -> Option<UnitType> {
+    UNIT_TYPES
+        .iter()
+        .copied()
+        .find(|t| t.systemctl_type().eq_ignore_ascii_case(name))
+}
+
+/// The type suffix of a unit name: `service` for `sshd.service`.
+pub fn unit_suffix(name: &str) -> &str {
+    name.rsplit_once('.').map_or("", |(_, suffix)| suffix)
+}
+
+/// The listable type of a unit, from its name's suffix. `None` for types
+/// with no view of their own (`.mount`, `.device`, ...).
+pub fn unit_type_from_name(name: &str) -> Option<UnitType> {
+    parse_unit_type(unit_suffix(name)).filter(|t| *t != UnitType::All)
+}
+
 #[derive(Debug, Clone)]
 pub struct LogEntry {
     pub timestamp: Option<i64>,
@@ -412,24 +517,63 @@ pub struct LogEntry {
     pub invocation_id: Option<String>,
     pub cursor: Option<String>,
     pub unit: Option<String>,
+    /// `_HOSTNAME`, the machine that logged the entry.
+    pub hostname: Option<String>,
+    /// Every field of the journal record as text, including the ones above
+    /// and the rest (`CODE_FILE`, `_CMDLINE`, ...), for the fields popup.
+    pub fields: HashMap<String, String>,
 }
 
 pub const PRIORITY_LABELS: [&str; 8] = [
     "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
 ];
 
+/// Builds the value for journalctl's `-p` flag from an inclusive priority
+/// range. A range starting at emerg collapses to the single-level form
+/// (`-p N` means "N and more severe"), anything else becomes `LOW..HIGH`.
+pub fn priority_filter_arg(range: (u8, u8)) -> String {
+    let (low, high) = if range.0 <= range.1 {
+        range
+    } else {
+        (range.1, range.0)
+    };
+    if low == 0 {
+        high.to_string()
+    } else {
+        format!("{}..{}", low, high)
+    }
+}
+
+/// Short label for a priority range, e.g. "err" or "err..warning".
+pub fn priority_filter_label(range: (u8, u8)) -> String {
+    let (low, high) = if range.0 <= range.1 {
+        range
+    } else {
+        (range.1, range.0)
+    };
+    if low == 0 {
+        priority_label(high).to_string()
+    } else {
+        format!("{}..{}", priority_label(low), priority_label(high))
+    }
+}
+
 pub fn priority_label(p: u8) -> &'static str {
     PRIORITY_LABELS.get(p as usize).unwrap_or(&"unknown")
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TimeRange {
+    #[default]
     All,
     FifteenMinutes,
     OneHour,
     OneDay,
     SevenDays,
     Today,
+    /// Free-text `--since`/`--until` values passed straight to journalctl.
+    Custom { since: String, until: Option<String> },
 }
 
 impl TimeRange {
@@ -441,10 +585,20 @@ impl TimeRange {
             TimeRange::OneDay => "Last 24 hours",
             TimeRange::SevenDays => "Last 7 days",
             TimeRange::Today => "Today",
+            TimeRange::Custom { .. } => "Custom...",
+        }
+    }
+
+    /// Label for the logs title; custom ranges show the typed bounds.
+    pub fn summary(&self) -> String {
+        match self {
+            TimeRange::Custom { since, until: Some(until) } => format!("{} .. {}", since, until),
+            TimeRange::Custom { since, until: None } => format!("since {}", since),
+            other => other.label().to_string(),
         }
     }
 
-    pub fn journalctl_since(&self) -> Option<&'static str> {
+    pub fn journalctl_since(&self) -> Option<&str> {
         match self {
             TimeRange::All => None,
             TimeRange::FifteenMinutes => Some("15 min ago"),
@@ -452,7 +606,85 @@ impl TimeRange {
             TimeRange::OneDay => Some("1 day ago"),
             TimeRange::SevenDays => Some("7 days ago"),
             TimeRange::Today => Some("today"),
+            TimeRange::Custom { since, .. } => Some(since),
+        }
+    }
+
+    pub fn journalctl_until(&self) -> Option<&str> {
+        match self {
+            TimeRange::Custom { until, .. } => until.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Server-side message filter passed to `journalctl --grep`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogGrep {
+    pub pattern: String,
+    pub case_sensitive: bool,
+}
+
+impl LogGrep {
+    fn journalctl_args(&self) -> [String; 2] {
+        [
+            format!("--grep={}", self.pattern),
+            format!("--case-sensitive={}", if self.case_sensitive { "yes" } else { "no" }),
+        ]
+    }
+}
+
+/// Where log entries come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSource {
+    /// Entries from systemd units: one unit, or the whole journal when no
+    /// unit is given.
+    Unit,
+    /// Kernel ring buffer messages for the current boot (`journalctl -k`).
+    Kernel,
+}
+
+/// Journal filters shared by every log fetch: the initial load, live-tail
+/// refreshes and history paging must all agree on them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilters {
+    pub source: LogSource,
+    pub priority: Option<(u8, u8)>,
+    pub time_range: TimeRange,
+    pub grep: Option<LogGrep>,
+    /// Only entries logged by this process (the unit's main PID).
+    pub main_pid: Option<u32>,
+}
+
+impl LogFilters {
+    fn journalctl_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(range) = self.priority {
+            args.push("-p".to_string());
+            args.push(priority_filter_arg(range));
+        }
+        let mut time_args = Vec::new();
+        push_time_range_args(&mut time_args, &self.time_range);
+        args.extend(time_args.into_iter().map(String::from));
+        if let Some(grep) = &self.grep {
+            args.extend(grep.journalctl_args());
         }
+        if let Some(pid) = self.main_pid {
+            args.push(format!("_PID={}", pid));
+        }
+        args
+    }
+}
+
+/// Appends `--since`/`--until` for the given range to a journalctl argument list.
+fn push_time_range_args<'a>(args: &mut Vec<&'a str>, time_range: &'a TimeRange) {
+    if let Some(since) = time_range.journalctl_since() {
+        args.push("--since");
+        args.push(since);
+    }
+    if let Some(until) = time_range.journalctl_until() {
+        args.push("--until");
+        args.push(until);
     }
 }
 
@@ -465,7 +697,10 @@ pub const TIME_RANGES: [TimeRange; 6] = [
     TimeRange::Today,
 ];
 
-#[derive(Debug, Clone, Deserialize)]
+/// A row of `systemctl list-units --output=json`. `detail` and `file_state`
+/// are merged in from other commands, so they are only serialized (for
+/// `systemdmgr export`), never read from the list-units JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemdUnit {
     pub unit: String,
     pub load: String,
@@ -473,12 +708,28 @@ pub struct SystemdUnit {
     pub active: String,
     pub sub: String,
     pub description: String,
-    #[serde(skip)]
+    #[serde(skip_deserializing)]
     pub detail: Option<String>,
-    #[serde(skip)]
+    #[serde(skip_deserializing)]
     pub file_state: Option<String>,
+    /// Timers only: the next trigger as absolute wall-clock microseconds
+    /// (0 when none is scheduled), so the countdown can be recomputed on
+    /// every draw instead of freezing at fetch time.
+    #[serde(skip)]
+    pub next_elapse_us: Option<u64>,
+    /// Timers only: when the timer last fired, like `next_elapse_us`
+    /// (0 when it never has).
+    #[serde(skip)]
+    pub last_trigger_us: Option<u64>,
+    /// Sockets only, and only with `Accept=yes`.
+    #[serde(skip)]
+    pub socket_stats: Option<SocketStats>,
 }
 
+/// Line counts the logs view can fetch per load (`journalctl -n`).
+pub const LOG_LINE_LIMITS: [usize; 4] = [500, 1000, 5000, 10000];
+pub const DEFAULT_LOG_LINE_LIMIT: usize = 1000;
+
 pub const FILE_STATE_OPTIONS: &[&str] = &["All", "enabled", "disabled", "static", "masked", "indirect"];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -489,9 +740,16 @@ pub enum UnitAction {
     Reload,
     Enable,
     Disable,
+    Unmask,
     DaemonReload,
+    /// Rotate the journal and delete archived entries older than
+    /// `JOURNAL_VACUUM_TIME`. Runs journalctl, not systemctl.
+    VacuumJournal,
 }
 
+/// Age passed to `journalctl --vacuum-time` by the vacuum action.
+pub const JOURNAL_VACUUM_TIME: &str = "2weeks";
+
 impl UnitAction {
     pub fn label(&self) -> &'static str {
         match self {
@@ -501,7 +759,9 @@ impl UnitAction {
             UnitAction::Reload => "Reload",
             UnitAction::Enable => "Enable",
             UnitAction::Disable => "Disable",
+            UnitAction::Unmask => "Unmask",
             UnitAction::DaemonReload => "Daemon Reload",
+            UnitAction::VacuumJournal => "Vacuum Journal",
         }
     }
 
@@ -513,10 +773,14 @@ impl UnitAction {
             UnitAction::Reload => 'l',
             UnitAction::Enable => 'e',
             UnitAction::Disable => 'd',
+            UnitAction::Unmask => 'u',
             UnitAction::DaemonReload => 'D',
+            UnitAction::VacuumJournal => 'V',
         }
     }
 
+    /// Empty for `VacuumJournal`, which `execute_unit_action` hands to
+    /// `vacuum_journal` instead.
     pub fn systemctl_verb(&self) -> &'static str {
         match self {
             UnitAction::Start => "start",
@@ -525,10 +789,78 @@ impl UnitAction {
             UnitAction::Reload => "reload",
             UnitAction::Enable => "enable",
             UnitAction::Disable => "disable",
+            UnitAction::Unmask => "unmask",
             UnitAction::DaemonReload => "daemon-reload",
+            UnitAction::VacuumJournal => "",
+        }
+    }
+
+    /// Whether systemctl runs this action as a job it waits for by
+    /// default, so `--no-block` applies.
+    pub fn queues_job(&self) -> bool {
+        matches!(self, UnitAction::Start | UnitAction::Stop | UnitAction::Restart | UnitAction::Reload)
+    }
+
+    /// systemctl arguments for this action, without the `--no-ask-password`
+    /// that `run_systemctl` adds. DaemonReload takes no unit. `no_block`
+    /// adds `--no-block` to job actions, so systemctl returns once the job
+    /// is queued.
+    pub fn systemctl_args<'a>(&self, unit_name: &'a str, user_mode: bool, no_block: bool) -> Vec<&'a str> {
+        let mut args = Vec::new();
+        if user_mode {
+            args.push("--user");
+        }
+        if no_block && self.queues_job() {
+            args.push("--no-block");
+        }
+        args.push(self.systemctl_verb());
+        if *self != UnitAction::DaemonReload {
+            args.push(unit_name);
+        }
+        args
+    }
+
+    /// The command line shown in the confirm dialog, built from the same
+    /// arguments `execute_unit_action` runs.
+    pub fn command_preview(&self, unit_name: &str, user_mode: bool, no_block: bool) -> String {
+        if *self == UnitAction::VacuumJournal {
+            let [rotate, vacuum] = vacuum_journal_args(JOURNAL_VACUUM_TIME, user_mode);
+            return format!("journalctl {} && journalctl {}", rotate.join(" "), vacuum.join(" "));
+        }
+        format!("systemctl {}", self.systemctl_args(unit_name, user_mode, no_block).join(" "))
+    }
+
+    /// The action that reverses this one, for undo. Restart and reload have
+    /// nothing to go back to, and there is no mask action to undo an unmask.
+    pub fn inverse(&self) -> Option<UnitAction> {
+        match self {
+            UnitAction::Start => Some(UnitAction::Stop),
+            UnitAction::Stop => Some(UnitAction::Start),
+            UnitAction::Enable => Some(UnitAction::Disable),
+            UnitAction::Disable => Some(UnitAction::Enable),
+            UnitAction::Restart
+            | UnitAction::Reload
+            | UnitAction::Unmask
+            | UnitAction::DaemonReload
+            | UnitAction::VacuumJournal => None,
         }
     }
 
+    /// Whether the confirm dialog is shown even with `--no-confirm`. Only
+    /// the everyday start/stop/restart/reload may skip it.
+    pub fn requires_confirm(&self) -> bool {
+        !matches!(
+            self,
+            UnitAction::Start | UnitAction::Stop | UnitAction::Restart | UnitAction::Reload
+        )
+    }
+
+    /// Destructive actions must be confirmed with an uppercase `Y`, so a
+    /// stray `y` can't trigger them.
+    pub fn requires_strong_confirmation(&self) -> bool {
+        *self == UnitAction::VacuumJournal
+    }
+
     pub fn progress_label(&self) -> &'static str {
         match self {
             UnitAction::Start => "Starting...",
@@ -537,19 +869,52 @@ impl UnitAction {
             UnitAction::Reload => "Reloading...",
             UnitAction::Enable => "Enabling...",
             UnitAction::Disable => "Disabling...",
+            UnitAction::Unmask => "Unmasking...",
             UnitAction::DaemonReload => "Reloading daemon...",
+            UnitAction::VacuumJournal => "Vacuuming journal...",
         }
     }
 
+    pub fn bulk_confirmation_message(&self, count: usize) -> String {
+        format!("{} {} units?", self.label(), count)
+    }
+
     pub fn confirmation_message(&self, unit_name: &str) -> String {
         match self {
             UnitAction::DaemonReload => "Reload systemd daemon configuration?".to_string(),
+            UnitAction::VacuumJournal => format!(
+                "Permanently delete ALL archived journal entries older than {}?",
+                JOURNAL_VACUUM_TIME
+            ),
             _ => format!("{} {}?", self.label(), unit_name),
         }
     }
 
-    pub fn available_actions(sub_state: &str, file_state: Option<&str>) -> Vec<UnitAction> {
+    /// Why a unit with this load state can't be started, if it can't.
+    pub fn start_blocked_reason(load_state: &str) -> Option<&'static str> {
+        match load_state {
+            "masked" => Some("unit is masked; unmask it before starting"),
+            "not-found" => Some("unit file not found; it cannot be started"),
+            _ => None,
+        }
+    }
+
+    pub fn available_actions(sub_state: &str, file_state: Option<&str>, load_state: &str) -> Vec<UnitAction> {
         let mut actions = Vec::new();
+        let running = matches!(sub_state, "running" | "active" | "listening" | "waiting");
+
+        // A masked or missing unit can only be stopped if it is somehow
+        // still running; start, restart and enable would all fail.
+        if UnitAction::start_blocked_reason(load_state).is_some() {
+            if running {
+                actions.push(UnitAction::Stop);
+            }
+            if load_state == "masked" {
+                actions.push(UnitAction::Unmask);
+            }
+            actions.push(UnitAction::DaemonReload);
+            return actions;
+        }
 
         match sub_state {
             "running" | "active" | "listening" | "waiting" => {
@@ -577,23 +942,183 @@ impl UnitAction {
     }
 }
 
-pub fn execute_unit_action(action: UnitAction, unit_name: &str, user_mode: bool, runner: &dyn CommandRunner) -> Result<String, String> {
-    let mut args = Vec::new();
-    if user_mode {
-        args.push("--user");
+/// Runs an action. A success message is followed by whatever systemctl
+/// printed, one line each, so e.g. the symlinks `enable` created are shown
+/// (systemctl reports those on stderr, hence both streams). With
+/// `no_block`, a job action succeeds once queued and reports "requested".
+pub fn execute_unit_action(
+    action: UnitAction,
+    unit_name: &str,
+    user_mode: bool,
+    no_block: bool,
+    runner: &dyn CommandRunner,
+) -> Result<String, String> {
+    execute_unit_action_with_output(action, unit_name, user_mode, no_block, runner).0
+}
+
+/// Runs the action like `execute_unit_action`, also returning everything
+/// the command printed (see `combined_output`), failed or not. A command
+/// that couldn't be run at all, and the journal vacuum, report their
+/// message instead.
+pub fn execute_unit_action_with_output(
+    action: UnitAction,
+    unit_name: &str,
+    user_mode: bool,
+    no_block: bool,
+    runner: &dyn CommandRunner,
+) -> (Result<String, String>, String) {
+    if action == UnitAction::VacuumJournal {
+        let result = vacuum_journal(JOURNAL_VACUUM_TIME, user_mode, runner);
+        let output = match &result {
+            Ok(message) | Err(message) => message.clone(),
+        };
+        return (result, output);
     }
-    args.push(action.systemctl_verb());
-    if action != UnitAction::DaemonReload {
-        args.push(unit_name);
+    let args = action.systemctl_args(unit_name, user_mode, no_block);
+    let output = match run_systemctl(runner, &args) {
+        Ok(output) => output,
+        Err(e) => return (Err(e.clone()), e),
+    };
+    let printed = combined_output(&output.stdout, &output.stderr);
+
+    let result = if output.success {
+        let outcome = if no_block && action.queues_job() { "requested" } else { "succeeded" };
+        let mut message = format!("{} {} for {}", action.label(), outcome, unit_name);
+        if !printed.is_empty() {
+            message.push('\n');
+            message.push_str(&printed);
+        }
+        Ok(message)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        Err(format!("{} failed: {}", action.label(), stderr.trim()))
+    };
+    (result, printed)
+}
+
+/// Whether a failed systemctl's stderr says the caller lacks the privilege
+/// for the operation: polkit wanted to ask for a password (we pass
+/// `--no-ask-password`) or refused outright.
+pub fn needs_privilege(stderr: &str) -> bool {
+    stderr.contains("Interactive authentication required") || stderr.contains("Access denied")
+}
+
+/// How a failed action is retried with elevated privileges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escalation {
+    Pkexec,
+    Sudo,
+}
+
+impl Escalation {
+    pub fn program(&self) -> &'static str {
+        match self {
+            Escalation::Pkexec => "pkexec",
+            Escalation::Sudo => "sudo",
+        }
     }
+}
 
-    let output = run_systemctl(runner, &args)?;
+/// The full command line for retrying `action` through `escalation`, e.g.
+/// `sudo systemctl start nginx.service`. Run attached to the terminal so
+/// sudo or a polkit text agent can ask for a password; only system-scope
+/// actions are escalated, so there is no `--user`. `no_block` is passed on
+/// as for the original run.
+pub fn escalated_command(escalation: Escalation, action: UnitAction, unit_name: &str, no_block: bool) -> Vec<String> {
+    let mut command = vec![escalation.program().to_string(), "systemctl".to_string()];
+    command.extend(action.systemctl_args(unit_name, false, no_block).into_iter().map(String::from));
+    command
+}
+
+/// A command's stdout then its stderr, each trimmed, joined by a newline;
+/// an empty stream is left out.
+pub fn combined_output(stdout: &[u8], stderr: &[u8]) -> String {
+    [stdout, stderr]
+        .iter()
+        .map(|stream| String::from_utf8_lossy(stream).trim().to_string())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Arguments for the two journalctl runs of a vacuum: `--rotate` first so
+/// the active files are archived and become eligible, then `--vacuum-time`.
+fn vacuum_journal_args(time_spec: &str, user_mode: bool) -> [Vec<String>; 2] {
+    let scope: Vec<String> = if user_mode { vec!["--user".into()] } else { Vec::new() };
+    let mut rotate = scope.clone();
+    rotate.push("--rotate".into());
+    let mut vacuum = scope;
+    vacuum.push(format!("--vacuum-time={}", time_spec));
+    [rotate, vacuum]
+}
+
+/// Rotates the journal and removes archived entries older than `time_spec`
+/// (e.g. "2weeks"). This affects the whole journal, not a single unit.
+pub fn vacuum_journal(time_spec: &str, user_mode: bool, runner: &dyn CommandRunner) -> Result<String, String> {
+    let [rotate, vacuum] = vacuum_journal_args(time_spec, user_mode);
+    let run = |step: &str, args: &[String]| {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = run_journalctl(runner, &args)?;
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if output.success {
+            Ok(stderr)
+        } else {
+            Err(format!("Journal {} failed: {}", step, stderr))
+        }
+    };
+    run("rotate", &rotate)?;
+    // journalctl reports "Vacuuming done, freed X of archived journals ..."
+    // on stderr; show that last line when there is one.
+    let report = run("vacuum", &vacuum)?;
+    Ok(match report.lines().last() {
+        Some(summary) => summary.trim().to_string(),
+        None => format!("Journal vacuumed (older than {})", time_spec),
+    })
+}
+
+/// Runs `action` on each unit in turn and folds the outcomes into a single
+/// result for the confirm dialog. `on_done` sees each unit's outcome as soon
+/// as it completes, for live progress.
+pub fn execute_unit_action_bulk(
+    action: UnitAction,
+    unit_names: &[String],
+    user_mode: bool,
+    no_block: bool,
+    runner: &dyn CommandRunner,
+    mut on_done: impl FnMut(&str, &Result<String, String>),
+) -> Result<String, String> {
+    let outcomes: Vec<(&str, Result<String, String>)> = unit_names
+        .iter()
+        .map(|name| {
+            let result = execute_unit_action(action, name, user_mode, no_block, runner);
+            on_done(name, &result);
+            (name.as_str(), result)
+        })
+        .collect();
+    summarize_bulk_action(action, &outcomes)
+}
 
-    if output.success {
-        Ok(format!("{} succeeded for {}", action.label(), unit_name))
+fn summarize_bulk_action(
+    action: UnitAction,
+    outcomes: &[(&str, Result<String, String>)],
+) -> Result<String, String> {
+    let total = outcomes.len();
+    let failed: Vec<&str> = outcomes
+        .iter()
+        .filter(|(_, r)| r.is_err())
+        .map(|(name, _)| *name)
+        .collect();
+    let succeeded = total - failed.len();
+    if failed.is_empty() {
+        Ok(format!("{}: {}/{} succeeded", action.label(), succeeded, total))
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        Err(format!("{} failed: {}", action.label(), stderr.trim()))
+        Err(format!(
+            "{}: {}/{} succeeded, failed: {}",
+            action.label(),
+            succeeded,
+            total,
+            failed.join(", ")
+        ))
     }
 }
 
@@ -608,7 +1133,10 @@ pub struct UnitProperties {
     pub description: String,
     pub main_pid: u32,
     pub exec_main_start_timestamp: String,
+    pub n_restarts: u32,
+    pub exec_main_status: i32,
     pub memory_current: Option<u64>,
+    pub memory_max: Option<u64>,
     pub cpu_usage_nsec: Option<u64>,
     pub requires: Vec<String>,
     pub wants: Vec<String>,
@@ -632,6 +1160,24 @@ pub struct UnitProperties {
     pub accept: String,
     pub n_connections: String,
     pub n_accepted: String,
+    // Environment properties
+    pub environment: Vec<String>,
+    pub environment_files: Vec<String>,
+    // Command lines, one entry per configured command
+    pub exec_start: Vec<String>,
+    pub exec_stop: Vec<String>,
+    pub exec_reload: Vec<String>,
+}
+
+impl UnitProperties {
+    /// The unit a timer starts: its `Triggers` (set by `Unit=`, which may
+    /// name anything), or failing that the same-named `.service`.
+    pub fn triggered_service(&self, timer_name: &str) -> Option<String> {
+        if let Some(unit) = self.triggers.first() {
+            return Some(unit.clone());
+        }
+        timer_name.strip_suffix(".timer").map(|base| format!("{}.service", base))
+    }
 }
 
 impl SystemdUnit {
@@ -640,17 +1186,15 @@ impl SystemdUnit {
     }
 
     pub fn status_color(&self) -> Color {
-        match self.sub.as_str() {
-            "running" => Color::Green,
-            "exited" => Color::Yellow,
-            "dead" | "stopped" => COLOR_MUTED,
-            "failed" => Color::Red,
-            "waiting" => Color::Cyan,
-            "listening" => Color::Green,
-            "active" => Color::Green,
-            "inactive" => COLOR_MUTED,
-            "elapsed" => Color::Yellow,
-            _ => Color::White,
+        theme().sub_state_color(&self.sub)
+    }
+
+    /// The detail shown after the description. For timers the countdown is
+    /// recomputed against `now_us`; other units use the fetched `detail`.
+    pub fn detail_at(&self, now_us: u64) -> Option<String> {
+        match self.next_elapse_us {
+            Some(next) => Some(format_next_trigger(next, now_us)),
+            None => self.detail.clone(),
         }
     }
 }
@@ -659,33 +1203,90 @@ pub fn fetch_log_entries(
     unit_name: Option<&str>,
     lines: usize,
     user_mode: bool,
-    priority: Option<u8>,
-    time_range: TimeRange,
+    filters: &LogFilters,
     runner: &dyn CommandRunner,
 ) -> Result<Vec<LogEntry>, String> {
     let lines_str = lines.to_string();
-    let mut args = vec!["-n", &lines_str, "--no-pager", "--output=json"];
-    if let Some(name) = unit_name {
-        let unit_flag = if user_mode { "--user-unit" } else { "-u" };
-        args.insert(0, name);
-        args.insert(0, unit_flag);
-    }
+    run_log_query(unit_name, user_mode, &["-n", &lines_str], filters, runner)
+}
+
+pub fn fetch_log_entries_after_cursor(
+    unit_name: Option<&str>,
+    cursor: &str,
+    user_mode: bool,
+    filters: &LogFilters,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<LogEntry>, String> {
+    let after_cursor = format!("--after-cursor={}", cursor);
+    run_log_query(unit_name, user_mode, &[&after_cursor], filters, runner)
+}
+
+/// Fetches up to `lines` entries immediately preceding `cursor`, oldest
+/// first. journalctl has no `--before-cursor`; `--after-cursor` combined
+/// with `--reverse` walks backwards from the cursor, skipping the cursor
+/// entry itself, so the result just needs flipping back into order.
+pub fn fetch_log_entries_before_cursor(
+    unit_name: Option<&str>,
+    cursor: &str,
+    lines: usize,
+    user_mode: bool,
+    filters: &LogFilters,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<LogEntry>, String> {
+    let after_cursor = format!("--after-cursor={}", cursor);
+    let lines_str = lines.to_string();
+    let mut entries = run_log_query(
+        unit_name,
+        user_mode,
+        &[&after_cursor, "--reverse", "-n", &lines_str],
+        filters,
+        runner,
+    )?;
+    entries.reverse();
+    Ok(entries)
+}
 
-    let priority_str;
-    if let Some(p) = priority {
-        priority_str = p.to_string();
-        args.push("-p");
-        args.push(&priority_str);
+/// Counts every journal entry matching `filters` (typically with a `grep`
+/// built from the log search query), with no line limit. Only the cursor
+/// field is requested so the output stays small.
+pub fn count_journal_matches(
+    unit_name: Option<&str>,
+    user_mode: bool,
+    filters: &LogFilters,
+    runner: &dyn CommandRunner,
+) -> Result<usize, String> {
+    let output = run_journal_query(unit_name, user_mode, &["--output-fields=__CURSOR"], filters, runner)?;
+    if !output.success {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        return Err(format!("journalctl failed: {}", stderr.trim()));
     }
+    Ok(output.stdout.split(|&b| b == b'\n').filter(|line| !line.is_empty()).count())
+}
 
-    let since_value;
-    if let Some(since) = time_range.journalctl_since() {
-        since_value = since.to_string();
-        args.push("--since");
-        args.push(&since_value);
+/// Escapes PCRE metacharacters so `text` matches literally in journalctl's
+/// `--grep`.
+pub fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.^$|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
     }
+    escaped
+}
 
-    let output = run_journalctl(runner, &args)?;
+fn run_log_query(
+    unit_name: Option<&str>,
+    user_mode: bool,
+    position_args: &[&str],
+    filters: &LogFilters,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<LogEntry>, String> {
+    let output = run_journal_query(unit_name, user_mode, position_args, filters, runner)?;
+    if let Some(error) = journal_output_error(&output) {
+        return Err(error);
+    }
 
     let entries = String::from_utf8_lossy(&output.stdout)
         .lines()
@@ -696,45 +1297,134 @@ pub fn fetch_log_entries(
     Ok(entries)
 }
 
-pub fn fetch_log_entries_after_cursor(
+/// Shown in place of the logs when journalctl can't read the journal files
+/// as the current user.
+pub const JOURNAL_PERMISSION_HINT: &str =
+    "Permission denied \u{2014} try running as root or add yourself to systemd-journal";
+
+/// The error to report for a journalctl run that produced no entries,
+/// judged from its stderr: a friendly hint when access to the journal was
+/// refused, or stderr itself when it exited non-zero. Runs that returned
+/// entries are never errors, whatever the hints on stderr.
+fn journal_output_error(output: &CommandOutput) -> Option<String> {
+    if output.stdout.iter().any(|b| !b.is_ascii_whitespace()) {
+        return None;
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if is_journal_permission_error(&stderr) {
+        return Some(JOURNAL_PERMISSION_HINT.to_string());
+    }
+    let stderr = stderr.trim();
+    // A --grep with no matches exits non-zero without saying anything.
+    (!output.success && !stderr.is_empty()).then(|| format!("journalctl failed: {}", stderr))
+}
+
+/// journalctl's ways of saying the user may not read (all of) the journal:
+/// the hard failure, and the hint printed when only the user's own journal
+/// was readable.
+fn is_journal_permission_error(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    ["permission denied", "insufficient permissions", "not seeing messages from other users"]
+        .iter()
+        .any(|needle| stderr.contains(needle))
+}
+
+/// The journalctl arguments selecting where entries come from: the kernel,
+/// one unit, or (no arguments) the whole journal.
+fn journal_source_args(source: LogSource, unit_name: Option<&str>, user_mode: bool) -> Vec<&str> {
+    match (source, unit_name) {
+        (LogSource::Kernel, _) => vec!["-k"],
+        (LogSource::Unit, Some(name)) => {
+            let unit_flag = if user_mode { "--user-unit" } else { "-u" };
+            vec![unit_flag, name]
+        }
+        (LogSource::Unit, None) => Vec::new(),
+    }
+}
+
+/// Arguments for viewing logs in journalctl's own pager, jumped to the end
+/// (`-e`), with the same filters as the in-app viewer.
+pub fn journal_pager_args(unit_name: Option<&str>, user_mode: bool, filters: &LogFilters) -> Vec<String> {
+    let mut args: Vec<String> = journal_source_args(filters.source, unit_name, user_mode)
+        .into_iter()
+        .map(String::from)
+        .collect();
+    args.push("-e".to_string());
+    args.extend(filters.journalctl_args());
+    args
+}
+
+fn run_journal_query(
     unit_name: Option<&str>,
-    cursor: &str,
     user_mode: bool,
-    priority: Option<u8>,
-    time_range: TimeRange,
+    position_args: &[&str],
+    filters: &LogFilters,
     runner: &dyn CommandRunner,
-) -> Result<Vec<LogEntry>, String> {
-    let after_cursor = format!("--after-cursor={}", cursor);
-    let mut args = vec![&*after_cursor, "--no-pager", "--output=json"];
-    if let Some(name) = unit_name {
-        let unit_flag = if user_mode { "--user-unit" } else { "-u" };
-        args.insert(0, name);
-        args.insert(0, unit_flag);
-    }
+) -> Result<CommandOutput, String> {
+    let mut args = journal_source_args(filters.source, unit_name, user_mode);
+    args.extend_from_slice(position_args);
+    args.push("--no-pager");
+    args.push("--output=json");
 
-    let priority_str;
-    if let Some(p) = priority {
-        priority_str = p.to_string();
-        args.push("-p");
-        args.push(&priority_str);
-    }
+    let filter_args = filters.journalctl_args();
+    args.extend(filter_args.iter().map(String::as_str));
 
-    let since_value;
-    if let Some(since) = time_range.journalctl_since() {
-        since_value = since.to_string();
-        args.push("--since");
-        args.push(&since_value);
+    run_journalctl(runner, &args)
+}
+
+/// Byte ranges over `text` of case-insensitive matches of `query_lower`.
+///
+/// Works by lowercasing char-by-char while tracking the original byte offset
+/// of each char boundary, so that byte positions found in the lowered string
+/// can be mapped back to the correct slice in the original text — even when
+/// `to_lowercase()` changes the byte length of a character.
+pub fn search_match_ranges(text: &str, query_lower: &str) -> Vec<(usize, usize)> {
+    if query_lower.is_empty() {
+        return Vec::new();
     }
 
-    let output = run_journalctl(runner, &args)?;
+    // Build the lowered string and a mapping from lowered byte offset → original byte offset.
+    // `lower_to_orig[i]` gives the original byte offset that corresponds to lowered byte offset `i`.
+    let mut lowered = String::with_capacity(text.len());
+    let mut lower_to_orig: Vec<usize> = Vec::with_capacity(text.len() + 1);
 
-    let entries = String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(parse_journal_json_line)
-        .collect();
+    for (orig_byte, ch) in text.char_indices() {
+        for lc in ch.to_lowercase() {
+            let lc_start = lowered.len();
+            lowered.push(lc);
+            // Map each new byte in the lowered string to this char's original byte offset
+            for _ in lc_start..lowered.len() {
+                lower_to_orig.push(orig_byte);
+            }
+        }
+    }
+    // Sentinel: map the end-of-lowered-string position to end-of-original-string
+    lower_to_orig.push(text.len());
 
-    Ok(entries)
+    let mut ranges = Vec::new();
+    let mut lower_pos = 0;
+    while let Some(found) = lowered[lower_pos..].find(query_lower) {
+        let lower_start = lower_pos + found;
+        let lower_end = lower_start + query_lower.len();
+        ranges.push((lower_to_orig[lower_start], lower_to_orig[lower_end]));
+        lower_pos = lower_end;
+    }
+    ranges
+}
+
+/// Case-insensitive matches of `needle` that stand as a whole word: the
+/// characters on either side (if any) are not word characters (alphanumeric
+/// or `_`). "err" matches in "err: x" but not in "error" or "ferret".
+pub fn find_word_matches(haystack: &str, needle: &str) -> Vec<std::ops::Range<usize>> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    search_match_ranges(haystack, &needle.to_lowercase())
+        .into_iter()
+        .filter(|&(start, end)| {
+            !haystack[..start].chars().next_back().is_some_and(is_word)
+                && !haystack[end..].chars().next().is_some_and(is_word)
+        })
+        .map(|(start, end)| start..end)
+        .collect()
 }
 
 /// Parses ANSI SGR escape sequences out of a log message, returning the
@@ -904,6 +1594,8 @@ fn parse_journal_json_line(line: &str) -> LogEntry {
             invocation_id: None,
             cursor: None,
             unit: None,
+            hostname: None,
+            fields: HashMap::new(),
         };
     };
 
@@ -940,6 +1632,13 @@ fn parse_journal_json_line(line: &str) -> LogEntry {
 
     let unit = val["_SYSTEMD_UNIT"].as_str().map(|s| s.to_string());
 
+    let hostname = val["_HOSTNAME"].as_str().map(|s| s.to_string());
+
+    let fields = val
+        .as_object()
+        .map(|obj| obj.iter().map(|(key, value)| (key.clone(), journal_field_text(value))).collect())
+        .unwrap_or_default();
+
     LogEntry {
         timestamp,
         priority,
@@ -951,33 +1650,154 @@ fn parse_journal_json_line(line: &str) -> LogEntry {
         invocation_id,
         cursor,
         unit,
+        hostname,
+        fields,
     }
 }
 
-pub fn format_log_timestamp(timestamp_us: i64) -> String {
-    let secs = timestamp_us / 1_000_000;
-    let nsecs = ((timestamp_us % 1_000_000) * 1000) as u32;
-    match chrono::Local.timestamp_opt(secs, nsecs) {
-        chrono::LocalResult::Single(dt) => dt.format("%b %d %H:%M:%S").to_string(),
-        _ => String::new(),
-    }
-}
+/// A journal JSON field as text. Binary fields come as byte arrays and are
+/// decoded lossily, like MESSAGE.
+fn journal_field_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(arr) if arr.iter().all(|v| v.is_u64()) => {
+            let bytes: Vec<u8> = arr.iter().filter_map(|v| v.as_u64().map(|n| n as u8)).collect();
+            String::from_utf8_lossy(&bytes).to_string()
+        }
+        other => other.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampFormat {
+    Short,
+    Iso,
+    Relative,
+}
+
+impl TimestampFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimestampFormat::Short => "Short (Mon DD HH:MM:SS)",
+            TimestampFormat::Iso => "ISO-8601",
+            TimestampFormat::Relative => "Relative (5s ago)",
+        }
+    }
+}
+
+pub const TIMESTAMP_FORMATS: [TimestampFormat; 3] = [
+    TimestampFormat::Short,
+    TimestampFormat::Iso,
+    TimestampFormat::Relative,
+];
+
+/// File formats offered when exporting the loaded log entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogExportFormat {
+    Plain,
+    Csv,
+    Json,
+}
+
+impl LogExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogExportFormat::Plain => "Plain text",
+            LogExportFormat::Csv => "CSV",
+            LogExportFormat::Json => "JSON",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LogExportFormat::Plain => "log",
+            LogExportFormat::Csv => "csv",
+            LogExportFormat::Json => "json",
+        }
+    }
+}
+
+pub const LOG_EXPORT_FORMATS: [LogExportFormat; 3] =
+    [LogExportFormat::Plain, LogExportFormat::Csv, LogExportFormat::Json];
+
+/// Formats a journal timestamp. With `micros`, the absolute formats carry
+/// the sub-second part (e.g. `Nov 14 22:13:20.000123`) for telling apart
+/// lines logged within the same second; relative times ignore it.
+pub fn format_log_timestamp(timestamp_us: i64, format: TimestampFormat, use_utc: bool, micros: bool) -> String {
+    let now_us = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as i64)
+        .unwrap_or(0);
+    format_log_timestamp_at(timestamp_us, format, use_utc, micros, now_us)
+}
+
+fn format_log_timestamp_at(
+    timestamp_us: i64,
+    format: TimestampFormat,
+    use_utc: bool,
+    micros: bool,
+    now_us: i64,
+) -> String {
+    let fraction = if micros {
+        format!(".{:06}", timestamp_us.rem_euclid(1_000_000))
+    } else {
+        String::new()
+    };
+    let pattern = match format {
+        TimestampFormat::Short => format!("%b %d %H:%M:%S{}", fraction),
+        TimestampFormat::Iso => format!("%Y-%m-%dT%H:%M:%S{}%:z", fraction),
+        TimestampFormat::Relative => {
+            let diff_secs = now_us.saturating_sub(timestamp_us).max(0) / 1_000_000;
+            return format!("{} ago", format_duration_secs(diff_secs as u64));
+        }
+    };
+    let secs = timestamp_us.div_euclid(1_000_000);
+    let nsecs = (timestamp_us.rem_euclid(1_000_000) * 1000) as u32;
+    if use_utc {
+        format_in_zone(&chrono::Utc, secs, nsecs, &pattern)
+    } else {
+        format_in_zone(&chrono::Local, secs, nsecs, &pattern)
+    }
+}
 
-pub fn fetch_units(unit_type: UnitType, user_mode: bool, runner: &dyn CommandRunner) -> Result<Vec<SystemdUnit>, String> {
+fn format_in_zone<Tz: TimeZone>(tz: &Tz, secs: i64, nsecs: u32, pattern: &str) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    match tz.timestamp_opt(secs, nsecs) {
+        chrono::LocalResult::Single(dt) => dt.format(pattern).to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Fetches the unit list with details. `show_all` passes `--all`, listing
+/// inactive units too; without it systemctl lists only active and failed
+/// ones (plus those with pending jobs).
+pub fn fetch_units(
+    unit_type: UnitType,
+    user_mode: bool,
+    show_all: bool,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<SystemdUnit>, String> {
     // The unit list, detail entries, and file states come from independent
     // systemctl calls; fetch them concurrently so a remote runner (SSH) pays
     // one network round trip instead of three.
-    let (units, timer_entries, socket_entries, file_states) = std::thread::scope(|s| {
-        let timers = (unit_type == UnitType::Timer)
+    let (units, timer_entries, socket_entries, socket_stats, file_states) = std::thread::scope(|s| {
+        // The mixed list gets timer and socket details too; they are keyed
+        // by unit name, so other units are left alone.
+        let timers = matches!(unit_type, UnitType::Timer | UnitType::All)
             .then(|| s.spawn(|| fetch_timer_entries(user_mode, runner)));
-        let sockets = (unit_type == UnitType::Socket)
+        let sockets = matches!(unit_type, UnitType::Socket | UnitType::All)
             .then(|| s.spawn(|| fetch_socket_entries(user_mode, runner)));
+        let socket_stats = (unit_type == UnitType::Socket)
+            .then(|| s.spawn(|| fetch_socket_stats(user_mode, runner)));
         let file_states = s.spawn(|| fetch_unit_file_states(unit_type, user_mode, runner));
-        let units = fetch_unit_list(unit_type, user_mode, runner);
+        let units = fetch_unit_list(unit_type, user_mode, show_all, runner);
         (
             units,
             timers.map_or_else(Vec::new, |h| h.join().unwrap_or_default()),
             sockets.map_or_else(Vec::new, |h| h.join().unwrap_or_default()),
+            socket_stats.map_or_else(HashMap::new, |h| h.join().unwrap_or_default()),
             file_states.join().unwrap_or_default(),
         )
     });
@@ -985,17 +1805,28 @@ pub fn fetch_units(unit_type: UnitType, user_mode: bool, runner: &dyn CommandRun
     let mut units = units?;
     apply_timer_details(&mut units, &timer_entries);
     apply_socket_details(&mut units, &socket_entries);
+    apply_socket_stats(&mut units, &socket_stats);
     apply_file_states(&mut units, &file_states);
     Ok(units)
 }
 
-fn fetch_unit_list(unit_type: UnitType, user_mode: bool, runner: &dyn CommandRunner) -> Result<Vec<SystemdUnit>, String> {
+fn fetch_unit_list(
+    unit_type: UnitType,
+    user_mode: bool,
+    show_all: bool,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<SystemdUnit>, String> {
     let mut args = Vec::new();
     if user_mode {
         args.push("--user");
     }
-    let type_arg = format!("--type={}", unit_type.systemctl_type());
-    args.extend(["list-units", &type_arg, "--all", "--no-pager", "--output=json"]);
+    let type_arg = unit_type.type_filter_arg();
+    args.push("list-units");
+    args.extend(type_arg.as_deref());
+    if show_all {
+        args.push("--all");
+    }
+    args.extend(["--no-pager", "--output=json"]);
     let output = run_systemctl(runner, &args)?;
 
     if !output.success {
@@ -1008,10 +1839,16 @@ fn fetch_unit_list(unit_type: UnitType, user_mode: bool, runner: &dyn CommandRun
     serde_json::from_slice(&output.stdout).map_err(|e| format!("Failed to parse JSON: {}", e))
 }
 
+/// A row of `systemctl list-timers --output=json`. Timestamps are
+/// wall-clock microseconds; `last` is null (or 0) for a timer that never
+/// fired. The `left`/`passed` fields are not read: they would freeze at
+/// fetch time, so the columns derive them from `next`/`last` on each draw.
 #[derive(Deserialize)]
 struct TimerEntry {
     unit: String,
     next: u64,
+    #[serde(default)]
+    last: Option<u64>,
 }
 
 fn fetch_timer_entries(user_mode: bool, runner: &dyn CommandRunner) -> Vec<TimerEntry> {
@@ -1035,27 +1872,70 @@ fn apply_timer_details(units: &mut [SystemdUnit], entries: &[TimerEntry]) {
 
     for unit in units.iter_mut() {
         if let Some(entry) = map.get(unit.unit.as_str()) {
-            unit.detail = Some(if entry.next == 0 {
-                "next: n/a".to_string()
-            } else {
-                format!("next: {}", format_relative_time(entry.next))
-            });
+            unit.detail = Some(format_next_trigger(entry.next, now_micros()));
+            unit.next_elapse_us = Some(entry.next);
+            unit.last_trigger_us = Some(entry.last.unwrap_or(0));
         }
     }
 }
 
-pub fn format_relative_time(target_us: u64) -> String {
-    let now_us = SystemTime::now()
+/// "next: 2h 5m" for a timer's next trigger (`0` means none scheduled).
+pub fn format_next_trigger(next_us: u64, now_us: u64) -> String {
+    if next_us == 0 {
+        "next: n/a".to_string()
+    } else {
+        format!("next: {}", format_relative_time_at(next_us, now_us))
+    }
+}
+
+/// A timer's absolute next/last time for the timer columns, in local time
+/// ("Tue 03-05 14:07"); `n/a` for 0.
+pub fn format_timer_time(us: u64) -> String {
+    if us == 0 {
+        return "n/a".to_string();
+    }
+    match chrono::Local.timestamp_micros(us as i64).single() {
+        Some(dt) => dt.format("%a %m-%d %H:%M").to_string(),
+        None => "n/a".to_string(),
+    }
+}
+
+/// Time until the next trigger (the LEFT column).
+pub fn format_time_left(next_us: u64, now_us: u64) -> String {
+    if next_us == 0 {
+        "n/a".to_string()
+    } else {
+        format_relative_time_at(next_us, now_us)
+    }
+}
+
+/// Time since the last trigger (the PASSED column).
+pub fn format_time_passed(last_us: u64, now_us: u64) -> String {
+    if last_us == 0 {
+        "n/a".to_string()
+    } else {
+        format!("{} ago", format_duration_secs(now_us.saturating_sub(last_us) / 1_000_000))
+    }
+}
+
+/// Current wall-clock time in microseconds since the epoch.
+pub fn now_micros() -> u64 {
+    SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_micros() as u64)
-        .unwrap_or(0);
+        .unwrap_or(0)
+}
 
+fn format_relative_time_at(target_us: u64, now_us: u64) -> String {
     if target_us <= now_us {
         return "elapsed".to_string();
     }
 
-    let diff_secs = (target_us - now_us) / 1_000_000;
+    format_duration_secs((target_us - now_us) / 1_000_000)
+}
 
+/// Compact two-unit duration ("2d 3h", "5m 10s", "42s").
+fn format_duration_secs(diff_secs: u64) -> String {
     let days = diff_secs / 86400;
     let hours = (diff_secs % 86400) / 3600;
     let minutes = (diff_secs % 3600) / 60;
@@ -1115,8 +1995,10 @@ fn fetch_unit_file_states(unit_type: UnitType, user_mode: bool, runner: &dyn Com
     if user_mode {
         args.push("--user");
     }
-    let type_arg = format!("--type={}", unit_type.systemctl_type());
-    args.extend(["list-unit-files", &type_arg, "--no-pager", "--output=json"]);
+    let type_arg = unit_type.type_filter_arg();
+    args.push("list-unit-files");
+    args.extend(type_arg.as_deref());
+    args.extend(["--no-pager", "--output=json"]);
 
     let Ok(output) = run_systemctl(runner, &args) else {
         return HashMap::new();
@@ -1153,6 +2035,146 @@ fn apply_file_states(units: &mut [SystemdUnit], states: &HashMap<String, String>
     }
 }
 
+/// When each active unit of the type entered the active state, as
+/// `CLOCK_MONOTONIC` microseconds (`ActiveEnterTimestampMonotonic`). One
+/// `systemctl show` for every loaded unit, much lighter than fetching full
+/// properties. Inactive units are left out: their timestamp is stale.
+pub fn fetch_active_timestamps(
+    unit_type: UnitType,
+    user_mode: bool,
+    runner: &dyn CommandRunner,
+) -> HashMap<String, i64> {
+    let mut args = Vec::new();
+    if user_mode {
+        args.push("--user");
+    }
+    let pattern = unit_type.show_pattern();
+    args.extend([
+        "show",
+        &pattern,
+        "--property=Id,ActiveState,ActiveEnterTimestampMonotonic",
+        "--no-pager",
+    ]);
+
+    let Ok(output) = run_systemctl(runner, &args) else {
+        return HashMap::new();
+    };
+    if !output.success {
+        return HashMap::new();
+    }
+    parse_active_timestamps(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses `systemctl show` output for several units: one `key=value` block
+/// per unit, separated by blank lines.
+fn parse_active_timestamps(stdout: &str) -> HashMap<String, i64> {
+    let mut timestamps = HashMap::new();
+    for block in parse_show_blocks(stdout) {
+        let timestamp = block
+            .get("ActiveEnterTimestampMonotonic")
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(0);
+        if let Some(id) = block.get("Id")
+            && block.get("ActiveState") == Some(&"active")
+            && timestamp > 0
+        {
+            timestamps.insert(id.to_string(), timestamp);
+        }
+    }
+    timestamps
+}
+
+/// Splits `systemctl show` output for several units into one `key -> value`
+/// map per unit.
+fn parse_show_blocks(stdout: &str) -> Vec<HashMap<&str, &str>> {
+    stdout
+        .split("\n\n")
+        .map(|block| block.lines().filter_map(|line| line.split_once('=')).collect::<HashMap<_, _>>())
+        .filter(|block| !block.is_empty())
+        .collect()
+}
+
+/// Connection counters of a socket with `Accept=yes`, which spawns one
+/// service instance per connection. Other sockets hand the listening socket
+/// to a single service and keep no counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SocketStats {
+    /// Connections currently open.
+    pub connections: u32,
+    /// Connections accepted since the socket started.
+    pub accepted: u32,
+}
+
+/// `NConnections`/`NAccepted` for every loaded socket unit in one
+/// `systemctl show`; sockets without `Accept=yes` are left out.
+fn fetch_socket_stats(user_mode: bool, runner: &dyn CommandRunner) -> HashMap<String, SocketStats> {
+    let mut args = Vec::new();
+    if user_mode {
+        args.push("--user");
+    }
+    args.extend([
+        "show",
+        "*.socket",
+        "--property=Id,Accept,NConnections,NAccepted",
+        "--no-pager",
+    ]);
+
+    let Ok(output) = run_systemctl(runner, &args) else {
+        return HashMap::new();
+    };
+    if !output.success {
+        return HashMap::new();
+    }
+    parse_socket_stats(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_socket_stats(stdout: &str) -> HashMap<String, SocketStats> {
+    let count = |block: &HashMap<&str, &str>, key: &str| -> u32 {
+        block.get(key).and_then(|v| v.parse().ok()).unwrap_or(0)
+    };
+    parse_show_blocks(stdout)
+        .into_iter()
+        .filter(|block| block.get("Accept") == Some(&"yes"))
+        .filter_map(|block| {
+            let id = block.get("Id")?.to_string();
+            let stats = SocketStats {
+                connections: count(&block, "NConnections"),
+                accepted: count(&block, "NAccepted"),
+            };
+            Some((id, stats))
+        })
+        .collect()
+}
+
+fn apply_socket_stats(units: &mut [SystemdUnit], stats: &HashMap<String, SocketStats>) {
+    for unit in units.iter_mut() {
+        unit.socket_stats = stats.get(&unit.unit).copied();
+    }
+}
+
+/// The host's current monotonic time in microseconds, read from
+/// `/proc/uptime` through the runner so it also works over SSH. Uptime keeps
+/// counting during suspend, so elapsed times across a suspend come out a
+/// little long.
+pub fn fetch_monotonic_now(runner: &dyn CommandRunner) -> Option<i64> {
+    let output = runner.run("cat", &["/proc/uptime"]).ok()?;
+    if !output.success {
+        return None;
+    }
+    parse_uptime_usec(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_uptime_usec(uptime: &str) -> Option<i64> {
+    let secs = uptime.split_whitespace().next()?.parse::<f64>().ok()?;
+    Some((secs * 1_000_000.0) as i64)
+}
+
+/// How long a unit has been active ("2h 5m"), from its monotonic enter
+/// timestamp and the current monotonic time, both in microseconds.
+pub fn format_elapsed(enter_usec: i64, now_usec: i64) -> String {
+    format_duration_secs((now_usec - enter_usec).max(0) as u64 / 1_000_000)
+}
+
 fn parse_timer_specs(raw: &str) -> Vec<String> {
     if raw.is_empty() {
         return Vec::new();
@@ -1173,6 +2195,60 @@ fn parse_timer_specs(raw: &str) -> Vec<String> {
         .collect()
 }
 
+/// The unparsed `systemctl show` output for a unit, every property
+/// included, for attaching to bug reports.
+pub fn fetch_raw_properties(unit_name: &str, user_mode: bool, runner: &dyn CommandRunner) -> Result<String, String> {
+    let mut args = Vec::new();
+    if user_mode {
+        args.push("--user");
+    }
+    args.extend(["show", unit_name, "--no-pager"]);
+    let output = run_systemctl(runner, &args)?;
+    if !output.success {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("systemctl show failed: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Where a unit's raw properties are saved: `systemdmgr-<unit>-<time>.txt`
+/// in `dir`.
+pub fn raw_properties_path(dir: &Path, unit_name: &str, now: chrono::DateTime<chrono::Local>) -> PathBuf {
+    dir.join(format!("systemdmgr-{}-{}.txt", unit_name, now.format("%Y%m%d-%H%M%S")))
+}
+
+/// Where exported logs are saved: `systemdmgr-<source>-logs-<time>.<ext>`
+/// in `dir`, `source` being the unit name or `journal`/`kernel`.
+pub fn log_export_path(
+    dir: &Path,
+    source: &str,
+    format: LogExportFormat,
+    now: chrono::DateTime<chrono::Local>,
+) -> PathBuf {
+    dir.join(format!(
+        "systemdmgr-{}-logs-{}.{}",
+        source,
+        now.format("%Y%m%d-%H%M%S"),
+        format.extension()
+    ))
+}
+
+/// Writes `content` to a new file readable only by the user. Saved output
+/// can hold secrets (`Environment=` values, log messages) and lands in the
+/// shared temp dir, so an existing file or planted symlink at `path` is an
+/// error rather than something to write through.
+pub fn save_text_file(path: &Path, content: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
 pub fn fetch_unit_properties(unit_name: &str, user_mode: bool, runner: &dyn CommandRunner) -> UnitProperties {
     let mut args = Vec::new();
     if user_mode {
@@ -1188,18 +2264,28 @@ pub fn fetch_unit_properties(unit_name: &str, user_mode: bool, runner: &dyn Comm
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let map: HashMap<&str, &str> = stdout
-        .lines()
-        .filter_map(|line| line.split_once('='))
-        .collect();
+    let mut map: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (key, value) in stdout.lines().filter_map(|line| line.split_once('=')) {
+        map.entry(key).or_default().push(value);
+    }
+    parse_unit_properties(&map)
+}
+
+/// Builds `UnitProperties` from `systemctl show` output keyed by property
+/// name. Most properties appear once; list-valued ones like
+/// `EnvironmentFiles` repeat the key on one line per entry.
+fn parse_unit_properties(map: &HashMap<&str, Vec<&str>>) -> UnitProperties {
+    let value = |key: &str| -> &str {
+        map.get(key).and_then(|values| values.last()).copied().unwrap_or("")
+    };
 
     let get = |key: &str| -> String {
-        map.get(key).unwrap_or(&"").to_string()
+        value(key).to_string()
     };
 
     let parse_optional_u64 = |key: &str| -> Option<u64> {
-        let val = map.get(key).unwrap_or(&"");
-        if val.is_empty() || *val == "[not set]" || *val == "infinity" {
+        let val = value(key);
+        if val.is_empty() || val == "[not set]" || val == "infinity" {
             None
         } else {
             val.parse::<u64>().ok()
@@ -1207,12 +2293,13 @@ pub fn fetch_unit_properties(unit_name: &str, user_mode: bool, runner: &dyn Comm
     };
 
     let split_deps = |key: &str| -> Vec<String> {
-        let val = map.get(key).unwrap_or(&"");
-        if val.is_empty() {
-            Vec::new()
-        } else {
-            val.split_whitespace().map(|s| s.to_string()).collect()
-        }
+        value(key).split_whitespace().map(|s| s.to_string()).collect()
+    };
+
+    let exec_commands = |key: &str| -> Vec<String> {
+        map.get(key)
+            .map(|values| values.iter().filter_map(|v| parse_exec_command(v)).collect())
+            .unwrap_or_default()
     };
 
     UnitProperties {
@@ -1223,13 +2310,12 @@ pub fn fetch_unit_properties(unit_name: &str, user_mode: bool, runner: &dyn Comm
         sub_state: get("SubState"),
         load_state: get("LoadState"),
         description: get("Description"),
-        main_pid: map
-            .get("MainPID")
-            .unwrap_or(&"0")
-            .parse::<u32>()
-            .unwrap_or(0),
+        main_pid: value("MainPID").parse::<u32>().unwrap_or(0),
         exec_main_start_timestamp: get("ExecMainStartTimestamp"),
+        n_restarts: value("NRestarts").parse::<u32>().unwrap_or(0),
+        exec_main_status: value("ExecMainStatus").parse::<i32>().unwrap_or(0),
         memory_current: parse_optional_u64("MemoryCurrent"),
+        memory_max: parse_optional_u64("MemoryMax").or_else(|| parse_optional_u64("MemoryLimit")),
         cpu_usage_nsec: parse_optional_u64("CPUUsageNSec"),
         requires: split_deps("Requires"),
         wants: split_deps("Wants"),
@@ -1251,9 +2337,90 @@ pub fn fetch_unit_properties(unit_name: &str, user_mode: bool, runner: &dyn Comm
         accept: get("Accept"),
         n_connections: get("NConnections"),
         n_accepted: get("NAccepted"),
+        environment: parse_environment(value("Environment")),
+        environment_files: map
+            .get("EnvironmentFiles")
+            .map(|values| values.iter().filter_map(|v| parse_environment_file(v)).collect())
+            .unwrap_or_default(),
+        exec_start: exec_commands("ExecStart"),
+        exec_stop: exec_commands("ExecStop"),
+        exec_reload: exec_commands("ExecReload"),
     }
 }
 
+/// Extracts a readable command line from an `Exec*` property value such as
+/// `{ path=/usr/sbin/sshd ; argv[]=/usr/sbin/sshd -D ; ignore_errors=no ; ... }`.
+/// Falls back to the binary path when argv is missing, and prefixes `-` when
+/// failures are ignored, matching unit file syntax.
+fn parse_exec_command(raw: &str) -> Option<String> {
+    let body = raw.trim().trim_start_matches('{').trim_end_matches('}').trim();
+    if body.is_empty() {
+        return None;
+    }
+    let field = |name: &str| -> Option<&str> {
+        let start = body.find(name)? + name.len();
+        let rest = &body[start..];
+        // argv may itself contain " ; ", so stop at the next known field name
+        let end = [" ; ignore_errors=", " ; start_time=", " ; flags="]
+            .iter()
+            .filter_map(|sep| rest.find(sep))
+            .chain(rest.find(" ; argv[]=").filter(|_| name == "path="))
+            .min()
+            .unwrap_or(rest.len());
+        Some(rest[..end].trim())
+    };
+    let command = field("argv[]=")
+        .filter(|argv| !argv.is_empty())
+        .or_else(|| field("path="))
+        .filter(|cmd| !cmd.is_empty())?;
+    let ignore_errors = field("ignore_errors=").is_some_and(|v| v.starts_with("yes"));
+    Some(if ignore_errors { format!("-{}", command) } else { command.to_string() })
+}
+
+/// Splits the `Environment` property into `KEY=value` entries. systemctl
+/// separates entries with spaces and double-quotes any entry that itself
+/// contains whitespace, e.g. `A=1 "B=x y"`.
+fn parse_environment(raw: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_quotes => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    entries.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        entries.push(current);
+    }
+    entries
+}
+
+/// Formats one `EnvironmentFiles` entry (`/etc/default/foo (ignore_errors=yes)`)
+/// the way it is written in a unit file, with a leading `-` for optional files.
+fn parse_environment_file(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    let (path, optional) = match raw.rsplit_once(" (ignore_errors=") {
+        Some((path, flag)) => (path, flag.trim_end_matches(')') == "yes"),
+        None => (raw, false),
+    };
+    Some(if optional { format!("-{}", path) } else { path.to_string() })
+}
+
 pub fn fetch_unit_file_content(unit: &str, user_mode: bool, runner: &dyn CommandRunner) -> Result<Vec<String>, String> {
     let mut args = Vec::new();
     if user_mode {
@@ -1272,6 +2439,204 @@ pub fn fetch_unit_file_content(unit: &str, user_mode: bool, runner: &dyn Command
     Ok(stdout.lines().map(|l| l.to_string()).collect())
 }
 
+/// The units `unit` depends on, recursively, from `systemctl
+/// list-dependencies --plain`; the unit itself comes first.
+pub fn fetch_dependencies(unit: &str, user_mode: bool, runner: &dyn CommandRunner) -> Result<Vec<String>, String> {
+    list_dependencies(unit, user_mode, false, runner)
+}
+
+/// The units that depend on `unit`, recursively, from `systemctl
+/// list-dependencies --reverse --plain`, without the unit itself.
+pub fn fetch_reverse_dependencies(
+    unit: &str,
+    user_mode: bool,
+    runner: &dyn CommandRunner,
+) -> Result<Vec<String>, String> {
+    let mut units = list_dependencies(unit, user_mode, true, runner)?;
+    units.retain(|name| name != unit);
+    Ok(units)
+}
+
+fn list_dependencies(unit: &str, user_mode: bool, reverse: bool, runner: &dyn CommandRunner) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    if user_mode {
+        args.push("--user");
+    }
+    args.extend(["list-dependencies", unit, "--plain", "--no-pager"]);
+    if reverse {
+        args.push("--reverse");
+    }
+    let output = run_systemctl(runner, &args)?;
+    if !output.success {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("systemctl list-dependencies failed: {}", stderr.trim()));
+    }
+    Ok(parse_dependency_list(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Unit names from `list-dependencies` output, in order and without
+/// repeats. `--plain` only indents, but tree glyphs and state bullets are
+/// stripped too in case a systemd version adds them.
+fn parse_dependency_list(stdout: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let name = line
+                .trim_start_matches(|c: char| c.is_whitespace() || "\u{25cf}\u{25cb}\u{2514}\u{251c}\u{2502}\u{2500}*".contains(c))
+                .trim_end();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .filter(|name| seen.insert(name.clone()))
+        .collect()
+}
+
+/// The report of `systemd-analyze [--user] security <unit>`: one line per
+/// sandboxing setting, then the overall exposure level. Only services have
+/// a sandbox to rate.
+pub fn fetch_security_analysis(unit: &str, user_mode: bool, runner: &dyn CommandRunner) -> Result<Vec<String>, String> {
+    if !unit.ends_with(".service") {
+        return Err(format!("Security analysis only applies to services, not {}", unit));
+    }
+    let mut args = Vec::new();
+    if user_mode {
+        args.push("--user");
+    }
+    args.extend(["security", unit, "--no-pager"]);
+    let output = runner.run("systemd-analyze", &args)?;
+    if !output.success {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("systemd-analyze security failed: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect())
+}
+
+/// The score and verdict from the report's last line, e.g. `("9.6",
+/// "UNSAFE")` from "→ Overall exposure level for foo.service: 9.6 UNSAFE 😨".
+pub fn parse_security_overall(lines: &[String]) -> Option<(String, String)> {
+    let line = lines.iter().rev().find(|l| l.contains("Overall exposure level"))?;
+    let (_, rest) = line.rsplit_once(": ")?;
+    let mut words = rest.split_whitespace();
+    let score = words.next()?;
+    score.parse::<f32>().ok()?;
+    Some((score.to_string(), words.next().unwrap_or_default().to_string()))
+}
+
+/// A unit file listing for the viewer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitFile {
+    /// The `FragmentPath` the unit was loaded from, if it has one on disk.
+    pub fragment_path: Option<String>,
+    pub lines: Vec<String>,
+}
+
+/// The unit's fragment followed by its drop-ins, in the order systemd
+/// applies them, each file under a `# <path>` header. Units without a
+/// fragment on disk (transient or generated) fall back to `systemctl cat`.
+pub fn fetch_unit_file_with_dropins(
+    unit: &str,
+    user_mode: bool,
+    runner: &dyn CommandRunner,
+) -> Result<UnitFile, String> {
+    let mut args = Vec::new();
+    if user_mode {
+        args.push("--user");
+    }
+    args.extend(["show", unit, "--property=FragmentPath,DropInPaths", "--no-pager"]);
+
+    let output = run_systemctl(runner, &args)?;
+    if !output.success {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("systemctl show failed: {}", stderr.trim()));
+    }
+
+    let (fragment_path, drop_ins) = parse_unit_file_paths(&String::from_utf8_lossy(&output.stdout));
+    if fragment_path.is_none() && drop_ins.is_empty() {
+        return Ok(UnitFile {
+            fragment_path: None,
+            lines: fetch_unit_file_content(unit, user_mode, runner)?,
+        });
+    }
+    let files: Vec<(String, Result<String, String>)> = fragment_path
+        .iter()
+        .chain(&drop_ins)
+        .map(|path| (path.clone(), read_unit_file(path, runner)))
+        .collect();
+    Ok(UnitFile {
+        fragment_path,
+        lines: assemble_unit_files(&files),
+    })
+}
+
+/// Reads a file through the runner, so `--ssh` sessions see the remote host's
+/// copy.
+fn read_unit_file(path: &str, runner: &dyn CommandRunner) -> Result<String, String> {
+    let output = runner.run("cat", &["--", path])?;
+    if !output.success {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `FragmentPath` (when set) and the `DropInPaths` entries.
+fn parse_unit_file_paths(stdout: &str) -> (Option<String>, Vec<String>) {
+    let mut fragment = None;
+    let mut drop_ins = Vec::new();
+    for (key, value) in stdout.lines().filter_map(|line| line.split_once('=')) {
+        match key {
+            "FragmentPath" if !value.is_empty() => fragment = Some(value.to_string()),
+            "DropInPaths" => drop_ins.extend(value.split_whitespace().map(String::from)),
+            _ => {}
+        }
+    }
+    (fragment, drop_ins)
+}
+
+/// Joins files into one listing, `# <path>` above each and a blank line
+/// between them. A file that could not be read shows the error in its
+/// header instead of content.
+fn assemble_unit_files(files: &[(String, Result<String, String>)]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (i, (path, content)) in files.iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        match content {
+            Ok(content) => {
+                lines.push(format!("# {}", path));
+                lines.extend(content.lines().map(String::from));
+            }
+            Err(e) => lines.push(format!("# {}: {}", path, e)),
+        }
+    }
+    lines
+}
+
+/// Arguments for `systemctl edit`, which opens a drop-in override for the
+/// unit in `$EDITOR`.
+pub fn unit_edit_args(unit: &str, user_mode: bool) -> Vec<&str> {
+    let mut args = vec!["edit"];
+    if user_mode {
+        args.push("--user");
+    }
+    args.push(unit);
+    args
+}
+
+/// Memory usage as a percentage of the unit's limit. `None` when there is no
+/// usable limit (zero, or `u64::MAX` which some systemd versions report in
+/// place of "infinity").
+pub fn memory_percent(current: u64, max: u64) -> Option<u8> {
+    if max == 0 || max == u64::MAX {
+        return None;
+    }
+    let percent = (current as u128 * 100 / max as u128).min(100);
+    Some(percent as u8)
+}
+
 pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = 1024 * KB;
@@ -1297,10 +2662,213 @@ pub fn format_cpu_time(nsec: u64) -> String {
     }
 }
 
+/// Runners for the tests of every module.
+#[cfg(test)]
+pub mod test_runners {
+    use super::{CommandOutput, CommandRunner};
+
+    /// Returns the same output for every command.
+    pub struct CannedRunner {
+        pub success: bool,
+        pub stdout: &'static str,
+        pub stderr: &'static str,
+    }
+
+    impl CannedRunner {
+        /// Succeeds with `stdout` for every command.
+        pub fn ok(stdout: &'static str) -> Self {
+            CannedRunner { success: true, stdout, stderr: "" }
+        }
+    }
+
+    impl CommandRunner for CannedRunner {
+        fn run(&self, _program: &str, _args: &[&str]) -> Result<CommandOutput, String> {
+            Ok(CommandOutput {
+                success: self.success,
+                stdout: self.stdout.as_bytes().to_vec(),
+                stderr: self.stderr.as_bytes().to_vec(),
+            })
+        }
+    }
+
+    /// Fails every command before it runs, so tests never reach the host's
+    /// systemd.
+    pub struct UnavailableRunner;
+
+    impl CommandRunner for UnavailableRunner {
+        fn run(&self, program: &str, _args: &[&str]) -> Result<CommandOutput, String> {
+            Err(format!("{} is not available in tests", program))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::test_runners::CannedRunner;
     use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    /// Records the arguments of every command and returns empty success.
+    #[derive(Default)]
+    struct RecordingRunner {
+        calls: Mutex<Vec<Vec<String>>>,
+        stdout: Vec<u8>,
+    }
+
+    impl RecordingRunner {
+        fn last_args(&self) -> Vec<String> {
+            self.calls.lock().unwrap().last().cloned().unwrap_or_default()
+        }
+    }
+
+    impl CommandRunner for RecordingRunner {
+        fn run(&self, _program: &str, args: &[&str]) -> Result<CommandOutput, String> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(args.iter().map(|a| a.to_string()).collect());
+            Ok(CommandOutput {
+                success: true,
+                stdout: self.stdout.clone(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_unit_file_paths_fragment_then_dropins() {
+        let stdout = "FragmentPath=/usr/lib/systemd/system/foo.service\n\
+                      DropInPaths=/etc/systemd/system/foo.service.d/10-a.conf /run/systemd/system/foo.service.d/20-b.conf\n";
+        let (fragment, drop_ins) = parse_unit_file_paths(stdout);
+        assert_eq!(fragment.as_deref(), Some("/usr/lib/systemd/system/foo.service"));
+        assert_eq!(
+            drop_ins,
+            [
+                "/etc/systemd/system/foo.service.d/10-a.conf",
+                "/run/systemd/system/foo.service.d/20-b.conf",
+            ]
+        );
+        assert_eq!(parse_unit_file_paths("FragmentPath=\nDropInPaths=\n"), (None, Vec::new()));
+    }
+
+    #[test]
+    fn test_assemble_unit_files_separates_with_path_headers() {
+        let files = vec![
+            ("/usr/lib/systemd/system/foo.service".to_string(), Ok("[Service]\nExecStart=/bin/foo\n".to_string())),
+            ("/etc/systemd/system/foo.service.d/override.conf".to_string(), Ok("[Service]\nRestart=always".to_string())),
+            ("/etc/systemd/system/foo.service.d/secret.conf".to_string(), Err("Permission denied".to_string())),
+        ];
+        assert_eq!(
+            assemble_unit_files(&files),
+            [
+                "# /usr/lib/systemd/system/foo.service",
+                "[Service]",
+                "ExecStart=/bin/foo",
+                "",
+                "# /etc/systemd/system/foo.service.d/override.conf",
+                "[Service]",
+                "Restart=always",
+                "",
+                "# /etc/systemd/system/foo.service.d/secret.conf: Permission denied",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fetch_unit_file_with_dropins_show_failure() {
+        let runner = CannedRunner { success: false, stdout: "", stderr: "Unit foo.service not found." };
+        assert_eq!(
+            fetch_unit_file_with_dropins("foo.service", false, &runner),
+            Err("systemctl show failed: Unit foo.service not found.".to_string())
+        );
+    }
+
+    const SECURITY_SAMPLE: &str = "  NAME                                                        DESCRIPTION                                                             EXPOSURE
+\u{2717} PrivateNetwork=                                             Service has access to the host's network                                     0.5
+\u{2713} User=/DynamicUser=                                          Service runs under a static non-root user identity
+\u{2717} ProtectSystem=                                              Service has full access to the OS file hierarchy                             0.2
+
+\u{2192} Overall exposure level for nginx.service: 9.2 UNSAFE \u{1f628}
+";
+
+    #[test]
+    fn test_fetch_security_analysis_args() {
+        let runner = RecordingRunner {
+            calls: Mutex::new(Vec::new()),
+            stdout: SECURITY_SAMPLE.as_bytes().to_vec(),
+        };
+        let lines = fetch_security_analysis("nginx.service", true, &runner).unwrap();
+        assert_eq!(runner.last_args(), ["--user", "security", "nginx.service", "--no-pager"]);
+        assert_eq!(lines.len(), 6);
+        assert!(lines[1].starts_with("\u{2717} PrivateNetwork="));
+    }
+
+    #[test]
+    fn test_fetch_security_analysis_rejects_non_services() {
+        let runner = RecordingRunner::default();
+        let err = fetch_security_analysis("backup.timer", false, &runner).unwrap_err();
+        assert!(err.contains("only applies to services"));
+        assert!(runner.calls.lock().unwrap().is_empty(), "nothing is run");
+    }
+
+    #[test]
+    fn test_parse_security_overall() {
+        let lines: Vec<String> = SECURITY_SAMPLE.lines().map(String::from).collect();
+        assert_eq!(parse_security_overall(&lines), Some(("9.2".into(), "UNSAFE".into())));
+        let ok = vec!["\u{2192} Overall exposure level for sshd.service: 1.9 OK \u{1f642}".to_string()];
+        assert_eq!(parse_security_overall(&ok), Some(("1.9".into(), "OK".into())));
+        assert_eq!(parse_security_overall(&lines[..4]), None);
+    }
+
+    #[test]
+    fn test_parse_dependency_list_plain() {
+        let stdout = "sshd.service\n  -.mount\n  system.slice\n  sysinit.target\n    -.mount\n    dev-hugepages.mount\n";
+        assert_eq!(
+            parse_dependency_list(stdout),
+            ["sshd.service", "-.mount", "system.slice", "sysinit.target", "dev-hugepages.mount"]
+        );
+    }
+
+    #[test]
+    fn test_parse_dependency_list_reverse() {
+        let stdout = "dbus.socket\n  dbus.service\n    systemd-logind.service\n  sockets.target\n    basic.target\n      multi-user.target\n        graphical.target\n  systemd-logind.service\n";
+        assert_eq!(
+            parse_dependency_list(stdout),
+            [
+                "dbus.socket",
+                "dbus.service",
+                "systemd-logind.service",
+                "sockets.target",
+                "basic.target",
+                "multi-user.target",
+                "graphical.target",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fetch_reverse_dependencies_args_and_drops_unit() {
+        let runner = RecordingRunner {
+            calls: Mutex::new(Vec::new()),
+            stdout: b"dbus.socket\n  dbus.service\n  sockets.target\n".to_vec(),
+        };
+        let units = fetch_reverse_dependencies("dbus.socket", true, &runner).unwrap();
+        assert_eq!(units, ["dbus.service", "sockets.target"]);
+        assert_eq!(
+            runner.last_args(),
+            ["--no-ask-password", "--user", "list-dependencies", "dbus.socket", "--plain", "--no-pager", "--reverse"]
+        );
+    }
+
+    #[test]
+    fn test_parse_dependency_list_strips_tree_glyphs() {
+        let stdout = "sshd.service\n\u{25cf} \u{251c}\u{2500}system.slice\n\u{25cb} \u{2514}\u{2500}sysinit.target\n\n";
+        assert_eq!(
+            parse_dependency_list(stdout),
+            ["sshd.service", "system.slice", "sysinit.target"]
+        );
+    }
 
     fn make_unit(sub: &str) -> SystemdUnit {
         SystemdUnit {
@@ -1311,9 +2879,63 @@ mod tests {
             description: "Test".into(),
             detail: None,
             file_state: None,
+            next_elapse_us: None,
+            last_trigger_us: None,
+            socket_stats: None,
         }
     }
 
+    #[test]
+    fn test_needs_privilege() {
+        assert!(needs_privilege("Failed to start nginx.service: Interactive authentication required."));
+        assert!(needs_privilege("Failed to stop nginx.service: Access denied"));
+        assert!(!needs_privilege("Failed to start nginx.service: Unit nginx.service not found."));
+        assert!(!needs_privilege(""));
+    }
+
+    #[test]
+    fn test_escalated_command() {
+        assert_eq!(
+            escalated_command(Escalation::Sudo, UnitAction::Restart, "nginx.service", false),
+            vec!["sudo", "systemctl", "restart", "nginx.service"]
+        );
+        assert_eq!(
+            escalated_command(Escalation::Pkexec, UnitAction::Enable, "nginx.service", false),
+            vec!["pkexec", "systemctl", "enable", "nginx.service"]
+        );
+        assert_eq!(
+            escalated_command(Escalation::Sudo, UnitAction::DaemonReload, "", false),
+            vec!["sudo", "systemctl", "daemon-reload"]
+        );
+        assert_eq!(
+            escalated_command(Escalation::Pkexec, UnitAction::Start, "nginx.service", true),
+            vec!["pkexec", "systemctl", "--no-block", "start", "nginx.service"]
+        );
+    }
+
+    #[test]
+    fn test_spawn_error_names_missing_binary() {
+        let missing = io::Error::from(io::ErrorKind::NotFound);
+        let msg = spawn_error("journalctl", &missing);
+        assert_eq!(msg, "journalctl not found \u{2014} is this a systemd system?");
+        assert!(is_missing_binary_error(&msg));
+
+        let msg = spawn_error("pkexec", &missing);
+        assert_eq!(msg, "pkexec is not installed");
+        assert!(!is_missing_binary_error(&msg));
+
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        let msg = spawn_error("systemctl", &denied);
+        assert!(msg.starts_with("Failed to execute systemctl: "));
+        assert!(!is_missing_binary_error(&msg));
+    }
+
+    #[test]
+    fn test_local_runner_reports_missing_binary() {
+        let err = LocalRunner.run("systemdmgr-no-such-binary", &[]).err().unwrap();
+        assert_eq!(err, "systemdmgr-no-such-binary is not installed");
+    }
+
     #[test]
     fn test_parse_systemd_version() {
         let output = "systemd 257 (257.13-1~deb13u1)\n+PAM +OPENSSL\n";
@@ -1361,6 +2983,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fetch_units_remote_command() {
+        // The ssh invocation SshRunner::run builds for the unit list.
+        let runner = RecordingRunner {
+            stdout: b"[]".to_vec(),
+            ..Default::default()
+        };
+        fetch_units(UnitType::Timer, true, true, &runner).unwrap();
+        let calls = runner.calls.lock().unwrap();
+        let list_units = calls
+            .iter()
+            .find(|args| args.iter().any(|a| a == "list-units"))
+            .unwrap();
+        let args: Vec<&str> = list_units.iter().map(String::as_str).collect();
+        let control_dir = std::path::Path::new("/nonexistent/cdir");
+        let ssh_options = ["-p".to_string(), "2222".to_string()];
+        let ssh_args = remote_command_args(control_dir, &ssh_options, "deploy@web1", "systemctl", &args);
+        assert_eq!(ssh_args[..ssh_args.len() - 5], multiplex_args(control_dir, true)[..]);
+        assert_eq!(
+            ssh_args[ssh_args.len() - 5..],
+            [
+                "-p",
+                "2222",
+                "--",
+                "deploy@web1",
+                "systemctl --no-ask-password --user list-units --type=timer --all --no-pager --output=json",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fetch_units_active_only_omits_all() {
+        let runner = RecordingRunner {
+            stdout: b"[]".to_vec(),
+            ..Default::default()
+        };
+        fetch_units(UnitType::Service, false, false, &runner).unwrap();
+        let calls = runner.calls.lock().unwrap();
+        let list_units = calls
+            .iter()
+            .find(|args| args.iter().any(|a| a == "list-units"))
+            .unwrap();
+        assert_eq!(
+            list_units,
+            &["--no-ask-password", "list-units", "--type=service", "--no-pager", "--output=json"]
+        );
+    }
+
     // SshRunner argument construction
 
     #[test]
@@ -1518,6 +3188,49 @@ mod tests {
         assert_eq!(UnitType::Path.label(), "Paths");
     }
 
+    #[test]
+    fn test_systemd_unit_serializes_merged_fields() {
+        let units = vec![SystemdUnit {
+            unit: "backup.timer".into(),
+            load: "loaded".into(),
+            active: "active".into(),
+            sub: "waiting".into(),
+            description: "Backup".into(),
+            detail: Some("Mon 2026-01-05 03:00:00 UTC".into()),
+            file_state: Some("enabled".into()),
+            next_elapse_us: None,
+            last_trigger_us: None,
+            socket_stats: None,
+        }];
+        let json = serde_json::to_string(&units).unwrap();
+        assert!(json.contains(r#""file_state":"enabled""#));
+        assert!(json.contains(r#""detail":"Mon 2026-01-05 03:00:00 UTC""#));
+        assert!(json.starts_with('['));
+    }
+
+    #[test]
+    fn test_systemd_unit_deserialize_ignores_merged_fields() {
+        let json = r#"[{"unit":"a.service","load":"loaded","active":"active","sub":"running","description":"A","file_state":"masked"}]"#;
+        let units: Vec<SystemdUnit> = serde_json::from_str(json).unwrap();
+        assert_eq!(units[0].file_state, None);
+    }
+
+    #[test]
+    fn test_parse_unit_type_valid_names() {
+        assert_eq!(parse_unit_type("service"), Some(UnitType::Service));
+        assert_eq!(parse_unit_type("timer"), Some(UnitType::Timer));
+        assert_eq!(parse_unit_type("socket"), Some(UnitType::Socket));
+        assert_eq!(parse_unit_type("target"), Some(UnitType::Target));
+        assert_eq!(parse_unit_type("path"), Some(UnitType::Path));
+        assert_eq!(parse_unit_type("Timer"), Some(UnitType::Timer));
+    }
+
+    #[test]
+    fn test_parse_unit_type_invalid() {
+        assert_eq!(parse_unit_type("mount"), None);
+        assert_eq!(parse_unit_type(""), None);
+    }
+
     // Phase 2 — UnitType::systemctl_type
 
     #[test]
@@ -1601,7 +3314,57 @@ mod tests {
 
     #[test]
     fn test_unit_types_count() {
-        assert_eq!(UNIT_TYPES.len(), 5);
+        assert_eq!(UNIT_TYPES.len(), 6);
+    }
+
+    #[test]
+    fn test_status_options_all_units() {
+        assert_eq!(UnitType::All.status_options(), &["All"]);
+        assert_eq!(UnitType::All.label(), "All Units");
+        assert_eq!(parse_unit_type("all"), Some(UnitType::All));
+    }
+
+    #[test]
+    fn test_fetch_units_all_omits_type() {
+        let runner = RecordingRunner {
+            stdout: b"[]".to_vec(),
+            ..Default::default()
+        };
+        fetch_units(UnitType::All, false, true, &runner).unwrap();
+        let calls = runner.calls.lock().unwrap();
+        let find = |command: &str| {
+            calls
+                .iter()
+                .find(|args| args.iter().any(|a| a == command))
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(
+            find("list-units"),
+            ["--no-ask-password", "list-units", "--all", "--no-pager", "--output=json"]
+        );
+        assert_eq!(
+            find("list-unit-files"),
+            ["--no-ask-password", "list-unit-files", "--no-pager", "--output=json"]
+        );
+        // Timer and socket details are merged into the mixed list.
+        assert!(calls.iter().any(|args| args.iter().any(|a| a == "list-timers")));
+        assert!(calls.iter().any(|args| args.iter().any(|a| a == "list-sockets")));
+    }
+
+    #[test]
+    fn test_unit_type_from_name() {
+        assert_eq!(unit_type_from_name("sshd.service"), Some(UnitType::Service));
+        assert_eq!(unit_type_from_name("logrotate.timer"), Some(UnitType::Timer));
+        assert_eq!(unit_type_from_name("dbus.socket"), Some(UnitType::Socket));
+        assert_eq!(unit_type_from_name("multi-user.target"), Some(UnitType::Target));
+        assert_eq!(unit_type_from_name("foo.path"), Some(UnitType::Path));
+        assert_eq!(unit_type_from_name("home.mount"), None);
+        assert_eq!(unit_type_from_name("weird.all"), None);
+        assert_eq!(unit_type_from_name("noext"), None);
+        assert_eq!(unit_suffix("systemd-journald.service"), "service");
+        assert_eq!(unit_suffix("home.mount"), "mount");
+        assert_eq!(unit_suffix("noext"), "");
     }
 
     // Phase 1 — SystemdUnit methods
@@ -1624,12 +3387,12 @@ mod tests {
 
     #[test]
     fn test_status_color_dead() {
-        assert_eq!(make_unit("dead").status_color(), COLOR_MUTED);
+        assert_eq!(make_unit("dead").status_color(), theme().status.inactive);
     }
 
     #[test]
     fn test_status_color_stopped() {
-        assert_eq!(make_unit("stopped").status_color(), COLOR_MUTED);
+        assert_eq!(make_unit("stopped").status_color(), theme().status.inactive);
     }
 
     #[test]
@@ -1654,7 +3417,7 @@ mod tests {
 
     #[test]
     fn test_status_color_inactive() {
-        assert_eq!(make_unit("inactive").status_color(), COLOR_MUTED);
+        assert_eq!(make_unit("inactive").status_color(), theme().status.inactive);
     }
 
     #[test]
@@ -1667,6 +3430,23 @@ mod tests {
         assert_eq!(make_unit("something_else").status_color(), Color::White);
     }
 
+    #[test]
+    fn test_status_color_neutral_without_color() {
+        // What main installs for --no-color / NO_COLOR.
+        crate::theme::init(crate::theme::Theme::monochrome());
+        for sub in [
+            "running", "exited", "dead", "stopped", "failed", "waiting", "listening", "active",
+            "inactive", "elapsed", "something_else",
+        ] {
+            assert_eq!(make_unit(sub).status_color(), Color::Reset, "{sub}");
+        }
+        for p in 0..=7 {
+            assert_eq!(theme().priority_color(p), Color::Reset);
+        }
+        assert_eq!(theme().file_state_color("masked"), Color::Reset);
+        assert_eq!(theme().load_color("error"), Color::Reset);
+    }
+
     // Phase 3 — priority_label
 
     #[test]
@@ -1719,6 +3499,29 @@ mod tests {
         assert_eq!(priority_label(255), "unknown");
     }
 
+    #[test]
+    fn test_priority_filter_arg_single_level() {
+        assert_eq!(priority_filter_arg((0, 3)), "3");
+        assert_eq!(priority_filter_arg((0, 0)), "0");
+    }
+
+    #[test]
+    fn test_priority_filter_arg_range() {
+        assert_eq!(priority_filter_arg((3, 4)), "3..4");
+        assert_eq!(priority_filter_arg((5, 5)), "5..5");
+    }
+
+    #[test]
+    fn test_priority_filter_arg_reversed_bounds() {
+        assert_eq!(priority_filter_arg((4, 3)), "3..4");
+    }
+
+    #[test]
+    fn test_priority_filter_label() {
+        assert_eq!(priority_filter_label((0, 3)), "err");
+        assert_eq!(priority_filter_label((4, 3)), "err..warning");
+    }
+
     #[test]
     fn test_priority_labels_count() {
         assert_eq!(PRIORITY_LABELS.len(), 8);
@@ -1797,6 +3600,391 @@ mod tests {
         assert_eq!(TIME_RANGES.len(), 6);
     }
 
+    #[test]
+    fn test_time_range_custom_label_and_summary() {
+        let range = TimeRange::Custom {
+            since: "2024-01-01 08:00".into(),
+            until: Some("2024-01-01 09:00".into()),
+        };
+        assert_eq!(range.label(), "Custom...");
+        assert_eq!(range.summary(), "2024-01-01 08:00 .. 2024-01-01 09:00");
+        assert_eq!(TimeRange::OneHour.summary(), "Last 1 hour");
+    }
+
+    #[test]
+    fn test_log_grep_args_case_insensitive() {
+        let grep = LogGrep {
+            pattern: "timeout|refused".into(),
+            case_sensitive: false,
+        };
+        assert_eq!(
+            grep.journalctl_args(),
+            ["--grep=timeout|refused".to_string(), "--case-sensitive=no".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_log_grep_args_case_sensitive() {
+        let grep = LogGrep {
+            pattern: "OOM".into(),
+            case_sensitive: true,
+        };
+        assert_eq!(
+            grep.journalctl_args(),
+            ["--grep=OOM".to_string(), "--case-sensitive=yes".to_string()]
+        );
+    }
+
+    fn filters_with_grep(grep: Option<LogGrep>) -> LogFilters {
+        LogFilters {
+            source: LogSource::Unit,
+            priority: None,
+            time_range: TimeRange::All,
+            grep,
+            main_pid: None,
+        }
+    }
+
+    #[test]
+    fn test_fetch_log_entries_permission_error_is_friendly() {
+        let runner = CannedRunner {
+            success: false,
+            stdout: "",
+            stderr: "No journal files were opened due to insufficient permissions.\n",
+        };
+        let filters = filters_with_grep(None);
+        assert_eq!(
+            fetch_log_entries(Some("nginx.service"), 100, false, &filters, &runner).err(),
+            Some(JOURNAL_PERMISSION_HINT.to_string())
+        );
+    }
+
+    #[test]
+    fn test_fetch_log_entries_other_user_hint_without_entries() {
+        let runner = CannedRunner {
+            success: true,
+            stdout: "",
+            stderr: "Hint: You are currently not seeing messages from other users and the system.\n",
+        };
+        let filters = filters_with_grep(None);
+        assert_eq!(
+            fetch_log_entries(Some("nginx.service"), 100, false, &filters, &runner).err(),
+            Some(JOURNAL_PERMISSION_HINT.to_string())
+        );
+    }
+
+    #[test]
+    fn test_fetch_log_entries_other_failure_reports_stderr() {
+        let runner = CannedRunner {
+            success: false,
+            stdout: "",
+            stderr: "Failed to parse timestamp: yesterdayish\n",
+        };
+        let filters = filters_with_grep(None);
+        assert_eq!(
+            fetch_log_entries(None, 100, false, &filters, &runner).err(),
+            Some("journalctl failed: Failed to parse timestamp: yesterdayish".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fetch_log_entries_silent_failure_is_empty() {
+        // journalctl --grep exits non-zero when nothing matches.
+        let runner = CannedRunner { success: false, stdout: "", stderr: "" };
+        let filters = filters_with_grep(None);
+        assert!(fetch_log_entries(None, 100, false, &filters, &runner).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fetch_log_entries_keeps_entries_despite_hint() {
+        let runner = CannedRunner {
+            success: true,
+            stdout: "{\"MESSAGE\":\"hello\"}\n",
+            stderr: "Hint: You are currently not seeing messages from other users and the system.\n",
+        };
+        let filters = filters_with_grep(None);
+        let entries = fetch_log_entries(None, 100, false, &filters, &runner).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message, "hello");
+    }
+
+    #[test]
+    fn test_fetch_log_entries_passes_line_limit() {
+        let runner = RecordingRunner::default();
+        fetch_log_entries(None, 5000, false, &filters_with_grep(None), &runner).unwrap();
+        let args = runner.last_args();
+        let n = args.iter().position(|a| a == "-n").unwrap();
+        assert_eq!(args[n + 1], "5000");
+    }
+
+    #[test]
+    fn test_fetch_log_entries_without_grep() {
+        let runner = RecordingRunner::default();
+        fetch_log_entries(Some("nginx.service"), 1000, false, &filters_with_grep(None), &runner)
+            .unwrap();
+        let args = runner.last_args();
+        assert!(!args.iter().any(|a| a.starts_with("--grep")));
+        assert!(!args.iter().any(|a| a.starts_with("--case-sensitive")));
+    }
+
+    #[test]
+    fn test_fetch_log_entries_with_grep() {
+        let runner = RecordingRunner::default();
+        let filters = filters_with_grep(Some(LogGrep {
+            pattern: "failed".into(),
+            case_sensitive: false,
+        }));
+        fetch_log_entries(Some("nginx.service"), 1000, false, &filters, &runner).unwrap();
+        let args = runner.last_args();
+        assert!(args.contains(&"--grep=failed".to_string()));
+        assert!(args.contains(&"--case-sensitive=no".to_string()));
+    }
+
+    #[test]
+    fn test_fetch_log_entries_after_cursor_with_grep() {
+        let runner = RecordingRunner::default();
+        let filters = filters_with_grep(Some(LogGrep {
+            pattern: "Started".into(),
+            case_sensitive: true,
+        }));
+        fetch_log_entries_after_cursor(None, "s=abc", false, &filters, &runner).unwrap();
+        let args = runner.last_args();
+        assert_eq!(args[0], "--after-cursor=s=abc");
+        assert!(args.contains(&"--grep=Started".to_string()));
+        assert!(args.contains(&"--case-sensitive=yes".to_string()));
+    }
+
+    #[test]
+    fn test_fetch_log_entries_with_main_pid() {
+        let runner = RecordingRunner::default();
+        let mut filters = filters_with_grep(None);
+        filters.main_pid = Some(1234);
+        fetch_log_entries(Some("nginx.service"), 1000, false, &filters, &runner).unwrap();
+        let args = runner.last_args();
+        assert_eq!(&args[..2], ["-u", "nginx.service"]);
+        assert_eq!(args.last().map(String::as_str), Some("_PID=1234"));
+    }
+
+    #[test]
+    fn test_fetch_log_entries_without_main_pid_has_no_pid_match() {
+        let runner = RecordingRunner::default();
+        fetch_log_entries(Some("nginx.service"), 1000, false, &filters_with_grep(None), &runner)
+            .unwrap();
+        assert!(!runner.last_args().iter().any(|a| a.starts_with("_PID=")));
+    }
+
+    #[test]
+    fn test_log_filters_args_order() {
+        let filters = LogFilters {
+            source: LogSource::Unit,
+            priority: Some((3, 4)),
+            time_range: TimeRange::OneHour,
+            grep: Some(LogGrep {
+                pattern: "x".into(),
+                case_sensitive: false,
+            }),
+            main_pid: None,
+        };
+        assert_eq!(
+            filters.journalctl_args(),
+            vec!["-p", "3..4", "--since", "1 hour ago", "--grep=x", "--case-sensitive=no"]
+        );
+    }
+
+    #[test]
+    fn test_fetch_log_entries_before_cursor_args() {
+        let runner = RecordingRunner::default();
+        let filters = LogFilters {
+            source: LogSource::Unit,
+            priority: Some((0, 3)),
+            time_range: TimeRange::All,
+            grep: None,
+            main_pid: None,
+        };
+        fetch_log_entries_before_cursor(Some("nginx.service"), "s=abc", 500, false, &filters, &runner)
+            .unwrap();
+        assert_eq!(
+            runner.last_args(),
+            vec![
+                "-u",
+                "nginx.service",
+                "--after-cursor=s=abc",
+                "--reverse",
+                "-n",
+                "500",
+                "--no-pager",
+                "--output=json",
+                "-p",
+                "3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unit_edit_args_system() {
+        assert_eq!(unit_edit_args("foo.service", false), vec!["edit", "foo.service"]);
+    }
+
+    #[test]
+    fn test_unit_edit_args_user() {
+        assert_eq!(
+            unit_edit_args("foo.service", true),
+            vec!["edit", "--user", "foo.service"]
+        );
+    }
+
+    #[test]
+    fn test_local_interactive_command() {
+        let cmd = LocalRunner.interactive_command("systemctl", &unit_edit_args("foo.service", true));
+        assert_eq!(cmd.get_program(), "systemctl");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, vec!["edit", "--user", "foo.service"]);
+    }
+
+    #[test]
+    fn test_summarize_bulk_action_all_succeeded() {
+        let outcomes = vec![
+            ("a.service", Ok("ok".to_string())),
+            ("b.service", Ok("ok".to_string())),
+            ("c.service", Ok("ok".to_string())),
+        ];
+        assert_eq!(
+            summarize_bulk_action(UnitAction::Restart, &outcomes),
+            Ok("Restart: 3/3 succeeded".to_string())
+        );
+    }
+
+    #[test]
+    fn test_summarize_bulk_action_partial_failure() {
+        let outcomes = vec![
+            ("a.service", Ok("ok".to_string())),
+            ("b.service", Err("boom".to_string())),
+        ];
+        assert_eq!(
+            summarize_bulk_action(UnitAction::Stop, &outcomes),
+            Err("Stop: 1/2 succeeded, failed: b.service".to_string())
+        );
+    }
+
+    #[test]
+    fn test_summarize_bulk_action_all_failed() {
+        let outcomes = vec![
+            ("a.service", Err("boom".to_string())),
+            ("b.service", Err("boom".to_string())),
+        ];
+        assert_eq!(
+            summarize_bulk_action(UnitAction::Restart, &outcomes),
+            Err("Restart: 0/2 succeeded, failed: a.service, b.service".to_string())
+        );
+    }
+
+    #[test]
+    fn test_execute_unit_action_bulk_runs_each_unit() {
+        let runner = RecordingRunner::default();
+        let units = vec!["a.service".to_string(), "b.service".to_string()];
+        let mut done = Vec::new();
+        let result = execute_unit_action_bulk(UnitAction::Start, &units, false, false, &runner, |name, r| {
+            done.push((name.to_string(), r.is_ok()))
+        });
+        assert_eq!(result, Ok("Start: 2/2 succeeded".to_string()));
+        assert_eq!(done, vec![("a.service".to_string(), true), ("b.service".to_string(), true)]);
+        let calls = runner.calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0], vec!["--no-ask-password", "start", "a.service"]);
+        assert_eq!(calls[1], vec!["--no-ask-password", "start", "b.service"]);
+    }
+
+    #[test]
+    fn test_fetch_log_entries_kernel_source() {
+        let runner = RecordingRunner::default();
+        let filters = LogFilters {
+            source: LogSource::Kernel,
+            priority: Some((0, 4)),
+            time_range: TimeRange::OneHour,
+            grep: None,
+            main_pid: None,
+        };
+        fetch_log_entries(Some("nginx.service"), 1000, false, &filters, &runner).unwrap();
+        let args = runner.last_args();
+        assert_eq!(args[0], "-k");
+        assert!(!args.contains(&"-u".to_string()));
+        assert!(!args.contains(&"nginx.service".to_string()));
+        assert!(args.contains(&"-p".to_string()));
+        assert!(args.contains(&"--since".to_string()));
+    }
+
+    #[test]
+    fn test_fetch_log_entries_kernel_source_user_mode() {
+        let runner = RecordingRunner::default();
+        let filters = LogFilters {
+            source: LogSource::Kernel,
+            priority: None,
+            time_range: TimeRange::All,
+            grep: None,
+            main_pid: None,
+        };
+        fetch_log_entries_after_cursor(Some("app.service"), "c", true, &filters, &runner).unwrap();
+        let args = runner.last_args();
+        assert_eq!(args[0], "-k");
+        assert!(!args.contains(&"--user-unit".to_string()));
+    }
+
+    #[test]
+    fn test_fetch_log_entries_before_cursor_returns_oldest_first() {
+        let runner = RecordingRunner {
+            stdout: concat!(
+                r#"{"MESSAGE":"newer","__CURSOR":"c2"}"#,
+                "\n",
+                r#"{"MESSAGE":"older","__CURSOR":"c1"}"#,
+                "\n",
+            )
+            .as_bytes()
+            .to_vec(),
+            ..Default::default()
+        };
+        let entries =
+            fetch_log_entries_before_cursor(None, "c3", 10, false, &filters_with_grep(None), &runner)
+                .unwrap();
+        let messages: Vec<&str> = entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["older", "newer"]);
+    }
+
+    #[test]
+    fn test_push_time_range_args_custom_with_until() {
+        let range = TimeRange::Custom {
+            since: "2024-01-01 08:00".into(),
+            until: Some("2024-01-01 09:00".into()),
+        };
+        let mut args = vec!["--no-pager"];
+        push_time_range_args(&mut args, &range);
+        assert_eq!(
+            args,
+            vec!["--no-pager", "--since", "2024-01-01 08:00", "--until", "2024-01-01 09:00"]
+        );
+    }
+
+    #[test]
+    fn test_push_time_range_args_custom_without_until() {
+        let range = TimeRange::Custom {
+            since: "yesterday".into(),
+            until: None,
+        };
+        let mut args = Vec::new();
+        push_time_range_args(&mut args, &range);
+        assert_eq!(args, vec!["--since", "yesterday"]);
+    }
+
+    #[test]
+    fn test_push_time_range_args_preset_and_all() {
+        let mut args = Vec::new();
+        push_time_range_args(&mut args, &TimeRange::OneDay);
+        assert_eq!(args, vec!["--since", "1 day ago"]);
+
+        let mut args: Vec<&str> = Vec::new();
+        push_time_range_args(&mut args, &TimeRange::All);
+        assert!(args.is_empty());
+    }
+
     // Phase 3 — parse_journal_json_line
 
     #[test]
@@ -1992,21 +4180,138 @@ mod tests {
         assert_eq!(entry.invocation_id, None);
     }
 
-    // Phase 3 — format_log_timestamp
-
+    // Phase 3 — format_log_timestamp
+
+    #[test]
+    fn test_format_log_timestamp_valid() {
+        let ts = 1700000000000000_i64; // 2023-11-14
+        let result = format_log_timestamp(ts, TimestampFormat::Short, false, false);
+        assert!(!result.is_empty());
+        // Format is "Mon DD HH:MM:SS" → 15 chars
+        assert_eq!(result.len(), 15);
+    }
+
+    #[test]
+    fn test_format_log_timestamp_zero() {
+        let result = format_log_timestamp(0, TimestampFormat::Short, false, false);
+        assert!(!result.is_empty());
+    }
+
+    #[test]
+    fn test_format_log_timestamp_short_month_name() {
+        let ts = 1700000000000000_i64; // 2023-11-14 22:13:20 UTC
+        let result = format_log_timestamp_at(ts, TimestampFormat::Short, false, false, ts);
+        assert!(result.starts_with("Nov 1"), "got {result}");
+    }
+
+    #[test]
+    fn test_format_log_timestamp_iso() {
+        let ts = 1700000000000000_i64;
+        let result = format_log_timestamp_at(ts, TimestampFormat::Iso, false, false, ts);
+        // "2023-11-14T22:13:20+00:00" → 25 chars, in whatever the local offset is
+        assert!(result.starts_with("2023-11-1"), "got {result}");
+        assert_eq!(result.len(), 25);
+        assert_eq!(&result[10..11], "T");
+    }
+
+    #[test]
+    fn test_format_log_timestamp_relative_seconds() {
+        let ts = 1700000000000000_i64;
+        let now = ts + 5_000_000;
+        assert_eq!(format_log_timestamp_at(ts, TimestampFormat::Relative, false, false, now), "5s ago");
+    }
+
+    #[test]
+    fn test_format_log_timestamp_relative_hours() {
+        let ts = 1700000000000000_i64;
+        let now = ts + (2 * 3600 + 30 * 60) * 1_000_000;
+        assert_eq!(format_log_timestamp_at(ts, TimestampFormat::Relative, false, false, now), "2h 30m ago");
+    }
+
+    #[test]
+    fn test_format_log_timestamp_relative_future_clamps() {
+        let ts = 1700000000000000_i64;
+        assert_eq!(format_log_timestamp_at(ts, TimestampFormat::Relative, false, false, ts - 1_000_000), "0s ago");
+    }
+
+    #[test]
+    fn test_format_log_timestamp_utc_short() {
+        let ts = 1700000000000000_i64; // 2023-11-14 22:13:20 UTC
+        assert_eq!(
+            format_log_timestamp_at(ts, TimestampFormat::Short, true, false, ts),
+            "Nov 14 22:13:20"
+        );
+    }
+
+    #[test]
+    fn test_format_log_timestamp_micros_short() {
+        let ts = 1700000000000123_i64; // 2023-11-14 22:13:20.000123 UTC
+        assert_eq!(
+            format_log_timestamp_at(ts, TimestampFormat::Short, true, true, ts),
+            "Nov 14 22:13:20.000123"
+        );
+        let ts = 1700000000987654_i64;
+        assert_eq!(
+            format_log_timestamp_at(ts, TimestampFormat::Short, true, true, ts),
+            "Nov 14 22:13:20.987654"
+        );
+    }
+
+    #[test]
+    fn test_format_log_timestamp_micros_iso_before_offset() {
+        let ts = 1700000000500000_i64;
+        assert_eq!(
+            format_log_timestamp_at(ts, TimestampFormat::Iso, true, true, ts),
+            "2023-11-14T22:13:20.500000+00:00"
+        );
+    }
+
+    #[test]
+    fn test_format_log_timestamp_micros_ignored_for_relative() {
+        let ts = 1700000000123456_i64;
+        assert_eq!(
+            format_log_timestamp_at(ts, TimestampFormat::Relative, false, true, ts + 5_000_000),
+            "5s ago"
+        );
+    }
+
+    #[test]
+    fn test_format_log_timestamp_utc_iso() {
+        let ts = 1700000000000000_i64;
+        assert_eq!(
+            format_log_timestamp_at(ts, TimestampFormat::Iso, true, false, ts),
+            "2023-11-14T22:13:20+00:00"
+        );
+    }
+
+    #[test]
+    fn test_format_log_timestamp_local_vs_utc() {
+        let ts = 1700000000000000_i64;
+        let expected_local = chrono::Local
+            .timestamp_opt(1_700_000_000, 0)
+            .unwrap()
+            .format("%b %d %H:%M:%S")
+            .to_string();
+        let local = format_log_timestamp_at(ts, TimestampFormat::Short, false, false, ts);
+        let utc = format_log_timestamp_at(ts, TimestampFormat::Short, true, false, ts);
+        assert_eq!(local, expected_local);
+        let offset = chrono::Local.timestamp_opt(1_700_000_000, 0).unwrap().offset().local_minus_utc();
+        assert_eq!(local == utc, offset == 0);
+    }
+
     #[test]
-    fn test_format_log_timestamp_valid() {
-        let ts = 1700000000000000_i64; // 2023-11-14
-        let result = format_log_timestamp(ts);
-        assert!(!result.is_empty());
-        // Format is "Mon DD HH:MM:SS" → 15 chars
-        assert_eq!(result.len(), 15);
+    fn test_format_log_timestamp_relative_ignores_zone() {
+        let ts = 1700000000000000_i64;
+        let now = ts + 5_000_000;
+        assert_eq!(
+            format_log_timestamp_at(ts, TimestampFormat::Relative, true, false, now),
+            format_log_timestamp_at(ts, TimestampFormat::Relative, false, false, now)
+        );
     }
 
     #[test]
-    fn test_format_log_timestamp_zero() {
-        let result = format_log_timestamp(0);
-        assert!(!result.is_empty());
+    fn test_timestamp_formats_count() {
+        assert_eq!(TIMESTAMP_FORMATS.len(), 3);
     }
 
     // Phase 4 — format_bytes
@@ -2147,11 +4452,332 @@ mod tests {
             UnitAction::Enable,
             UnitAction::Disable,
             UnitAction::DaemonReload,
+            UnitAction::VacuumJournal,
         ];
         let shortcuts: HashSet<char> = actions.iter().map(UnitAction::shortcut).collect();
         assert_eq!(shortcuts.len(), actions.len());
     }
 
+    // Whole-word search
+
+    #[test]
+    fn test_find_word_matches_requires_word_boundaries() {
+        assert_eq!(find_word_matches("err: disk full", "err"), vec![0..3]);
+        assert!(find_word_matches("error reading", "err").is_empty());
+        assert!(find_word_matches("ferret", "err").is_empty());
+        assert!(find_word_matches("my_err", "err").is_empty());
+    }
+
+    #[test]
+    fn test_find_word_matches_case_insensitive_multiple() {
+        assert_eq!(find_word_matches("ERR (err) errs err", "err"), vec![0..3, 5..8, 15..18]);
+        assert!(find_word_matches("anything", "").is_empty());
+    }
+
+    // Socket columns
+
+    #[test]
+    fn test_parse_socket_stats_only_accepting_sockets() {
+        let stdout = "Id=sshd.socket\nAccept=yes\nNConnections=2\nNAccepted=157\n\n\
+                      Id=dbus.socket\nAccept=no\nNConnections=0\nNAccepted=0\n";
+        let stats = parse_socket_stats(stdout);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats["sshd.socket"], SocketStats { connections: 2, accepted: 157 });
+    }
+
+    #[test]
+    fn test_apply_socket_stats() {
+        let mut units = vec![make_unit("listening"), make_unit("listening")];
+        units[0].unit = "sshd.socket".into();
+        units[1].unit = "dbus.socket".into();
+        let stats = HashMap::from([("sshd.socket".to_string(), SocketStats { connections: 1, accepted: 3 })]);
+        apply_socket_stats(&mut units, &stats);
+        assert_eq!(units[0].socket_stats, Some(SocketStats { connections: 1, accepted: 3 }));
+        assert_eq!(units[1].socket_stats, None);
+    }
+
+    // Timer countdown
+
+    #[test]
+    fn test_format_next_trigger() {
+        let now = 1_700_000_000_000_000;
+        assert_eq!(format_next_trigger(0, now), "next: n/a");
+        assert_eq!(format_next_trigger(now + 7_500_000_000, now), "next: 2h 5m");
+        assert_eq!(format_next_trigger(now - 1, now), "next: elapsed");
+    }
+
+    #[test]
+    fn test_timer_detail_counts_down() {
+        let now = 1_700_000_000_000_000;
+        let mut unit = make_unit("waiting");
+        unit.detail = Some("next: 2m 0s".into());
+        unit.next_elapse_us = Some(now + 120_000_000);
+        assert_eq!(unit.detail_at(now).as_deref(), Some("next: 2m 0s"));
+        assert_eq!(unit.detail_at(now + 30_000_000).as_deref(), Some("next: 1m 30s"));
+        assert_eq!(unit.detail_at(now + 120_000_000).as_deref(), Some("next: elapsed"));
+
+        // Other units keep the fetched detail.
+        unit.next_elapse_us = None;
+        unit.detail = Some("0.0.0.0:22".into());
+        assert_eq!(unit.detail_at(now).as_deref(), Some("0.0.0.0:22"));
+    }
+
+    #[test]
+    fn test_apply_timer_details_stores_next() {
+        let mut units = vec![make_unit("waiting")];
+        units[0].unit = "backup.timer".into();
+        apply_timer_details(
+            &mut units,
+            &[TimerEntry { unit: "backup.timer".into(), next: 42, last: None }],
+        );
+        assert_eq!(units[0].next_elapse_us, Some(42));
+        assert_eq!(units[0].last_trigger_us, Some(0));
+    }
+
+    #[test]
+    fn test_parse_timer_entries_with_last() {
+        let json = r#"[
+            {"next":1700003600000000,"left":3600000000,"last":1699996400000000,"passed":3600000000,"unit":"backup.timer","activates":"backup.service"},
+            {"next":1700086400000000,"left":86400000000,"last":null,"passed":null,"unit":"fresh.timer","activates":"fresh.service"}
+        ]"#;
+        let entries: Vec<TimerEntry> = serde_json::from_str(json).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].next, 1_700_003_600_000_000);
+        assert_eq!(entries[0].last, Some(1_699_996_400_000_000));
+        assert_eq!(entries[1].last, None);
+    }
+
+    #[test]
+    fn test_timer_column_formatting() {
+        let now = 1_700_000_000_000_000;
+        assert_eq!(format_time_left(now + 7_500_000_000, now), "2h 5m");
+        assert_eq!(format_time_left(0, now), "n/a");
+        assert_eq!(format_time_left(now - 5, now), "elapsed");
+        assert_eq!(format_time_passed(now - 90_000_000, now), "1m 30s ago");
+        assert_eq!(format_time_passed(0, now), "n/a");
+        assert_eq!(format_timer_time(0), "n/a");
+        let expected = chrono::Local
+            .timestamp_micros(now as i64)
+            .unwrap()
+            .format("%a %m-%d %H:%M")
+            .to_string();
+        assert_eq!(format_timer_time(now), expected);
+    }
+
+    // Raw properties export
+
+    #[test]
+    fn test_fetch_raw_properties_keeps_output() {
+        let runner = RecordingRunner {
+            stdout: b"Id=foo.service\nMainPID=42\n".to_vec(),
+            ..Default::default()
+        };
+        assert_eq!(
+            fetch_raw_properties("foo.service", true, &runner).unwrap(),
+            "Id=foo.service\nMainPID=42\n"
+        );
+        assert_eq!(runner.last_args(), ["--no-ask-password", "--user", "show", "foo.service", "--no-pager"]);
+    }
+
+    #[test]
+    fn test_raw_properties_path() {
+        let now = chrono::Local.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap();
+        assert_eq!(
+            raw_properties_path(Path::new("/tmp"), "foo.service", now),
+            PathBuf::from("/tmp/systemdmgr-foo.service-20240305-140709.txt")
+        );
+    }
+
+    #[test]
+    fn test_log_export_path() {
+        let now = chrono::Local.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap();
+        assert_eq!(
+            log_export_path(Path::new("/tmp"), "foo.service", LogExportFormat::Csv, now),
+            PathBuf::from("/tmp/systemdmgr-foo.service-logs-20240305-140709.csv")
+        );
+    }
+
+    #[test]
+    fn test_parse_journal_json_line_keeps_all_fields() {
+        let entry = parse_journal_json_line(
+            r#"{"MESSAGE":"hi","CODE_FILE":"src/main.c","CODE_LINE":"42","_CMDLINE":[47,98,105,110]}"#,
+        );
+        assert_eq!(entry.fields.get("CODE_LINE").map(String::as_str), Some("42"));
+        assert_eq!(entry.fields.get("CODE_FILE").map(String::as_str), Some("src/main.c"));
+        assert_eq!(entry.fields.get("_CMDLINE").map(String::as_str), Some("/bin"));
+        assert_eq!(entry.fields.get("MESSAGE").map(String::as_str), Some("hi"));
+        assert_eq!(entry.fields.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_journal_json_line_invalid_has_no_fields() {
+        assert!(parse_journal_json_line("not json").fields.is_empty());
+    }
+
+    #[test]
+    fn test_parse_journal_json_line_hostname() {
+        let entry = parse_journal_json_line(r#"{"MESSAGE":"hi","_HOSTNAME":"web-01"}"#);
+        assert_eq!(entry.hostname.as_deref(), Some("web-01"));
+        assert_eq!(parse_journal_json_line(r#"{"MESSAGE":"hi"}"#).hostname, None);
+    }
+
+    #[test]
+    fn test_save_text_file_writes_content() {
+        let path = std::env::temp_dir()
+            .join(format!("systemdmgr-test-raw-props-{}.txt", std::process::id()));
+        let content = "Id=foo.service\nDescription=Foo\nExecStart={ path=/usr/bin/foo ; argv[]=/usr/bin/foo }\n";
+        save_text_file(&path, content).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(save_text_file(Path::new("/nonexistent-dir/x.txt"), content).is_err());
+    }
+
+    #[test]
+    fn test_save_text_file_is_private_and_never_overwrites() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir()
+            .join(format!("systemdmgr-test-private-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        save_text_file(&path, "Environment=TOKEN=secret\n").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let err = save_text_file(&path, "other").unwrap_err();
+        assert!(err.starts_with("Failed to write"), "{err}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Environment=TOKEN=secret\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    // Active-since column
+
+    #[test]
+    fn test_parse_active_timestamps_skips_inactive() {
+        let stdout = "Id=a.service\nActiveState=active\nActiveEnterTimestampMonotonic=5000000\n\n\
+                      Id=b.service\nActiveState=inactive\nActiveEnterTimestampMonotonic=7000000\n\n\
+                      Id=c.service\nActiveState=active\nActiveEnterTimestampMonotonic=0\n";
+        let timestamps = parse_active_timestamps(stdout);
+        assert_eq!(timestamps.len(), 1);
+        assert_eq!(timestamps["a.service"], 5_000_000);
+    }
+
+    #[test]
+    fn test_fetch_active_timestamps_args() {
+        let runner = RecordingRunner::default();
+        fetch_active_timestamps(UnitType::Socket, true, &runner);
+        let args = runner.last_args();
+        assert_eq!(args[..3], ["--no-ask-password", "--user", "show"]);
+        assert!(args.contains(&"*.socket".to_string()));
+    }
+
+    #[test]
+    fn test_parse_uptime_usec() {
+        assert_eq!(parse_uptime_usec("12345.67 54321.00\n"), Some(12_345_670_000));
+        assert_eq!(parse_uptime_usec(""), None);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        let hour = 3_600_000_000;
+        assert_eq!(format_elapsed(0, 2 * hour + 5 * 60_000_000), "2h 5m");
+        assert_eq!(format_elapsed(hour, hour + 42_000_000), "42s");
+        assert_eq!(format_elapsed(0, 3 * 24 * hour + hour), "3d 1h");
+        // A timestamp from after "now" (clock skew between calls) is 0s.
+        assert_eq!(format_elapsed(hour, 0), "0s");
+    }
+
+    // External pager
+
+    #[test]
+    fn test_journal_pager_args_unit() {
+        let mut filters = filters_with_grep(None);
+        filters.priority = Some((0, 3));
+        filters.time_range = TimeRange::OneHour;
+        assert_eq!(
+            journal_pager_args(Some("nginx.service"), false, &filters),
+            vec!["-u", "nginx.service", "-e", "-p", "3", "--since", "1 hour ago"]
+        );
+        assert_eq!(
+            journal_pager_args(Some("app.service"), true, &filters_with_grep(None)),
+            vec!["--user-unit", "app.service", "-e"]
+        );
+    }
+
+    #[test]
+    fn test_journal_pager_args_system_and_kernel() {
+        assert_eq!(journal_pager_args(None, false, &filters_with_grep(None)), vec!["-e"]);
+        let mut filters = filters_with_grep(None);
+        filters.source = LogSource::Kernel;
+        assert_eq!(journal_pager_args(None, false, &filters), vec!["-k", "-e"]);
+    }
+
+    // Journal-wide match count
+
+    #[test]
+    fn test_regex_escape() {
+        assert_eq!(regex_escape("a.b*c"), "a\\.b\\*c");
+        assert_eq!(regex_escape("(x)[y]{z}|^$?+\\"), "\\(x\\)\\[y\\]\\{z\\}\\|\\^\\$\\?\\+\\\\");
+        assert_eq!(regex_escape("plain"), "plain");
+    }
+
+    #[test]
+    fn test_count_journal_matches() {
+        let runner = RecordingRunner {
+            stdout: b"{\"__CURSOR\":\"a\"}\n{\"__CURSOR\":\"b\"}\n\n{\"__CURSOR\":\"c\"}\n".to_vec(),
+            ..Default::default()
+        };
+        let filters = filters_with_grep(Some(LogGrep {
+            pattern: "failed".into(),
+            case_sensitive: false,
+        }));
+        assert_eq!(count_journal_matches(Some("nginx.service"), false, &filters, &runner), Ok(3));
+        let args = runner.last_args();
+        assert!(args.contains(&"--grep=failed".to_string()));
+        assert!(args.contains(&"--output-fields=__CURSOR".to_string()));
+        assert!(!args.contains(&"-n".to_string()));
+    }
+
+    // Journal vacuum
+
+    #[test]
+    fn test_vacuum_journal_args() {
+        assert_eq!(
+            vacuum_journal_args("2weeks", false),
+            [vec!["--rotate".to_string()], vec!["--vacuum-time=2weeks".to_string()]]
+        );
+        assert_eq!(
+            vacuum_journal_args("1d", true),
+            [
+                vec!["--user".to_string(), "--rotate".to_string()],
+                vec!["--user".to_string(), "--vacuum-time=1d".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vacuum_journal_rotates_then_vacuums() {
+        let runner = RecordingRunner::default();
+        let result = vacuum_journal("2weeks", false, &runner).unwrap();
+        assert_eq!(result, "Journal vacuumed (older than 2weeks)");
+        assert_eq!(
+            *runner.calls.lock().unwrap(),
+            vec![vec!["--rotate".to_string()], vec!["--vacuum-time=2weeks".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_vacuum_action_runs_journalctl_and_needs_strong_confirmation() {
+        let runner = RecordingRunner::default();
+        execute_unit_action(UnitAction::VacuumJournal, "", false, false, &runner).unwrap();
+        assert_eq!(runner.last_args(), vec![format!("--vacuum-time={}", JOURNAL_VACUUM_TIME)]);
+        assert!(UnitAction::VacuumJournal.requires_strong_confirmation());
+        assert!(!UnitAction::Stop.requires_strong_confirmation());
+        assert_eq!(
+            UnitAction::VacuumJournal.command_preview("", true, false),
+            "journalctl --user --rotate && journalctl --user --vacuum-time=2weeks"
+        );
+    }
+
     // UnitAction — systemctl_verb
 
     #[test]
@@ -2159,6 +4785,108 @@ mod tests {
         assert_eq!(UnitAction::Start.systemctl_verb(), "start");
     }
 
+    #[test]
+    fn test_command_preview_system_mode() {
+        assert_eq!(
+            UnitAction::Restart.command_preview("foo.service", false, false),
+            "systemctl restart foo.service"
+        );
+    }
+
+    #[test]
+    fn test_command_preview_user_mode() {
+        assert_eq!(
+            UnitAction::Enable.command_preview("foo.timer", true, false),
+            "systemctl --user enable foo.timer"
+        );
+    }
+
+    #[test]
+    fn test_command_preview_daemon_reload_omits_unit() {
+        assert_eq!(UnitAction::DaemonReload.command_preview("foo.service", false, false), "systemctl daemon-reload");
+        assert_eq!(UnitAction::DaemonReload.command_preview("foo.service", true, false), "systemctl --user daemon-reload");
+    }
+
+    #[test]
+    fn test_execute_unit_action_success_includes_output() {
+        let runner = CannedRunner {
+            success: true,
+            stdout: "  Unit started.\n",
+            stderr: "Created symlink /etc/systemd/system/multi-user.target.wants/foo.service → /etc/systemd/system/foo.service.\n",
+        };
+        let msg = execute_unit_action(UnitAction::Enable, "foo.service", false, false, &runner).unwrap();
+        assert_eq!(
+            msg,
+            "Enable succeeded for foo.service\nUnit started.\nCreated symlink /etc/systemd/system/multi-user.target.wants/foo.service → /etc/systemd/system/foo.service."
+        );
+    }
+
+    #[test]
+    fn test_execute_unit_action_success_without_output_is_one_line() {
+        let runner = CannedRunner { success: true, stdout: "\n", stderr: "" };
+        let msg = execute_unit_action(UnitAction::Restart, "foo.service", false, false, &runner).unwrap();
+        assert_eq!(msg, "Restart succeeded for foo.service");
+    }
+
+    #[test]
+    fn test_execute_unit_action_failure_unchanged() {
+        let runner = CannedRunner { success: false, stdout: "ignored", stderr: "Unit foo.service not found.\n" };
+        let err = execute_unit_action(UnitAction::Stop, "foo.service", false, false, &runner).unwrap_err();
+        assert_eq!(err, "Stop failed: Unit foo.service not found.");
+    }
+
+    #[test]
+    fn test_combined_output_joins_trimmed_streams() {
+        assert_eq!(combined_output(b"  out\nmore\n", b"\nerr\n"), "out\nmore\nerr");
+        assert_eq!(combined_output(b"\n", b"only stderr"), "only stderr");
+        assert_eq!(combined_output(b"", b"  "), "");
+    }
+
+    #[test]
+    fn test_execute_unit_action_with_output_keeps_output_on_failure() {
+        let runner = CannedRunner {
+            success: false,
+            stdout: "partial\n",
+            stderr: "Job for foo.service failed.\nSee \"journalctl -xeu foo.service\" for details.\n",
+        };
+        let (result, output) = execute_unit_action_with_output(UnitAction::Start, "foo.service", false, false, &runner);
+        assert!(result.is_err());
+        assert_eq!(
+            output,
+            "partial\nJob for foo.service failed.\nSee \"journalctl -xeu foo.service\" for details."
+        );
+    }
+
+    #[test]
+    fn test_no_block_args_and_message() {
+        assert_eq!(
+            UnitAction::Start.systemctl_args("foo.service", true, true),
+            vec!["--user", "--no-block", "start", "foo.service"]
+        );
+        assert_eq!(UnitAction::Restart.systemctl_args("foo.service", false, false), vec!["restart", "foo.service"]);
+        // Only job actions wait, so only they take --no-block.
+        assert_eq!(UnitAction::Enable.systemctl_args("foo.service", false, true), vec!["enable", "foo.service"]);
+
+        let runner = RecordingRunner::default();
+        let msg = execute_unit_action(UnitAction::Start, "foo.service", false, true, &runner).unwrap();
+        assert_eq!(runner.last_args(), vec!["--no-ask-password", "--no-block", "start", "foo.service"]);
+        assert_eq!(msg, "Start requested for foo.service");
+        assert_eq!(
+            UnitAction::Start.command_preview("foo.service", false, true),
+            "systemctl --no-block start foo.service"
+        );
+    }
+
+    #[test]
+    fn test_execute_unit_action_matches_preview() {
+        let runner = RecordingRunner::default();
+        let _ = execute_unit_action(UnitAction::Stop, "foo.service", true, false, &runner);
+        let preview = UnitAction::Stop.command_preview("foo.service", true, false);
+        let mut expected = vec!["--no-ask-password".to_string()];
+        expected.extend(preview.split(' ').skip(1).map(String::from));
+        assert_eq!(runner.last_args(), expected);
+    }
+
     #[test]
     fn test_unit_action_verb_stop() {
         assert_eq!(UnitAction::Stop.systemctl_verb(), "stop");
@@ -2211,7 +4939,7 @@ mod tests {
 
     #[test]
     fn test_available_actions_running() {
-        let actions = UnitAction::available_actions("running", None);
+        let actions = UnitAction::available_actions("running", None, "loaded");
         assert!(actions.contains(&UnitAction::Stop));
         assert!(actions.contains(&UnitAction::Restart));
         assert!(actions.contains(&UnitAction::Reload));
@@ -2221,7 +4949,7 @@ mod tests {
 
     #[test]
     fn test_available_actions_dead() {
-        let actions = UnitAction::available_actions("dead", None);
+        let actions = UnitAction::available_actions("dead", None, "loaded");
         assert!(actions.contains(&UnitAction::Start));
         assert!(!actions.contains(&UnitAction::Stop));
         assert!(actions.contains(&UnitAction::DaemonReload));
@@ -2229,14 +4957,14 @@ mod tests {
 
     #[test]
     fn test_available_actions_failed() {
-        let actions = UnitAction::available_actions("failed", None);
+        let actions = UnitAction::available_actions("failed", None, "loaded");
         assert!(actions.contains(&UnitAction::Start));
         assert!(!actions.contains(&UnitAction::Stop));
     }
 
     #[test]
     fn test_available_actions_unknown_sub_state() {
-        let actions = UnitAction::available_actions("something-unknown", None);
+        let actions = UnitAction::available_actions("something-unknown", None, "loaded");
         assert!(actions.contains(&UnitAction::Start));
         assert!(actions.contains(&UnitAction::Stop));
         assert!(actions.contains(&UnitAction::DaemonReload));
@@ -2244,50 +4972,124 @@ mod tests {
 
     #[test]
     fn test_available_actions_enabled_file_state() {
-        let actions = UnitAction::available_actions("running", Some("enabled"));
+        let actions = UnitAction::available_actions("running", Some("enabled"), "loaded");
         assert!(actions.contains(&UnitAction::Disable));
         assert!(!actions.contains(&UnitAction::Enable));
     }
 
     #[test]
     fn test_available_actions_disabled_file_state() {
-        let actions = UnitAction::available_actions("dead", Some("disabled"));
+        let actions = UnitAction::available_actions("dead", Some("disabled"), "loaded");
         assert!(actions.contains(&UnitAction::Enable));
         assert!(!actions.contains(&UnitAction::Disable));
     }
 
     #[test]
     fn test_available_actions_static_file_state() {
-        let actions = UnitAction::available_actions("running", Some("static"));
+        let actions = UnitAction::available_actions("running", Some("static"), "loaded");
         assert!(!actions.contains(&UnitAction::Enable));
         assert!(!actions.contains(&UnitAction::Disable));
     }
 
     #[test]
     fn test_available_actions_listening() {
-        let actions = UnitAction::available_actions("listening", None);
+        let actions = UnitAction::available_actions("listening", None, "loaded");
         assert!(actions.contains(&UnitAction::Stop));
         assert!(actions.contains(&UnitAction::Restart));
     }
 
     #[test]
     fn test_available_actions_waiting() {
-        let actions = UnitAction::available_actions("waiting", None);
+        let actions = UnitAction::available_actions("waiting", None, "loaded");
         assert!(actions.contains(&UnitAction::Stop));
         assert!(actions.contains(&UnitAction::Restart));
     }
 
     #[test]
     fn test_available_actions_exited() {
-        let actions = UnitAction::available_actions("exited", None);
+        let actions = UnitAction::available_actions("exited", None, "loaded");
         assert!(actions.contains(&UnitAction::Start));
         assert!(!actions.contains(&UnitAction::Stop));
     }
 
+    #[test]
+    fn test_available_actions_masked_offers_unmask_not_start() {
+        let actions = UnitAction::available_actions("dead", Some("masked"), "masked");
+        assert_eq!(actions, vec![UnitAction::Unmask, UnitAction::DaemonReload]);
+    }
+
+    #[test]
+    fn test_available_actions_masked_but_running_can_stop() {
+        let actions = UnitAction::available_actions("running", Some("masked"), "masked");
+        assert_eq!(actions, vec![UnitAction::Stop, UnitAction::Unmask, UnitAction::DaemonReload]);
+    }
+
+    #[test]
+    fn test_available_actions_not_found_offers_no_start() {
+        let actions = UnitAction::available_actions("dead", None, "not-found");
+        assert_eq!(actions, vec![UnitAction::DaemonReload]);
+        assert!(UnitAction::start_blocked_reason("not-found").is_some());
+        assert!(UnitAction::start_blocked_reason("loaded").is_none());
+    }
+
+    #[test]
+    fn test_triggered_service_from_triggers() {
+        let props = UnitProperties { triggers: vec!["backup-run.service".into()], ..Default::default() };
+        assert_eq!(props.triggered_service("nightly.timer").as_deref(), Some("backup-run.service"));
+    }
+
+    #[test]
+    fn test_triggered_service_falls_back_to_naming_convention() {
+        let props = UnitProperties::default();
+        assert_eq!(props.triggered_service("logrotate.timer").as_deref(), Some("logrotate.service"));
+        assert_eq!(props.triggered_service("sshd.socket"), None);
+    }
+
+    #[test]
+    fn test_inverse_actions() {
+        assert_eq!(UnitAction::Start.inverse(), Some(UnitAction::Stop));
+        assert_eq!(UnitAction::Stop.inverse(), Some(UnitAction::Start));
+        assert_eq!(UnitAction::Enable.inverse(), Some(UnitAction::Disable));
+        assert_eq!(UnitAction::Disable.inverse(), Some(UnitAction::Enable));
+        for action in [
+            UnitAction::Restart,
+            UnitAction::Reload,
+            UnitAction::Unmask,
+            UnitAction::DaemonReload,
+            UnitAction::VacuumJournal,
+        ] {
+            assert_eq!(action.inverse(), None, "{:?}", action);
+        }
+    }
+
+    #[test]
+    fn test_requires_confirm_classification() {
+        for action in [UnitAction::Start, UnitAction::Stop, UnitAction::Restart, UnitAction::Reload] {
+            assert!(!action.requires_confirm(), "{:?}", action);
+        }
+        for action in [
+            UnitAction::Enable,
+            UnitAction::Disable,
+            UnitAction::Unmask,
+            UnitAction::DaemonReload,
+            UnitAction::VacuumJournal,
+        ] {
+            assert!(action.requires_confirm(), "{:?}", action);
+        }
+    }
+
+    #[test]
+    fn test_unmask_command_preview() {
+        assert_eq!(
+            UnitAction::Unmask.command_preview("foo.service", true, false),
+            "systemctl --user unmask foo.service"
+        );
+    }
+
     #[test]
     fn test_available_actions_always_has_daemon_reload() {
         for sub in &["running", "dead", "failed", "unknown", "listening"] {
-            let actions = UnitAction::available_actions(sub, None);
+            let actions = UnitAction::available_actions(sub, None, "loaded");
             assert!(
                 actions.contains(&UnitAction::DaemonReload),
                 "DaemonReload missing for sub_state={}",
@@ -2372,4 +5174,133 @@ mod tests {
         let result = parse_timer_specs("");
         assert!(result.is_empty());
     }
+
+    fn show_map<'a>(lines: &[(&'a str, &'a str)]) -> HashMap<&'a str, Vec<&'a str>> {
+        let mut map: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (key, value) in lines {
+            map.entry(*key).or_default().push(*value);
+        }
+        map
+    }
+
+    #[test]
+    fn test_parse_unit_properties_basic_fields() {
+        let map = show_map(&[
+            ("ActiveState", "active"),
+            ("MainPID", "1234"),
+            ("MemoryCurrent", "[not set]"),
+            ("Requires", "a.service b.socket"),
+        ]);
+        let props = parse_unit_properties(&map);
+        assert_eq!(props.active_state, "active");
+        assert_eq!(props.main_pid, 1234);
+        assert_eq!(props.memory_current, None);
+        assert_eq!(props.requires, vec!["a.service", "b.socket"]);
+        assert!(props.environment.is_empty());
+        assert!(props.environment_files.is_empty());
+    }
+
+    #[test]
+    fn test_parse_unit_properties_environment() {
+        let map = show_map(&[
+            ("Environment", r#"LANG=C "OPTS=--foo --bar" EMPTY="#),
+            ("EnvironmentFiles", "/etc/default/app (ignore_errors=no)"),
+            ("EnvironmentFiles", "/etc/app.env (ignore_errors=yes)"),
+        ]);
+        let props = parse_unit_properties(&map);
+        assert_eq!(props.environment, vec!["LANG=C", "OPTS=--foo --bar", "EMPTY="]);
+        assert_eq!(props.environment_files, vec!["/etc/default/app", "-/etc/app.env"]);
+    }
+
+    #[test]
+    fn test_parse_environment_escaped_quote() {
+        assert_eq!(
+            parse_environment(r#""MSG=say \"hi\"" X=1"#),
+            vec![r#"MSG=say "hi""#, "X=1"]
+        );
+    }
+
+    #[test]
+    fn test_parse_environment_empty() {
+        assert!(parse_environment("").is_empty());
+        assert!(parse_environment("   ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_environment_file_without_flag() {
+        assert_eq!(parse_environment_file("/etc/app.env"), Some("/etc/app.env".to_string()));
+        assert_eq!(parse_environment_file(""), None);
+    }
+
+    #[test]
+    fn test_parse_unit_properties_restart_fields() {
+        let props = parse_unit_properties(&show_map(&[
+            ("NRestarts", "5"),
+            ("ExecMainStatus", "203"),
+        ]));
+        assert_eq!(props.n_restarts, 5);
+        assert_eq!(props.exec_main_status, 203);
+    }
+
+    #[test]
+    fn test_parse_unit_properties_restart_fields_missing() {
+        let props = parse_unit_properties(&show_map(&[("NRestarts", ""), ("ActiveState", "inactive")]));
+        assert_eq!(props.n_restarts, 0);
+        assert_eq!(props.exec_main_status, 0);
+    }
+
+    #[test]
+    fn test_memory_percent() {
+        assert_eq!(memory_percent(37 * 1024, 100 * 1024), Some(37));
+        assert_eq!(memory_percent(0, 1024), Some(0));
+        assert_eq!(memory_percent(2048, 1024), Some(100));
+    }
+
+    #[test]
+    fn test_memory_percent_zero_max() {
+        assert_eq!(memory_percent(1024, 0), None);
+    }
+
+    #[test]
+    fn test_memory_percent_infinity() {
+        assert_eq!(memory_percent(1024, u64::MAX), None);
+        let props = parse_unit_properties(&show_map(&[
+            ("MemoryCurrent", "1048576"),
+            ("MemoryMax", "infinity"),
+        ]));
+        assert_eq!(props.memory_max, None);
+        let props = parse_unit_properties(&show_map(&[("MemoryMax", "4194304")]));
+        assert_eq!(props.memory_max, Some(4194304));
+    }
+
+    #[test]
+    fn test_parse_exec_command_argv() {
+        let raw = "{ path=/usr/sbin/sshd ; argv[]=/usr/sbin/sshd -D $SSHD_OPTS ; ignore_errors=no ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }";
+        assert_eq!(parse_exec_command(raw), Some("/usr/sbin/sshd -D $SSHD_OPTS".to_string()));
+    }
+
+    #[test]
+    fn test_parse_exec_command_argv_with_semicolon() {
+        let raw = "{ path=/bin/sh ; argv[]=/bin/sh -c echo a ; echo b ; ignore_errors=yes ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }";
+        assert_eq!(parse_exec_command(raw), Some("-/bin/sh -c echo a ; echo b".to_string()));
+    }
+
+    #[test]
+    fn test_parse_exec_command_path_only() {
+        assert_eq!(parse_exec_command("{ path=/usr/bin/true ; ignore_errors=no }"), Some("/usr/bin/true".to_string()));
+        assert_eq!(parse_exec_command(""), None);
+    }
+
+    #[test]
+    fn test_parse_unit_properties_exec_commands() {
+        let map = show_map(&[
+            ("ExecStart", "{ path=/usr/bin/app ; argv[]=/usr/bin/app --serve ; ignore_errors=no ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }"),
+            ("ExecStop", "{ path=/usr/bin/app ; argv[]=/usr/bin/app --stop ; ignore_errors=no ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }"),
+            ("ExecStop", "{ path=/bin/rm ; argv[]=/bin/rm -f /run/app.pid ; ignore_errors=yes ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }"),
+        ]);
+        let props = parse_unit_properties(&map);
+        assert_eq!(props.exec_start, vec!["/usr/bin/app --serve"]);
+        assert_eq!(props.exec_stop, vec!["/usr/bin/app --stop", "-/bin/rm -f /run/app.pid"]);
+        assert!(props.exec_reload.is_empty());
+    }
 }
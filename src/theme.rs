@@ -0,0 +1,440 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::OnceLock;
+
+/// Colors for unit states and log severities. Loaded once at startup
+/// from the `theme` section of the config file; any color left out keeps its
+/// default, so a light-terminal user can override just the few that clash.
+///
+/// Colors are written as names (`"red"`, `"dark gray"`), hex (`"#8a8a8a"`)
+/// or 256-color indices (`"244"`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// Secondary text: stopped units, static files, hints.
+    #[serde(with = "color_str")]
+    pub muted: Color,
+    pub status: StatusColors,
+    pub file_state: FileStateColors,
+    pub load: LoadColors,
+    pub priority: PriorityColors,
+    pub kind: KindColors,
+}
+
+/// Unit sub-state colors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusColors {
+    /// running, listening, active
+    #[serde(with = "color_str")]
+    pub active: Color,
+    /// exited, elapsed
+    #[serde(with = "color_str")]
+    pub exited: Color,
+    /// dead, stopped, inactive
+    #[serde(with = "color_str")]
+    pub inactive: Color,
+    #[serde(with = "color_str")]
+    pub failed: Color,
+    #[serde(with = "color_str")]
+    pub waiting: Color,
+    /// Any other sub-state.
+    #[serde(with = "color_str")]
+    pub other: Color,
+    /// Background of a failed unit's row (with `row_severity_colors`).
+    #[serde(with = "color_str")]
+    pub failed_row_bg: Color,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileStateColors {
+    #[serde(with = "color_str")]
+    pub enabled: Color,
+    #[serde(with = "color_str")]
+    pub disabled: Color,
+    #[serde(with = "color_str")]
+    pub r#static: Color,
+    #[serde(with = "color_str")]
+    pub masked: Color,
+    #[serde(with = "color_str")]
+    pub indirect: Color,
+    #[serde(with = "color_str")]
+    pub other: Color,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoadColors {
+    #[serde(with = "color_str")]
+    pub loaded: Color,
+    /// masked, error, bad-setting
+    #[serde(with = "color_str")]
+    pub error: Color,
+    #[serde(with = "color_str")]
+    pub not_found: Color,
+    #[serde(with = "color_str")]
+    pub other: Color,
+}
+
+/// Unit name colors by type in the all-units view, keyed off the name's
+/// suffix.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KindColors {
+    #[serde(with = "color_str")]
+    pub service: Color,
+    #[serde(with = "color_str")]
+    pub timer: Color,
+    #[serde(with = "color_str")]
+    pub socket: Color,
+    #[serde(with = "color_str")]
+    pub target: Color,
+    #[serde(with = "color_str")]
+    pub path: Color,
+    /// mount, device, slice, scope and the rest.
+    #[serde(with = "color_str")]
+    pub other: Color,
+}
+
+/// Log line colors by syslog priority. emerg, alert and crit are also bold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PriorityColors {
+    #[serde(with = "color_str")]
+    pub emerg: Color,
+    #[serde(with = "color_str")]
+    pub alert: Color,
+    #[serde(with = "color_str")]
+    pub crit: Color,
+    #[serde(with = "color_str")]
+    pub err: Color,
+    #[serde(with = "color_str")]
+    pub warning: Color,
+    #[serde(with = "color_str")]
+    pub notice: Color,
+    #[serde(with = "color_str")]
+    pub info: Color,
+    #[serde(with = "color_str")]
+    pub debug: Color,
+}
+
+const DEFAULT_MUTED: Color = Color::Rgb(100, 100, 100);
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            muted: DEFAULT_MUTED,
+            status: StatusColors::default(),
+            file_state: FileStateColors::default(),
+            load: LoadColors::default(),
+            priority: PriorityColors::default(),
+            kind: KindColors::default(),
+        }
+    }
+}
+
+impl Default for StatusColors {
+    fn default() -> Self {
+        StatusColors {
+            active: Color::Green,
+            exited: Color::Yellow,
+            inactive: DEFAULT_MUTED,
+            failed: Color::Red,
+            waiting: Color::Cyan,
+            other: Color::White,
+            failed_row_bg: Color::Rgb(60, 20, 20),
+        }
+    }
+}
+
+impl Default for FileStateColors {
+    fn default() -> Self {
+        FileStateColors {
+            enabled: Color::Green,
+            disabled: Color::Yellow,
+            r#static: DEFAULT_MUTED,
+            masked: Color::Red,
+            indirect: Color::Cyan,
+            other: Color::White,
+        }
+    }
+}
+
+impl Default for LoadColors {
+    fn default() -> Self {
+        LoadColors {
+            loaded: Color::Green,
+            error: Color::Red,
+            not_found: DEFAULT_MUTED,
+            other: Color::White,
+        }
+    }
+}
+
+impl Default for KindColors {
+    fn default() -> Self {
+        KindColors {
+            service: Color::White,
+            timer: Color::Magenta,
+            socket: Color::LightBlue,
+            target: Color::LightCyan,
+            path: Color::LightGreen,
+            other: Color::Gray,
+        }
+    }
+}
+
+impl Default for PriorityColors {
+    fn default() -> Self {
+        PriorityColors {
+            emerg: Color::Red,
+            alert: Color::Red,
+            crit: Color::Red,
+            err: Color::Red,
+            warning: Color::Yellow,
+            notice: Color::Cyan,
+            info: Color::White,
+            debug: Color::DarkGray,
+        }
+    }
+}
+
+impl Theme {
+    /// Every color set to the terminal default, for `--no-color` / `NO_COLOR`.
+    pub fn monochrome() -> Self {
+        let none = Color::Reset;
+        Theme {
+            muted: none,
+            status: StatusColors {
+                active: none,
+                exited: none,
+                inactive: none,
+                failed: none,
+                waiting: none,
+                other: none,
+                failed_row_bg: none,
+            },
+            file_state: FileStateColors {
+                enabled: none,
+                disabled: none,
+                r#static: none,
+                masked: none,
+                indirect: none,
+                other: none,
+            },
+            load: LoadColors {
+                loaded: none,
+                error: none,
+                not_found: none,
+                other: none,
+            },
+            priority: PriorityColors {
+                emerg: none,
+                alert: none,
+                crit: none,
+                err: none,
+                warning: none,
+                notice: none,
+                info: none,
+                debug: none,
+            },
+            kind: KindColors {
+                service: none,
+                timer: none,
+                socket: none,
+                target: none,
+                path: none,
+                other: none,
+            },
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Theme::default()
+    }
+
+    pub fn sub_state_color(&self, sub: &str) -> Color {
+        match sub {
+            "running" | "listening" | "active" => self.status.active,
+            "exited" | "elapsed" => self.status.exited,
+            "dead" | "stopped" | "inactive" => self.status.inactive,
+            "failed" => self.status.failed,
+            "waiting" => self.status.waiting,
+            _ => self.status.other,
+        }
+    }
+
+    pub fn file_state_color(&self, state: &str) -> Color {
+        match state {
+            "enabled" => self.file_state.enabled,
+            "disabled" => self.file_state.disabled,
+            "static" => self.file_state.r#static,
+            "masked" => self.file_state.masked,
+            "indirect" => self.file_state.indirect,
+            _ => self.file_state.other,
+        }
+    }
+
+    pub fn load_color(&self, state: &str) -> Color {
+        match state {
+            "loaded" => self.load.loaded,
+            "masked" | "error" | "bad-setting" => self.load.error,
+            "not-found" => self.load.not_found,
+            _ => self.load.other,
+        }
+    }
+
+    /// The color for a unit name by its type suffix (`.timer`, `.socket`, ...).
+    pub fn unit_kind_color(&self, name: &str) -> Color {
+        let k = &self.kind;
+        match name.rsplit_once('.').map_or("", |(_, suffix)| suffix) {
+            "service" => k.service,
+            "timer" => k.timer,
+            "socket" => k.socket,
+            "target" => k.target,
+            "path" => k.path,
+            _ => k.other,
+        }
+    }
+
+    pub fn priority_color(&self, priority: u8) -> Color {
+        let p = &self.priority;
+        match priority {
+            0 => p.emerg,
+            1 => p.alert,
+            2 => p.crit,
+            3 => p.err,
+            4 => p.warning,
+            5 => p.notice,
+            6 => p.info,
+            7 => p.debug,
+            _ => p.info,
+        }
+    }
+}
+
+#[cfg(not(test))]
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+// Each test runs on its own thread, so a test can install a theme without
+// leaking it into the others.
+#[cfg(test)]
+thread_local! {
+    static THEME: &'static OnceLock<Theme> = Box::leak(Box::new(OnceLock::new()));
+}
+
+#[cfg(not(test))]
+fn theme_cell() -> &'static OnceLock<Theme> {
+    &THEME
+}
+
+#[cfg(test)]
+fn theme_cell() -> &'static OnceLock<Theme> {
+    THEME.with(|cell| *cell)
+}
+
+/// Installs the theme for the rest of the process. Only the first call has
+/// any effect; it must happen before the first frame is drawn.
+pub fn init(theme: Theme) {
+    let _ = theme_cell().set(theme);
+}
+
+/// The active theme, or the defaults if none was installed (e.g. in tests).
+pub fn theme() -> &'static Theme {
+    theme_cell().get_or_init(Theme::default)
+}
+
+mod color_str {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(color)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid color '{}'", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_theme_is_default() {
+        let theme: Theme = serde_json::from_str("{}").unwrap();
+        assert!(theme.is_default());
+    }
+
+    #[test]
+    fn test_partial_override_keeps_other_defaults() {
+        let theme: Theme = serde_json::from_str(
+            r##"{"muted": "#8a8a8a", "status": {"failed": "magenta"}, "priority": {"debug": "gray"}}"##,
+        )
+        .unwrap();
+        let defaults = Theme::default();
+        assert_eq!(theme.muted, Color::Rgb(0x8a, 0x8a, 0x8a));
+        assert_eq!(theme.status.failed, Color::Magenta);
+        assert_eq!(theme.priority.debug, Color::Gray);
+        // Everything not mentioned is unchanged.
+        assert_eq!(theme.status.active, defaults.status.active);
+        assert_eq!(theme.status.inactive, defaults.status.inactive);
+        assert_eq!(theme.priority.err, defaults.priority.err);
+        assert_eq!(theme.file_state, defaults.file_state);
+        assert_eq!(theme.load, defaults.load);
+    }
+
+    #[test]
+    fn test_failed_row_background_override() {
+        let theme: Theme = serde_json::from_str(r##"{"status": {"failed_row_bg": "#ffe0e0"}}"##).unwrap();
+        assert_eq!(theme.status.failed_row_bg, Color::Rgb(0xff, 0xe0, 0xe0));
+        assert_eq!(theme.status.failed, Theme::default().status.failed);
+    }
+
+    #[test]
+    fn test_invalid_color_is_error() {
+        assert!(serde_json::from_str::<Theme>(r#"{"muted": "not-a-color"}"#).is_err());
+    }
+
+    #[test]
+    fn test_theme_round_trips() {
+        let mut theme = Theme::default();
+        theme.status.failed = Color::Indexed(196);
+        theme.load.loaded = Color::LightGreen;
+        let json = serde_json::to_string(&theme).unwrap();
+        assert_eq!(serde_json::from_str::<Theme>(&json).unwrap(), theme);
+    }
+
+    #[test]
+    fn test_sub_state_color_uses_theme() {
+        let mut theme = Theme::default();
+        theme.status.inactive = Color::Blue;
+        assert_eq!(theme.sub_state_color("dead"), Color::Blue);
+        assert_eq!(theme.sub_state_color("inactive"), Color::Blue);
+        assert_eq!(theme.sub_state_color("running"), Color::Green);
+        assert_eq!(theme.sub_state_color("something"), Color::White);
+    }
+
+    #[test]
+    fn test_unit_kind_color_by_suffix() {
+        let theme = Theme::default();
+        assert_eq!(theme.unit_kind_color("logrotate.timer"), Color::Magenta);
+        assert_eq!(theme.unit_kind_color("dbus.socket"), Color::LightBlue);
+        assert_eq!(theme.unit_kind_color("sshd.service"), Color::White);
+        assert_eq!(theme.unit_kind_color("home.mount"), Color::Gray);
+        assert_eq!(theme.unit_kind_color("noext"), Color::Gray);
+        assert_ne!(theme.unit_kind_color("a.timer"), theme.unit_kind_color("a.socket"));
+    }
+
+    #[test]
+    fn test_priority_color_defaults() {
+        let theme = Theme::default();
+        assert_eq!(theme.priority_color(0), Color::Red);
+        assert_eq!(theme.priority_color(4), Color::Yellow);
+        assert_eq!(theme.priority_color(7), Color::DarkGray);
+        assert_eq!(theme.priority_color(255), Color::White);
+    }
+}
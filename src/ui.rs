@@ -1,19 +1,28 @@
 use ratatui::{
+    buffer::Buffer,
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
     Frame,
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+use std::collections::HashMap;
 use std::sync::OnceLock;
+use std::time::Duration;
 
-use crate::app::App;
+use crate::app::{log_boundary_before_entry, App, Pane, Picker, BATCH_RESULTS_SHOWN};
+use crate::config::LogFilterPreset;
 use crate::service::{
-    format_bytes, format_cpu_time, format_log_timestamp, priority_label, COLOR_MUTED,
-    LogEntry, TimeRange, UnitAction, FILE_STATE_OPTIONS, PRIORITY_LABELS, TIME_RANGES, UNIT_TYPES,
+    find_word_matches, format_bytes, format_cpu_time, format_log_timestamp, format_time_left,
+    format_time_passed, format_timer_time, memory_percent, now_micros, parse_security_overall,
+    priority_filter_label, priority_label, search_match_ranges, unit_suffix, LogEntry, SystemdUnit,
+    TimeRange, TimestampFormat, UnitAction, UnitProperties, UnitType, DEFAULT_LOG_LINE_LIMIT,
+    FILE_STATE_OPTIONS, LOG_EXPORT_FORMATS, PRIORITY_LABELS, TIMESTAMP_FORMATS, TIME_RANGES,
+    UNIT_TYPES,
 };
+use crate::theme::theme;
 
 fn get_current_username() -> &'static str {
     static USERNAME: OnceLock<String> = OnceLock::new();
@@ -38,8 +47,21 @@ pub struct LayoutRegions {
     pub logs_panel: Option<Rect>,
 }
 
+/// Share of the middle area's width the unit list gets in the split view.
+const SPLIT_LIST_PERCENT: u16 = 40;
+
+/// The split view's panes: unit list on the left, logs on the right.
+fn split_middle_area(middle: Rect) -> (Rect, Rect) {
+    let panes = Layout::horizontal([
+        Constraint::Percentage(SPLIT_LIST_PERCENT),
+        Constraint::Min(1),
+    ])
+    .split(middle);
+    (panes[0], panes[1])
+}
+
 /// Get layout regions for mouse hit testing
-pub fn get_layout_regions(area: Rect, show_logs: bool) -> LayoutRegions {
+pub fn get_layout_regions(area: Rect, show_logs: bool, split_view: bool) -> LayoutRegions {
     let chunks = Layout::vertical([
         Constraint::Length(3),
         Constraint::Min(1),
@@ -47,7 +69,19 @@ pub fn get_layout_regions(area: Rect, show_logs: bool) -> LayoutRegions {
     ])
     .split(area);
 
-    if show_logs {
+    if show_logs && split_view {
+        let (list_pane, logs_pane) = split_middle_area(chunks[1]);
+        // Like the full list, minus its 1-row column header
+        let service_chunks = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .split(list_pane);
+        LayoutRegions {
+            services_list: service_chunks[1],
+            logs_panel: Some(logs_pane),
+        }
+    } else if show_logs {
         LayoutRegions {
             services_list: chunks[1],
             logs_panel: Some(chunks[1]),
@@ -66,16 +100,14 @@ pub fn get_layout_regions(area: Rect, show_logs: bool) -> LayoutRegions {
     }
 }
 
-/// Map a Y position within the logs panel content area to a log entry index.
-pub fn log_entry_at_y(app: &App, y_in_panel: usize) -> Option<usize> {
+/// Maps a row within the logs panel content area to the log entry drawn
+/// there, given each entry's visual height (wrapped lines plus any boot or
+/// restart separator above it) and the first entry on screen. A separator
+/// row belongs to the entry below it; folded repeats have no rows.
+pub fn log_entry_at_row(entry_heights: &[usize], logs_scroll: usize, row: usize) -> Option<usize> {
     let mut y = 0;
-    for (i, &height) in app
-        .cached_entry_heights
-        .iter()
-        .enumerate()
-        .skip(app.logs_scroll)
-    {
-        if y_in_panel < y + height {
+    for (i, &height) in entry_heights.iter().enumerate().skip(logs_scroll) {
+        if row < y + height {
             return Some(i);
         }
         y += height;
@@ -144,7 +176,20 @@ fn build_footer_line(segments: &[&str], suffix: &str, width: usize) -> Line<'sta
     Line::from(spans)
 }
 
-pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_FRAME_DURATION: Duration = Duration::from_millis(100);
+
+/// Name color for units whose state changed since the last refresh (`D`).
+const CHANGED_UNIT_COLOR: Color = Color::Magenta;
+
+/// Spinner frame for the time elapsed since startup, so every in-flight
+/// indicator animates in step without any per-fetch state.
+pub fn spinner_frame(elapsed: Duration) -> char {
+    let index = elapsed.as_millis() / SPINNER_FRAME_DURATION.as_millis();
+    SPINNER_FRAMES[(index % SPINNER_FRAMES.len() as u128) as usize]
+}
+
+pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool, spinner: char) {
     // Load logs for selected service if selection changed (only if logs are visible)
     if app.show_logs {
         app.load_logs_for_selected();
@@ -160,6 +205,9 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
     // When logs or unit file are shown, give full middle area to them; hide services list
     let (services_area, logs_area, unit_file_area) = if app.show_unit_file {
         (None, None, Some(chunks[1]))
+    } else if app.show_logs && app.split_view {
+        let (list_pane, logs_pane) = split_middle_area(chunks[1]);
+        (Some(list_pane), Some(logs_pane), None)
     } else if app.show_logs {
         (None, Some(chunks[1]), None)
     } else {
@@ -197,28 +245,31 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
             .style(Style::default().fg(Color::Magenta))
             .block(Block::default().borders(Borders::ALL))
     } else if app.log_search_mode {
-        let match_info = if app.log_search_matches.is_empty() {
-            if app.log_search_query.is_empty() {
-                String::new()
-            } else {
-                " (no matches)".to_string()
-            }
+        let match_info = if app.log_search_query.is_empty() {
+            String::new()
         } else {
-            format!(
-                " ({}/{})",
-                app.log_search_match_index.map_or(0, |i| i + 1),
-                app.log_search_matches.len()
+            format_log_match_info(
+                app.log_search_match_index,
+                app.log_search_matches.len(),
+                app.log_journal_match_count.as_ref(),
+                app.journal_count_in_flight(),
             )
         };
         let search_text = format!("/{}_{}",  app.log_search_query, match_info);
+        let title = if app.log_search_whole_word { "Log Search [whole word]" } else { "Log Search" };
         Paragraph::new(search_text)
             .style(Style::default().fg(Color::Magenta))
-            .block(Block::default().borders(Borders::ALL).title("Log Search"))
+            .block(Block::default().borders(Borders::ALL).title(title))
     } else if !app.log_search_query.is_empty() && app.show_logs {
         let match_info = format!(
-            "Log search: \"{}\" ({} matches) | n/N: Next/Prev",
+            "Log search: \"{}\"{} ({} matches{}) | n/N: Next/Prev  #: Count in journal",
             app.log_search_query,
-            app.log_search_matches.len()
+            if app.log_search_whole_word { " [whole word]" } else { "" },
+            app.log_search_matches.len(),
+            journal_count_suffix(
+                app.log_journal_match_count.as_ref(),
+                app.journal_count_in_flight()
+            )
         );
         Paragraph::new(match_info)
             .style(Style::default().fg(Color::Magenta))
@@ -230,16 +281,32 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
         Paragraph::new(search_text)
             .style(Style::default().fg(Color::Yellow))
             .block(Block::default().borders(Borders::ALL).title(title))
+    } else if app.jump_mode {
+        let scope_label = if app.user_mode { "User" } else { "System" };
+        let title = format!("{} [{}]{host_suffix} Go to Unit", app.unit_type.label(), scope_label);
+        let no_match = !app.jump_query.is_empty()
+            && !app.selected_unit().is_some_and(|u| {
+                u.unit.to_ascii_lowercase().starts_with(&app.jump_query.to_ascii_lowercase())
+            });
+        let jump_text = format!("{}_{}", app.jump_query, if no_match { " (no match)" } else { "" });
+        Paragraph::new(jump_text)
+            .style(Style::default().fg(Color::Yellow))
+            .block(Block::default().borders(Borders::ALL).title(title))
+    } else if let Some(ref alert) = app.failure_alert {
+        Paragraph::new(alert.as_str())
+            .style(Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD))
+            .block(Block::default().borders(Borders::ALL))
     } else if !app.search_query.is_empty() || app.status_filter.is_some() || app.file_state_filter.is_some() {
         let mut info_parts = Vec::new();
         if !app.search_query.is_empty() {
             info_parts.push(format!("Search: {}", app.search_query));
         }
+        let negation = if app.filter_inverted { "NOT " } else { "" };
         if let Some(ref status) = app.status_filter {
-            info_parts.push(format!("Status: {}", status));
+            info_parts.push(format!("Status: {}{}", negation, status));
         }
         if let Some(ref fs) = app.file_state_filter {
-            info_parts.push(format!("File state: {}", fs));
+            info_parts.push(format!("File state: {}{}", negation, fs));
         }
         let scope_label = if app.user_mode { "User" } else { "System" };
         let prefix = format!("{} [{}]{host_suffix}", app.unit_type.label(), scope_label);
@@ -251,6 +318,10 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
         Paragraph::new(msg.as_str())
             .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
             .block(Block::default().borders(Borders::ALL))
+    } else if app.showing_kernel_logs() {
+        Paragraph::new(format!("SystemD Kernel Logs{host_suffix}"))
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .block(Block::default().borders(Borders::ALL))
     } else if app.system_logs_mode {
         Paragraph::new(format!("SystemD Logs{host_suffix}"))
             .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
@@ -263,11 +334,24 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
             .last_refreshed
             .map(|t| format!("  (loaded {})", t.format("%b %d %H:%M:%S %Z")))
             .unwrap_or_default();
-        Paragraph::new(format!("{}{}", title, refreshed))
+        let auto_refresh = app
+            .auto_refresh_interval
+            .map(|i| format!(" [auto {}s]", i.as_secs()))
+            .unwrap_or_default();
+        let listing = if app.show_all_units { "" } else { " [active only]" };
+        Paragraph::new(format!("{}{}{}{}", title, listing, refreshed, auto_refresh))
             .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
             .block(Block::default().borders(Borders::ALL))
     };
     frame.render_widget(header, chunks[0]);
+    if app.action_in_progress
+        || app.refresh_in_flight()
+        || app.security_loading()
+        || app.dependency_filter_loading()
+        || app.reverse_deps_loading()
+    {
+        render_title_spinner(frame, chunks[0], spinner);
+    }
 
     // Services list (hidden when logs are full-screen)
     if let Some(services_area) = services_area {
@@ -280,22 +364,64 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
         let header_area = service_chunks[0];
         let list_area = service_chunks[1];
 
-        // Name column: dynamic width capped at 35 chars, +2 for padding
+        // Name column: dynamic width capped at 35 chars plus markers, +2 for padding
         const NAME_MAX: usize = 35;
+        let changed = |unit: &str| app.show_state_changes && app.state_changes.contains_key(unit);
+        let name_marker = |unit: &str| {
+            let mut marker = String::new();
+            if changed(unit) {
+                marker.push('\u{2192}');
+            }
+            if app.selected_units.contains(unit) {
+                marker.push('*');
+            }
+            if app.favorites.contains(unit) {
+                marker.push('\u{2605}');
+            }
+            marker
+        };
         let name_width = app
             .filtered_indices
             .iter()
-            .map(|&i| app.services[i].unit.len().min(NAME_MAX))
+            .map(|&i| {
+                let unit = &app.services[i].unit;
+                name_marker(unit).width() + unit.len().min(NAME_MAX)
+            })
             .max()
             .unwrap_or(4)
             .max(4)
             + 2;
 
+        // Optional UPTIME column, right after STATUS
+        let uptime_header = if app.show_active_since { format!("{:<10}", "UPTIME") } else { String::new() };
+
+        // The all-units list names each unit's type after NAME
+        let all_view = app.unit_type == UnitType::All;
+        let type_header = if all_view { format!("{:<TYPE_WIDTH$}", "TYPE") } else { String::new() };
+
+        // Timers and sockets replace ENABLED/LOAD with their own columns
+        let timer_view = app.unit_type == UnitType::Timer;
+        let socket_view = app.unit_type == UnitType::Socket;
+        let rest_header = if timer_view {
+            format!(
+                "{:<17}{:<10}{:<17}{:<12}{}",
+                "NEXT", "LEFT", "LAST", "PASSED", "DESCRIPTION"
+            )
+        } else if socket_view {
+            format!(
+                "{:<lw$}{:<7}{:<10}{}",
+                "LISTEN", "CONNS", "ACCEPTED", "DESCRIPTION",
+                lw = SOCKET_LISTEN_WIDTH
+            )
+        } else {
+            format!("{:<16}{:<10}{}", "ENABLED", "LOAD", "DESCRIPTION")
+        };
+
         // Column header
         let header_line = Line::from(Span::styled(
             format!(
-                " {:<nw$}{:<10}{:<16}{:<10}{}",
-                "NAME", "STATUS", "ENABLED", "LOAD", "DESCRIPTION",
+                " {:<nw$}{}{:<10}{}{}",
+                "NAME", type_header, "STATUS", uptime_header, rest_header,
                 nw = name_width,
             ),
             Style::default()
@@ -310,49 +436,113 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
                 .block(Block::default().borders(Borders::ALL).title("Error"));
             frame.render_widget(error_msg, list_area);
         } else {
+            let highlighted = app.list_state.selected();
+            let now_us = now_micros();
             let items: Vec<ListItem> = app
                 .filtered_indices
                 .iter()
                 .map(|&i| &app.services[i])
-                .map(|unit| {
+                .enumerate()
+                .map(|(row, unit)| {
                     let status_color = unit.status_color();
                     let file_state_str = unit.file_state.as_deref().unwrap_or("");
                     let mut desc = unit.description.clone();
-                    if let Some(ref detail) = unit.detail {
+                    if !timer_view
+                        && !socket_view
+                        && let Some(ref detail) = unit.detail_at(now_us)
+                    {
                         desc.push_str(&format!(" ({})", detail));
                     }
+                    if changed(&unit.unit) {
+                        let (old, _) = &app.state_changes[&unit.unit];
+                        desc = format!("[was {}] {}", old, desc);
+                    }
                     let display_name = if unit.unit.len() > NAME_MAX {
                         format!("{}...", &unit.unit[..NAME_MAX - 3])
                     } else {
                         unit.unit.clone()
                     };
-                    let spans = vec![
-                        Span::styled(
-                            format!("{:<nw$}", display_name, nw = name_width),
-                            Style::default().fg(Color::White),
-                        ),
-                        Span::styled(
-                            format!("{:<10}", unit.status_display()),
-                            Style::default().fg(status_color),
-                        ),
-                        Span::styled(
-                            format!("{:<16}", file_state_str),
-                            Style::default().fg(file_state_color(file_state_str)),
-                        ),
-                        Span::styled(
-                            format!("{:<10}", unit.load),
-                            Style::default().fg(load_color(&unit.load)),
-                        ),
-                        Span::styled(desc, Style::default().fg(Color::Gray)),
-                    ];
-                    ListItem::new(Line::from(spans))
+                    // "\u{2192}" marks units whose state changed since the last
+                    // refresh, "*" multi-selected units, "\u{2605}" favorites.
+                    let marker = name_marker(&unit.unit);
+                    let name_style = if app.selected_units.contains(&unit.unit) {
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                    } else if changed(&unit.unit) {
+                        Style::default().fg(CHANGED_UNIT_COLOR).add_modifier(Modifier::BOLD)
+                    } else if all_view {
+                        Style::default().fg(unit_kind_color(&unit.unit))
+                    } else {
+                        Style::default().fg(Color::White)
+                    };
+                    let padding = name_width.saturating_sub(marker.width() + display_name.len());
+                    let mut spans = vec![Span::styled(
+                        format!("{}{}{}", marker, display_name, " ".repeat(padding)),
+                        name_style,
+                    )];
+                    if all_view {
+                        spans.push(Span::styled(
+                            format!("{:<TYPE_WIDTH$}", unit_suffix(&unit.unit)),
+                            Style::default().fg(unit_kind_color(&unit.unit)),
+                        ));
+                    }
+                    spans.push(Span::styled(
+                        format!("{:<10}", unit.status_display()),
+                        Style::default().fg(status_color),
+                    ));
+                    if app.show_active_since {
+                        let uptime = app.active_elapsed(&unit.unit).unwrap_or_default();
+                        spans.push(Span::styled(
+                            format!("{:<10}", uptime),
+                            Style::default().fg(Color::Gray),
+                        ));
+                    }
+                    if timer_view {
+                        let [next, left, last, passed] = timer_columns(unit, now_us);
+                        spans.extend([
+                            Span::styled(format!("{:<17}", next), Style::default().fg(Color::White)),
+                            Span::styled(format!("{:<10}", left), Style::default().fg(Color::Cyan)),
+                            Span::styled(format!("{:<17}", last), Style::default().fg(Color::White)),
+                            Span::styled(format!("{:<12}", passed), Style::default().fg(theme().muted)),
+                        ]);
+                    } else if socket_view {
+                        let [listen, conns, accepted] = socket_columns(unit);
+                        let padding = SOCKET_LISTEN_WIDTH.saturating_sub(listen.width());
+                        spans.extend([
+                            Span::styled(
+                                format!("{}{}", listen, " ".repeat(padding)),
+                                Style::default().fg(Color::White),
+                            ),
+                            Span::styled(format!("{:<7}", conns), Style::default().fg(Color::Cyan)),
+                            Span::styled(format!("{:<10}", accepted), Style::default().fg(theme().muted)),
+                        ]);
+                    } else {
+                        spans.extend([
+                            Span::styled(
+                                format!("{:<16}", file_state_str),
+                                Style::default().fg(file_state_color(file_state_str)),
+                            ),
+                            Span::styled(
+                                format!("{:<10}", unit.load),
+                                Style::default().fg(load_color(&unit.load)),
+                            ),
+                        ]);
+                    }
+                    spans.push(Span::styled(desc, Style::default().fg(Color::Gray)));
+                    let item = ListItem::new(Line::from(spans));
+                    if app.row_severity_colors {
+                        item.style(row_style(&unit.sub, highlighted == Some(row), app.color_enabled))
+                    } else {
+                        item
+                    }
                 })
                 .collect();
 
             let type_label = app.unit_type.label();
-            let title = if app.search_query.is_empty()
+            let mut title = if app.search_query.is_empty()
                 && app.status_filter.is_none()
                 && app.file_state_filter.is_none()
+                && app.dependency_filter.is_none()
+                && app.filtered_indices.len() == app.services.len()
             {
                 format!("{} ({})", type_label, app.services.len())
             } else {
@@ -363,12 +553,31 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
                     app.services.len()
                 )
             };
+            if !app.selected_units.is_empty() {
+                title.push_str(&format!(" [{} selected]", app.selected_units.len()));
+            }
+            if app.show_state_changes {
+                title.push_str(&format!(" [{} changed]", app.state_changes.len()));
+            }
+            if app.hiding_noise() {
+                title.push_str(" [static/indirect hidden]");
+            }
+            if let Some(unit) = &app.dependency_filter_unit {
+                title.push_str(&format!(" [deps of {}]", unit));
+            }
 
+            // In the split view the focused pane gets the yellow border.
+            let list_border_style = if app.split_view && app.focused_pane == Pane::List {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
             let list = List::new(items)
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title(title),
+                        .title(title)
+                        .border_style(list_border_style),
                 )
                 .highlight_style(
                     Style::default()
@@ -382,7 +591,9 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
 
     // Logs panel (only if visible)
     if let Some(logs_area) = logs_area {
-        let mut logs_title = if app.system_logs_mode {
+        let mut logs_title = if app.showing_kernel_logs() {
+            "Kernel Logs".to_string()
+        } else if app.system_logs_mode {
             "System Logs".to_string()
         } else if let Some(ref service_name) = app.last_selected_service {
             format!("Logs: {}", service_name)
@@ -390,23 +601,64 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
             "Logs".to_string()
         };
 
-        if let Some(p) = app.log_priority_filter {
-            logs_title.push_str(&format!(" [p:{}]", priority_label(p)));
+        if let Some(range) = app.log_priority_filter {
+            logs_title.push_str(&format!(" [p:{}]", priority_filter_label(range)));
         }
         if app.log_time_range != TimeRange::All {
-            logs_title.push_str(&format!(" [t:{}]", app.log_time_range.label()));
+            logs_title.push_str(&format!(" [t:{}]", app.log_time_range.summary()));
+        }
+        if let Some(grep) = &app.log_grep {
+            logs_title.push_str(&format!(" [grep:{}]", grep.pattern));
+        }
+        if app.filter_main_pid && !app.system_logs_mode {
+            match app.log_main_pid {
+                Some(pid) => logs_title.push_str(&format!(" [pid:{}]", pid)),
+                None if !app.logs_loading() => logs_title.push_str(" [pid:none]"),
+                None => {}
+            }
+        }
+        if app.log_line_limit != DEFAULT_LOG_LINE_LIMIT {
+            logs_title.push_str(&format!(" [n:{}]", app.log_line_limit));
+        }
+        if !app.log_wrap {
+            logs_title.push_str(" [nowrap]");
+        }
+        if app.collapse_log_repeats {
+            logs_title.push_str(" [collapsed]");
+        }
+        if app.use_utc {
+            logs_title.push_str(" [UTC]");
+        }
+        if app.log_timestamp_micros && app.log_timestamp_format != TimestampFormat::Relative {
+            logs_title.push_str(" [\u{b5}s]");
+        }
+        if app.log_show_hostname {
+            logs_title.push_str(" [host]");
         }
 
-        let focused_suffix = " [FOCUSED]";
+        let focused_suffix = if app.logs_focused() { " [FOCUSED]" } else { "" };
 
         // Calculate visible area (subtract 2 for borders)
         let visible_lines = logs_area.height.saturating_sub(2) as usize;
         let content_width = logs_area.width.saturating_sub(2) as usize;
 
         // Resolve "go to bottom" sentinel against wrapped visual lines.
-        ensure_log_entry_heights_cache(app, content_width);
+        // Unwrapped entries are always one line tall, which a zero width
+        // expresses to the height cache.
+        let wrap_width = if app.log_wrap { content_width } else { 0 };
+        ensure_log_entry_heights_cache(app, wrap_width);
+        if !app.cached_log_repeat_counts.is_empty() {
+            // A search jump or selection can land inside a collapsed run;
+            // show the run's row instead.
+            if app.logs_scroll != usize::MAX {
+                app.logs_scroll = app.log_row_start(app.logs_scroll);
+            }
+            app.log_selected_entry = app.log_selected_entry.map(|sel| app.log_row_start(sel));
+        }
         let bottom_scroll = bottom_scroll_index(&app.cached_entry_heights, visible_lines);
-        if app.logs_scroll == usize::MAX {
+        // A following live view stays pinned to the newest entry, even as
+        // the panel resizes.
+        if app.logs_scroll == usize::MAX || (app.log_follow && !app.log_paused) {
             app.logs_scroll = bottom_scroll;
             app.logs_at_bottom = true;
         } else if app.logs.is_empty() {
@@ -461,13 +713,19 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
                 .find_map(|e| e.invocation_id.as_deref())
         };
 
+        let boot_numbers = app.log_boot_indices.then(|| boot_indices(&app.logs));
+
         // Create log content with scroll, search highlighting, and boot separators
         let mut log_lines: Vec<Line> = Vec::new();
+        let mut entry_line_indices: Vec<usize> = Vec::new();
         let mut entries_shown = 0;
         for (entry_idx, entry) in app.logs.iter().enumerate().skip(app.logs_scroll) {
             if log_lines.len() >= visible_lines {
                 break;
             }
+            if app.log_repeat_count(entry_idx) == 0 {
+                continue;
+            }
             if entry_idx > 0 {
                 let prev = &app.logs[entry_idx - 1];
                 let check_invocation = !app.system_logs_mode;
@@ -476,12 +734,16 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
 
                 // Boot boundary separator
                 if boot_changed {
-                    let short_id = entry.boot_id.as_ref().map(|id| &id[..id.len().min(12)]).unwrap_or("?");
+                    let boot_name = match (&boot_numbers, &entry.boot_id) {
+                        (Some(numbers), Some(id)) => format!("#{}", numbers[id]),
+                        (None, Some(id)) => id[..id.len().min(12)].to_string(),
+                        _ => "?".to_string(),
+                    };
                     let boot_ts = entry
                         .timestamp
-                        .map(|ts| format!(" · {}", format_log_timestamp(ts)))
+                        .map(|ts| format!(" · {}", format_log_timestamp(ts, app.log_timestamp_format, app.use_utc, app.log_timestamp_micros)))
                         .unwrap_or_default();
-                    let label = format!(" Boot {}{} ", short_id, boot_ts);
+                    let label = format!(" Boot {}{} ", boot_name, boot_ts);
                     let pad_total = content_width.saturating_sub(label.width());
                     let pad_left = pad_total / 2;
                     let pad_right = pad_total - pad_left;
@@ -504,7 +766,7 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
                 if invocation_changed {
                     let restart_ts = entry
                         .timestamp
-                        .map(|ts| format!(" · {}", format_log_timestamp(ts)))
+                        .map(|ts| format!(" · {}", format_log_timestamp(ts, app.log_timestamp_format, app.use_utc, app.log_timestamp_micros)))
                         .unwrap_or_default();
                     let label = format!(" Restarted{} ", restart_ts);
                     let pad_total = content_width.saturating_sub(label.width());
@@ -529,7 +791,7 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
                 && let Some(id) = entry.invocation_id.as_deref() {
                     last_invocation_id = Some(id);
             }
-            let mut line = render_log_entry(entry, entry_idx, app);
+            let mut line = render_log_entry(entry, entry_idx, app, is_current_log_match(app, entry_idx));
             if app.log_selected_entry == Some(entry_idx) {
                 // Apply selection highlight: DarkGray bg, brighten dim fg spans
                 line = Line::from(
@@ -539,7 +801,7 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
                             let mut style = span.style.bg(Color::DarkGray);
                             // Brighten DarkGray/muted foreground so it's readable on DarkGray bg
                             if span.style.fg == Some(Color::DarkGray)
-                                || span.style.fg == Some(COLOR_MUTED)
+                                || span.style.fg == Some(theme().muted)
                             {
                                 style = style.fg(Color::Gray);
                             }
@@ -548,16 +810,34 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
                         .collect::<Vec<_>>(),
                 );
             }
+            entry_line_indices.push(log_lines.len());
             log_lines.push(line);
             entries_shown += 1;
         }
 
+        // Horizontal scroll for unwrapped lines; separators stay put.
+        if !app.log_wrap {
+            let max_width = entry_line_indices
+                .iter()
+                .map(|&i| log_lines[i].width())
+                .max()
+                .unwrap_or(0);
+            app.logs_h_scroll = clamp_h_scroll(app.logs_h_scroll, max_width, content_width);
+            if app.logs_h_scroll > 0 {
+                for &i in &entry_line_indices {
+                    let line = std::mem::take(&mut log_lines[i]);
+                    log_lines[i] = skip_line_columns(line, app.logs_h_scroll);
+                }
+            }
+        }
+
         let scroll_info = if !app.logs.is_empty() {
             format!(
-                " [{}-{}/{}]",
+                " [{}-{}/{} ~{}]",
                 app.logs_scroll + 1,
                 app.logs_scroll + entries_shown,
-                app.logs.len()
+                app.logs.len(),
+                format_bytes(app.logs_byte_size())
             )
         } else {
             String::new()
@@ -565,42 +845,48 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
 
         let mut title_spans = vec![Span::raw(logs_title)];
         if !app.log_paused {
-            let live_style = if app.logs_at_bottom && live_indicator_on {
-                Style::default().fg(Color::LightGreen)
-            } else if app.logs_at_bottom {
-                Style::default().fg(Color::DarkGray)
-            } else {
-                // Scrolled up: solid green (no blink)
-                Style::default().fg(Color::LightGreen)
-            };
             title_spans.push(Span::raw(" "));
-            title_spans.push(Span::styled("[LIVE]", live_style));
+            title_spans.push(live_indicator(app.log_follow, live_indicator_on));
+        }
+        if app.logs_loading() {
+            title_spans.push(Span::styled(format!(" {}", spinner), Style::default().fg(Color::Yellow)));
         }
         title_spans.push(Span::raw(focused_suffix));
         title_spans.push(Span::raw(scroll_info));
 
-        let border_style = Style::default().fg(Color::Yellow);
+        let border_style = if app.logs_focused() {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
 
-        let logs_paragraph = Paragraph::new(log_lines)
-            .style(Style::default().fg(Color::White))
+        // While a new buffer loads, the previous one stays visible but dimmed.
+        let mut logs_style = Style::default().fg(Color::White);
+        if app.logs_loading() {
+            if app.logs.is_empty() {
+                log_lines.push(Line::styled("Loading logs\u{2026}", Style::default().fg(theme().muted)));
+            }
+            logs_style = logs_style.add_modifier(Modifier::DIM);
+        }
+
+        let mut logs_paragraph = Paragraph::new(log_lines)
+            .style(logs_style)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(Line::from(title_spans))
                     .border_style(border_style),
-            )
-            .wrap(Wrap { trim: false });
+            );
+        if app.log_wrap {
+            logs_paragraph = logs_paragraph.wrap(Wrap { trim: false });
+        }
 
         frame.render_widget(logs_paragraph, logs_area);
     }
 
     // Unit file panel (only if visible)
     if let Some(unit_file_area) = unit_file_area {
-        let unit_file_title = if let Some(ref name) = app.unit_file_unit_name {
-            format!("Unit File: {}", name)
-        } else {
-            "Unit File".to_string()
-        };
+        let unit_file_title = unit_file_title(app.unit_file_unit_name.as_deref(), app.unit_file_path.as_deref());
 
         let visible_lines = unit_file_area.height.saturating_sub(2) as usize;
 
@@ -618,7 +904,7 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
             if file_lines.len() >= visible_lines {
                 break;
             }
-            file_lines.push(highlight_unit_file_line(line, line_idx, app));
+            file_lines.push(render_unit_file_line(line, line_idx, app));
             lines_shown += 1;
         }
 
@@ -654,28 +940,62 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
     // suffix is always visible and right-aligned.
     let content_width = chunks[2].width.saturating_sub(2) as usize; // subtract borders
 
-    let (segments, suffix): (&[&str], &str) = if app.show_help {
-        (&["\u{2191}/\u{2193}: Scroll", "g/G: Top/Bottom", "PgUp/PgDn: Page"], "Esc/q: Close")
+    let action_hints = app.footer_action_hints();
+    let (segments, suffix): (&[&str], &str) = if app.pending_quit_confirm {
+        (&[], "Y: Quit | N/Esc: Keep running")
+    } else if app.show_help && app.help_search_mode {
+        (&["Type to filter keys", "Enter: Done"], "Esc: Clear")
+    } else if app.show_help {
+        (&["\u{2191}/\u{2193}: Scroll", "g/G: Top/Bottom", "PgUp/PgDn: Page", "/: Filter"], "Esc/q: Close")
     } else if app.show_confirm && app.action_in_progress {
-        (&[], "Executing...")
+        (&[], "Executing... | q: Quit")
+    } else if app.show_confirm && app.can_retry_privileged() {
+        (&["p: Retry with pkexec", "s: Retry with sudo"], "Any other key: Dismiss")
     } else if app.show_confirm && app.action_result.is_some() {
         (&[], "Press any key to dismiss")
+    } else if app.show_confirm && app.confirm_action.is_some_and(|a| a.queues_job()) {
+        (&[if app.no_block { "b: Wait for job" } else { "b: --no-block" }], "Y: Confirm | N/Esc: Cancel")
     } else if app.show_confirm {
         (&[], "Y: Confirm | N/Esc: Cancel")
     } else if app.show_action_picker {
         (&["\u{2191}/\u{2193}: Navigate", "Enter/shortcut: Select", "Esc/x: Close"], "?: Help")
+    } else if app.show_details && app.detail_search_mode {
+        (&["Type to search", "Enter: Done"], "Esc: Clear")
     } else if app.show_details {
-        (&["\u{2191}/\u{2193}: Scroll", "g/G: Top/Bottom", "PgUp/PgDn: Page", "Esc/i: Close"], "?: Help")
+        (
+            &["\u{2191}/\u{2193}: Scroll", "g/G: Top/Bottom", "PgUp/PgDn: Page", "^u/^d: Half page", "/: Search", "n/N: Next/Prev", "w: Save raw", "Esc/i: Close"],
+            "?: Help",
+        )
     } else if app.show_status_picker {
         (&["\u{2191}/\u{2193}: Navigate", "Enter: Select", "Esc/s: Close"], "?: Help")
     } else if app.show_type_picker {
         (&["\u{2191}/\u{2193}: Navigate", "Enter: Select", "Esc/t: Close"], "?: Help")
     } else if app.show_priority_picker {
-        (&["\u{2191}/\u{2193}: Navigate", "Enter: Select", "Esc/p: Close"], "?: Help")
+        (&["\u{2191}/\u{2193}: Navigate", "Space: Mark range end", "Enter: Select", "Esc/p: Close"], "?: Help")
     } else if app.show_time_picker {
         (&["\u{2191}/\u{2193}: Navigate", "Enter: Select", "Esc/T: Close"], "?: Help")
+    } else if app.show_custom_time_input {
+        (&["Type since/until", "Tab: Switch field", "Enter: Apply"], "Esc: Cancel")
+    } else if app.show_grep_input {
+        (&["Type pattern", "Tab: Toggle case", "Enter: Apply"], "Esc: Cancel")
     } else if app.show_file_state_picker {
         (&["\u{2191}/\u{2193}: Navigate", "Enter: Select", "Esc/f: Close"], "?: Help")
+    } else if app.show_timestamp_format_picker {
+        (&["\u{2191}/\u{2193}: Navigate", "Enter: Select", "Esc/d: Close"], "?: Help")
+    } else if app.show_preset_picker {
+        (&["\u{2191}/\u{2193}: Navigate", "Enter: Apply/Save", "d: Delete"], "Esc/m: Close")
+    } else if app.show_preset_name_input {
+        (&["Type a name", "Enter: Save"], "Esc: Cancel")
+    } else if app.show_log_fields {
+        (&["\u{2191}/\u{2193}: Scroll", "PgUp/PgDn: Page", "g: Top"], "Esc/i: Close")
+    } else if app.show_reverse_deps {
+        (&["\u{2191}/\u{2193}: Scroll", "PgUp/PgDn: Page", "g: Top"], "Esc/w: Close")
+    } else if app.show_action_output {
+        (&["\u{2191}/\u{2193}: Scroll", "PgUp/PgDn: Page", "g: Top"], "Esc/O: Close")
+    } else if app.show_security {
+        (&["\u{2191}/\u{2193}: Scroll", "PgUp/PgDn: Page", "g: Top"], "Esc/a: Close")
+    } else if app.show_export_picker {
+        (&["\u{2191}/\u{2193}: Navigate", "Enter: Export"], "Esc/E: Close")
     } else if app.unit_file_search_mode {
         (&["Type to search unit file", "Esc/Enter: Exit search"], "?: Help & more")
     } else if app.show_unit_file && !app.unit_file_search_query.is_empty() {
@@ -683,25 +1003,33 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
     } else if app.show_unit_file {
         (&["v/Esc: Back", "\u{2191}/\u{2193}: Scroll", "g/G: Top/Bottom", "/: Search"], "?: Help & more")
     } else if app.log_search_mode {
-        (&["Type to search logs", "Esc/Enter: Exit search"], "?: Help & more")
-    } else if app.show_logs && !app.log_search_query.is_empty() {
+        (&["Type to search logs", "Tab: Whole word", "Esc/Enter: Exit search"], "?: Help & more")
+    } else if app.logs_focused() && !app.log_search_query.is_empty() {
         if app.log_paused {
             (&["q/Esc: Back", "\u{2191}/\u{2193}: Scroll", "n/N: Next/Prev match", "x: Actions", "f: Resume", "L: All logs", "p: Priority", "t: Time", "/: Search"], "?: Help & more")
+        } else if !app.log_follow {
+            (&["q/Esc: Back", "\u{2191}/\u{2193}: Scroll", "n/N: Next/Prev match", "x: Actions", "f: Follow", "L: All logs", "p: Priority", "t: Time", "/: Search"], "?: Help & more")
         } else {
             (&["q/Esc: Back", "\u{2191}/\u{2193}: Scroll", "n/N: Next/Prev match", "x: Actions", "f: Pause", "L: All logs", "p: Priority", "t: Time", "/: Search"], "?: Help & more")
         }
-    } else if app.show_logs {
+    } else if app.logs_focused() {
         if app.log_paused {
             (&["q/Esc: Back", "\u{2191}/\u{2193}: Scroll", "g/G: Top/Bottom", "x: Actions", "f: Resume", "L: All logs", "/: Search", "p: Priority", "t: Time"], "?: Help & more")
+        } else if !app.log_follow {
+            (&["q/Esc: Back", "\u{2191}/\u{2193}: Scroll", "g/G: Top/Bottom", "x: Actions", "f: Follow", "L: All logs", "/: Search", "p: Priority", "t: Time"], "?: Help & more")
         } else {
             (&["q/Esc: Back", "\u{2191}/\u{2193}: Scroll", "g/G: Top/Bottom", "x: Actions", "f: Pause", "L: All logs", "/: Search", "p: Priority", "t: Time"], "?: Help & more")
         }
     } else if app.search_mode {
         (&["Type to search", "Esc/Enter: Exit search"], "?: Help & more")
+    } else if app.jump_mode {
+        (&["Type a unit name prefix", "Esc/Enter: Done"], "?: Help & more")
+    } else if app.split_view {
+        (&["|: Close split", "Tab: Focus logs", "\u{2191}/\u{2193}: Select unit", &action_hints, "i: Details", "/: Search", "s: Status", "t: Type"], "?: Help & more")
     } else if !app.search_query.is_empty() || app.status_filter.is_some() || app.file_state_filter.is_some() {
-        (&["q: Quit", "/: Search", "s: Status", "f: File state", "x: Actions", "i: Details", "t: Type", "l: Logs", "L: All logs", "r: Refresh", "u: User/System", "Esc: Clear"], "?: Help & more")
+        (&["q: Quit", "/: Search", "s: Status", "f: File state", &action_hints, "i: Details", "t: Type", "l: Logs", "L: All logs", "r: Refresh", "u: User/System", "Esc: Clear"], "?: Help & more")
     } else {
-        (&["q/Esc: Quit", "/: Search", "s: Status", "f: File state", "x: Actions", "i: Details", "t: Type", "l: Logs", "L: All logs", "r: Refresh", "u: User/System"], "?: Help & more")
+        (&["q/Esc: Quit", "/: Search", "s: Status", "f: File state", &action_hints, "i: Details", "t: Type", "l: Logs", "L: All logs", "r: Refresh", "u: User/System"], "?: Help & more")
     };
 
     let footer_line = build_footer_line(segments, suffix, content_width);
@@ -729,11 +1057,59 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
         render_time_picker(frame, app);
     }
 
+    // Custom time range input overlay
+    if app.show_custom_time_input {
+        render_custom_time_input(frame, app);
+    }
+
+    // journalctl --grep input overlay
+    if app.show_grep_input {
+        render_grep_input(frame, app);
+    }
+
     // File state picker overlay
     if app.show_file_state_picker {
         render_file_state_picker(frame, app);
     }
 
+    // Timestamp format picker overlay
+    if app.show_timestamp_format_picker {
+        render_timestamp_format_picker(frame, app);
+    }
+
+    // Log filter presets overlays
+    if app.show_preset_picker {
+        render_preset_picker(frame, app);
+    }
+    if app.show_preset_name_input {
+        render_preset_name_input(frame, app);
+    }
+
+    // Log export format picker overlay
+    if app.show_export_picker {
+        render_export_picker(frame, app);
+    }
+
+    // Journal fields popup
+    if app.show_log_fields {
+        render_log_fields(frame, app);
+    }
+
+    // Reverse dependencies popup
+    if app.show_reverse_deps {
+        render_reverse_deps(frame, app);
+    }
+
+    // Last action output popup
+    if app.show_action_output {
+        render_action_output(frame, app);
+    }
+
+    // Security analysis popup
+    if app.show_security {
+        render_security_analysis(frame, app);
+    }
+
     // Action picker overlay
     if app.show_action_picker {
         render_action_picker(frame, app);
@@ -746,42 +1122,69 @@ pub fn render(frame: &mut Frame, app: &mut App, live_indicator_on: bool) {
 
     // Details modal (on top of pickers)
     if app.show_details {
-        render_details_modal(frame, app);
+        render_details_modal(frame, app, spinner);
     }
 
     // Help overlay
     if app.show_help {
         render_help(frame, app);
     }
+
+    if app.pending_quit_confirm {
+        render_quit_confirm(frame, app.action_in_progress);
+    }
+
+    if !app.color_enabled {
+        strip_colors(frame.buffer_mut());
+    }
+}
+
+/// Removes all colors from a rendered frame for `--no-color`. Cells drawn on
+/// a highlight background (selected row, selected log entry) become reversed
+/// so the selection stays visible; modal backdrops (black) are just cleared.
+fn strip_colors(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if !matches!(cell.bg, Color::Reset | Color::Black) {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
 }
 
 fn priority_color(p: u8) -> (Color, bool) {
-    match p {
-        0..=2 => (Color::Red, true),    // emerg/alert/crit - bold
-        3 => (Color::Red, false),        // err
-        4 => (Color::Yellow, false),     // warning
-        5 => (Color::Cyan, false),       // notice
-        6 => (Color::White, false),      // info
-        7 => (Color::DarkGray, false),   // debug
-        _ => (Color::White, false),
-    }
-}
-
-fn log_boundary_before_entry(
-    prev: &LogEntry,
-    current: &LogEntry,
-    last_invocation_id: Option<&str>,
-) -> (bool, bool) {
-    let boot_changed = matches!(
-        (&prev.boot_id, &current.boot_id),
-        (Some(a), Some(b)) if a != b
-    );
-    let invocation_changed = !boot_changed
-        && matches!(
-            (last_invocation_id, current.invocation_id.as_deref()),
-            (Some(a), Some(b)) if a != b
-        );
-    (boot_changed, invocation_changed)
+    // emerg/alert/crit are also bold
+    (theme().priority_color(p), p <= 2)
+}
+
+/// One sample per priority level, styled exactly like a log line of that
+/// priority, for the legend in the logs help.
+fn priority_legend() -> Vec<Span<'static>> {
+    PRIORITY_LABELS
+        .iter()
+        .enumerate()
+        .map(|(i, &label)| {
+            let (color, bold) = priority_color(i as u8);
+            let mut style = Style::default().fg(color);
+            if bold {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            Span::styled(label, style)
+        })
+        .collect()
+}
+
+/// Numbers the distinct boot ids in `logs` from 1, in order of first
+/// appearance, for `Boot #N` separators. The numbers only hold for the
+/// loaded entries; reloading with another limit or range can shift them.
+pub fn boot_indices(logs: &[LogEntry]) -> HashMap<String, usize> {
+    let mut indices = HashMap::new();
+    for id in logs.iter().filter_map(|e| e.boot_id.as_ref()) {
+        if !indices.contains_key(id) {
+            indices.insert(id.clone(), indices.len() + 1);
+        }
+    }
+    indices
 }
 
 fn wrapped_line_count(line: &Line<'_>, content_width: usize) -> usize {
@@ -791,12 +1194,48 @@ fn wrapped_line_count(line: &Line<'_>, content_width: usize) -> usize {
     line.width().max(1).div_ceil(content_width)
 }
 
+/// Largest useful horizontal offset: far enough to bring the end of the
+/// longest visible line to the right edge, and no further.
+fn clamp_h_scroll(offset: usize, max_line_width: usize, content_width: usize) -> usize {
+    offset.min(max_line_width.saturating_sub(content_width))
+}
+
+/// Drops the first `columns` display columns from a line, preserving span
+/// styles. A wide character straddling the cut is dropped entirely.
+fn skip_line_columns(line: Line<'_>, columns: usize) -> Line<'static> {
+    let mut remaining = columns;
+    let mut spans = Vec::new();
+    for span in line.spans {
+        if remaining == 0 {
+            spans.push(Span::styled(span.content.into_owned(), span.style));
+            continue;
+        }
+        let mut kept = String::new();
+        for ch in span.content.chars() {
+            if remaining > 0 {
+                remaining = remaining.saturating_sub(ch.width().unwrap_or(0));
+            } else {
+                kept.push(ch);
+            }
+        }
+        if !kept.is_empty() {
+            spans.push(Span::styled(kept, span.style));
+        }
+    }
+    Line::from(spans)
+}
+
 fn ensure_log_entry_heights_cache(app: &mut App, content_width: usize) {
     if app.cached_entry_heights_dirty
         || app.cached_entry_heights_width != content_width
         || app.cached_entry_heights_query != app.log_search_query
         || app.cached_entry_heights.len() != app.logs.len()
     {
+        app.cached_log_repeat_counts = if app.collapse_log_repeats {
+            collapse_repeats(&app.logs)
+        } else {
+            Vec::new()
+        };
         app.cached_entry_heights = log_entry_visual_heights(app, content_width);
         app.cached_entry_heights_width = content_width;
         app.cached_entry_heights_query = app.log_search_query.clone();
@@ -809,7 +1248,12 @@ fn log_entry_visual_heights(app: &App, content_width: usize) -> Vec<usize> {
     let mut last_invocation_id: Option<&str> = None;
 
     for (entry_idx, entry) in app.logs.iter().enumerate() {
-        let mut entry_lines = wrapped_line_count(&render_log_entry(entry, entry_idx, app), content_width);
+        if app.log_repeat_count(entry_idx) == 0 {
+            heights.push(0);
+            continue;
+        }
+        // Styling doesn't change widths, so the current-match flag is moot here.
+        let mut entry_lines = wrapped_line_count(&render_log_entry(entry, entry_idx, app, false), content_width);
         if entry_idx > 0 {
             let prev = &app.logs[entry_idx - 1];
             let check_invocation = !app.system_logs_mode;
@@ -835,22 +1279,58 @@ fn bottom_scroll_index(entry_heights: &[usize], visible_lines: usize) -> usize {
     }
 
     let mut used = 0;
+    let mut top = None;
     for idx in (0..entry_heights.len()).rev() {
-        let entry_lines = entry_heights[idx].max(1);
+        let entry_lines = entry_heights[idx];
+        if entry_lines == 0 {
+            // Folded into a collapsed run; takes no space.
+            continue;
+        }
         if used + entry_lines > visible_lines {
-            return if used == 0 { idx } else { idx + 1 };
+            return top.unwrap_or(idx);
         }
         used += entry_lines;
+        top = Some(idx);
     }
     0
 }
 
-fn render_log_entry<'a>(entry: &LogEntry, line_idx: usize, app: &App) -> Line<'a> {
+/// Groups consecutive entries with the same message (within the same boot
+/// and invocation, so separators are never hidden). Returns one count per
+/// entry: the run length for the first entry of a run, 0 for the rest.
+fn collapse_repeats(logs: &[LogEntry]) -> Vec<usize> {
+    let mut counts = vec![0; logs.len()];
+    let mut start = 0;
+    for i in 1..=logs.len() {
+        let same = i < logs.len() && {
+            let (a, b) = (&logs[start], &logs[i]);
+            a.message == b.message && a.boot_id == b.boot_id && a.invocation_id == b.invocation_id
+        };
+        if !same {
+            counts[start] = i - start;
+            start = i;
+        }
+    }
+    counts
+}
+
+/// Background for the whole log line holding the current search match
+/// (`n`/`N`), on top of the substring highlight.
+const CURRENT_MATCH_LINE_BG: Color = Color::Rgb(60, 60, 20);
+
+/// Whether the entry at `line_idx` holds the search match `n`/`N` is on.
+fn is_current_log_match(app: &App, line_idx: usize) -> bool {
+    app.log_search_match_index
+        .and_then(|mi| app.log_search_matches.get(mi))
+        .is_some_and(|&idx| idx == line_idx)
+}
+
+fn render_log_entry<'a>(entry: &LogEntry, line_idx: usize, app: &App, is_current_match: bool) -> Line<'a> {
     let mut spans: Vec<Span<'a>> = Vec::new();
 
     // Timestamp
     if let Some(ts) = entry.timestamp {
-        let formatted = format_log_timestamp(ts);
+        let formatted = format_log_timestamp(ts, app.log_timestamp_format, app.use_utc, app.log_timestamp_micros);
         if !formatted.is_empty() {
             spans.push(Span::styled(
                 formatted,
@@ -860,15 +1340,29 @@ fn render_log_entry<'a>(entry: &LogEntry, line_idx: usize, app: &App) -> Line<'a
         }
     }
 
-    // Priority label
-    let (msg_color, msg_bold) = entry
-        .priority
-        .map(priority_color)
-        .unwrap_or((Color::White, false));
+    // Hostname
+    if app.log_show_hostname
+        && let Some(host) = &entry.hostname
+    {
+        spans.push(Span::styled(host.clone(), Style::default().fg(Color::DarkGray)));
+        spans.push(Span::raw(" "));
+    }
+
+    // Priority label. Entries less severe than the dim threshold (or with no
+    // priority at all) lose their severity color.
+    let dimmed = app
+        .dim_below_priority
+        .is_some_and(|threshold| entry.priority.is_none_or(|p| p > threshold));
+    let severity_color = |p: u8| if dimmed { (theme().muted, false) } else { priority_color(p) };
+    let (msg_color, msg_bold) = if dimmed {
+        (theme().muted, false)
+    } else {
+        entry.priority.map(priority_color).unwrap_or((Color::White, false))
+    };
 
     if let Some(p) = entry.priority {
         let label = priority_label(p);
-        let (color, bold) = priority_color(p);
+        let (color, bold) = severity_color(p);
         let mut style = Style::default().fg(color);
         if bold {
             style = style.add_modifier(Modifier::BOLD);
@@ -906,10 +1400,56 @@ fn render_log_entry<'a>(entry: &LogEntry, line_idx: usize, app: &App) -> Line<'a
         base_style = base_style.add_modifier(Modifier::BOLD);
     }
 
-    let message_spans = styled_message_spans(entry, line_idx, app, base_style);
+    let message_spans = styled_message_spans(entry, app, base_style, is_current_match);
     spans.extend(message_spans);
 
-    Line::from(spans)
+    let repeats = app.log_repeat_count(line_idx);
+    if repeats > 1 {
+        spans.push(Span::styled(
+            format!(" (\u{d7}{})", repeats),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let line = Line::from(spans);
+    if is_current_match {
+        // A line-level style reaches every grapheme, so the background
+        // carries across wrapped rows too.
+        line.style(Style::default().bg(CURRENT_MATCH_LINE_BG))
+    } else {
+        line
+    }
+}
+
+/// The `, N in journal` part of the log search match info: the result of a
+/// journal-wide count, or its progress.
+fn journal_count_suffix(journal: Option<&Result<usize, String>>, counting: bool) -> String {
+    if counting {
+        return ", counting in journal\u{2026}".to_string();
+    }
+    match journal {
+        Some(Ok(total)) => format!(", {} in journal", total),
+        Some(Err(_)) => ", journal count failed".to_string(),
+        None => String::new(),
+    }
+}
+
+/// Match info shown after the log search query, e.g. ` (3/12)` or, once the
+/// journal has been counted, ` (showing 3/12, 480 in journal)`.
+fn format_log_match_info(
+    current: Option<usize>,
+    shown: usize,
+    journal: Option<&Result<usize, String>>,
+    counting: bool,
+) -> String {
+    let suffix = journal_count_suffix(journal, counting);
+    if shown == 0 {
+        format!(" (no matches{})", suffix)
+    } else if matches!(journal, Some(Ok(_))) && !counting {
+        format!(" (showing {}/{}{})", current.map_or(0, |i| i + 1), shown, suffix)
+    } else {
+        format!(" ({}/{}{})", current.map_or(0, |i| i + 1), shown, suffix)
+    }
 }
 
 /// Builds the message spans for a log entry, overlaying (in order of
@@ -917,13 +1457,18 @@ fn render_log_entry<'a>(entry: &LogEntry, line_idx: usize, app: &App) -> Line<'a
 /// sequences in the message, and the severity base style.
 fn styled_message_spans<'a>(
     entry: &LogEntry,
-    line_idx: usize,
     app: &App,
     base_style: Style,
+    is_current_match: bool,
 ) -> Vec<Span<'a>> {
     let message = &entry.message;
     let match_ranges = if app.log_search_query.is_empty() {
         Vec::new()
+    } else if app.log_search_whole_word {
+        find_word_matches(message, &app.log_search_query)
+            .into_iter()
+            .map(|r| (r.start, r.end))
+            .collect()
     } else {
         search_match_ranges(message, &app.log_search_query.to_lowercase())
     };
@@ -932,12 +1477,6 @@ fn styled_message_spans<'a>(
         return vec![Span::styled(message.clone(), base_style)];
     }
 
-    let is_current_match = app.log_search_match_index.is_some_and(|mi| {
-        app.log_search_matches
-            .get(mi)
-            .is_some_and(|&idx| idx == line_idx)
-    });
-
     let highlight_style = if is_current_match {
         Style::default().bg(Color::Yellow).fg(Color::Black)
     } else {
@@ -975,6 +1514,36 @@ fn styled_message_spans<'a>(
     spans
 }
 
+/// Keeps the help entries whose text contains `query` (case-insensitive),
+/// each under its section heading, with a blank line between sections.
+/// An empty query keeps everything.
+fn filter_help_lines<'a>(lines: Vec<Line<'a>>, query: &str) -> Vec<Line<'a>> {
+    if query.is_empty() {
+        return lines;
+    }
+    let query = query.to_lowercase();
+    let mut kept = Vec::new();
+    let mut heading: Option<Line> = None;
+    for line in lines {
+        let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+        if text.trim().is_empty() {
+            continue;
+        }
+        if !text.starts_with(' ') {
+            heading = Some(line);
+        } else if text.to_lowercase().contains(&query) {
+            if let Some(heading) = heading.take() {
+                if !kept.is_empty() {
+                    kept.push(Line::from(""));
+                }
+                kept.push(heading);
+            }
+            kept.push(line);
+        }
+    }
+    kept
+}
+
 fn render_help(frame: &mut Frame, app: &mut App) {
     let section_style = Style::default()
         .fg(Color::Yellow)
@@ -1005,9 +1574,17 @@ fn render_help(frame: &mut Frame, app: &mut App) {
             Line::from("  g / Home      Go to top"),
             Line::from("  G / End       Go to bottom"),
             Line::from("  PgUp / PgDn   Page scroll"),
+            Line::from("  Ctrl+u / ^d   Half page scroll"),
+            Line::from(""),
+            Line::from(vec![Span::styled("Search", section_style)]),
+            Line::from("  /             Search details"),
+            Line::from("  n             Next match"),
+            Line::from("  N             Previous match"),
             Line::from(""),
             Line::from(vec![Span::styled("General", section_style)]),
-            Line::from("  Esc / i       Close details"),
+            Line::from("  w             Save systemctl show output"),
+            Line::from("  Esc           Clear search / Close details"),
+            Line::from("  i             Close details"),
             Line::from("  Enter         Close details"),
             Line::from("  ?             Toggle this help"),
         ]);
@@ -1028,10 +1605,11 @@ fn render_help(frame: &mut Frame, app: &mut App) {
             Line::from("  N             Previous match"),
             Line::from(""),
             Line::from(vec![Span::styled("General", section_style)]),
+            Line::from("  e             Edit drop-in (systemctl edit)"),
             Line::from("  v / Esc / q   Close unit file"),
             Line::from("  ?             Toggle this help"),
         ]);
-    } else if app.show_logs {
+    } else if app.logs_focused() {
         title = "Help: Logs";
         help_text.extend(vec![
             Line::from(vec![Span::styled("Navigation", section_style)]),
@@ -1041,21 +1619,58 @@ fn render_help(frame: &mut Frame, app: &mut App) {
             Line::from("  G / End       Go to bottom"),
             Line::from("  PgUp / PgDn   Page scroll"),
             Line::from("  Ctrl+u / d    Half page scroll"),
+            Line::from("  Left / Right  Scroll sideways (unwrapped)"),
             Line::from(""),
             Line::from(vec![Span::styled("Search", section_style)]),
-            Line::from("  /             Search logs"),
+            Line::from("  /             Search logs (Tab: whole word)"),
             Line::from("  n             Next match"),
             Line::from("  N             Previous match"),
+            Line::from("  #             Count matches in whole journal"),
             Line::from(""),
             Line::from(vec![Span::styled("Filters", section_style)]),
             Line::from("  p             Priority filter"),
             Line::from("  t             Time range filter"),
+            Line::from("  m             Filter presets (save / recall)"),
+            Line::from("  F             journalctl --grep filter"),
+            Line::from("  P             Only the main PID's lines"),
+            Line::from("  + / -         More / fewer lines fetched"),
+            Line::from("  d             Timestamp format"),
+            Line::from("  E             Export loaded logs (text/CSV/JSON)"),
+            Line::from("  z             Toggle UTC / local time"),
+            Line::from("  u             Toggle microseconds"),
+            Line::from("  h             Toggle hostname"),
+            Line::from("  i             All journal fields of the entry"),
+            Line::from("  B             Number boots instead of boot ids"),
+            Line::from("  ] / [         Next / previous boot"),
+            Line::from("  } / {         Next / previous error"),
+            Line::from("  D             Dim below warning / err / off"),
+            Line::from(""),
+            Line::from(vec![Span::styled("Priority Colors", section_style)]),
+        ]);
+        let mut legend = vec![Span::raw("  ")];
+        for span in priority_legend() {
+            legend.push(span);
+            legend.push(Span::raw(" "));
+        }
+        legend.pop();
+        help_text.push(Line::from(legend));
+        help_text.extend(vec![
             Line::from(""),
             Line::from(vec![Span::styled("General", section_style)]),
             Line::from("  x             Action picker"),
-            Line::from("  f             Toggle live tail (auto-refresh)"),
+            Line::from("  o             Open in journalctl pager"),
+            Line::from("  f             Pause live tail / resume following"),
+            Line::from("  s             Lock view while live tail keeps fetching"),
+            Line::from("  A             Cycle live tail interval (250ms/500ms/1s/2s)"),
+            Line::from("  r             Reload logs from the journal"),
+            Line::from("  w             Toggle line wrap"),
+            Line::from("  C             Collapse repeated lines"),
+            Line::from("  V             Vacuum journal (older than 2 weeks)"),
             Line::from("  l             Exit logs"),
+            Line::from("  |             List and logs side by side"),
+            Line::from("  Tab           Focus the list (split view)"),
             Line::from("  L             System-wide logs"),
+            Line::from("  K             Kernel logs (journalctl -k)"),
             Line::from("  q / Esc       Clear search / Exit logs"),
             Line::from("  ?             Toggle this help"),
         ]);
@@ -1068,25 +1683,50 @@ fn render_help(frame: &mut Frame, app: &mut App) {
             Line::from("  g / Home      Go to top"),
             Line::from("  G / End       Go to bottom"),
             Line::from("  PgUp / PgDn   Page up/down"),
+            Line::from("  j / k         Move down / up"),
+            Line::from("  5j / 10G      Count prefix: repeat / go to line"),
             Line::from(""),
             Line::from(vec![Span::styled("Search & Filter", section_style)]),
             Line::from("  /             Search units"),
+            Line::from("  J             Go to unit by name prefix"),
             Line::from("  s             Status filter"),
             Line::from("  f             File state filter"),
             Line::from("  t             Unit type picker"),
-            Line::from("  Esc           Clear search"),
+            Line::from("  Left/Right    Previous / next unit type"),
+            Line::from("  !             Invert status / file state filters"),
+            Line::from("  d             Show only the unit's dependencies"),
+            Line::from("  w             Show what depends on the unit"),
+            Line::from("  O             Show the last action's full output"),
+            Line::from("  a             Analyze service security (sandboxing)"),
+            Line::from("  Esc           Clear search, dependency filter, then selection"),
             Line::from(""),
             Line::from(vec![Span::styled("Unit Operations", section_style)]),
             Line::from("  i / Enter     Open details"),
-            Line::from("  x             Action picker"),
+            Line::from("  Space         Select unit for bulk actions"),
+            Line::from("  b             Toggle favorite (pinned to top)"),
+            Line::from("  A             Cycle auto-refresh (off/2s/5s/10s/30s)"),
+            Line::from("  D             Highlight changes since last refresh"),
+            Line::from("  H             Hide static/indirect units"),
+            Line::from("  I             Toggle inactive units (--all)"),
+            Line::from("  U             Toggle UPTIME column"),
+            Line::from("  x             Action picker (all selected units)"),
+            Line::from("  z             Undo last start/stop/enable/disable"),
+            Line::from("  F             Restart all failed units"),
             Line::from("  R             Daemon reload"),
-            Line::from("  l             Open logs"),
+            Line::from("  l             Open logs (full screen from split)"),
+            Line::from("  |             Toggle list and logs side by side"),
+            Line::from("  Tab           Focus the logs (split view)"),
             Line::from("  L             System-wide logs"),
+            Line::from("  S             Logs of the unit a timer triggers"),
+            Line::from("  K             Kernel logs (journalctl -k)"),
+            Line::from("  o             Logs in journalctl pager"),
             Line::from("  v             View unit file"),
             Line::from(""),
             Line::from(vec![Span::styled("Mouse", section_style)]),
             Line::from("  Click         Select unit"),
-            Line::from("  Scroll        Navigate list"),
+            Line::from("  Double-click  Open unit logs"),
+            Line::from("  Click option  Pick it in any picker"),
+            Line::from("  Scroll        Navigate list / pickers / views"),
             Line::from(""),
             Line::from(vec![Span::styled("General", section_style)]),
             Line::from("  r             Refresh units"),
@@ -1096,6 +1736,17 @@ fn render_help(frame: &mut Frame, app: &mut App) {
         ]);
     }
 
+    let mut help_text = filter_help_lines(help_text, &app.help_search_query);
+    if help_text.is_empty() {
+        help_text.push(Line::styled("  No matching keys", Style::default().fg(theme().muted)));
+    }
+    let title = if app.help_search_mode {
+        format!("{} /{}_", title, app.help_search_query)
+    } else if !app.help_search_query.is_empty() {
+        format!("{} /{}", title, app.help_search_query)
+    } else {
+        title.to_string()
+    };
     let area = centered_rect(50, 70, frame.area());
 
     let content_lines = help_text.len() as u16;
@@ -1116,7 +1767,7 @@ fn render_help(frame: &mut Frame, app: &mut App) {
             max_scroll + 1,
         )
     } else {
-        title.to_string()
+        title
     };
 
     let help = Paragraph::new(help_text)
@@ -1140,16 +1791,7 @@ fn render_status_picker(frame: &mut Frame, app: &mut App) {
         .map(|&opt| {
             let color = match opt {
                 "All" => Color::Cyan,
-                "running" => Color::Green,
-                "exited" => Color::Yellow,
-                "failed" => Color::Red,
-                "dead" => COLOR_MUTED,
-                "waiting" => Color::Cyan,
-                "listening" => Color::Green,
-                "active" => Color::Green,
-                "inactive" => COLOR_MUTED,
-                "elapsed" => Color::Yellow,
-                _ => Color::White,
+                other => theme().sub_state_color(other),
             };
             let is_active = match (&app.status_filter, opt) {
                 (None, "All") => true,
@@ -1175,7 +1817,7 @@ fn render_status_picker(frame: &mut Frame, app: &mut App) {
                 .add_modifier(Modifier::BOLD),
         );
 
-    let area = centered_fixed_rect(30, options.len() as u16 + 2, frame.area());
+    let area = picker_rect(Picker::Status, app, frame.area());
     frame.render_widget(Clear, area);
     frame.render_stateful_widget(list, area, &mut app.status_picker_state);
 }
@@ -1204,7 +1846,7 @@ fn render_type_picker(frame: &mut Frame, app: &mut App) {
                 .add_modifier(Modifier::BOLD),
         );
 
-    let area = centered_fixed_rect(30, UNIT_TYPES.len() as u16 + 2, frame.area());
+    let area = picker_rect(Picker::Type, app, frame.area());
     frame.render_widget(Clear, area);
     frame.render_stateful_widget(list, area, &mut app.type_picker_state);
 }
@@ -1224,8 +1866,15 @@ fn render_priority_picker(frame: &mut Frame, app: &mut App) {
     // Priority levels 0-7
     for (i, &label) in PRIORITY_LABELS.iter().enumerate() {
         let p = i as u8;
-        let is_active = app.log_priority_filter == Some(p);
-        let marker = if is_active { " *" } else { "" };
+        let is_active = app.log_priority_filter.is_some_and(|(low, high)| p == high && low == 0);
+        let in_active_range = app.log_priority_filter.is_some_and(|(low, high)| low > 0 && (low..=high).contains(&p));
+        let marker = if app.priority_range_anchor == Some(p) {
+            " <"
+        } else if is_active || in_active_range {
+            " *"
+        } else {
+            ""
+        };
         let (color, bold) = priority_color(p);
         let mut style = Style::default().fg(color);
         if bold {
@@ -1247,21 +1896,27 @@ fn render_priority_picker(frame: &mut Frame, app: &mut App) {
                 .add_modifier(Modifier::BOLD),
         );
 
-    let area = centered_fixed_rect(30, 11, frame.area()); // 9 items + 2 border
+    let area = picker_rect(Picker::Priority, app, frame.area());
     frame.render_widget(Clear, area);
     frame.render_stateful_widget(list, area, &mut app.priority_picker_state);
 }
 
 fn render_time_picker(frame: &mut Frame, app: &mut App) {
-    let items: Vec<ListItem> = TIME_RANGES
+    let mut items: Vec<ListItem> = TIME_RANGES
         .iter()
-        .map(|&tr| {
-            let is_active = tr == app.log_time_range;
+        .map(|tr| {
+            let is_active = *tr == app.log_time_range;
             let marker = if is_active { " *" } else { "" };
             let text = format!("  {}{}", tr.label(), marker);
             ListItem::new(text).style(Style::default().fg(Color::Cyan))
         })
         .collect();
+    let custom_active = matches!(app.log_time_range, TimeRange::Custom { .. });
+    let custom_marker = if custom_active { " *" } else { "" };
+    items.push(
+        ListItem::new(format!("  Custom...{}", custom_marker))
+            .style(Style::default().fg(Color::Cyan)),
+    );
 
     let list = List::new(items)
         .block(
@@ -1276,42 +1931,419 @@ fn render_time_picker(frame: &mut Frame, app: &mut App) {
                 .add_modifier(Modifier::BOLD),
         );
 
-    let area = centered_fixed_rect(30, TIME_RANGES.len() as u16 + 2, frame.area());
+    let area = picker_rect(Picker::Time, app, frame.area());
     frame.render_widget(Clear, area);
     frame.render_stateful_widget(list, area, &mut app.time_picker_state);
 }
 
-fn centered_fixed_rect(width: u16, height: u16, area: Rect) -> Rect {
-    let x = area.x + area.width.saturating_sub(width) / 2;
-    let y = area.y + area.height.saturating_sub(height) / 2;
-    Rect::new(
-        x,
-        y,
-        width.min(area.width),
-        height.min(area.height),
-    )
-}
+fn render_custom_time_input(frame: &mut Frame, app: &App) {
+    let field = |label: &str, value: &str, focused: bool| {
+        let cursor = if focused { "_" } else { "" };
+        let label_style = if focused {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        Line::from(vec![
+            Span::styled(format!("  {:<7}", label), label_style),
+            Span::styled(format!("{}{}", value, cursor), Style::default().fg(Color::White)),
+        ])
+    };
+    let text = vec![
+        Line::from(""),
+        field("Since:", &app.custom_since_input, !app.custom_time_editing_until),
+        field("Until:", &app.custom_until_input, app.custom_time_editing_until),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "  e.g. \"2024-01-01 08:00\", \"yesterday\", \"-2h\"",
+            Style::default().fg(Color::DarkGray),
+        )]),
+    ];
 
-fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
-    let popup_layout = Layout::vertical([
-        Constraint::Percentage((100 - percent_y) / 2),
-        Constraint::Percentage(percent_y),
-        Constraint::Percentage((100 - percent_y) / 2),
-    ])
-    .split(area);
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Custom Time Range")
+            .style(Style::default().bg(Color::Black)),
+    );
 
-    Layout::horizontal([
-        Constraint::Percentage((100 - percent_x) / 2),
-        Constraint::Percentage(percent_x),
-        Constraint::Percentage((100 - percent_x) / 2),
-    ])
-    .split(popup_layout[1])[1]
+    let area = centered_fixed_rect(50, 7, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
 }
 
-/// Returns the number of visible lines in the unit file panel
-fn middle_area_visible_lines(frame: &Frame) -> usize {
-    let chunks = Layout::vertical([
-        Constraint::Length(3),
+fn render_grep_input(frame: &mut Frame, app: &App) {
+    let case_label = if app.grep_case_sensitive_input {
+        "case-sensitive"
+    } else {
+        "ignore case"
+    };
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Pattern: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}_", app.grep_input), Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::styled("  Case:    ", Style::default().fg(Color::DarkGray)),
+            Span::styled(case_label, Style::default().fg(Color::White)),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "  Regex, matched by journalctl; empty clears",
+            Style::default().fg(Color::DarkGray),
+        )]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("journalctl --grep")
+            .style(Style::default().bg(Color::Black)),
+    );
+
+    let area = centered_fixed_rect(50, 7, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_preset_picker(frame: &mut Frame, app: &mut App) {
+    let mut items = vec![ListItem::new("  + Save current filters...").style(Style::default().fg(Color::Cyan))];
+    for preset in &app.log_presets {
+        let is_active =
+            preset.priority == app.log_priority_filter && preset.time_range == app.log_time_range;
+        let marker = if is_active { " *" } else { "" };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!("  {}{}", preset.name, marker), Style::default().fg(Color::White)),
+            Span::styled(format!("  {}", preset.summary()), Style::default().fg(Color::DarkGray)),
+        ])));
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Log Filter Presets")
+                .style(Style::default().bg(Color::Black)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let area = picker_rect(Picker::Preset, app, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut app.preset_picker_state);
+}
+
+fn render_preset_name_input(frame: &mut Frame, app: &App) {
+    let current = LogFilterPreset {
+        name: String::new(),
+        priority: app.log_priority_filter,
+        time_range: app.log_time_range.clone(),
+    };
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Name:    ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}_", app.preset_name_input), Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::styled("  Filters: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(current.summary(), Style::default().fg(Color::White)),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "  An existing preset with this name is replaced",
+            Style::default().fg(Color::DarkGray),
+        )]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Save Log Filter Preset")
+            .style(Style::default().bg(Color::Black)),
+    );
+
+    let area = centered_fixed_rect(50, 7, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_timestamp_format_picker(frame: &mut Frame, app: &mut App) {
+    let items: Vec<ListItem> = TIMESTAMP_FORMATS
+        .iter()
+        .map(|&tf| {
+            let is_active = tf == app.log_timestamp_format;
+            let marker = if is_active { " *" } else { "" };
+            let text = format!("  {}{}", tf.label(), marker);
+            ListItem::new(text).style(Style::default().fg(Color::Cyan))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Timestamp Format")
+                .style(Style::default().bg(Color::Black)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let area = picker_rect(Picker::TimestampFormat, app, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut app.timestamp_format_picker_state);
+}
+
+/// Every journal field of the current log entry as `NAME=value` lines, the
+/// way `journalctl -o verbose` prints them.
+fn render_log_fields(frame: &mut Frame, app: &mut App) {
+    let key_style = Style::default().fg(Color::Cyan);
+    let lines: Vec<Line> = app
+        .log_fields()
+        .into_iter()
+        .map(|(key, value)| {
+            Line::from(vec![
+                Span::styled(key.to_string(), key_style),
+                Span::styled("=", Style::default().fg(Color::DarkGray)),
+                Span::raw(value.to_string()),
+            ])
+        })
+        .collect();
+
+    let area = centered_rect(80, 80, frame.area());
+    let viewport = area.height.saturating_sub(2) as usize;
+    let width = area.width.saturating_sub(2) as usize;
+    let content_height: usize = lines.iter().map(|line| wrapped_line_count(line, width)).sum();
+    app.log_fields_scroll = app.log_fields_scroll.min(content_height.saturating_sub(viewport));
+    let title = format!("Journal Fields ({})", lines.len());
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(Color::White))
+        .wrap(Wrap { trim: false })
+        .scroll((app.log_fields_scroll as u16, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// `[LIVE: FOLLOWING]` blinks while new entries scroll the view;
+/// `[LIVE: LOCKED]` is solid yellow while the view stays put.
+fn live_indicator(following: bool, blink_on: bool) -> Span<'static> {
+    if !following {
+        Span::styled("[LIVE: LOCKED]", Style::default().fg(Color::Yellow))
+    } else if blink_on {
+        Span::styled("[LIVE: FOLLOWING]", Style::default().fg(Color::LightGreen))
+    } else {
+        Span::styled("[LIVE: FOLLOWING]", Style::default().fg(Color::DarkGray))
+    }
+}
+
+/// Color for a `systemd-analyze security` verdict.
+fn security_level_color(level: &str) -> Color {
+    let theme = theme();
+    match level {
+        "SAFE" | "OK" => theme.status.active,
+        "MEDIUM" => theme.priority.warning,
+        _ => theme.priority.err,
+    }
+}
+
+fn render_security_analysis(frame: &mut Frame, app: &mut App) {
+    // Findings start with a check or a cross; the verdict line gets its
+    // level's color.
+    let lines: Vec<Line> = app
+        .security_lines
+        .iter()
+        .map(|line| {
+            let style = if line.starts_with('\u{2713}') {
+                Style::default().fg(theme().status.active)
+            } else if line.starts_with('\u{2717}') {
+                Style::default().fg(theme().priority.err)
+            } else if line.contains("Overall exposure level") {
+                let level = parse_security_overall(std::slice::from_ref(line)).map(|(_, level)| level);
+                Style::default()
+                    .fg(security_level_color(level.as_deref().unwrap_or_default()))
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::styled(line.clone(), style)
+        })
+        .collect();
+
+    let area = centered_rect(90, 80, frame.area());
+    let viewport = area.height.saturating_sub(2) as usize;
+    app.security_scroll = app.security_scroll.min(lines.len().saturating_sub(viewport));
+    let title = match parse_security_overall(&app.security_lines) {
+        Some((score, level)) => format!("Security: {} \u{2014} {} {}", app.security_unit, score, level),
+        None => format!("Security: {}", app.security_unit),
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(theme().priority.info))
+        .scroll((app.security_scroll as u16, 0))
+        .block(Block::default().borders(Borders::ALL).title(title));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_action_output(frame: &mut Frame, app: &mut App) {
+    let output = app.last_action_output.as_deref().unwrap_or_default();
+    let lines: Vec<Line> = if output.is_empty() {
+        vec![Line::styled("The command printed nothing", Style::default().fg(theme().muted))]
+    } else {
+        output.lines().map(|line| Line::raw(line.to_string())).collect()
+    };
+
+    let area = centered_rect(80, 70, frame.area());
+    let viewport = area.height.saturating_sub(2) as usize;
+    let width = area.width.saturating_sub(2) as usize;
+    let content_height: usize = lines.iter().map(|line| wrapped_line_count(line, width)).sum();
+    app.action_output_scroll = app.action_output_scroll.min(content_height.saturating_sub(viewport));
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(Color::White))
+        .wrap(Wrap { trim: false })
+        .scroll((app.action_output_scroll as u16, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Last Action Output")
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_reverse_deps(frame: &mut Frame, app: &mut App) {
+    let lines: Vec<Line> = if app.reverse_deps.is_empty() {
+        vec![Line::styled("Nothing depends on this unit", Style::default().fg(Color::DarkGray))]
+    } else {
+        app.reverse_deps
+            .iter()
+            .map(|name| Line::styled(name.clone(), Style::default().fg(unit_kind_color(name))))
+            .collect()
+    };
+
+    let area = centered_rect(60, 70, frame.area());
+    let viewport = area.height.saturating_sub(2) as usize;
+    app.reverse_deps_scroll = app.reverse_deps_scroll.min(lines.len().saturating_sub(viewport));
+    let title = format!("Required By: {} ({})", app.reverse_deps_unit, app.reverse_deps.len());
+
+    let paragraph = Paragraph::new(lines)
+        .scroll((app.reverse_deps_scroll as u16, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_export_picker(frame: &mut Frame, app: &mut App) {
+    let items: Vec<ListItem> = LOG_EXPORT_FORMATS
+        .iter()
+        .map(|format| {
+            ListItem::new(format!("  {} (.{})", format.label(), format.extension()))
+                .style(Style::default().fg(Color::Cyan))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Export {} Entries", app.logs.len()))
+                .style(Style::default().bg(Color::Black)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let area = picker_rect(Picker::Export, app, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(list, area, &mut app.export_picker_state);
+}
+
+/// Where a picker is drawn: its rows plus borders, centered in `area`.
+pub fn picker_rect(picker: Picker, app: &App, area: Rect) -> Rect {
+    let width = match picker {
+        Picker::TimestampFormat => 32,
+        Picker::Action => 40,
+        Picker::Preset => 50,
+        _ => 30,
+    };
+    centered_fixed_rect(width, app.picker_len(picker) as u16 + 2, area)
+}
+
+/// The picker row under a click at (`column`, `row`), given the picker's
+/// rect and its list's scroll offset. Clicks on the border or below the last
+/// of `len` rows hit nothing.
+pub fn picker_row_at(rect: Rect, offset: usize, len: usize, column: u16, row: u16) -> Option<usize> {
+    let inner = Rect::new(
+        rect.x.saturating_add(1),
+        rect.y.saturating_add(1),
+        rect.width.saturating_sub(2),
+        rect.height.saturating_sub(2),
+    );
+    if column < inner.x || column >= inner.x + inner.width || row < inner.y || row >= inner.y + inner.height {
+        return None;
+    }
+    let index = offset + (row - inner.y) as usize;
+    (index < len).then_some(index)
+}
+
+fn centered_fixed_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    Rect::new(
+        x,
+        y,
+        width.min(area.width),
+        height.min(area.height),
+    )
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(area);
+
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(popup_layout[1])[1]
+}
+
+/// Returns the number of visible lines in the unit file panel
+fn middle_area_visible_lines(frame: &Frame) -> usize {
+    let chunks = Layout::vertical([
+        Constraint::Length(3),
         Constraint::Min(1),
         Constraint::Length(3),
     ])
@@ -1344,75 +2376,69 @@ pub fn get_services_visible_lines(frame: &Frame, show_logs: bool) -> usize {
 }
 
 /// Returns the number of visible lines in the details modal
-pub fn get_details_visible_lines(frame: &Frame) -> usize {
-    let area = centered_rect(70, 80, frame.area());
+pub fn get_details_visible_lines(frame_area: Rect) -> usize {
+    let area = centered_rect(70, 80, frame_area);
     // Subtract 2 for borders
     area.height.saturating_sub(2) as usize
 }
 
-fn highlight_unit_file_line<'a>(line: &'a str, line_idx: usize, app: &App) -> Line<'a> {
+/// Splits a unit file line into INI-style pieces: comments, `[Section]`
+/// headers, and `Key=Value` pairs with the key, `=` and value apart.
+fn unit_file_syntax(line: &str) -> Vec<(&str, Style)> {
     let trimmed = line.trim();
+    if trimmed.starts_with('#') || trimmed.starts_with(';') {
+        return vec![(line, Style::default().fg(Color::DarkGray))];
+    }
+    if trimmed.starts_with('[') && trimmed.ends_with(']') {
+        return vec![(line, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))];
+    }
+    match line.find('=') {
+        Some(eq_pos) => vec![
+            (&line[..eq_pos], Style::default().fg(Color::Cyan)),
+            (&line[eq_pos..eq_pos + 1], Style::default().fg(Color::DarkGray)),
+            (&line[eq_pos + 1..], Style::default().fg(Color::White)),
+        ],
+        None => vec![(line, Style::default().fg(Color::White))],
+    }
+}
 
-    // Determine base style from INI-like syntax
-    let base_style = if trimmed.starts_with('#') || trimmed.starts_with(';') {
-        Style::default().fg(Color::DarkGray)
-    } else if trimmed.starts_with('[') && trimmed.ends_with(']') {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::White)
-    };
+/// A unit file line with syntax colors only.
+fn highlight_unit_file_line(line: &str) -> Line<'_> {
+    Line::from(
+        unit_file_syntax(line)
+            .into_iter()
+            .map(|(text, style)| Span::styled(text, style))
+            .collect::<Vec<_>>(),
+    )
+}
 
-    // Check if this line is a search match
+/// A unit file line with syntax colors plus search highlighting: matched
+/// text in yellow, and the current match's line on a gray background.
+fn render_unit_file_line<'a>(line: &'a str, line_idx: usize, app: &App) -> Line<'a> {
     let is_match = app
         .unit_file_search_match_index
         .and_then(|mi| app.unit_file_search_matches.get(mi))
         .is_some_and(|&idx| idx == line_idx);
 
-    // For Key=Value lines, render key in cyan and value in white
-    if !(trimmed.starts_with('#')
-        || trimmed.starts_with(';')
-        || trimmed.starts_with('[') && trimmed.ends_with(']'))
-        && let Some(eq_pos) = line.find('=')
-    {
-        let key = &line[..eq_pos + 1];
-        let value = &line[eq_pos + 1..];
+    if app.unit_file_search_query.is_empty() && !is_match {
+        return highlight_unit_file_line(line);
+    }
 
-        let key_style = if is_match {
-            Style::default().fg(Color::Cyan).bg(Color::DarkGray)
-        } else {
-            Style::default().fg(Color::Cyan)
+    let mut spans = Vec::new();
+    for (text, style) in unit_file_syntax(line) {
+        let style = match (is_match, style.fg) {
+            // Keep gray text readable on the gray match background.
+            (true, Some(Color::DarkGray)) => style.fg(Color::Gray).bg(Color::DarkGray),
+            (true, _) => style.bg(Color::DarkGray),
+            (false, _) => style,
         };
-        let val_style = if is_match {
-            Style::default().fg(Color::White).bg(Color::DarkGray)
+        if app.unit_file_search_query.is_empty() {
+            spans.push(Span::styled(text, style));
         } else {
-            Style::default().fg(Color::White)
-        };
-
-        if !app.unit_file_search_query.is_empty() {
-            let mut spans = Vec::new();
-            spans.extend(highlight_search_in_span(key, &app.unit_file_search_query, key_style));
-            spans.extend(highlight_search_in_span(value, &app.unit_file_search_query, val_style));
-            return Line::from(spans);
+            spans.extend(highlight_search_in_span(text, &app.unit_file_search_query, style));
         }
-
-        return Line::from(vec![
-            Span::styled(key.to_string(), key_style),
-            Span::styled(value.to_string(), val_style),
-        ]);
     }
-
-    let style = if is_match {
-        base_style.bg(Color::DarkGray)
-    } else {
-        base_style
-    };
-
-    if !app.unit_file_search_query.is_empty() {
-        let spans = highlight_search_in_span(line, &app.unit_file_search_query, style);
-        return Line::from(spans);
-    }
-
-    Line::from(Span::styled(line.to_string(), style))
+    Line::from(spans)
 }
 
 fn highlight_search_in_span<'a>(
@@ -1431,46 +2457,6 @@ fn highlight_search_in_span<'a>(
 }
 
 /// Build highlighted spans by finding case-insensitive matches in `text`.
-///
-/// Works by lowercasing char-by-char while tracking the original byte offset
-/// of each char boundary, so that byte positions found in the lowered string
-/// can be mapped back to the correct slice in the original text — even when
-/// `to_lowercase()` changes the byte length of a character.
-/// Byte ranges over `text` of case-insensitive matches of `query_lower`.
-fn search_match_ranges(text: &str, query_lower: &str) -> Vec<(usize, usize)> {
-    if query_lower.is_empty() {
-        return Vec::new();
-    }
-
-    // Build the lowered string and a mapping from lowered byte offset → original byte offset.
-    // `lower_to_orig[i]` gives the original byte offset that corresponds to lowered byte offset `i`.
-    let mut lowered = String::with_capacity(text.len());
-    let mut lower_to_orig: Vec<usize> = Vec::with_capacity(text.len() + 1);
-
-    for (orig_byte, ch) in text.char_indices() {
-        for lc in ch.to_lowercase() {
-            let lc_start = lowered.len();
-            lowered.push(lc);
-            // Map each new byte in the lowered string to this char's original byte offset
-            for _ in lc_start..lowered.len() {
-                lower_to_orig.push(orig_byte);
-            }
-        }
-    }
-    // Sentinel: map the end-of-lowered-string position to end-of-original-string
-    lower_to_orig.push(text.len());
-
-    let mut ranges = Vec::new();
-    let mut lower_pos = 0;
-    while let Some(found) = lowered[lower_pos..].find(query_lower) {
-        let lower_start = lower_pos + found;
-        let lower_end = lower_start + query_lower.len();
-        ranges.push((lower_to_orig[lower_start], lower_to_orig[lower_end]));
-        lower_pos = lower_end;
-    }
-    ranges
-}
-
 fn find_and_highlight_matches<'a>(
     text: &str,
     query_lower: &str,
@@ -1498,24 +2484,15 @@ fn find_and_highlight_matches<'a>(
 }
 
 fn load_color(state: &str) -> Color {
-    match state {
-        "loaded" => Color::Green,
-        "masked" => Color::Red,
-        "not-found" => COLOR_MUTED,
-        "error" | "bad-setting" => Color::Red,
-        _ => Color::White,
-    }
+    theme().load_color(state)
 }
 
 fn file_state_color(state: &str) -> Color {
-    match state {
-        "enabled" => Color::Green,
-        "disabled" => Color::Yellow,
-        "static" => COLOR_MUTED,
-        "masked" => Color::Red,
-        "indirect" => Color::Cyan,
-        _ => Color::White,
-    }
+    theme().file_state_color(state)
+}
+
+fn unit_kind_color(name: &str) -> Color {
+    theme().unit_kind_color(name)
 }
 
 fn render_file_state_picker(frame: &mut Frame, app: &mut App) {
@@ -1550,7 +2527,7 @@ fn render_file_state_picker(frame: &mut Frame, app: &mut App) {
                 .add_modifier(Modifier::BOLD),
         );
 
-    let area = centered_fixed_rect(30, FILE_STATE_OPTIONS.len() as u16 + 2, frame.area());
+    let area = picker_rect(Picker::FileState, app, frame.area());
     frame.render_widget(Clear, area);
     frame.render_stateful_widget(list, area, &mut app.file_state_picker_state);
 }
@@ -1563,7 +2540,9 @@ fn action_color(action: &UnitAction) -> Color {
         UnitAction::Reload => Color::Cyan,
         UnitAction::Enable => Color::Green,
         UnitAction::Disable => Color::Yellow,
+        UnitAction::Unmask => Color::Green,
         UnitAction::DaemonReload => Color::Magenta,
+        UnitAction::VacuumJournal => Color::Red,
     }
 }
 
@@ -1605,11 +2584,43 @@ fn render_action_picker(frame: &mut Frame, app: &mut App) {
                 .add_modifier(Modifier::BOLD),
         );
 
-    let area = centered_fixed_rect(40, app.available_actions.len() as u16 + 2, frame.area());
+    let area = picker_rect(Picker::Action, app, frame.area());
     frame.render_widget(Clear, area);
     frame.render_stateful_widget(list, area, &mut app.action_picker_state);
 }
 
+/// The visible window of per-unit bulk results, one `OK`/`FAIL` row each,
+/// left-aligned so the columns line up inside the centered dialog.
+fn batch_result_lines(app: &App) -> Vec<Line<'static>> {
+    let offset = app.batch_scroll_offset();
+    let mut lines: Vec<Line> = app
+        .batch_results
+        .iter()
+        .skip(offset)
+        .take(BATCH_RESULTS_SHOWN)
+        .map(|(name, result)| {
+            let line = match result {
+                Ok(()) => Line::from(vec![
+                    Span::styled(" OK   ", Style::default().fg(Color::Green)),
+                    Span::raw(name.clone()),
+                ]),
+                Err(e) => Line::from(vec![
+                    Span::styled(" FAIL ", Style::default().fg(Color::Red)),
+                    Span::raw(format!("{}: {}", name, e)),
+                ]),
+            };
+            line.left_aligned()
+        })
+        .collect();
+    if !lines.is_empty() {
+        lines.insert(0, Line::from(""));
+    }
+    lines
+}
+
+/// Bulk confirmations list at most this many unit names.
+const CONFIRM_TARGETS_SHOWN: usize = 8;
+
 fn render_confirm_dialog(frame: &mut Frame, app: &App) {
     let (action, unit_name) = match (&app.confirm_action, &app.confirm_unit_name) {
         (Some(a), Some(n)) => (a, n),
@@ -1622,18 +2633,36 @@ fn render_confirm_dialog(frame: &mut Frame, app: &App) {
             Ok(msg) => (msg.as_str(), Color::Green),
             Err(msg) => (msg.as_str(), Color::Red),
         };
-        let text = vec![
+        // The first line is the outcome; any further lines are the
+        // command's own output.
+        let mut msg_lines = msg.lines();
+        let mut text = vec![
             Line::from(""),
             Line::from(vec![Span::styled(
-                msg.to_string(),
+                msg_lines.next().unwrap_or_default().to_string(),
                 Style::default().fg(color).add_modifier(Modifier::BOLD),
             )]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "Press any key to dismiss",
-                Style::default().fg(Color::DarkGray),
-            )]),
         ];
+        text.extend(msg_lines.map(|line| {
+            Line::from(Span::styled(line.to_string(), Style::default().fg(theme().muted)))
+        }));
+        text.extend(batch_result_lines(app));
+        if app.can_retry_privileged() {
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(
+                "p: retry with pkexec \u{b7} s: retry with sudo",
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(vec![Span::styled(
+            if app.batch_results.len() > BATCH_RESULTS_SHOWN {
+                "\u{2191}/\u{2193} scroll \u{b7} o: full output \u{b7} any other key to dismiss"
+            } else {
+                "o: full output \u{b7} any other key to dismiss"
+            },
+            Style::default().fg(Color::DarkGray),
+        )]));
         let title = if result.is_ok() {
             "Action Succeeded"
         } else {
@@ -1642,40 +2671,96 @@ fn render_confirm_dialog(frame: &mut Frame, app: &App) {
         (text, title)
     } else if app.action_in_progress {
         // Show progress
-        let text = vec![
+        let label = if app.confirm_targets.is_empty() {
+            action.progress_label().to_string()
+        } else {
+            format!(
+                "{} {}/{} done",
+                action.progress_label(),
+                app.batch_results.len(),
+                app.confirm_targets.len()
+            )
+        };
+        let mut text = vec![
             Line::from(""),
             Line::from(vec![Span::styled(
-                action.progress_label().to_string(),
+                label,
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             )]),
-            Line::from(""),
-            Line::from(""),
         ];
+        text.extend(batch_result_lines(app));
+        text.extend([Line::from(""), Line::from("")]);
         (text, "Executing")
     } else {
         // Show confirmation prompt
-        let message = action.confirmation_message(unit_name);
-        let text = vec![
+        let (message, preview) = match app.confirm_targets.as_slice() {
+            [] => (
+                action.confirmation_message(unit_name),
+                action.command_preview(unit_name, app.user_mode, app.no_block),
+            ),
+            [only] => (
+                action.confirmation_message(only),
+                action.command_preview(only, app.user_mode, app.no_block),
+            ),
+            targets => (
+                action.bulk_confirmation_message(targets.len()),
+                action.command_preview(&format!("<each of {} units>", targets.len()), app.user_mode, app.no_block),
+            ),
+        };
+        let mut text = vec![
             Line::from(""),
             Line::from(vec![Span::styled(
                 message,
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(if action.requires_strong_confirmation() { Color::Red } else { Color::Yellow })
                     .add_modifier(Modifier::BOLD),
             )]),
-            Line::from(""),
+        ];
+        // Name the units of a bulk action, so a restart of every failed unit
+        // shows what it will touch.
+        if app.confirm_targets.len() > 1 {
+            let targets = &app.confirm_targets;
+            text.extend(targets.iter().take(CONFIRM_TARGETS_SHOWN).map(|name| Line::from(name.as_str())));
+            if targets.len() > CONFIRM_TARGETS_SHOWN {
+                text.push(Line::from(format!("\u{2026}and {} more", targets.len() - CONFIRM_TARGETS_SHOWN)));
+            }
+        }
+        if let Some(warning) = &app.confirm_warning {
+            text.push(Line::from(Span::styled(
+                format!("\u{26a0} {}", warning),
+                Style::default().fg(Color::Red),
+            )));
+        } else if app.confirm_warning_loading() {
+            text.push(Line::from(Span::styled(
+                "Checking what depends on it\u{2026}",
+                Style::default().fg(theme().muted),
+            )));
+        }
+        text.extend([
+            Line::from(vec![Span::styled(preview, Style::default().fg(theme().muted))]),
             Line::from(vec![
-                Span::styled("[Y]", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    if action.requires_strong_confirmation() { "[Shift+Y]" } else { "[Y]" },
+                    Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+                ),
                 Span::raw(" Confirm  "),
                 Span::styled("[N/Esc]", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
                 Span::raw(" Cancel"),
             ]),
-        ];
-        (text, "Confirm Action")
+        ]);
+        let title = if action.requires_strong_confirmation() {
+            "Confirm Destructive Action"
+        } else {
+            "Confirm Action"
+        };
+        (text, title)
     };
 
+    // Widen past the default for long command previews.
+    let width = text.iter().map(|line| line.width() as u16 + 4).max().unwrap_or(0).max(50);
+    let height = text.len() as u16 + 2;
     let paragraph = Paragraph::new(text)
         .style(Style::default().fg(Color::White))
         .block(
@@ -1686,18 +2771,144 @@ fn render_confirm_dialog(frame: &mut Frame, app: &App) {
         )
         .alignment(ratatui::layout::Alignment::Center);
 
-    let area = centered_fixed_rect(50, 6, frame.area());
+    let area = centered_fixed_rect(width, height, frame.area());
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
 }
 
-fn render_details_modal(frame: &mut Frame, app: &mut App) {
+fn render_quit_confirm(frame: &mut Frame, action_in_progress: bool) {
+    let question = if action_in_progress {
+        "An action is still running. Quit anyway?"
+    } else {
+        "Quit systemdmgr?"
+    };
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            question,
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("[Y]", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            Span::raw(" Quit  "),
+            Span::styled("[N/Esc]", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::raw(" Keep running"),
+        ]),
+    ];
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(Color::White))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Quit")
+                .style(Style::default().bg(Color::Black)),
+        )
+        .alignment(ratatui::layout::Alignment::Center);
+
+    let area = centered_fixed_rect(50, 5, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_details_modal(frame: &mut Frame, app: &mut App, spinner: char) {
+    let unit_name = app.detail_unit_name.clone().unwrap_or_default();
     let props = match &app.detail_properties {
         Some(p) => p.clone(),
-        None => return,
+        None => {
+            if app.details_loading() {
+                render_details_loading(frame, &unit_name, spinner);
+            }
+            return;
+        }
     };
-    let unit_name = app.detail_unit_name.clone().unwrap_or_default();
 
+    let mut lines = build_detail_lines(&unit_name, &props);
+    app.set_detail_content(lines.iter().map(|line| line.to_string()).collect());
+
+    // Search highlighting: matched substrings in yellow, the current match's
+    // line on a gray background (as in the unit file view).
+    if !app.detail_search_query.is_empty() {
+        let current = app
+            .detail_search_match_index
+            .and_then(|i| app.detail_search_matches.get(i))
+            .copied();
+        for &idx in &app.detail_search_matches {
+            let line = std::mem::take(&mut lines[idx]);
+            let spans = line
+                .spans
+                .into_iter()
+                .flat_map(|span| {
+                    let style = if current == Some(idx) { span.style.bg(Color::DarkGray) } else { span.style };
+                    highlight_search_in_span(&span.content, &app.detail_search_query, style)
+                        .into_iter()
+                        .map(|s| Span::styled(s.content.into_owned(), s.style))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            lines[idx] = Line::from(spans);
+        }
+    }
+
+    // Store content height for scroll bounds
+    app.detail_content_height = lines.len();
+
+    let area = centered_rect(70, 80, frame.area());
+    let visible_height = area.height.saturating_sub(2) as usize;
+
+    let scroll_info = if lines.len() > visible_height {
+        let start = app.detail_scroll + 1;
+        let end = (app.detail_scroll + visible_height).min(lines.len());
+        format!(" [{}-{}/{}]", start, end, lines.len())
+    } else {
+        String::new()
+    };
+
+    let visible_lines: Vec<Line> = lines
+        .into_iter()
+        .skip(app.detail_scroll)
+        .take(visible_height)
+        .collect();
+
+    let title_name = if unit_name.len() > 35 {
+        format!("{}...", &unit_name[..32])
+    } else {
+        unit_name.clone()
+    };
+    let search_info = if app.detail_search_mode || !app.detail_search_query.is_empty() {
+        let cursor = if app.detail_search_mode { "_" } else { "" };
+        let count = if app.detail_search_query.is_empty() {
+            String::new()
+        } else if app.detail_search_matches.is_empty() {
+            " (no matches)".to_string()
+        } else {
+            format!(
+                " ({}/{})",
+                app.detail_search_match_index.map_or(0, |i| i + 1),
+                app.detail_search_matches.len()
+            )
+        };
+        format!(" /{}{}{} ", app.detail_search_query, cursor, count)
+    } else {
+        String::new()
+    };
+    let title = format!(" {} {}{}", title_name, scroll_info, search_info);
+
+    let paragraph = Paragraph::new(visible_lines)
+        .style(Style::default().fg(Color::White))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// The details modal content for a unit, one entry per line; also what the
+/// modal's `/` search runs over.
+fn build_detail_lines(unit_name: &str, props: &UnitProperties) -> Vec<Line<'static>> {
     let mut lines: Vec<Line> = Vec::new();
 
     let section_style = Style::default()
@@ -1710,7 +2921,7 @@ fn render_details_modal(frame: &mut Frame, app: &mut App) {
     lines.push(Line::from(vec![Span::styled("General", section_style)]));
     lines.push(Line::from(vec![
         Span::styled("  Name:           ", label_style),
-        Span::styled(unit_name.clone(), value_style),
+        Span::styled(unit_name.to_string(), value_style),
     ]));
     lines.push(Line::from(vec![
         Span::styled("  Status:         ", label_style),
@@ -1887,29 +3098,20 @@ fn render_details_modal(frame: &mut Frame, app: &mut App) {
         lines.push(Line::from(""));
     }
 
-    // Process section (only if PID > 0)
-    if props.main_pid > 0 {
-        lines.push(Line::from(vec![Span::styled("Process", section_style)]));
-        lines.push(Line::from(vec![
-            Span::styled("  Main PID:       ", label_style),
-            Span::styled(props.main_pid.to_string(), value_style),
-        ]));
-        if !props.exec_main_start_timestamp.is_empty() {
-            lines.push(Line::from(vec![
-                Span::styled("  Started:        ", label_style),
-                Span::styled(props.exec_main_start_timestamp.clone(), value_style),
-            ]));
-        }
-        lines.push(Line::from(""));
-    }
+    // Process section (only if there is anything to show)
+    render_process_lines(&mut lines, props, section_style, label_style, value_style);
 
     // Resources section (only if any data)
     if props.memory_current.is_some() || props.cpu_usage_nsec.is_some() {
         lines.push(Line::from(vec![Span::styled("Resources", section_style)]));
         if let Some(mem) = props.memory_current {
+            let memory_text = match props.memory_max.and_then(|max| memory_percent(mem, max)) {
+                Some(percent) => format!("{} ({}%)", format_bytes(mem), percent),
+                None => format_bytes(mem),
+            };
             lines.push(Line::from(vec![
                 Span::styled("  Memory:         ", label_style),
-                Span::styled(format_bytes(mem), value_style),
+                Span::styled(memory_text, value_style),
             ]));
         }
         if let Some(cpu) = props.cpu_usage_nsec {
@@ -1921,6 +3123,28 @@ fn render_details_modal(frame: &mut Frame, app: &mut App) {
         lines.push(Line::from(""));
     }
 
+    // Commands section (only if any Exec* lines are configured)
+    let command_sections = [
+        ("ExecStart", &props.exec_start),
+        ("ExecReload", &props.exec_reload),
+        ("ExecStop", &props.exec_stop),
+    ];
+    if command_sections.iter().any(|(_, cmds)| !cmds.is_empty()) {
+        lines.push(Line::from(vec![Span::styled("Commands", section_style)]));
+        for (label, cmds) in &command_sections {
+            render_list_lines(&mut lines, label, cmds, label_style, value_style);
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Environment section (only if the unit sets any)
+    if !props.environment.is_empty() || !props.environment_files.is_empty() {
+        lines.push(Line::from(vec![Span::styled("Environment", section_style)]));
+        render_list_lines(&mut lines, "Variables", &props.environment, label_style, value_style);
+        render_list_lines(&mut lines, "Files", &props.environment_files, label_style, value_style);
+        lines.push(Line::from(""));
+    }
+
     // Dependencies section
     let dep_sections: Vec<(&str, &Vec<String>)> = vec![
         ("Requires", &props.requires),
@@ -1946,42 +3170,114 @@ fn render_details_modal(frame: &mut Frame, app: &mut App) {
         }
     }
 
-    // Store content height for scroll bounds
-    app.detail_content_height = lines.len();
+    lines
+}
+
+/// NEXT, LEFT, LAST and PASSED for the timer list. Relative values are
+/// computed against `now_us` so they keep moving between refreshes; a timer
+/// missing from `list-timers` shows `n/a` throughout.
+fn timer_columns(unit: &SystemdUnit, now_us: u64) -> [String; 4] {
+    let next = unit.next_elapse_us.unwrap_or(0);
+    let last = unit.last_trigger_us.unwrap_or(0);
+    [
+        format_timer_time(next),
+        format_time_left(next, now_us),
+        format_timer_time(last),
+        format_time_passed(last, now_us),
+    ]
+}
+
+/// Longest source path shown in the unit file title before it is cut.
+const UNIT_FILE_PATH_MAX: usize = 45;
 
-    let area = centered_rect(70, 80, frame.area());
-    let visible_height = area.height.saturating_sub(2) as usize;
+/// "Unit File: <unit> (<fragment path>)", the path cut with `...` when long.
+fn unit_file_title(unit_name: Option<&str>, path: Option<&str>) -> String {
+    let Some(name) = unit_name else {
+        return "Unit File".to_string();
+    };
+    match path {
+        Some(path) if path.chars().count() > UNIT_FILE_PATH_MAX => {
+            let cut: String = path.chars().take(UNIT_FILE_PATH_MAX - 3).collect();
+            format!("Unit File: {} ({}...)", name, cut)
+        }
+        Some(path) => format!("Unit File: {} ({})", name, path),
+        None => format!("Unit File: {}", name),
+    }
+}
 
-    let scroll_info = if lines.len() > visible_height {
-        let start = app.detail_scroll + 1;
-        let end = (app.detail_scroll + visible_height).min(lines.len());
-        format!(" [{}-{}/{}]", start, end, lines.len())
+/// Width of the all-units view's TYPE column, including padding; fits
+/// `automount`.
+const TYPE_WIDTH: usize = 11;
+
+/// Width of the socket view's LISTEN column, including padding.
+const SOCKET_LISTEN_WIDTH: usize = 30;
+
+/// LISTEN, CONNS and ACCEPTED for the socket list. Sockets without
+/// `Accept=yes` keep no connection counts and show `-`.
+fn socket_columns(unit: &SystemdUnit) -> [String; 3] {
+    let listen = unit.detail.as_deref().unwrap_or("");
+    let max = SOCKET_LISTEN_WIDTH - 2;
+    let listen = if listen.width() > max {
+        // Cut by display width so wide characters can't overflow the column.
+        let mut cut = String::new();
+        let mut width = 0;
+        for ch in listen.chars() {
+            width += ch.width().unwrap_or(0);
+            if width > max - 3 {
+                break;
+            }
+            cut.push(ch);
+        }
+        cut.push_str("...");
+        cut
     } else {
-        String::new()
+        listen.to_string()
     };
+    match unit.socket_stats {
+        Some(stats) => [listen, stats.connections.to_string(), stats.accepted.to_string()],
+        None => [listen, "-".to_string(), "-".to_string()],
+    }
+}
 
-    let visible_lines: Vec<Line> = lines
-        .into_iter()
-        .skip(app.detail_scroll)
-        .take(visible_height)
-        .collect();
+/// Whole-row style for the unit list: a subtle red background for failed
+/// units (bold and underlined without color) and dimmed text for stopped
+/// ones. The highlighted row gets no tint so the selection highlight always
+/// reads the same.
+fn row_style(sub_state: &str, selected: bool, color_enabled: bool) -> Style {
+    if selected {
+        return Style::default();
+    }
+    match sub_state {
+        "failed" if !color_enabled => Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        "failed" => Style::default().bg(theme().status.failed_row_bg),
+        "dead" | "inactive" => Style::default().add_modifier(Modifier::DIM),
+        _ => Style::default(),
+    }
+}
 
-    let title_name = if unit_name.len() > 35 {
-        format!("{}...", &unit_name[..32])
-    } else {
-        unit_name.clone()
-    };
-    let title = format!(" {} {}", title_name, scroll_info);
+/// Draws the spinner over the right end of a bordered area's top edge.
+fn render_title_spinner(frame: &mut Frame, area: Rect, spinner: char) {
+    if area.width < 6 {
+        return;
+    }
+    let spinner_area = Rect::new(area.x + area.width - 4, area.y, 3, 1);
+    frame.render_widget(
+        Paragraph::new(format!(" {} ", spinner)).style(Style::default().fg(Color::Yellow)),
+        spinner_area,
+    );
+}
 
-    let paragraph = Paragraph::new(visible_lines)
-        .style(Style::default().fg(Color::White))
+fn render_details_loading(frame: &mut Frame, unit_name: &str, spinner: char) {
+    let paragraph = Paragraph::new("Loading\u{2026}")
+        .style(Style::default().fg(theme().muted))
+        .alignment(ratatui::layout::Alignment::Center)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(title)
+                .title(format!(" {} {} ", unit_name, spinner))
                 .style(Style::default().bg(Color::Black)),
         );
-
+    let area = centered_fixed_rect(50, 3, frame.area());
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
 }
@@ -2013,9 +3309,72 @@ fn render_dep_lines<'a>(
     }
 }
 
+/// Renders the Process section: main PID and start time while running, plus
+/// restart count and last exit status, which stay useful after the process
+/// has exited. Nothing is rendered when none of these are known.
+fn render_process_lines<'a>(
+    lines: &mut Vec<Line<'a>>,
+    props: &UnitProperties,
+    section_style: Style,
+    label_style: Style,
+    value_style: Style,
+) {
+    if props.main_pid == 0 && props.n_restarts == 0 && props.exec_main_status == 0 {
+        return;
+    }
+    lines.push(Line::from(vec![Span::styled("Process", section_style)]));
+    if props.main_pid > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("  Main PID:       ", label_style),
+            Span::styled(props.main_pid.to_string(), value_style),
+        ]));
+        if !props.exec_main_start_timestamp.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("  Started:        ", label_style),
+                Span::styled(props.exec_main_start_timestamp.clone(), value_style),
+            ]));
+        }
+    }
+    lines.push(Line::from(vec![
+        Span::styled("  Restarts:       ", label_style),
+        Span::styled(props.n_restarts.to_string(), value_style),
+    ]));
+    let status_style = if props.exec_main_status != 0 {
+        Style::default().fg(Color::Red)
+    } else {
+        value_style
+    };
+    lines.push(Line::from(vec![
+        Span::styled("  Exit Status:    ", label_style),
+        Span::styled(props.exec_main_status.to_string(), status_style),
+    ]));
+    lines.push(Line::from(""));
+}
+
+/// Renders a labeled list with one entry per line; skipped when empty.
+fn render_list_lines<'a>(
+    lines: &mut Vec<Line<'a>>,
+    label: &str,
+    entries: &[String],
+    label_style: Style,
+    value_style: Style,
+) {
+    if entries.is_empty() {
+        return;
+    }
+    lines.push(Line::from(vec![Span::styled(format!("  {}:", label), label_style)]));
+    for entry in entries {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(entry.clone(), value_style),
+        ]));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::service::SocketStats;
 
     fn make_log_entry(boot_id: Option<&str>, invocation_id: Option<&str>) -> LogEntry {
         LogEntry {
@@ -2029,11 +3388,242 @@ mod tests {
             invocation_id: invocation_id.map(str::to_string),
             cursor: None,
             unit: None,
+            hostname: None,
+            fields: HashMap::new(),
         }
     }
 
     // Phase 4 — file_state_color
 
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_filter_help_lines_keeps_matches_under_headings() {
+        let heading = Style::default().fg(Color::Yellow);
+        let lines = vec![
+            Line::from(vec![Span::styled("Navigation", heading)]),
+            Line::from("  Down          Move down"),
+            Line::from("  g / Home      Go to top"),
+            Line::from(""),
+            Line::from(vec![Span::styled("Logs", heading)]),
+            Line::from("  f             Pause live tail"),
+            Line::from("  G / End       Go to bottom"),
+        ];
+        let kept = filter_help_lines(lines.clone(), "GO TO");
+        let texts: Vec<String> = kept.iter().map(line_text).collect();
+        assert_eq!(
+            texts,
+            ["Navigation", "  g / Home      Go to top", "", "Logs", "  G / End       Go to bottom"]
+        );
+        assert!(filter_help_lines(lines.clone(), "nothing like this").is_empty());
+        assert_eq!(filter_help_lines(lines, "").len(), 7);
+    }
+
+    #[test]
+    fn test_socket_columns() {
+        let mut unit = SystemdUnit {
+            unit: "sshd.socket".into(),
+            load: "loaded".into(),
+            active: "active".into(),
+            sub: "listening".into(),
+            description: "SSH Socket".into(),
+            detail: Some("0.0.0.0:22".into()),
+            file_state: None,
+            next_elapse_us: None,
+            last_trigger_us: None,
+            socket_stats: Some(SocketStats { connections: 2, accepted: 157 }),
+        };
+        assert_eq!(socket_columns(&unit), ["0.0.0.0:22", "2", "157"].map(String::from));
+
+        // Accept=no: no counts.
+        unit.socket_stats = None;
+        assert_eq!(socket_columns(&unit), ["0.0.0.0:22", "-", "-"].map(String::from));
+
+        unit.detail = Some("/run/user/1000/very/long/path/to/some/daemon.sock".into());
+        let [listen, _, _] = socket_columns(&unit);
+        assert_eq!(listen.width(), SOCKET_LISTEN_WIDTH - 2);
+        assert!(listen.ends_with("..."));
+
+        // Wide characters count two columns each.
+        unit.detail = Some("/run/\u{30bd}\u{30b1}\u{30c3}\u{30c8}/\u{9577}\u{3044}\u{30d1}\u{30b9}/\u{30c7}\u{30fc}\u{30e2}\u{30f3}.sock".into());
+        let [listen, _, _] = socket_columns(&unit);
+        assert!(listen.width() <= SOCKET_LISTEN_WIDTH - 2);
+        assert!(listen.ends_with("..."));
+    }
+
+    #[test]
+    fn test_timer_columns() {
+        let now = 1_700_000_000_000_000;
+        let mut unit = SystemdUnit {
+            unit: "backup.timer".into(),
+            load: "loaded".into(),
+            active: "active".into(),
+            sub: "waiting".into(),
+            description: "Backup".into(),
+            detail: None,
+            file_state: None,
+            next_elapse_us: Some(now + 3_600_000_000),
+            last_trigger_us: Some(now - 300_000_000),
+            socket_stats: None,
+        };
+        let [next, left, last, passed] = timer_columns(&unit, now);
+        assert_eq!(next, format_timer_time(now + 3_600_000_000));
+        assert_eq!(left, "1h 0m");
+        assert_eq!(last, format_timer_time(now - 300_000_000));
+        assert_eq!(passed, "5m 0s ago");
+
+        // Never fired, nothing scheduled, or not in list-timers at all.
+        unit.next_elapse_us = Some(0);
+        unit.last_trigger_us = None;
+        assert_eq!(timer_columns(&unit, now), ["n/a", "n/a", "n/a", "n/a"].map(String::from));
+    }
+
+    #[test]
+    fn test_detail_search_matches_rendered_lines() {
+        let props = UnitProperties {
+            sub_state: "running".into(),
+            description: "Network Time Sync".into(),
+            after: vec!["network.target".into(), "time-set.target".into()],
+            wants: vec!["time-set.target".into()],
+            ..Default::default()
+        };
+        let text: Vec<String> = build_detail_lines("chronyd.service", &props)
+            .iter()
+            .map(line_text)
+            .collect();
+        let matches = crate::app::find_matching_lines(&text, "TIME-SET");
+        assert_eq!(matches.len(), 2);
+        for &i in &matches {
+            assert!(text[i].contains("time-set.target"), "{}", text[i]);
+        }
+        assert!(text[matches[0]].contains("Wants:"));
+        assert!(text[matches[1]].contains("After:"));
+        assert_eq!(crate::app::find_matching_lines(&text, "network time").len(), 1);
+        assert!(crate::app::find_matching_lines(&text, "").is_empty());
+    }
+
+    fn process_lines(props: &UnitProperties) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        let style = Style::default();
+        render_process_lines(&mut lines, props, style, style, style);
+        lines
+    }
+
+    #[test]
+    fn test_row_style_failed_tinted() {
+        assert_eq!(row_style("failed", false, true).bg, Some(theme().status.failed_row_bg));
+    }
+
+    #[test]
+    fn test_row_style_dead_dimmed() {
+        assert!(row_style("dead", false, true).add_modifier.contains(Modifier::DIM));
+        assert!(row_style("inactive", false, true).add_modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn test_priority_legend_has_one_entry_per_level() {
+        let legend = priority_legend();
+        assert_eq!(legend.len(), PRIORITY_LABELS.len());
+        for (i, span) in legend.iter().enumerate() {
+            let p = i as u8;
+            assert_eq!(span.content, priority_label(p));
+            let (color, bold) = priority_color(p);
+            assert_eq!(span.style.fg, Some(color));
+            assert_eq!(span.style.add_modifier.contains(Modifier::BOLD), bold);
+        }
+    }
+
+    #[test]
+    fn test_format_log_match_info() {
+        assert_eq!(format_log_match_info(Some(2), 12, None, false), " (3/12)");
+        assert_eq!(format_log_match_info(None, 0, None, false), " (no matches)");
+        assert_eq!(
+            format_log_match_info(Some(2), 12, Some(&Ok(480)), false),
+            " (showing 3/12, 480 in journal)"
+        );
+        assert_eq!(
+            format_log_match_info(None, 0, Some(&Ok(4)), false),
+            " (no matches, 4 in journal)"
+        );
+        assert_eq!(
+            format_log_match_info(Some(0), 12, None, true),
+            " (1/12, counting in journal\u{2026})"
+        );
+        assert_eq!(
+            format_log_match_info(Some(0), 1, Some(&Err("boom".into())), false),
+            " (1/1, journal count failed)"
+        );
+    }
+
+    #[test]
+    fn test_row_style_failed_without_color_uses_modifiers() {
+        let style = row_style("failed", false, false);
+        assert_eq!(style.bg, None);
+        assert!(style.add_modifier.contains(Modifier::BOLD | Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_strip_colors_keeps_highlight_visible() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer[(0, 0)].set_fg(Color::Red);
+        buffer[(1, 0)].set_bg(Color::Rgb(40, 40, 80));
+        buffer[(2, 0)].set_bg(Color::Black);
+        strip_colors(&mut buffer);
+        for x in 0..3 {
+            assert_eq!(buffer[(x, 0)].fg, Color::Reset);
+            assert_eq!(buffer[(x, 0)].bg, Color::Reset);
+        }
+        assert!(buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(2, 0)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_row_style_normal_and_selected_untouched() {
+        assert_eq!(row_style("running", false, true), Style::default());
+        assert_eq!(row_style("failed", true, true), Style::default());
+        assert_eq!(row_style("dead", true, true), Style::default());
+    }
+
+    #[test]
+    fn test_spinner_frame_advances_every_frame_duration() {
+        assert_eq!(spinner_frame(Duration::ZERO), '⠋');
+        assert_eq!(spinner_frame(Duration::from_millis(99)), '⠋');
+        assert_eq!(spinner_frame(Duration::from_millis(100)), '⠙');
+        assert_eq!(spinner_frame(Duration::from_millis(250)), '⠹');
+        assert_eq!(spinner_frame(Duration::from_millis(950)), '⠏');
+    }
+
+    #[test]
+    fn test_spinner_frame_wraps_around() {
+        assert_eq!(spinner_frame(Duration::from_millis(1000)), '⠋');
+        assert_eq!(spinner_frame(Duration::from_millis(1_000_000 + 300)), '⠸');
+    }
+
+    #[test]
+    fn test_process_section_hidden_without_data() {
+        assert!(process_lines(&UnitProperties::default()).is_empty());
+    }
+
+    #[test]
+    fn test_process_section_renders_with_only_restart_fields() {
+        let props = UnitProperties {
+            n_restarts: 3,
+            exec_main_status: 1,
+            ..Default::default()
+        };
+        let lines = process_lines(&props);
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+        assert_eq!(texts[0], "Process");
+        assert!(!texts.iter().any(|t| t.contains("Main PID")));
+        assert!(texts.contains(&"  Restarts:       3".to_string()));
+        assert!(texts.contains(&"  Exit Status:    1".to_string()));
+        let status = lines.iter().find(|l| line_text(l).contains("Exit Status")).unwrap();
+        assert_eq!(status.spans[1].style.fg, Some(Color::Red));
+    }
+
     #[test]
     fn test_file_state_color_enabled() {
         assert_eq!(file_state_color("enabled"), Color::Green);
@@ -2046,7 +3636,7 @@ mod tests {
 
     #[test]
     fn test_file_state_color_static() {
-        assert_eq!(file_state_color("static"), COLOR_MUTED);
+        assert_eq!(file_state_color("static"), theme().file_state.r#static);
     }
 
     #[test]
@@ -2116,6 +3706,25 @@ mod tests {
         assert_eq!(priority_color(255), (Color::White, false));
     }
 
+    #[test]
+    fn test_boot_indices_in_order_of_first_appearance() {
+        let logs = vec![
+            make_log_entry(Some("bbb"), None),
+            make_log_entry(Some("bbb"), None),
+            make_log_entry(None, None),
+            make_log_entry(Some("aaa"), None),
+            make_log_entry(Some("bbb"), None),
+            make_log_entry(Some("ccc"), None),
+        ];
+        let indices = boot_indices(&logs);
+        assert_eq!(indices.len(), 3);
+        assert_eq!(indices["bbb"], 1);
+        assert_eq!(indices["aaa"], 2);
+        assert_eq!(indices["ccc"], 3);
+        // Same input, same numbering.
+        assert_eq!(boot_indices(&logs), indices);
+    }
+
     #[test]
     fn test_log_boundary_before_entry_boot_id_changed() {
         let prev = make_log_entry(Some("boot-a"), Some("inv-1"));
@@ -2170,6 +3779,245 @@ mod tests {
         assert_eq!(bottom_scroll_index(&heights, 2), 0);
     }
 
+    #[test]
+    fn test_bottom_scroll_index_skips_collapsed_entries() {
+        // Entries 1-2 are folded into entry 0; 3 and 4 fill the window.
+        let heights = vec![1, 0, 0, 1, 1];
+        assert_eq!(bottom_scroll_index(&heights, 2), 3);
+        assert_eq!(bottom_scroll_index(&heights, 3), 0);
+    }
+
+    #[test]
+    fn test_log_entry_at_row_single_line_entries() {
+        let heights = vec![1, 1, 1, 1];
+        assert_eq!(log_entry_at_row(&heights, 0, 0), Some(0));
+        assert_eq!(log_entry_at_row(&heights, 0, 2), Some(2));
+        assert_eq!(log_entry_at_row(&heights, 1, 0), Some(1));
+        assert_eq!(log_entry_at_row(&heights, 1, 3), None);
+    }
+
+    #[test]
+    fn test_log_entry_at_row_wrapped_entries() {
+        // Entry 1 wraps onto three rows.
+        let heights = vec![1, 3, 2];
+        assert_eq!(log_entry_at_row(&heights, 0, 1), Some(1));
+        assert_eq!(log_entry_at_row(&heights, 0, 3), Some(1));
+        assert_eq!(log_entry_at_row(&heights, 0, 4), Some(2));
+        assert_eq!(log_entry_at_row(&heights, 0, 5), Some(2));
+        assert_eq!(log_entry_at_row(&heights, 0, 6), None);
+        assert_eq!(log_entry_at_row(&heights, 1, 2), Some(1));
+        assert_eq!(log_entry_at_row(&heights, 1, 3), Some(2));
+    }
+
+    #[test]
+    fn test_log_entry_at_row_skips_collapsed_entries() {
+        // Entries 1-2 are folded into entry 0.
+        let heights = vec![1, 0, 0, 2];
+        assert_eq!(log_entry_at_row(&heights, 0, 1), Some(3));
+        assert_eq!(log_entry_at_row(&heights, 0, 2), Some(3));
+    }
+
+    #[test]
+    fn test_log_entry_at_row_past_end_or_unresolved_scroll() {
+        assert_eq!(log_entry_at_row(&[], 0, 0), None);
+        assert_eq!(log_entry_at_row(&[1, 1], usize::MAX, 0), None);
+    }
+
+    /// An `App` for tests that render through one; its runner fails every
+    /// command, so nothing touches the host's systemd.
+    fn test_app() -> App {
+        App::new(
+            std::sync::Arc::new(crate::service::test_runners::UnavailableRunner),
+            None,
+            false,
+            crate::service::UnitType::Service,
+        )
+    }
+
+    #[test]
+    fn test_current_match_line_gets_background() {
+        let mut app = test_app();
+        app.logs = ["error one", "fine", "error two"].iter().map(|m| log_with_message(m)).collect();
+        app.log_search_query = "error".into();
+        app.update_log_search();
+        let line_bgs = |app: &App| -> Vec<Option<Color>> {
+            app.logs
+                .iter()
+                .enumerate()
+                .map(|(i, e)| render_log_entry(e, i, app, is_current_log_match(app, i)).style.bg)
+                .collect()
+        };
+        assert_eq!(line_bgs(&app), vec![Some(CURRENT_MATCH_LINE_BG), None, None]);
+        app.next_log_match(10);
+        assert_eq!(line_bgs(&app), vec![None, None, Some(CURRENT_MATCH_LINE_BG)]);
+        app.clear_log_search();
+        assert_eq!(line_bgs(&app), vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_log_entry_hostname_shown_only_when_enabled() {
+        let mut app = test_app();
+        let entry = LogEntry { hostname: Some("web-01".into()), ..log_with_message("hello") };
+        assert_eq!(line_text(&render_log_entry(&entry, 0, &app, false)), "hello");
+        app.log_show_hostname = true;
+        assert_eq!(line_text(&render_log_entry(&entry, 0, &app, false)), "web-01 hello");
+        let without = log_with_message("hello");
+        assert_eq!(line_text(&render_log_entry(&without, 0, &app, false)), "hello");
+    }
+
+    #[test]
+    fn test_dim_below_priority_greys_less_severe_entries() {
+        let mut app = test_app();
+        app.dim_below_priority = Some(4);
+        let info = LogEntry { priority: Some(6), ..log_with_message("info") };
+        let warning = LogEntry { priority: Some(4), ..log_with_message("warning") };
+        let err = LogEntry { priority: Some(3), ..log_with_message("err") };
+        let fg = |entry: &LogEntry, app: &App| {
+            render_log_entry(entry, 0, app, false)
+                .spans
+                .iter()
+                .map(|s| s.style.fg)
+                .collect::<Vec<_>>()
+        };
+        assert!(fg(&info, &app).iter().flatten().all(|c| *c == theme().muted));
+        assert_eq!(fg(&warning, &app).last().copied().flatten(), Some(priority_color(4).0));
+        assert_eq!(fg(&err, &app).last().copied().flatten(), Some(priority_color(3).0));
+
+        app.dim_below_priority = None;
+        assert_eq!(fg(&info, &app).last().copied().flatten(), Some(priority_color(6).0));
+    }
+
+    #[test]
+    fn test_unit_file_title_includes_path() {
+        assert_eq!(unit_file_title(None, None), "Unit File");
+        assert_eq!(unit_file_title(Some("foo.service"), None), "Unit File: foo.service");
+        assert_eq!(
+            unit_file_title(Some("foo.service"), Some("/etc/systemd/system/foo.service")),
+            "Unit File: foo.service (/etc/systemd/system/foo.service)"
+        );
+    }
+
+    #[test]
+    fn test_unit_file_title_truncates_long_path() {
+        let path = "/usr/lib/systemd/system/a-very-long-vendor-unit-name-indeed.service";
+        let title = unit_file_title(Some("x.service"), Some(path));
+        assert_eq!(
+            title,
+            format!("Unit File: x.service ({}...)", &path[..UNIT_FILE_PATH_MAX - 3])
+        );
+    }
+
+    fn span_styles(line: &Line) -> Vec<(String, Style)> {
+        line.spans.iter().map(|s| (s.content.to_string(), s.style)).collect()
+    }
+
+    #[test]
+    fn test_highlight_unit_file_section_header() {
+        assert_eq!(
+            span_styles(&highlight_unit_file_line("[Service]")),
+            vec![("[Service]".to_string(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))]
+        );
+    }
+
+    #[test]
+    fn test_highlight_unit_file_key_value() {
+        assert_eq!(
+            span_styles(&highlight_unit_file_line("ExecStart=/usr/bin/foo --bar=1")),
+            vec![
+                ("ExecStart".to_string(), Style::default().fg(Color::Cyan)),
+                ("=".to_string(), Style::default().fg(Color::DarkGray)),
+                ("/usr/bin/foo --bar=1".to_string(), Style::default().fg(Color::White)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_highlight_unit_file_comment() {
+        for line in ["# /etc/systemd/system/foo.service", "; Restart=always"] {
+            assert_eq!(
+                span_styles(&highlight_unit_file_line(line)),
+                vec![(line.to_string(), Style::default().fg(Color::DarkGray))]
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_unit_file_line_search_keeps_syntax_colors() {
+        let mut app = test_app();
+        app.unit_file_search_query = "foo".into();
+        app.unit_file_search_matches = vec![0];
+        app.unit_file_search_match_index = Some(0);
+        let styles = span_styles(&render_unit_file_line("ExecStart=/bin/foo", 0, &app));
+        assert_eq!(styles[0], ("ExecStart".to_string(), Style::default().fg(Color::Cyan).bg(Color::DarkGray)));
+        assert_eq!(styles[1], ("=".to_string(), Style::default().fg(Color::Gray).bg(Color::DarkGray)));
+        assert!(styles.iter().any(|(text, style)| text == "foo" && style.bg == Some(Color::Yellow)));
+    }
+
+    fn log_with_message(message: &str) -> LogEntry {
+        LogEntry {
+            message: message.to_string(),
+            ..make_log_entry(Some("boot"), None)
+        }
+    }
+
+    #[test]
+    fn test_collapse_repeats_run_of_three() {
+        let logs: Vec<LogEntry> = ["same", "same", "same"].iter().map(|m| log_with_message(m)).collect();
+        assert_eq!(collapse_repeats(&logs), vec![3, 0, 0]);
+    }
+
+    #[test]
+    fn test_collapse_repeats_mixed_runs() {
+        let logs: Vec<LogEntry> =
+            ["a", "b", "b", "a", "c", "c"].iter().map(|m| log_with_message(m)).collect();
+        assert_eq!(collapse_repeats(&logs), vec![1, 2, 0, 1, 2, 0]);
+        assert!(collapse_repeats(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_collapse_repeats_breaks_at_boot_boundary() {
+        let mut logs: Vec<LogEntry> = ["x", "x", "x"].iter().map(|m| log_with_message(m)).collect();
+        logs[2].boot_id = Some("other".into());
+        assert_eq!(collapse_repeats(&logs), vec![2, 0, 1]);
+    }
+
+    // Horizontal scrolling for unwrapped logs
+
+    #[test]
+    fn test_clamp_h_scroll_within_range() {
+        assert_eq!(clamp_h_scroll(10, 120, 80), 10);
+    }
+
+    #[test]
+    fn test_clamp_h_scroll_limits_to_overflow() {
+        // Longest line overflows by 40 columns; scrolling further is pointless.
+        assert_eq!(clamp_h_scroll(100, 120, 80), 40);
+    }
+
+    #[test]
+    fn test_clamp_h_scroll_lines_fit() {
+        assert_eq!(clamp_h_scroll(16, 50, 80), 0);
+    }
+
+    #[test]
+    fn test_skip_line_columns_across_spans() {
+        let line = Line::from(vec![
+            Span::styled("abc", Style::default().fg(Color::Red)),
+            Span::raw("defg"),
+        ]);
+        let skipped = skip_line_columns(line, 4);
+        assert_eq!(span_texts(&skipped.spans), vec!["efg"]);
+    }
+
+    #[test]
+    fn test_skip_line_columns_keeps_style() {
+        let style = Style::default().fg(Color::Red);
+        let line = Line::from(vec![Span::styled("abcdef", style)]);
+        let skipped = skip_line_columns(line, 2);
+        assert_eq!(span_texts(&skipped.spans), vec!["cdef"]);
+        assert_eq!(skipped.spans[0].style, style);
+    }
+
     // Layout geometry — centered_fixed_rect
 
     #[test]
@@ -2200,6 +4048,41 @@ mod tests {
         assert_eq!(result.height, 10);
     }
 
+    #[test]
+    fn test_picker_row_at_maps_rows_inside_border() {
+        let rect = Rect::new(10, 5, 30, 6); // 4 rows
+        assert_eq!(picker_row_at(rect, 0, 4, 15, 6), Some(0));
+        assert_eq!(picker_row_at(rect, 0, 4, 15, 9), Some(3));
+        assert_eq!(picker_row_at(rect, 0, 4, 38, 7), Some(1));
+    }
+
+    #[test]
+    fn test_picker_row_at_border_and_outside_miss() {
+        let rect = Rect::new(10, 5, 30, 6);
+        assert_eq!(picker_row_at(rect, 0, 4, 15, 5), None, "top border");
+        assert_eq!(picker_row_at(rect, 0, 4, 15, 10), None, "bottom border");
+        assert_eq!(picker_row_at(rect, 0, 4, 10, 7), None, "left border");
+        assert_eq!(picker_row_at(rect, 0, 4, 39, 7), None, "right border");
+        assert_eq!(picker_row_at(rect, 0, 4, 5, 7), None);
+    }
+
+    #[test]
+    fn test_picker_row_at_scrolled_and_short_list() {
+        let rect = Rect::new(0, 0, 30, 6);
+        assert_eq!(picker_row_at(rect, 3, 8, 5, 1), Some(3));
+        assert_eq!(picker_row_at(rect, 0, 2, 5, 3), None, "below the last row");
+    }
+
+    #[test]
+    fn test_picker_rect_fits_rows_and_border() {
+        let app = test_app();
+        let area = Rect::new(0, 0, 100, 40);
+        let rect = picker_rect(Picker::Priority, &app, area);
+        assert_eq!((rect.width, rect.height), (30, 11));
+        let rect = picker_rect(Picker::Time, &app, area);
+        assert_eq!(rect.height as usize, app.picker_len(Picker::Time) + 2);
+    }
+
     #[test]
     fn test_centered_fixed_rect_exact_fit() {
         let area = Rect::new(0, 0, 30, 10);
@@ -2231,7 +4114,7 @@ mod tests {
     #[test]
     fn test_layout_regions_no_logs() {
         let area = Rect::new(0, 0, 100, 50);
-        let regions = get_layout_regions(area, false);
+        let regions = get_layout_regions(area, false, false);
         // Services list should take full width
         assert_eq!(regions.services_list.width, 100);
         assert!(regions.logs_panel.is_none());
@@ -2240,7 +4123,7 @@ mod tests {
     #[test]
     fn test_layout_regions_with_logs() {
         let area = Rect::new(0, 0, 100, 50);
-        let regions = get_layout_regions(area, true);
+        let regions = get_layout_regions(area, true, false);
         // Logs take full middle area
         assert!(regions.logs_panel.is_some());
         let logs = regions.logs_panel.unwrap();
@@ -2249,10 +4132,37 @@ mod tests {
         assert_eq!(regions.services_list.width, 100);
     }
 
+    #[test]
+    fn test_layout_regions_split_view() {
+        let area = Rect::new(0, 0, 100, 50);
+        let regions = get_layout_regions(area, true, true);
+        let logs = regions.logs_panel.unwrap();
+        let list = regions.services_list;
+        // Side by side, list on the left, together spanning the full width
+        assert_eq!(list.x, 0);
+        assert_eq!(list.x + list.width, logs.x);
+        assert_eq!(logs.x + logs.width, 100);
+        assert_eq!(list.width, 40);
+        // Both fill the middle area's height (list minus its column header)
+        assert_eq!(logs.y, 3);
+        assert_eq!(logs.height, 50 - 3 - 3);
+        assert_eq!(list.y, 4);
+        assert_eq!(list.height, logs.height - 1);
+        assert!(list.intersection(logs).is_empty());
+    }
+
+    #[test]
+    fn test_layout_regions_split_view_needs_logs() {
+        let area = Rect::new(0, 0, 100, 50);
+        let regions = get_layout_regions(area, false, true);
+        assert!(regions.logs_panel.is_none());
+        assert_eq!(regions.services_list.width, 100);
+    }
+
     #[test]
     fn test_layout_regions_vertical_structure() {
         let area = Rect::new(0, 0, 100, 50);
-        let regions = get_layout_regions(area, false);
+        let regions = get_layout_regions(area, false, false);
         // Header is 3 rows, footer is 3 rows, column header is 1 row, rest is list body
         // Services list should start after header + column header (y=4)
         assert_eq!(regions.services_list.y, 4);